@@ -98,7 +98,7 @@ pub struct GrammarRuleContent {
     related_patterns: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FormatAction {
     AdjectiveRemovePostfix {},
     AdjectiveToKunai {},
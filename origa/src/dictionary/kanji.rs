@@ -3,6 +3,7 @@ use std::{collections::HashMap, sync::OnceLock};
 use serde::{Deserialize, Serialize, de};
 use tracing::debug;
 
+use crate::dictionary::user_dictionary::get_user_kanji;
 use crate::domain::{JapaneseLevel, NativeLanguage, OrigaError};
 
 fn deserialize_string_or_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -168,7 +169,21 @@ impl KanjiInfo {
     pub fn used_in(&self) -> u32 {
         self.used_in
     }
+    /// Meanings joined for display. When the built-in entry has none in
+    /// `lang`, meanings from an imported Yomitan kanji bank are used before
+    /// falling back to Russian.
     pub fn description(&self, lang: &NativeLanguage) -> String {
+        let own = match lang {
+            NativeLanguage::Russian => &self.description_ru,
+            NativeLanguage::English => &self.description_en,
+        };
+        if own.is_empty()
+            && let Some(user_kanji) = get_user_kanji(&self.kanji.to_string())
+            && !user_kanji.meanings(lang).is_empty()
+        {
+            return user_kanji.meanings(lang).join(", ");
+        }
+
         let descs = match lang {
             NativeLanguage::Russian => &self.description_ru,
             NativeLanguage::English => {
@@ -5,4 +5,5 @@ pub mod phrase;
 pub mod pitch_audio;
 pub mod radical;
 pub mod removed_popular_words;
+pub mod user_dictionary;
 pub mod vocabulary;
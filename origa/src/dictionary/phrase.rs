@@ -120,6 +120,19 @@ impl PhraseIndex {
             .unwrap_or_default()
     }
 
+    fn find_by_tokens(&self, tokens: &[String]) -> Option<&IndexEntry> {
+        let wanted: HashSet<&str> = tokens.iter().map(String::as_str).collect();
+        let first = tokens.first()?;
+        self.get_phrases_by_token(first).into_iter().find(|entry| {
+            entry
+                .tokens
+                .iter()
+                .map(String::as_str)
+                .collect::<HashSet<_>>()
+                == wanted
+        })
+    }
+
     fn iter_entries(&self) -> impl Iterator<Item = &IndexEntry> {
         self.entries.values()
     }
@@ -218,6 +231,14 @@ pub fn get_phrases_by_token(token: &str) -> Vec<&'static IndexEntry> {
         .unwrap_or_default()
 }
 
+/// Phrase whose vocabulary tokens are exactly `tokens`, ignoring order and
+/// repeats: the dataset entry for a sentence tokenized the same way.
+pub fn find_phrase_by_tokens(tokens: &[String]) -> Option<&'static IndexEntry> {
+    PHRASE_INDEX
+        .get()
+        .and_then(|idx| idx.find_by_tokens(tokens))
+}
+
 pub fn get_chunk_id(id: &Ulid) -> Option<u32> {
     PHRASE_INDEX
         .get()
@@ -407,6 +428,26 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[test]
+    fn find_by_tokens_matches_the_exact_token_set() {
+        let index = PhraseIndex::from_json(index_json()).expect("valid JSON");
+        let tokens = vec![
+            "world".to_string(),
+            "hello".to_string(),
+            "world".to_string(),
+        ];
+        let entry = index.find_by_tokens(&tokens).expect("entry");
+        assert_eq!(entry.id().to_string(), "01KPJ5S3N1DRFFD236Z4EZ03HJ");
+    }
+
+    #[test]
+    fn find_by_tokens_rejects_a_partial_match() {
+        let index = PhraseIndex::from_json(index_json()).expect("valid JSON");
+        assert!(index.find_by_tokens(&["world".to_string()]).is_none());
+        let extra = vec!["hello".to_string(), "world".to_string(), "cat".to_string()];
+        assert!(index.find_by_tokens(&extra).is_none());
+    }
+
     #[test]
     fn get_phrases_by_token_not_found() {
         let index = PhraseIndex::from_json(index_json()).expect("valid JSON");
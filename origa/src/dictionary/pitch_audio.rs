@@ -1,9 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::OnceLock;
 
 use serde::Deserialize;
 
-use crate::domain::OrigaError;
+use crate::domain::{
+    JapaneseChar, OrigaError, PitchAccent, hiragana_to_katakana, katakana_to_hiragana,
+    tokenize_text,
+};
 
 pub static PITCH_AUDIO_INDEX: OnceLock<PitchAudioIndex> = OnceLock::new();
 
@@ -29,6 +32,9 @@ impl PitchAudioEntry {
 
 pub struct PitchAudioIndex {
     entries: HashMap<String, PitchAudioEntry>,
+    /// Words that have at least one "word|reading" entry, i.e. whose plain
+    /// word entry is ambiguous between readings.
+    words_with_readings: HashSet<String>,
     version: u32,
 }
 
@@ -69,9 +75,15 @@ impl PitchAudioIndex {
                 )
             })
             .collect();
+        let words_with_readings = entries
+            .keys()
+            .filter_map(|key| key.split_once('|'))
+            .map(|(word, _)| word.to_string())
+            .collect();
 
         Ok(Self {
             entries,
+            words_with_readings,
             version: file.version,
         })
     }
@@ -87,6 +99,21 @@ impl PitchAudioIndex {
             .or_else(|| self.get_entry(reading))
             .or_else(|| self.get_entry(word))
     }
+
+    /// Lookup for pitch: the entry must belong to this word read this way.
+    /// "word|reading" matches; a plain "word" entry only when the index knows
+    /// a single reading for the word. A reading-only match is never used, as
+    /// homophones (橋/箸/端) have different accents.
+    pub fn find_pitch_entry(&self, word: &str, reading: &str) -> Option<&PitchAudioEntry> {
+        let composite = format!("{}|{}", word, reading);
+        self.get_entry(&composite).or_else(|| {
+            if self.words_with_readings.contains(word) {
+                None
+            } else {
+                self.get_entry(word)
+            }
+        })
+    }
 }
 
 pub fn init_pitch_audio_index(json: &str) -> Result<(), OrigaError> {
@@ -116,6 +143,48 @@ pub fn get_audio_for_reading(word: &str, reading: &str) -> Option<&'static Pitch
         .and_then(|idx| idx.find_audio_for_reading(word, reading))
 }
 
+/// Pitch accent of a word in its dictionary form, matched on word and reading
+/// (see [`PitchAudioIndex::find_pitch_entry`]). The reading may be katakana,
+/// as the tokenizer returns it; a reading that does not end in the word's
+/// okurigana (an inflected reading such as たべ for 食べる) yields `None`.
+pub fn get_pitch_accent(word: &str, reading: &str) -> Option<PitchAccent> {
+    if !reading_fits_okurigana(word, reading) {
+        return None;
+    }
+    let reading = katakana_to_hiragana(reading);
+    let pitch = PITCH_AUDIO_INDEX
+        .get()?
+        .find_pitch_entry(word, &reading)?
+        .pitch()?;
+    PitchAccent::new(&reading, pitch)
+}
+
+fn reading_fits_okurigana(word: &str, reading: &str) -> bool {
+    let okurigana: String = word
+        .chars()
+        .rev()
+        .take_while(|c| !c.is_kanji())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    hiragana_to_katakana(reading).ends_with(&hiragana_to_katakana(&okurigana))
+}
+
+/// Pitch accent of a card word, read through the tokenizer. Needs both the
+/// pitch audio index and the tokenizer dictionary; `None` otherwise.
+pub fn pitch_accent_for_word(word: &str) -> Option<PitchAccent> {
+    if !is_pitch_audio_loaded() {
+        return None;
+    }
+    let reading: String = tokenize_text(word)
+        .ok()?
+        .iter()
+        .map(|token| token.phonological_surface_form())
+        .collect();
+    get_pitch_accent(word, &reading)
+}
+
 pub fn pitch_audio_version() -> u32 {
     PITCH_AUDIO_INDEX.get().map(|idx| idx.version).unwrap_or(0)
 }
@@ -250,4 +319,37 @@ mod tests {
             .expect("kanji fallback");
         assert_eq!(entry.file(), "fallback.opus");
     }
+
+    #[test]
+    fn find_pitch_entry_ignores_homophones() {
+        let index = PitchAudioIndex::from_json(
+            r#"{"v":3,"total":3,"entries":{"橋|はし":{"f":"hashi_bridge.opus","p":2},"箸|はし":{"f":"hashi_chopsticks.opus","p":1},"はし":{"f":"hashi_kana.opus","p":0}}}"#,
+        )
+        .expect("valid JSON");
+
+        assert_eq!(
+            index.find_pitch_entry("箸", "はし").map(|e| e.file()),
+            Some("hashi_chopsticks.opus")
+        );
+        assert!(index.find_pitch_entry("端", "はし").is_none());
+    }
+
+    #[test]
+    fn find_pitch_entry_uses_plain_word_only_when_unambiguous() {
+        let index = PitchAudioIndex::from_json(index_v3_json()).expect("valid JSON");
+        assert!(index.find_pitch_entry("役", "NotExist").is_none());
+
+        let index = PitchAudioIndex::from_json(index_json()).expect("valid JSON");
+        assert_eq!(
+            index.find_pitch_entry("食べる", "たべる").map(|e| e.file()),
+            Some("e5f6a7b8.opus")
+        );
+    }
+
+    #[test]
+    fn inflected_reading_does_not_fit_the_lemma() {
+        assert!(reading_fits_okurigana("食べる", "タベル"));
+        assert!(reading_fits_okurigana("猫", "ねこ"));
+        assert!(!reading_fits_okurigana("食べる", "タベ"));
+    }
 }
@@ -0,0 +1,749 @@
+//! Local user-dictionary layer built from imported Yomitan dictionaries.
+//!
+//! The bundled `VocabularyDatabase` comes from the CDN and is read-only; the
+//! user layer holds the term, kanji and frequency banks the user imported on
+//! this device. The `vocabulary` lookups consult it after the built-in
+//! dictionary. Like the vocabulary, the layer is cached as rkyv bytes so a
+//! reload does not re-parse the zips.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::sync::{OnceLock, RwLock};
+
+use serde_json::Value;
+use zip::ZipArchive;
+
+use crate::dictionary::vocabulary::VocabularyInfo;
+use crate::domain::{NativeLanguage, OrigaError, katakana_to_hiragana};
+
+static USER_DICTIONARY: OnceLock<RwLock<UserDictionary>> = OnceLock::new();
+
+const INDEX_FILE: &str = "index.json";
+const TERM_BANK_PREFIX: &str = "term_bank_";
+const KANJI_BANK_PREFIX: &str = "kanji_bank_";
+const TERM_META_BANK_PREFIX: &str = "term_meta_bank_";
+const KANJI_META_BANK_PREFIX: &str = "kanji_meta_bank_";
+const FREQUENCY_MODE: &str = "freq";
+
+#[derive(Debug, Clone, PartialEq, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+pub struct UserDictionarySource {
+    title: String,
+    revision: String,
+    term_count: u32,
+    kanji_count: u32,
+}
+
+impl UserDictionarySource {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+
+    pub fn term_count(&self) -> u32 {
+        self.term_count
+    }
+
+    pub fn kanji_count(&self) -> u32 {
+        self.kanji_count
+    }
+}
+
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+pub struct UserKanjiInfo {
+    kanji: String,
+    onyomi: Vec<String>,
+    kunyomi: Vec<String>,
+    ru_meanings: Vec<String>,
+    en_meanings: Vec<String>,
+}
+
+impl UserKanjiInfo {
+    pub fn kanji(&self) -> &str {
+        &self.kanji
+    }
+
+    pub fn onyomi(&self) -> &[String] {
+        &self.onyomi
+    }
+
+    pub fn kunyomi(&self) -> &[String] {
+        &self.kunyomi
+    }
+
+    pub fn meanings(&self, lang: &NativeLanguage) -> &[String] {
+        match lang {
+            NativeLanguage::Russian => &self.ru_meanings,
+            NativeLanguage::English => &self.en_meanings,
+        }
+    }
+}
+
+/// One imported term: translations are kept per reading, so homographs
+/// such as 生 (なま / せい) keep their own glosses.
+#[derive(Debug, Clone, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+struct UserTerm {
+    /// Hiragana reading; the expression itself when the bank leaves it empty.
+    reading: String,
+    info: VocabularyInfo,
+}
+
+/// The banks of one imported zip, kept apart so the zip can be removed again.
+#[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+struct ImportedDictionary {
+    source: UserDictionarySource,
+    terms: HashMap<String, Vec<UserTerm>>,
+    kanji: HashMap<String, UserKanjiInfo>,
+    frequency_ranks: HashMap<String, u32>,
+}
+
+impl ImportedDictionary {
+    fn new(title: String, revision: String) -> Self {
+        Self {
+            source: UserDictionarySource {
+                title,
+                revision,
+                term_count: 0,
+                kanji_count: 0,
+            },
+            terms: HashMap::new(),
+            kanji: HashMap::new(),
+            frequency_ranks: HashMap::new(),
+        }
+    }
+
+    /// `[expression, reading, definitionTags, rules, score, glossary, ...]`
+    fn add_term(&mut self, row: &Value, lang: &NativeLanguage) {
+        let Some(expression) = row[0].as_str().filter(|e| !e.is_empty()) else {
+            return;
+        };
+        let translations = glossary_texts(&row[5]);
+        if translations.is_empty() {
+            return;
+        }
+        let reading = term_reading(expression, row[1].as_str().unwrap_or_default());
+        let entries = self.terms.entry(expression.to_string()).or_default();
+        let index = match entries.iter().position(|term| term.reading == reading) {
+            Some(index) => index,
+            None => {
+                entries.push(UserTerm {
+                    reading,
+                    info: VocabularyInfo::empty(expression.to_string()),
+                });
+                entries.len() - 1
+            },
+        };
+        entries[index].info.extend_translations(lang, translations);
+    }
+
+    /// `[character, onyomi, kunyomi, tags, meanings, stats]`
+    fn add_kanji(&mut self, row: &Value, lang: &NativeLanguage) {
+        let Some(kanji) = row[0].as_str().filter(|k| !k.is_empty()) else {
+            return;
+        };
+        let readings = |value: &Value| -> Vec<String> {
+            value
+                .as_str()
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect()
+        };
+        let meanings = glossary_texts(&row[4]);
+        let (ru_meanings, en_meanings) = match lang {
+            NativeLanguage::Russian => (meanings, Vec::new()),
+            NativeLanguage::English => (Vec::new(), meanings),
+        };
+        self.kanji.insert(
+            kanji.to_string(),
+            UserKanjiInfo {
+                kanji: kanji.to_string(),
+                onyomi: readings(&row[1]),
+                kunyomi: readings(&row[2]),
+                ru_meanings,
+                en_meanings,
+            },
+        );
+    }
+
+    /// `[expression, mode, data]`; only frequency metadata is kept.
+    fn add_meta(&mut self, row: &Value) {
+        let (Some(expression), Some(FREQUENCY_MODE)) = (row[0].as_str(), row[1].as_str()) else {
+            return;
+        };
+        if let Some(rank) = frequency_value(&row[2]) {
+            self.frequency_ranks
+                .entry(expression.to_string())
+                .and_modify(|best| *best = (*best).min(rank))
+                .or_insert(rank);
+        }
+    }
+}
+
+#[derive(Default, rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+pub struct UserDictionary {
+    /// In import order; earlier dictionaries are listed first in lookups.
+    dictionaries: Vec<ImportedDictionary>,
+}
+
+impl UserDictionary {
+    /// Parses a Yomitan (or legacy Yomichan) dictionary zip. Glossaries are
+    /// read in the `targetLanguage` declared by index.json, or in
+    /// `fallback_lang` when the index does not declare one.
+    pub fn from_yomitan_zip(
+        data: &[u8],
+        fallback_lang: &NativeLanguage,
+    ) -> Result<Self, OrigaError> {
+        let mut archive = ZipArchive::new(Cursor::new(data)).map_err(invalid_file)?;
+        let index = read_json(&mut archive, INDEX_FILE)?;
+        let title = index["title"]
+            .as_str()
+            .filter(|title| !title.trim().is_empty())
+            .ok_or_else(|| invalid_file("index.json has no title"))?
+            .trim()
+            .to_string();
+        let revision = index["revision"].as_str().unwrap_or_default().to_string();
+        let lang = glossary_language(&index, fallback_lang)?;
+
+        let mut bank_names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        bank_names.sort();
+
+        let mut dictionary = ImportedDictionary::new(title, revision);
+        for name in bank_names {
+            if name.starts_with(TERM_META_BANK_PREFIX) || name.starts_with(KANJI_META_BANK_PREFIX) {
+                for row in read_rows(&mut archive, &name)? {
+                    dictionary.add_meta(&row);
+                }
+            } else if name.starts_with(TERM_BANK_PREFIX) {
+                for row in read_rows(&mut archive, &name)? {
+                    dictionary.add_term(&row, &lang);
+                }
+            } else if name.starts_with(KANJI_BANK_PREFIX) {
+                for row in read_rows(&mut archive, &name)? {
+                    dictionary.add_kanji(&row, &lang);
+                }
+            }
+        }
+
+        dictionary.source.term_count =
+            dictionary.terms.values().map(Vec::len).sum::<usize>() as u32;
+        dictionary.source.kanji_count = dictionary.kanji.len() as u32;
+        Ok(Self {
+            dictionaries: vec![dictionary],
+        })
+    }
+
+    pub fn sources(&self) -> Vec<UserDictionarySource> {
+        self.dictionaries
+            .iter()
+            .map(|dictionary| dictionary.source.clone())
+            .collect()
+    }
+
+    /// Translations of `word` in `lang`. With a `reading` (kana of either
+    /// script) only the entries for that reading count; without one, every
+    /// reading of the word is listed.
+    pub fn translations(
+        &self,
+        word: &str,
+        reading: Option<&str>,
+        lang: &NativeLanguage,
+    ) -> Vec<String> {
+        let reading = reading.map(katakana_to_hiragana);
+        let mut translations: Vec<String> = Vec::new();
+        for term in self
+            .dictionaries
+            .iter()
+            .filter_map(|dictionary| dictionary.terms.get(word))
+            .flatten()
+            .filter(|term| reading.as_ref().map_or(true, |r| &term.reading == r))
+        {
+            for translation in term.info.translations(lang) {
+                if !translations.contains(translation) {
+                    translations.push(translation.clone());
+                }
+            }
+        }
+        translations
+    }
+
+    pub fn kanji(&self, kanji: &str) -> Option<&UserKanjiInfo> {
+        self.dictionaries
+            .iter()
+            .find_map(|dictionary| dictionary.kanji.get(kanji))
+    }
+
+    /// Frequency rank of `word` (1 = most frequent) across the imported
+    /// frequency banks; the best rank wins.
+    pub fn frequency_rank(&self, word: &str) -> Option<u32> {
+        self.dictionaries
+            .iter()
+            .filter_map(|dictionary| dictionary.frequency_ranks.get(word).copied())
+            .min()
+    }
+
+    fn contains_source(&self, source: &UserDictionarySource) -> bool {
+        self.dictionaries.iter().any(|dictionary| {
+            dictionary.source.title == source.title && dictionary.source.revision == source.revision
+        })
+    }
+
+    fn merge(&mut self, other: UserDictionary) {
+        self.dictionaries.extend(other.dictionaries);
+    }
+
+    /// Drops the dictionary imported from `source`; false when none was.
+    fn remove(&mut self, source: &UserDictionarySource) -> bool {
+        let before = self.dictionaries.len();
+        self.dictionaries.retain(|dictionary| {
+            dictionary.source.title != source.title || dictionary.source.revision != source.revision
+        });
+        self.dictionaries.len() != before
+    }
+}
+
+/// Adds a Yomitan dictionary zip to the user layer. Importing the same
+/// title and revision again is a no-op. `fallback_lang` is the glossary
+/// language for zips whose index.json does not declare one.
+pub fn import_yomitan_dictionary(
+    data: &[u8],
+    fallback_lang: &NativeLanguage,
+) -> Result<UserDictionarySource, OrigaError> {
+    let imported = UserDictionary::from_yomitan_zip(data, fallback_lang)?;
+    let source = imported.dictionaries[0].source.clone();
+    let mut layer = user_dictionary().write().unwrap_or_else(|e| e.into_inner());
+    if !layer.contains_source(&source) {
+        layer.merge(imported);
+    }
+    Ok(source)
+}
+
+pub fn user_dictionary_sources() -> Vec<UserDictionarySource> {
+    read_layer(UserDictionary::sources)
+}
+
+/// Removes an imported dictionary from the user layer. Returns false when
+/// it was not installed.
+pub fn remove_user_dictionary(source: &UserDictionarySource) -> bool {
+    user_dictionary()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(source)
+}
+
+pub fn serialize_user_dictionary_to_rkyv() -> Result<Vec<u8>, OrigaError> {
+    read_layer(|layer| {
+        rkyv::to_bytes::<rkyv::rancor::Error>(layer)
+            .map_err(|e| OrigaError::VocabularyParseError {
+                reason: format!("Failed to serialize user dictionary: {}", e),
+            })
+            .map(|bytes| bytes.to_vec())
+    })
+}
+
+/// Restores the layer from cached rkyv bytes, replacing its content.
+pub fn init_user_dictionary_from_rkyv(bytes: &[u8]) -> Result<(), OrigaError> {
+    let restored = rkyv::from_bytes::<UserDictionary, rkyv::rancor::Error>(bytes).map_err(|e| {
+        OrigaError::VocabularyParseError {
+            reason: format!("Failed to deserialize user dictionary: {}", e),
+        }
+    })?;
+    *user_dictionary().write().unwrap_or_else(|e| e.into_inner()) = restored;
+    Ok(())
+}
+
+pub fn get_user_kanji(kanji: &str) -> Option<UserKanjiInfo> {
+    read_layer(|layer| layer.kanji(kanji).cloned())
+}
+
+pub fn get_frequency_rank(word: &str) -> Option<u32> {
+    read_layer(|layer| layer.frequency_rank(word))
+}
+
+/// Translations of `word` in `lang` from the user layer, restricted to
+/// `reading` when given; `None` when the imported dictionaries have no
+/// glossary in that language.
+pub(crate) fn get_user_translations(
+    word: &str,
+    reading: Option<&str>,
+    lang: &NativeLanguage,
+) -> Option<Vec<String>> {
+    read_layer(|layer| Some(layer.translations(word, reading, lang)))
+        .filter(|translations| !translations.is_empty())
+}
+
+fn user_dictionary() -> &'static RwLock<UserDictionary> {
+    USER_DICTIONARY.get_or_init(|| RwLock::new(UserDictionary::default()))
+}
+
+fn read_layer<T>(f: impl FnOnce(&UserDictionary) -> T) -> T {
+    let guard = user_dictionary().read().unwrap_or_else(|e| e.into_inner());
+    f(&guard)
+}
+
+fn read_json(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Value, OrigaError> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| invalid_file(format!("{name}: {e}")))?;
+    let mut text = String::new();
+    entry
+        .read_to_string(&mut text)
+        .map_err(|e| invalid_file(format!("{name}: {e}")))?;
+    let text = text.strip_prefix('\u{FEFF}').unwrap_or(&text);
+    serde_json::from_str(text).map_err(|e| invalid_file(format!("{name}: {e}")))
+}
+
+fn read_rows(
+    archive: &mut ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<Vec<Value>, OrigaError> {
+    match read_json(archive, name)? {
+        Value::Array(rows) => Ok(rows),
+        _ => Err(invalid_file(format!("{name} is not an array"))),
+    }
+}
+
+/// Flattens a glossary into plain translations. Entries are strings,
+/// `{"type": "text"}` objects or `{"type": "structured-content"}` trees;
+/// images and deinflection references carry no translation.
+fn glossary_texts(glossary: &Value) -> Vec<String> {
+    let Some(items) = glossary.as_array() else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Value::String(text) => Some(text.clone()),
+            Value::Object(object) => match object.get("type").and_then(Value::as_str) {
+                Some("text") => object
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                Some("structured-content") => {
+                    let mut parts = Vec::new();
+                    structured_text(&item["content"], &mut parts);
+                    Some(parts.join(" "))
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+fn structured_text(node: &Value, parts: &mut Vec<String>) {
+    match node {
+        Value::String(text) => parts.push(text.clone()),
+        Value::Array(children) => children
+            .iter()
+            .for_each(|child| structured_text(child, parts)),
+        // Ruby readings duplicate the base text.
+        Value::Object(element)
+            if matches!(
+                element.get("tag").and_then(Value::as_str),
+                Some("rt" | "rp")
+            ) => {},
+        Value::Object(element) => {
+            if let Some(content) = element.get("content") {
+                structured_text(content, parts);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Frequency data is a number, a numeric string, `{"value": n}` or, for
+/// reading-specific entries, `{"reading": r, "frequency": <any of these>}`.
+fn frequency_value(data: &Value) -> Option<u32> {
+    match data {
+        Value::Number(number) => number.as_u64().and_then(|n| u32::try_from(n).ok()),
+        Value::String(text) => text.trim().parse().ok(),
+        Value::Object(object) => object
+            .get("frequency")
+            .or_else(|| object.get("value"))
+            .and_then(frequency_value),
+        _ => None,
+    }
+}
+
+/// Yomitan leaves the reading empty when it equals the expression.
+fn term_reading(expression: &str, reading: &str) -> String {
+    if reading.is_empty() {
+        katakana_to_hiragana(expression)
+    } else {
+        katakana_to_hiragana(reading)
+    }
+}
+
+/// The glossary language declared by index.json (`targetLanguage`, an ISO
+/// 639 code), or `fallback` when the index predates that field.
+fn glossary_language(
+    index: &Value,
+    fallback: &NativeLanguage,
+) -> Result<NativeLanguage, OrigaError> {
+    if let Some(source) = index["sourceLanguage"].as_str()
+        && source != "ja"
+    {
+        return Err(invalid_file(format!(
+            "not a Japanese dictionary (sourceLanguage \"{source}\")"
+        )));
+    }
+    match index["targetLanguage"].as_str() {
+        None => Ok(*fallback),
+        Some("ru") => Ok(NativeLanguage::Russian),
+        Some("en") => Ok(NativeLanguage::English),
+        Some(other) => Err(invalid_file(format!(
+            "glossary language \"{other}\" is not supported"
+        ))),
+    }
+}
+
+fn invalid_file(reason: impl std::fmt::Display) -> OrigaError {
+    OrigaError::YomitanInvalidFile {
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serde_json::json;
+    use std::io::Write;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    fn yomitan_zip(files: &[(&str, Value)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.to_string().as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    fn index(title: &str) -> (&'static str, Value) {
+        (
+            INDEX_FILE,
+            json!({ "title": title, "revision": "1", "format": 3 }),
+        )
+    }
+
+    #[test]
+    fn term_bank_glossaries_become_translations() {
+        let data = yomitan_zip(&[
+            index("JMdict"),
+            (
+                "term_bank_1.json",
+                json!([
+                    ["猫", "ねこ", "n", "", 10, ["cat", "feline"], 1, ""],
+                    ["猫", "ねこ", "n", "", 5, ["cat", { "type": "text", "text": "puss" }], 2, ""],
+                    ["犬", "いぬ", "n", "", 1, [{
+                        "type": "structured-content",
+                        "content": { "tag": "ul", "content": [
+                            { "tag": "li", "content": ["dog"] },
+                            { "tag": "ruby", "content": ["犬", { "tag": "rt", "content": "いぬ" }] }
+                        ] }
+                    }], 3, ""],
+                    ["画像", "がぞう", "", "", 0, [{ "type": "image", "path": "a.png" }], 4, ""]
+                ]),
+            ),
+        ]);
+
+        let dictionary = UserDictionary::from_yomitan_zip(&data, &NativeLanguage::English).unwrap();
+
+        assert_eq!(
+            dictionary.translations("猫", None, &NativeLanguage::English),
+            ["cat", "feline", "puss"]
+        );
+        assert!(
+            dictionary
+                .translations("猫", None, &NativeLanguage::Russian)
+                .is_empty()
+        );
+        assert_eq!(
+            dictionary.translations("犬", Some("イヌ"), &NativeLanguage::English),
+            ["dog 犬"]
+        );
+        assert!(
+            dictionary
+                .translations("画像", None, &NativeLanguage::English)
+                .is_empty()
+        );
+        assert_eq!(dictionary.sources()[0].term_count(), 2);
+    }
+
+    #[test]
+    fn homographs_keep_their_translations_per_reading() {
+        let data = yomitan_zip(&[
+            index("JMdict"),
+            (
+                "term_bank_1.json",
+                json!([
+                    ["生", "なま", "", "", 0, ["raw"], 1, ""],
+                    ["生", "せい", "", "", 0, ["life"], 2, ""]
+                ]),
+            ),
+        ]);
+
+        let dictionary = UserDictionary::from_yomitan_zip(&data, &NativeLanguage::English).unwrap();
+
+        assert_eq!(
+            dictionary.translations("生", Some("ナマ"), &NativeLanguage::English),
+            ["raw"]
+        );
+        assert_eq!(
+            dictionary.translations("生", Some("せい"), &NativeLanguage::English),
+            ["life"]
+        );
+        assert_eq!(
+            dictionary.translations("生", None, &NativeLanguage::English),
+            ["raw", "life"]
+        );
+        assert_eq!(dictionary.sources()[0].term_count(), 2);
+    }
+
+    #[rstest]
+    #[case(json!({ "title": "JMdict", "targetLanguage": "ru" }), NativeLanguage::Russian)]
+    #[case(json!({ "title": "JMdict", "sourceLanguage": "ja", "targetLanguage": "en" }), NativeLanguage::English)]
+    #[case(json!({ "title": "JMdict" }), NativeLanguage::English)]
+    fn glossary_language_comes_from_index(#[case] index: Value, #[case] expected: NativeLanguage) {
+        let data = yomitan_zip(&[
+            (INDEX_FILE, index),
+            (
+                "term_bank_1.json",
+                json!([["猫", "ねこ", "", "", 0, ["gloss"], 1, ""]]),
+            ),
+        ]);
+
+        let dictionary = UserDictionary::from_yomitan_zip(&data, &NativeLanguage::English).unwrap();
+
+        assert_eq!(dictionary.translations("猫", None, &expected), ["gloss"]);
+    }
+
+    #[rstest]
+    #[case(json!({ "title": "Dict", "targetLanguage": "de" }))]
+    #[case(json!({ "title": "Dict", "sourceLanguage": "zh", "targetLanguage": "en" }))]
+    fn unsupported_dictionary_languages_are_rejected(#[case] index: Value) {
+        let data = yomitan_zip(&[(INDEX_FILE, index)]);
+
+        let result = UserDictionary::from_yomitan_zip(&data, &NativeLanguage::English);
+
+        assert!(matches!(result, Err(OrigaError::YomitanInvalidFile { .. })));
+    }
+
+    #[test]
+    fn kanji_and_frequency_banks_are_kept() {
+        let data = yomitan_zip(&[
+            index("KANJIDIC"),
+            (
+                "kanji_bank_1.json",
+                json!([["猫", "ビョウ", "ねこ", "", ["кошка"], {}]]),
+            ),
+            (
+                "term_meta_bank_1.json",
+                json!([
+                    ["猫", "freq", 1200],
+                    ["猫", "freq", { "reading": "ねこ", "frequency": { "value": 800 } }],
+                    ["猫", "pitch", { "reading": "ねこ", "pitches": [] }]
+                ]),
+            ),
+        ]);
+
+        let dictionary = UserDictionary::from_yomitan_zip(&data, &NativeLanguage::Russian).unwrap();
+
+        let kanji = dictionary.kanji("猫").unwrap();
+        assert_eq!(kanji.onyomi(), ["ビョウ"]);
+        assert_eq!(kanji.kunyomi(), ["ねこ"]);
+        assert_eq!(kanji.meanings(&NativeLanguage::Russian), ["кошка"]);
+        assert_eq!(dictionary.frequency_rank("猫"), Some(800));
+    }
+
+    #[test]
+    fn zip_without_index_is_rejected() {
+        let data = yomitan_zip(&[("term_bank_1.json", json!([]))]);
+
+        let result = UserDictionary::from_yomitan_zip(&data, &NativeLanguage::English);
+
+        assert!(matches!(result, Err(OrigaError::YomitanInvalidFile { .. })));
+    }
+
+    #[test]
+    fn rkyv_user_dictionary_round_trip() {
+        let data = yomitan_zip(&[
+            index("JMdict"),
+            (
+                "term_bank_1.json",
+                json!([["猫", "ねこ", "", "", 0, ["cat"], 1, ""]]),
+            ),
+        ]);
+        let dictionary = UserDictionary::from_yomitan_zip(&data, &NativeLanguage::English).unwrap();
+
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&dictionary).unwrap();
+        let restored = rkyv::from_bytes::<UserDictionary, rkyv::rancor::Error>(&bytes).unwrap();
+
+        assert_eq!(restored.sources(), dictionary.sources());
+        assert_eq!(
+            restored.translations("猫", None, &NativeLanguage::English),
+            ["cat"]
+        );
+    }
+
+    #[test]
+    fn vocabulary_lookups_fall_back_to_imported_terms() {
+        // A word no built-in dictionary contains, so the shared layer does
+        // not affect other tests.
+        let data = yomitan_zip(&[
+            index("Fallback test"),
+            (
+                "term_bank_1.json",
+                json!([["ゆーざーじしょ", "", "", "", 0, ["user dictionary"], 1, ""]]),
+            ),
+        ]);
+
+        import_yomitan_dictionary(&data, &NativeLanguage::English).unwrap();
+
+        assert_eq!(
+            crate::dictionary::vocabulary::get_translations(
+                "ゆーざーじしょ",
+                &NativeLanguage::English
+            ),
+            Some(vec!["user dictionary".to_string()])
+        );
+        assert!(
+            crate::dictionary::vocabulary::get_translation(
+                "ゆーざーじしょ",
+                &NativeLanguage::Russian
+            )
+            .is_none()
+        );
+        assert!(
+            user_dictionary_sources()
+                .iter()
+                .any(|source| source.title() == "Fallback test")
+        );
+    }
+
+    #[test]
+    fn removed_dictionary_no_longer_answers_lookups() {
+        let data = yomitan_zip(&[
+            index("Removal test"),
+            (
+                "term_bank_1.json",
+                json!([["けすじしょ", "", "", "", 0, ["removed"], 1, ""]]),
+            ),
+        ]);
+        let source = import_yomitan_dictionary(&data, &NativeLanguage::English).unwrap();
+
+        let removed = remove_user_dictionary(&source);
+
+        assert!(removed);
+        assert!(get_user_translations("けすじしょ", None, &NativeLanguage::English).is_none());
+        assert!(!user_dictionary_sources().contains(&source));
+        assert!(!remove_user_dictionary(&source));
+    }
+}
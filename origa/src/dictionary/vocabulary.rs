@@ -2,6 +2,7 @@ use std::{collections::HashMap, sync::OnceLock};
 
 use serde::Deserialize;
 
+use crate::dictionary::user_dictionary::get_user_translations;
 use crate::domain::{NativeLanguage, OrigaError};
 
 pub static VOCABULARY_DICTIONARY: OnceLock<VocabularyDatabase> = OnceLock::new();
@@ -138,6 +139,35 @@ impl VocabularyInfo {
         self.en_description.as_deref()
     }
 
+    /// An entry without translations, filled in by imported user
+    /// dictionaries through [`Self::extend_translations`].
+    pub(crate) fn empty(word: String) -> Self {
+        Self {
+            word,
+            ru_translations: Vec::new(),
+            ru_description: None,
+            en_translations: Vec::new(),
+            en_description: None,
+        }
+    }
+
+    /// Appends `translations` not already listed for `lang`.
+    pub(crate) fn extend_translations(
+        &mut self,
+        lang: &NativeLanguage,
+        translations: impl IntoIterator<Item = String>,
+    ) {
+        let existing = match lang {
+            NativeLanguage::Russian => &mut self.ru_translations,
+            NativeLanguage::English => &mut self.en_translations,
+        };
+        for translation in translations {
+            if !existing.contains(&translation) {
+                existing.push(translation);
+            }
+        }
+    }
+
     pub fn translations(&self, lang: &NativeLanguage) -> &[String] {
         match lang {
             NativeLanguage::Russian => &self.ru_translations,
@@ -224,16 +254,39 @@ pub fn is_vocabulary_loaded() -> bool {
     VOCABULARY_DICTIONARY.get().is_some()
 }
 
+/// Looks `word` up in the built-in dictionary, then in the imported user
+/// dictionaries.
 pub fn get_translation(word: &str, native_language: &NativeLanguage) -> Option<String> {
+    get_translation_for_reading(word, None, native_language)
+}
+
+/// Like [`get_translation`], but entries from imported user dictionaries
+/// must match `reading` when one is given. The built-in dictionary is keyed
+/// by word only.
+pub fn get_translation_for_reading(
+    word: &str,
+    reading: Option<&str>,
+    native_language: &NativeLanguage,
+) -> Option<String> {
     VOCABULARY_DICTIONARY
         .get()
         .and_then(|db| db.get_translation(word, native_language))
+        .or_else(|| {
+            get_user_translations(word, reading, native_language).map(|translations| {
+                translations
+                    .iter()
+                    .map(|t| format!("- {}", t))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+        })
 }
 
 pub fn get_translations(word: &str, native_language: &NativeLanguage) -> Option<Vec<String>> {
     VOCABULARY_DICTIONARY
         .get()
         .and_then(|db| db.get_translations(word, native_language))
+        .or_else(|| get_user_translations(word, None, native_language))
 }
 
 pub fn get_description(word: &str, native_language: &NativeLanguage) -> Option<String> {
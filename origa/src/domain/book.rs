@@ -0,0 +1,637 @@
+use std::io::{Cursor, Read};
+
+use encoding_rs::{EUC_JP, Encoding, SHIFT_JIS};
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::domain::OrigaError;
+
+const SKIPPED_ELEMENTS: [&str; 6] = ["head", "rt", "rp", "script", "style", "svg"];
+const BLOCK_ELEMENTS: [&str; 14] = [
+    "p",
+    "div",
+    "br",
+    "li",
+    "tr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "section",
+    "blockquote",
+    "hr",
+];
+const HEADING_ELEMENTS: [&str; 3] = ["h1", "h2", "h3"];
+const CHAPTER_COUNTERS: [char; 5] = ['章', '話', '部', '幕', '節'];
+const SENTENCE_TERMINATORS: [char; 5] = ['。', '！', '？', '!', '?'];
+const OPENING_QUOTES: [char; 4] = ['「', '『', '（', '('];
+const CLOSING_QUOTES: [char; 4] = ['」', '』', '）', ')'];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookChapter {
+    title: String,
+    text: String,
+}
+
+impl BookChapter {
+    pub fn new(title: String, text: String) -> Self {
+        Self { title, text }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn sentences(&self) -> Vec<String> {
+        split_sentences(&self.text)
+    }
+}
+
+/// Splits text on sentence-final punctuation and line breaks. Punctuation
+/// inside quotes (`「行くぞ！」と言った。`) does not end the sentence.
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut quote_depth = 0usize;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            push_sentence(&mut sentences, &mut current);
+            quote_depth = 0;
+            continue;
+        }
+        current.push(c);
+        if OPENING_QUOTES.contains(&c) {
+            quote_depth += 1;
+        } else if CLOSING_QUOTES.contains(&c) {
+            quote_depth = quote_depth.saturating_sub(1);
+        } else if quote_depth == 0 && SENTENCE_TERMINATORS.contains(&c) {
+            while let Some(&next) = chars.peek().filter(|n| SENTENCE_TERMINATORS.contains(n)) {
+                current.push(next);
+                chars.next();
+            }
+            push_sentence(&mut sentences, &mut current);
+        }
+    }
+    push_sentence(&mut sentences, &mut current);
+
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, current: &mut String) {
+    let sentence = current.trim();
+    if !sentence.is_empty() {
+        sentences.push(sentence.to_string());
+    }
+    current.clear();
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Book {
+    title: String,
+    chapters: Vec<BookChapter>,
+}
+
+impl Book {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn chapters(&self) -> &[BookChapter] {
+        &self.chapters
+    }
+
+    /// Dispatches on the file extension: `.epub` archives or plain `.txt`
+    /// (see [`decode_text`] for the accepted encodings).
+    pub fn from_file(file_name: &str, data: &[u8]) -> Result<Self, OrigaError> {
+        let (stem, extension) = file_name.rsplit_once('.').unwrap_or((file_name, ""));
+        match extension.to_ascii_lowercase().as_str() {
+            "epub" => Self::from_epub(data),
+            "txt" => Ok(Self::from_plain_text(stem, &decode_text(file_name, data)?)),
+            _ => Err(OrigaError::BookParseError {
+                reason: format!("Unsupported book format: {file_name}"),
+            }),
+        }
+    }
+
+    /// Splits a plain-text book on chapter headings (`第一章`, `第3話`,
+    /// `Chapter 2`, `# Title`). Aozora Bunko ruby and editor notes are
+    /// stripped. A text without headings becomes a single chapter.
+    pub fn from_plain_text(title: &str, text: &str) -> Self {
+        let text = strip_aozora_markup(text.trim_start_matches('\u{feff}'));
+        let mut chapters = Vec::new();
+        let mut chapter_title = title.to_string();
+        let mut body = String::new();
+
+        for line in text.lines() {
+            let trimmed = line.trim();
+            if is_chapter_heading(trimmed) {
+                push_chapter(&mut chapters, &chapter_title, &body);
+                chapter_title = trimmed.trim_start_matches('#').trim().to_string();
+                body.clear();
+                continue;
+            }
+            body.push_str(trimmed);
+            body.push('\n');
+        }
+        push_chapter(&mut chapters, &chapter_title, &body);
+
+        Self {
+            title: title.to_string(),
+            chapters,
+        }
+    }
+
+    /// Reads chapters in spine order. Each spine document is one chapter,
+    /// titled by its first heading; furigana (`<rt>`) is dropped so readings
+    /// do not leak into the analysed text.
+    pub fn from_epub(data: &[u8]) -> Result<Self, OrigaError> {
+        let mut archive = ZipArchive::new(Cursor::new(data)).map_err(invalid_epub)?;
+
+        let container = read_entry(&mut archive, "META-INF/container.xml")?;
+        let opf_path = find_tags(&container, "rootfile")
+            .into_iter()
+            .find_map(|tag| attribute(tag, "full-path"))
+            .ok_or_else(|| invalid_epub("container.xml has no rootfile"))?;
+        let opf = read_entry(&mut archive, &opf_path)?;
+        let base_dir = opf_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+
+        let title = element_text(&opf, "dc:title").unwrap_or_default();
+        let manifest: Vec<(String, String)> = find_tags(&opf, "item")
+            .into_iter()
+            .filter_map(|tag| Some((attribute(tag, "id")?, attribute(tag, "href")?)))
+            .collect();
+
+        let mut chapters = Vec::new();
+        for itemref in find_tags(&opf, "itemref") {
+            if attribute(itemref, "linear").as_deref() == Some("no") {
+                continue;
+            }
+            let Some(href) = attribute(itemref, "idref")
+                .and_then(|id| manifest.iter().find(|(item_id, _)| *item_id == id))
+                .map(|(_, href)| href.clone())
+            else {
+                continue;
+            };
+
+            let path = resolve_path(base_dir, &href);
+            let document = read_entry(&mut archive, &path)?;
+            let (heading, text) = xhtml_to_text(&document);
+            let chapter_title = heading
+                .or_else(|| element_text(&document, "title"))
+                .unwrap_or_else(|| format!("{}", chapters.len() + 1));
+            push_chapter(&mut chapters, &chapter_title, &text);
+        }
+
+        if chapters.is_empty() {
+            return Err(invalid_epub("spine has no readable chapters"));
+        }
+
+        Ok(Self { title, chapters })
+    }
+}
+
+fn push_chapter(chapters: &mut Vec<BookChapter>, title: &str, body: &str) {
+    let text = body.trim();
+    if !text.is_empty() {
+        chapters.push(BookChapter::new(title.to_string(), text.to_string()));
+    }
+}
+
+fn is_chapter_heading(line: &str) -> bool {
+    if line.starts_with('#') {
+        return true;
+    }
+    if line
+        .get(..8)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("chapter "))
+    {
+        return true;
+    }
+    let Some(rest) = line.strip_prefix('第') else {
+        return false;
+    };
+    // `第一章`, `第12話 タイトル`: the counter must follow a short number.
+    rest.chars()
+        .take(6)
+        .take_while(|c| !c.is_whitespace())
+        .any(|c| CHAPTER_COUNTERS.contains(&c))
+}
+
+fn decode_text(file_name: &str, data: &[u8]) -> Result<String, OrigaError> {
+    decode_japanese_text(data).ok_or_else(|| OrigaError::BookParseError {
+        reason: format!("{file_name} is not UTF-8, Shift_JIS or EUC-JP text"),
+    })
+}
+
+/// Text files are taken as UTF-8, or UTF-16 when a BOM says so. Aozora
+/// Bunko and most older Japanese `.txt` files are Shift_JIS; EUC-JP is the
+/// last resort.
+pub(crate) fn decode_japanese_text(data: &[u8]) -> Option<String> {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(data) {
+        let (text, _) = encoding.decode_without_bom_handling(&data[bom_length..]);
+        return Some(text.into_owned());
+    }
+    if let Ok(text) = std::str::from_utf8(data) {
+        return Some(text.to_string());
+    }
+    [SHIFT_JIS, EUC_JP].into_iter().find_map(|encoding| {
+        let (text, had_errors) = encoding.decode_without_bom_handling(data);
+        (!had_errors).then(|| text.into_owned())
+    })
+}
+
+/// Aozora Bunko texts mark ruby as `｜漢字《かんじ》` and editor notes as `［＃…］`.
+fn strip_aozora_markup(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut closing: Option<char> = None;
+    for c in text.chars() {
+        match closing {
+            Some(end) if c == end => closing = None,
+            Some(_) => {},
+            None if c == '《' => closing = Some('》'),
+            None if c == '［' => closing = Some('］'),
+            None if c == '｜' => {},
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+fn invalid_epub(reason: impl std::fmt::Display) -> OrigaError {
+    OrigaError::BookParseError {
+        reason: format!("Invalid EPUB: {reason}"),
+    }
+}
+
+fn read_entry(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<String, OrigaError> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| invalid_epub(format!("{name}: {e}")))?;
+    let mut text = String::new();
+    entry
+        .read_to_string(&mut text)
+        .map_err(|e| invalid_epub(format!("{name}: {e}")))?;
+    Ok(text)
+}
+
+/// Hrefs in the OPF are relative to the OPF's own directory.
+fn resolve_path(base_dir: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href).replace("%20", " ");
+    let mut parts: Vec<&str> = base_dir.split('/').filter(|p| !p.is_empty()).collect();
+    for segment in href.split('/') {
+        match segment {
+            "." | "" => {},
+            ".." => {
+                parts.pop();
+            },
+            _ => parts.push(segment),
+        }
+    }
+    parts.join("/")
+}
+
+/// Returns the inner part of every opening tag named `name` (e.g. `item id="x"`).
+/// Tags named `name`, with or without a namespace prefix: packages that
+/// declare the OPF namespace under a prefix write `<opf:item>`.
+fn find_tags<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    xml.split('<')
+        .filter_map(|chunk| chunk.split_once('>').map(|(tag, _)| tag))
+        .filter(|tag| {
+            let tag_name = tag_name(tag);
+            tag_name
+                .rsplit_once(':')
+                .map_or(tag_name.as_str(), |(_, local)| local)
+                == name
+        })
+        .collect()
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let pattern = format!("{name}={quote}");
+        let mut search = tag;
+        while let Some(index) = search.find(&pattern) {
+            let preceded_by_space = search[..index]
+                .chars()
+                .last()
+                .is_some_and(char::is_whitespace);
+            let value = &search[index + pattern.len()..];
+            if preceded_by_space {
+                return value.split_once(quote).map(|(v, _)| decode_entities(v));
+            }
+            search = value;
+        }
+    }
+    None
+}
+
+fn element_text(xml: &str, name: &str) -> Option<String> {
+    let start = xml.find(&format!("<{name}"))?;
+    let content = &xml[start..];
+    let content = &content[content.find('>')? + 1..];
+    let end = content.find(&format!("</{name}"))?;
+    let text = decode_entities(strip_tags(&content[..end]).trim());
+    (!text.is_empty()).then_some(text)
+}
+
+fn strip_tags(xml: &str) -> String {
+    let mut result = String::with_capacity(xml.len());
+    let mut in_tag = false;
+    for c in xml.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {},
+        }
+    }
+    result
+}
+
+/// Converts an XHTML document to plain text with block elements on their own
+/// lines, and returns the first `h1`-`h3` heading as the chapter title.
+fn xhtml_to_text(xhtml: &str) -> (Option<String>, String) {
+    let mut text = String::new();
+    let mut heading: Option<String> = None;
+    let mut heading_buffer: Option<String> = None;
+    let mut skip_depth = 0usize;
+
+    let mut rest = xhtml;
+    while let Some(open) = rest.find('<') {
+        let chunk = &rest[..open];
+        if skip_depth == 0 {
+            let decoded = decode_entities(chunk);
+            text.push_str(&decoded);
+            if let Some(buffer) = heading_buffer.as_mut() {
+                buffer.push_str(&decoded);
+            }
+        }
+
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        let tag = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        let name = tag_name(tag);
+        let is_closing = tag.starts_with('/');
+        let is_self_closing = tag.ends_with('/');
+
+        if SKIPPED_ELEMENTS.contains(&name.as_str()) && !is_self_closing {
+            if is_closing {
+                skip_depth = skip_depth.saturating_sub(1);
+            } else {
+                skip_depth += 1;
+            }
+            continue;
+        }
+        if BLOCK_ELEMENTS.contains(&name.as_str()) {
+            text.push('\n');
+        }
+        if heading.is_none() && HEADING_ELEMENTS.contains(&name.as_str()) {
+            if is_closing {
+                heading = heading_buffer
+                    .take()
+                    .map(|h| h.trim().to_string())
+                    .filter(|h| !h.is_empty());
+            } else {
+                heading_buffer = Some(String::new());
+            }
+        }
+    }
+    if skip_depth == 0 {
+        text.push_str(&decode_entities(rest));
+    }
+
+    let text = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (heading, text)
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            result.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            },
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            },
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use rstest::rstest;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    fn build_epub(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn sample_epub() -> Vec<u8> {
+        build_epub(&[
+            ("mimetype", "application/epub+zip"),
+            (
+                "META-INF/container.xml",
+                r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles></container>"#,
+            ),
+            (
+                "OEBPS/content.opf",
+                r#"<package><metadata><dc:title>吾輩は猫である</dc:title></metadata>
+                <manifest>
+                  <item id="cover" href="cover.xhtml" media-type="application/xhtml+xml"/>
+                  <item id="c1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+                  <item id="c2" href="text/ch2.xhtml" media-type="application/xhtml+xml"/>
+                </manifest>
+                <spine><itemref idref="cover" linear="no"/><itemref idref="c1"/><itemref idref="c2"/></spine></package>"#,
+            ),
+            ("OEBPS/cover.xhtml", "<html><body><p>表紙</p></body></html>"),
+            (
+                "OEBPS/text/ch1.xhtml",
+                "<html><head><title>一</title></head><body><h1>第一章</h1><p>吾輩は<ruby>猫<rt>ねこ</rt></ruby>である。</p><p>名前はまだ無い&#12290;</p></body></html>",
+            ),
+            (
+                "OEBPS/text/ch2.xhtml",
+                "<html><head><title>二</title></head><body><p>どこで生れたか&amp;とんと見当がつかぬ。</p></body></html>",
+            ),
+        ])
+    }
+
+    #[test]
+    fn epub_chapters_follow_spine_and_drop_furigana() {
+        let book = Book::from_epub(&sample_epub()).unwrap();
+
+        assert_eq!(book.title(), "吾輩は猫である");
+        assert_eq!(book.chapters().len(), 2);
+        assert_eq!(book.chapters()[0].title(), "第一章");
+        assert_eq!(
+            book.chapters()[0].text(),
+            "第一章\n吾輩は猫である。\n名前はまだ無い。"
+        );
+        assert_eq!(book.chapters()[1].title(), "二");
+        assert_eq!(
+            book.chapters()[1].text(),
+            "どこで生れたか&とんと見当がつかぬ。"
+        );
+    }
+
+    #[test]
+    fn epub_with_prefixed_opf_tags_is_read() {
+        let data = build_epub(&[
+            ("mimetype", "application/epub+zip"),
+            (
+                "META-INF/container.xml",
+                r#"<container><rootfiles><rootfile full-path="content.opf"/></rootfiles></container>"#,
+            ),
+            (
+                "content.opf",
+                r#"<opf:package xmlns:opf="http://www.idpf.org/2007/opf"><opf:metadata><dc:title>坊っちゃん</dc:title></opf:metadata>
+                <opf:manifest><opf:item id="c1" href="ch1.xhtml" media-type="application/xhtml+xml"/></opf:manifest>
+                <opf:spine><opf:itemref idref="c1"/></opf:spine></opf:package>"#,
+            ),
+            (
+                "ch1.xhtml",
+                "<html><body><h1>一</h1><p>親譲りの無鉄砲で小供の時から損ばかりしている。</p></body></html>",
+            ),
+        ]);
+
+        let book = Book::from_epub(&data).unwrap();
+
+        assert_eq!(book.title(), "坊っちゃん");
+        assert_eq!(book.chapters().len(), 1);
+        assert_eq!(book.chapters()[0].title(), "一");
+    }
+
+    #[test]
+    fn epub_without_container_is_rejected() {
+        let data = build_epub(&[("mimetype", "application/epub+zip")]);
+
+        let error = Book::from_epub(&data).unwrap_err();
+
+        assert!(matches!(error, OrigaError::BookParseError { .. }));
+    }
+
+    #[test]
+    fn plain_text_splits_on_chapter_headings() {
+        let text =
+            "はじめに。\n第一章　出会い\n猫が来た。\n\n第2話\n犬も来た。\nChapter 3\n鳥が来た。";
+
+        let book = Book::from_plain_text("物語", text);
+
+        let titles: Vec<&str> = book.chapters().iter().map(BookChapter::title).collect();
+        assert_eq!(titles, ["物語", "第一章　出会い", "第2話", "Chapter 3"]);
+        assert_eq!(book.chapters()[1].text(), "猫が来た。");
+    }
+
+    #[test]
+    fn plain_text_strips_aozora_ruby_and_notes() {
+        let book = Book::from_plain_text("本", "｜吾輩《わがはい》は猫である。［＃改ページ］");
+
+        assert_eq!(book.chapters()[0].text(), "吾輩は猫である。");
+    }
+
+    #[test]
+    fn sentences_do_not_split_inside_quotes() {
+        let chapter = BookChapter::new(
+            "一".to_string(),
+            "「行くぞ！」と言った。本当か？！\n分からない".to_string(),
+        );
+
+        assert_eq!(
+            chapter.sentences(),
+            ["「行くぞ！」と言った。", "本当か？！", "分からない"]
+        );
+    }
+
+    #[rstest]
+    #[case(SHIFT_JIS)]
+    #[case(EUC_JP)]
+    fn from_file_decodes_legacy_japanese_encodings(#[case] encoding: &'static Encoding) {
+        let (data, _, _) = encoding.encode("第一章\n吾輩は猫である。");
+
+        let book = Book::from_file("neko.txt", &data).unwrap();
+
+        assert_eq!(book.title(), "neko");
+        assert_eq!(book.chapters()[0].text(), "吾輩は猫である。");
+    }
+
+    #[test]
+    fn from_file_rejects_undecodable_text() {
+        let error = Book::from_file("book.txt", &[0x82, 0xFF, 0xFE, 0x80]).unwrap_err();
+
+        assert!(matches!(error, OrigaError::BookParseError { .. }));
+    }
+
+    #[test]
+    fn from_file_rejects_unknown_extension() {
+        let error = Book::from_file("book.pdf", b"%PDF").unwrap_err();
+
+        assert!(matches!(error, OrigaError::BookParseError { .. }));
+    }
+}
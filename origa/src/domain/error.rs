@@ -30,6 +30,8 @@ pub enum OrigaError {
     InvalidMemoryState { reason: String },
     #[error("SRS calculation failed: {reason}")]
     SrsCalculationFailed { reason: String },
+    #[error("Invalid SRS settings: {reason}")]
+    InvalidSrsSettings { reason: String },
     #[error("Repository error: {reason}")]
     RepositoryError { reason: String },
     #[error("Embedding error: {reason}")]
@@ -74,6 +76,14 @@ pub enum OrigaError {
     AnkiDatabaseNotFound { filename: String },
     #[error("Field '{field_name}' not found in Anki deck models")]
     AnkiFieldNotFound { field_name: String },
+    #[error("Failed to write Anki package: {reason}")]
+    AnkiExportFailed { reason: String },
+    #[error("Invalid Yomitan dictionary: {reason}")]
+    YomitanInvalidFile { reason: String },
+    #[error("Subtitle parse error: {reason}")]
+    SubtitleParseError { reason: String },
+    #[error("Book parse error: {reason}")]
+    BookParseError { reason: String },
     #[error("No description for kanji: {kanji}")]
     KanjiNotFound { kanji: String },
     #[error("Grammar rule not found: {rule_id}")]
@@ -106,6 +116,7 @@ impl OrigaError {
             | Self::InvalidDifficulty { .. }
             | Self::InvalidMemoryState { .. }
             | Self::SrsCalculationFailed { .. }
+            | Self::InvalidSrsSettings { .. }
             | Self::FuriganaError { .. }
             | Self::VocabularyParseError { .. }
             | Self::InvalidValues { .. }
@@ -138,7 +149,11 @@ impl OrigaError {
             Self::KradfileError { .. }
             | Self::AnkiInvalidFile { .. }
             | Self::AnkiDatabaseNotFound { .. }
-            | Self::AnkiFieldNotFound { .. } => ErrorCategory::Import,
+            | Self::AnkiFieldNotFound { .. }
+            | Self::AnkiExportFailed { .. }
+            | Self::YomitanInvalidFile { .. }
+            | Self::SubtitleParseError { .. }
+            | Self::BookParseError { .. } => ErrorCategory::Import,
         }
     }
 
@@ -514,6 +529,51 @@ mod tests {
         assert_serialization_roundtrip(error);
     }
 
+    #[test]
+    fn anki_export_failed() {
+        let error = OrigaError::AnkiExportFailed {
+            reason: "disk full".into(),
+        };
+        assert_display_contains(&error, "disk full");
+        assert_serialization_roundtrip(error);
+    }
+
+    #[test]
+    fn yomitan_invalid_file() {
+        let error = OrigaError::YomitanInvalidFile {
+            reason: "index.json missing".into(),
+        };
+        assert_display_contains(&error, "index.json missing");
+        assert_serialization_roundtrip(error);
+    }
+
+    #[test]
+    fn subtitle_parse_error() {
+        let error = OrigaError::SubtitleParseError {
+            reason: "bad timestamp".into(),
+        };
+        assert_display_contains(&error, "bad timestamp");
+        assert_serialization_roundtrip(error);
+    }
+
+    #[test]
+    fn book_parse_error() {
+        let error = OrigaError::BookParseError {
+            reason: "spine has no readable chapters".into(),
+        };
+        assert_display_contains(&error, "spine has no readable chapters");
+        assert_serialization_roundtrip(error);
+    }
+
+    #[test]
+    fn invalid_srs_settings() {
+        let error = OrigaError::InvalidSrsSettings {
+            reason: "retention out of range".into(),
+        };
+        assert_display_contains(&error, "Invalid SRS settings");
+        assert_serialization_roundtrip(error);
+    }
+
     #[test]
     fn pitch_audio_parse_error() {
         let error = OrigaError::PitchAudioParseError {
@@ -682,6 +742,7 @@ mod tests {
             OrigaError::InvalidDifficulty { reason: "r".into() },
             OrigaError::InvalidMemoryState { reason: "r".into() },
             OrigaError::SrsCalculationFailed { reason: "r".into() },
+            OrigaError::InvalidSrsSettings { reason: "r".into() },
             OrigaError::FuriganaError { reason: "r".into() },
             OrigaError::VocabularyParseError { reason: "r".into() },
             OrigaError::InvalidValues { reason: "r".into() },
@@ -745,12 +806,16 @@ mod tests {
             OrigaError::AnkiFieldNotFound {
                 field_name: "f".into(),
             },
+            OrigaError::AnkiExportFailed { reason: "r".into() },
+            OrigaError::YomitanInvalidFile { reason: "r".into() },
+            OrigaError::SubtitleParseError { reason: "r".into() },
+            OrigaError::BookParseError { reason: "r".into() },
         ];
         for error in &all_import {
             assert!(error.is_import(), "{error:?} should be Import");
         }
 
         let total = all_domain.len() + all_infrastructure.len() + all_import.len();
-        assert_eq!(total, 40, "all 40 variants must be covered");
+        assert_eq!(total, 45, "all 45 variants must be covered");
     }
 }
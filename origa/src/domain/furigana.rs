@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use crate::dictionary::furigana_dict::{ReadingSpan, is_furigana_dict_loaded};
-use crate::domain::furigana_annotator::AnnotatedSpan;
+use crate::domain::furigana_annotator::{AnnotatedSpan, ReadingHint, annotate_text_with_hints};
 use crate::domain::{OrigaError, japanese::JapaneseChar, tokenizer::tokenize_text};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -40,9 +40,20 @@ impl FuriganaSegment {
 pub fn furiganize_segments(
     text: &str,
     known_kanji: &HashSet<char>,
+) -> Result<Vec<FuriganaSegment>, OrigaError> {
+    furiganize_segments_with_hints(text, &[], known_kanji)
+}
+
+/// Like `furiganize_segments`, but readings the source printed itself (OCR
+/// ruby) win over the dictionary's guess. Hints need the furigana dictionary;
+/// without it the tokenizer readings are used as before.
+pub fn furiganize_segments_with_hints(
+    text: &str,
+    hints: &[ReadingHint],
+    known_kanji: &HashSet<char>,
 ) -> Result<Vec<FuriganaSegment>, OrigaError> {
     if is_furigana_dict_loaded() {
-        let spans = crate::domain::furigana_annotator::annotate_text(text)?;
+        let spans = annotate_text_with_hints(text, hints)?;
         return Ok(spans_to_segments(spans, known_kanji));
     }
 
@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::dictionary::furigana_dict::{self, FuriganaEntry, ReadingSpan, get_furigana_dict};
-use crate::domain::OrigaError;
 use crate::domain::hiragana_to_katakana;
 use crate::domain::tokenizer::{TokenInfo, tokenize_text};
+use crate::domain::{JapaneseChar, OrigaError};
 
 #[derive(Debug, Clone)]
 pub struct AnnotatedSpan {
@@ -10,6 +12,14 @@ pub struct AnnotatedSpan {
     pub reading_spans: Vec<ReadingSpan>,
 }
 
+/// A reading the source itself gives for a kanji run, e.g. furigana
+/// printed as ruby in a scanned book. It beats the dictionary's guess.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadingHint {
+    pub base: String,
+    pub reading: String,
+}
+
 enum InternalToken {
     Single {
         surface: String,
@@ -39,6 +49,59 @@ pub fn annotate_text(text: &str) -> Result<Vec<AnnotatedSpan>, OrigaError> {
         .collect())
 }
 
+/// Like `annotate_text`, but spans whose every kanji run has a hint take
+/// their reading from the hints. When several hints share a base the first
+/// one wins, so callers order them nearest-first.
+pub fn annotate_text_with_hints(
+    text: &str,
+    hints: &[ReadingHint],
+) -> Result<Vec<AnnotatedSpan>, OrigaError> {
+    Ok(annotate_text(text)?
+        .into_iter()
+        .map(|span| apply_hints(span, hints))
+        .collect())
+}
+
+fn apply_hints(span: AnnotatedSpan, hints: &[ReadingHint]) -> AnnotatedSpan {
+    let chars: Vec<char> = span.text.chars().collect();
+    if hints.is_empty() || !chars.iter().any(|c| c.is_kanji()) {
+        return span;
+    }
+
+    let mut reading = String::new();
+    let mut reading_spans = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        if !chars[index].is_kanji() {
+            reading.push_str(&hiragana_to_katakana(&chars[index].to_string()));
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < chars.len() && chars[index].is_kanji() {
+            index += 1;
+        }
+        let run: String = chars[start..index].iter().collect();
+        let Some(hint) = hints.iter().find(|hint| hint.base == run) else {
+            return span;
+        };
+        let run_reading = hiragana_to_katakana(&hint.reading);
+        reading.push_str(&run_reading);
+        reading_spans.push(ReadingSpan {
+            start_index: start,
+            end_index: index - 1,
+            text: run_reading,
+        });
+    }
+
+    AnnotatedSpan {
+        text: span.text,
+        reading: Some(reading),
+        reading_spans,
+    }
+}
+
 fn build_internal_tokens(
     tokens: &[TokenInfo],
     dict: &furigana_dict::FuriganaDictionary,
@@ -311,4 +374,53 @@ mod tests {
             reading
         );
     }
+
+    fn span(text: &str, reading: &str) -> AnnotatedSpan {
+        AnnotatedSpan {
+            text: text.to_string(),
+            reading: Some(reading.to_string()),
+            reading_spans: vec![],
+        }
+    }
+
+    fn hint(base: &str, reading: &str) -> ReadingHint {
+        ReadingHint {
+            base: base.to_string(),
+            reading: reading.to_string(),
+        }
+    }
+
+    #[test]
+    fn printed_ruby_overrides_dictionary_reading() {
+        let hints = [hint("明日", "あす"), hint("食", "た"), hint("物", "もの")];
+
+        let tomorrow = apply_hints(span("明日", "アシタ"), &hints);
+        let food = apply_hints(span("食べ物", "タベモノ"), &hints);
+
+        assert_eq!(tomorrow.reading.as_deref(), Some("アス"));
+        assert_eq!(food.reading.as_deref(), Some("タベモノ"));
+        assert_eq!(
+            food.reading_spans
+                .iter()
+                .map(|s| (s.start_index, s.end_index, s.text.as_str()))
+                .collect::<Vec<_>>(),
+            [(0, 0, "タ"), (2, 2, "モノ")]
+        );
+    }
+
+    #[test]
+    fn span_with_unhinted_kanji_keeps_dictionary_reading() {
+        let result = apply_hints(span("大人", "オトナ"), &[hint("大", "だい")]);
+
+        assert_eq!(result.reading.as_deref(), Some("オトナ"));
+    }
+
+    #[test]
+    fn first_hint_for_a_base_wins() {
+        let hints = [hint("上手", "うわて"), hint("上手", "じょうず")];
+
+        let result = apply_hints(span("上手", "ジョウズ"), &hints);
+
+        assert_eq!(result.reading.as_deref(), Some("ウワテ"));
+    }
 }
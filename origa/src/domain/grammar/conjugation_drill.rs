@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+
+use super::apply_format_actions;
+use super::deconjugate::step_name;
+use crate::dictionary::grammar::FormatAction;
+use crate::domain::japanese::katakana_to_hiragana;
+use crate::domain::{OrigaError, PartOfSpeech};
+
+/// Form the word takes after one step of a conjugation chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConjugationStepForm {
+    pub label: String,
+    pub form: String,
+}
+
+/// Result of checking a typed conjugation against the forward chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConjugationCheck {
+    pub is_correct: bool,
+    pub expected: String,
+    pub steps: Vec<ConjugationStepForm>,
+    /// Index into `steps` of the first step the answer does not carry,
+    /// `None` when the answer is correct.
+    pub wrong_step: Option<usize>,
+}
+
+/// Checks `answer` against `actions` applied to `word`, ignoring the
+/// hiragana/katakana distinction. A kana `reading` of `word`, when given, is
+/// conjugated as well so answers typed without kanji are accepted.
+///
+/// A step counts as done when the answer starts with the part of that step's
+/// form which later steps keep unchanged, so for 食べさせられなかった the
+/// answer 食べさられなかった is blamed on the causative-passive step.
+pub fn check_conjugation(
+    word: &str,
+    reading: Option<&str>,
+    actions: &[FormatAction],
+    part_of_speech: &PartOfSpeech,
+    answer: &str,
+) -> Result<ConjugationCheck, OrigaError> {
+    let answer = katakana_to_hiragana(answer.trim());
+    let written = step_forms(word, actions, part_of_speech)?;
+    let expected = written
+        .last()
+        .map(|step| step.form.clone())
+        .unwrap_or_else(|| word.to_string());
+
+    let mut chains = vec![(expected.clone(), written)];
+    if let Some(reading) = reading.filter(|r| *r != word) {
+        let spoken = step_forms(reading, actions, part_of_speech)?;
+        let spoken_expected = spoken
+            .last()
+            .map(|step| step.form.clone())
+            .unwrap_or_else(|| reading.to_string());
+        chains.push((spoken_expected, spoken));
+    }
+
+    if chains
+        .iter()
+        .any(|(final_form, _)| katakana_to_hiragana(final_form) == answer)
+    {
+        return Ok(ConjugationCheck {
+            is_correct: true,
+            expected,
+            steps: chains.swap_remove(0).1,
+            wrong_step: None,
+        });
+    }
+
+    // Blame the chain the answer follows furthest; kanji and kana spellings
+    // of the same answer share nothing.
+    let (final_form, steps) = chains
+        .into_iter()
+        .max_by_key(|(final_form, _)| common_prefix_len(&katakana_to_hiragana(final_form), &answer))
+        .expect("at least the written chain");
+    let final_form = katakana_to_hiragana(&final_form);
+    let wrong_step = if steps.is_empty() {
+        None
+    } else {
+        let first_missed = steps.iter().position(|step| {
+            let form = katakana_to_hiragana(&step.form);
+            let kept: String = form
+                .chars()
+                .take(common_prefix_len(&form, &final_form))
+                .collect();
+            !answer.starts_with(&kept)
+        });
+        Some(first_missed.unwrap_or(steps.len() - 1))
+    };
+
+    Ok(ConjugationCheck {
+        is_correct: false,
+        expected,
+        steps,
+        wrong_step,
+    })
+}
+
+/// Label of one step for prompts and feedback ("negative", "+ようだ").
+pub fn conjugation_step_label(action: &FormatAction) -> String {
+    match action {
+        FormatAction::AddPostfix { postfix } => format!("+{}", postfix),
+        FormatAction::ReplacePostfix {
+            old_postfix,
+            new_postfix,
+        } => format!("{} → {}", old_postfix, new_postfix),
+        FormatAction::RemovePostfix { postfix } => format!("−{}", postfix),
+        other => step_name(other).to_string(),
+    }
+}
+
+fn step_forms(
+    word: &str,
+    actions: &[FormatAction],
+    part_of_speech: &PartOfSpeech,
+) -> Result<Vec<ConjugationStepForm>, OrigaError> {
+    (1..=actions.len())
+        .map(|end| {
+            Ok(ConjugationStepForm {
+                label: conjugation_step_label(&actions[end - 1]),
+                form: apply_format_actions(word, &actions[..end], part_of_speech)?,
+            })
+        })
+        .collect()
+}
+
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn causative_passive_negative_past() -> Vec<FormatAction> {
+        vec![
+            FormatAction::VerbToCausativePassive {},
+            FormatAction::VerbToNai {},
+            FormatAction::ReplacePostfix {
+                old_postfix: "い".to_string(),
+                new_postfix: "かった".to_string(),
+            },
+        ]
+    }
+
+    #[rstest]
+    #[case("食べさせられない")]
+    #[case("たべさせられない")]
+    #[case("タベサセラレナイ")]
+    #[case(" 食べさせられない ")]
+    fn accepts_answer_regardless_of_kana_and_kanji(#[case] answer: &str) {
+        // Arrange
+        let actions = vec![
+            FormatAction::VerbToCausativePassive {},
+            FormatAction::VerbToNai {},
+        ];
+
+        // Act
+        let check = check_conjugation(
+            "食べる",
+            Some("たべる"),
+            &actions,
+            &PartOfSpeech::Verb,
+            answer,
+        )
+        .unwrap();
+
+        // Assert
+        assert!(check.is_correct);
+        assert_eq!(check.expected, "食べさせられない");
+        assert_eq!(check.wrong_step, None);
+    }
+
+    #[rstest]
+    #[case("食べさられなかった", 0)]
+    #[case("たべさせれなかった", 0)]
+    #[case("食べさせられずかった", 1)]
+    #[case("食べさせられなかつた", 2)]
+    fn blames_first_step_missing_from_answer(#[case] answer: &str, #[case] step: usize) {
+        // Arrange
+        let actions = causative_passive_negative_past();
+
+        // Act
+        let check = check_conjugation(
+            "食べる",
+            Some("たべる"),
+            &actions,
+            &PartOfSpeech::Verb,
+            answer,
+        )
+        .unwrap();
+
+        // Assert
+        assert!(!check.is_correct);
+        assert_eq!(check.expected, "食べさせられなかった");
+        assert_eq!(check.wrong_step, Some(step));
+    }
+
+    #[test]
+    fn steps_carry_labels_and_intermediate_forms() {
+        // Arrange
+        let actions = causative_passive_negative_past();
+
+        // Act
+        let check = check_conjugation("食べる", None, &actions, &PartOfSpeech::Verb, "").unwrap();
+
+        // Assert
+        let steps: Vec<(&str, &str)> = check
+            .steps
+            .iter()
+            .map(|s| (s.label.as_str(), s.form.as_str()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("causative-passive", "食べさせられる"),
+                ("negative", "食べさせられない"),
+                ("い → かった", "食べさせられなかった"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use std::collections::{HashSet, VecDeque};
+
+use serde::Serialize;
+use ulid::Ulid;
+
+use super::apply_format_actions;
+use crate::dictionary::grammar::{FormatAction, GRAMMAR_RULES, GrammarRule};
+use crate::domain::PartOfSpeech;
+
+/// Longest chain tried; real forms rarely stack more than four steps.
+const MAX_CHAIN_LENGTH: usize = 5;
+/// Longest suffix a single step puts after the stem (書きませんでした adds
+/// the seven kana きませんでした).
+const MAX_SUFFIX_CHARS: usize = 7;
+
+const VERB_ENDINGS: &[&str] = &["う", "く", "ぐ", "す", "つ", "ぬ", "ぶ", "む", "る"];
+const IRREGULAR_VERBS: &[&str] = &["する", "くる", "来る"];
+
+/// One forward step of a conjugation chain: `action` applied to the previous
+/// form (or the lemma) gives `form`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeconjugationStep {
+    pub action: FormatAction,
+    pub form: String,
+    /// Grammar rule that teaches this single step, when one exists.
+    pub rule_id: Option<Ulid>,
+}
+
+/// A candidate dictionary form of an inflected word with the steps, in
+/// forward order, that turn it back into the surface form.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Deconjugation {
+    pub lemma: String,
+    pub part_of_speech: PartOfSpeech,
+    pub steps: Vec<DeconjugationStep>,
+}
+
+/// Word class a step takes or produces. Negative, desire and ease forms
+/// conjugate on as i-adjectives (食べない → 食べなかった).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Inflects {
+    Verb,
+    IAdjective,
+}
+
+impl Inflects {
+    fn part_of_speech(self) -> PartOfSpeech {
+        match self {
+            Inflects::Verb => PartOfSpeech::Verb,
+            Inflects::IAdjective => PartOfSpeech::IAdjective,
+        }
+    }
+}
+
+struct ReverseRule {
+    action: FormatAction,
+    input: Inflects,
+    /// `None` for final forms (past, te-form, polite…) that take no further
+    /// conjugation, so they can only be the last step.
+    output: Option<Inflects>,
+}
+
+fn reverse_rules() -> Vec<ReverseRule> {
+    use FormatAction as A;
+    use Inflects::{IAdjective, Verb};
+
+    let rule = |action, input, output| ReverseRule {
+        action,
+        input,
+        output,
+    };
+    vec![
+        rule(A::VerbToCausativePassive {}, Verb, Some(Verb)),
+        rule(A::VerbToCausative {}, Verb, Some(Verb)),
+        rule(A::VerbToPassive {}, Verb, Some(Verb)),
+        rule(A::VerbToPotential {}, Verb, Some(Verb)),
+        rule(A::VerbToSugiru {}, Verb, Some(Verb)),
+        rule(A::VerbToChau {}, Verb, Some(Verb)),
+        rule(A::VerbToToku {}, Verb, Some(Verb)),
+        rule(A::VerbToTeru {}, Verb, Some(Verb)),
+        rule(A::VerbToNai {}, Verb, Some(IAdjective)),
+        rule(A::VerbToTai {}, Verb, Some(IAdjective)),
+        rule(A::VerbToYasui {}, Verb, Some(IAdjective)),
+        rule(A::VerbToNikui {}, Verb, Some(IAdjective)),
+        rule(A::VerbToTeForm {}, Verb, None),
+        rule(A::VerbToTa {}, Verb, None),
+        rule(A::VerbToTara {}, Verb, None),
+        rule(A::VerbToBa {}, Verb, None),
+        rule(A::VerbToMasu {}, Verb, None),
+        rule(A::VerbToMasen {}, Verb, None),
+        rule(A::VerbToMashita {}, Verb, None),
+        rule(A::VerbToMasenDeshita {}, Verb, None),
+        rule(A::VerbToMashou {}, Verb, None),
+        rule(A::VerbToImperative {}, Verb, None),
+        rule(A::VerbToVolitional {}, Verb, None),
+        rule(A::VerbToZu {}, Verb, None),
+        rule(A::VerbToSou {}, Verb, None),
+        rule(A::AdjectiveToSugiru {}, IAdjective, Some(Verb)),
+        rule(A::AdjectiveToKunai {}, IAdjective, Some(IAdjective)),
+        rule(A::AdjectiveToKatta {}, IAdjective, None),
+        rule(A::AdjectiveToKunakatta {}, IAdjective, None),
+        rule(A::AdjectiveToKute {}, IAdjective, None),
+        rule(A::AdjectiveToKu {}, IAdjective, None),
+        rule(A::AdjectiveToKereba {}, IAdjective, None),
+        rule(A::AdjectiveToSou {}, IAdjective, None),
+    ]
+}
+
+/// Every candidate lemma of `surface` with the conjugation chain producing
+/// it, shortest chains first. Candidates are verified by running the forward
+/// conjugation, but are not checked against a dictionary: 行った yields both
+/// 行く and 行う.
+pub fn deconjugate(surface: &str) -> Vec<Deconjugation> {
+    let rules = GRAMMAR_RULES.get().map(Vec::as_slice).unwrap_or(&[]);
+    deconjugate_with_rules(surface, rules)
+}
+
+pub(crate) fn deconjugate_with_rules(surface: &str, rules: &[GrammarRule]) -> Vec<Deconjugation> {
+    search(surface, rules, None)
+}
+
+/// Shortest conjugation chain leading from `lemma` to `surface`. Only bases
+/// spelled with the lemma's stem are explored, and the search stops at the
+/// first chain that reaches the lemma.
+pub fn deconjugate_to(surface: &str, lemma: &str) -> Option<Deconjugation> {
+    let rules = GRAMMAR_RULES.get().map(Vec::as_slice).unwrap_or(&[]);
+    deconjugate_to_with_rules(surface, lemma, rules)
+}
+
+pub(crate) fn deconjugate_to_with_rules(
+    surface: &str,
+    lemma: &str,
+    rules: &[GrammarRule],
+) -> Option<Deconjugation> {
+    search(surface, rules, Some(lemma)).pop()
+}
+
+/// Breadth-first search over reverse rules, so chains come out shortest
+/// first. With a `target` lemma, returns as soon as it is reached.
+fn search(surface: &str, rules: &[GrammarRule], target: Option<&str>) -> Vec<Deconjugation> {
+    let reverse = reverse_rules();
+    let root = target.map(lemma_root);
+    let mut results: Vec<Deconjugation> = Vec::new();
+    let mut seen: HashSet<(String, Inflects)> = HashSet::new();
+    // (form, what it must inflect as, steps from the form to the surface)
+    let mut queue: VecDeque<(String, Option<Inflects>, Vec<DeconjugationStep>)> =
+        VecDeque::from([(surface.to_string(), None, Vec::new())]);
+
+    while let Some((form, inflects, steps)) = queue.pop_front() {
+        for rule in &reverse {
+            if inflects.is_some() && rule.output != inflects {
+                continue;
+            }
+            let pos = rule.input.part_of_speech();
+            for base in candidate_bases(&form, rule.input) {
+                if root.is_some_and(|root| !base.starts_with(root)) {
+                    continue;
+                }
+                let produced =
+                    apply_format_actions(&base, std::slice::from_ref(&rule.action), &pos);
+                if produced.ok().as_deref() != Some(form.as_str()) {
+                    continue;
+                }
+
+                let mut chain = vec![DeconjugationStep {
+                    action: rule.action.clone(),
+                    form: form.clone(),
+                    rule_id: rule_for_action(rules, &rule.action, &pos),
+                }];
+                chain.extend(steps.iter().cloned());
+
+                if chain.len() < MAX_CHAIN_LENGTH && seen.insert((base.clone(), rule.input)) {
+                    queue.push_back((base.clone(), Some(rule.input), chain.clone()));
+                }
+                let candidate = Deconjugation {
+                    lemma: base,
+                    part_of_speech: pos.clone(),
+                    steps: chain,
+                };
+                match target {
+                    Some(target) if candidate.lemma == target => return vec![candidate],
+                    Some(_) => {},
+                    None => results.push(candidate),
+                }
+            }
+        }
+    }
+
+    results
+}
+
+/// Part of the lemma every form in its paradigm is spelled with: 食べ for
+/// 食べる, nothing for the irregular する and くる whose stems change.
+fn lemma_root(lemma: &str) -> &str {
+    if let Some(root) = IRREGULAR_VERBS
+        .iter()
+        .find_map(|verb| lemma.strip_suffix(verb))
+    {
+        return root;
+    }
+    lemma
+        .char_indices()
+        .last()
+        .map_or(lemma, |(last, _)| &lemma[..last])
+}
+
+/// Dictionary forms that could sit under `form`: its prefixes with every
+/// possible ending put back, the same prefixes with an irregular verb after
+/// them (勉強させられた → 勉強する), plus the bare irregular verbs.
+fn candidate_bases(form: &str, inflects: Inflects) -> Vec<String> {
+    let chars: Vec<char> = form.chars().collect();
+    let endings: &[&str] = match inflects {
+        Inflects::Verb => VERB_ENDINGS,
+        Inflects::IAdjective => &["い"],
+    };
+
+    let mut bases: Vec<String> = Vec::new();
+    for cut in 1..=MAX_SUFFIX_CHARS.min(chars.len().saturating_sub(1)) {
+        let prefix: String = chars[..chars.len() - cut].iter().collect();
+        bases.extend(endings.iter().map(|ending| format!("{}{}", prefix, ending)));
+        if inflects == Inflects::Verb {
+            bases.extend(
+                IRREGULAR_VERBS
+                    .iter()
+                    .map(|verb| format!("{}{}", prefix, verb)),
+            );
+        }
+    }
+    if inflects == Inflects::Verb {
+        bases.extend(IRREGULAR_VERBS.iter().map(|verb| verb.to_string()));
+    }
+    let mut unique = HashSet::new();
+    bases.retain(|base| base != form && unique.insert(base.clone()));
+    bases
+}
+
+fn rule_for_action(
+    rules: &[GrammarRule],
+    action: &FormatAction,
+    pos: &PartOfSpeech,
+) -> Option<Ulid> {
+    rules
+        .iter()
+        .find(|rule| {
+            rule.format_actions_for_pos(pos)
+                .is_some_and(|actions| actions.as_slice() == std::slice::from_ref(action))
+        })
+        .map(|rule| *rule.rule_id())
+}
+
+pub(super) fn step_name(action: &FormatAction) -> &'static str {
+    use FormatAction as A;
+    match action {
+        A::VerbToCausativePassive {} => "causative-passive",
+        A::VerbToCausative {} => "causative",
+        A::VerbToPassive {} => "passive",
+        A::VerbToPotential {} => "potential",
+        A::VerbToSugiru {} | A::AdjectiveToSugiru {} => "excessive",
+        A::VerbToChau {} => "completion",
+        A::VerbToToku {} => "preparatory",
+        A::VerbToTeru {} => "progressive",
+        A::VerbToNai {} | A::AdjectiveToKunai {} => "negative",
+        A::VerbToTai {} => "desiderative",
+        A::VerbToYasui {} => "easy to",
+        A::VerbToNikui {} => "hard to",
+        A::VerbToTeForm {} | A::AdjectiveToKute {} => "te-form",
+        A::VerbToTa {} | A::AdjectiveToKatta {} => "past",
+        A::AdjectiveToKunakatta {} => "negative past",
+        A::VerbToTara {} => "conditional (tara)",
+        A::VerbToBa {} | A::AdjectiveToKereba {} => "conditional (ba)",
+        A::VerbToMasu {} => "polite",
+        A::VerbToMasen {} => "polite negative",
+        A::VerbToMashita {} => "polite past",
+        A::VerbToMasenDeshita {} => "polite negative past",
+        A::VerbToMashou {} => "polite volitional",
+        A::VerbToImperative {} => "imperative",
+        A::VerbToVolitional {} => "volitional",
+        A::VerbToZu {} => "negative (zu)",
+        A::VerbToSou {} | A::AdjectiveToSou {} => "appearance (sou)",
+        A::AdjectiveToKu {} => "adverbial",
+        _ => "form",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::JapaneseLevel;
+    use std::collections::HashMap;
+
+    fn chain(result: &Deconjugation) -> Vec<FormatAction> {
+        result.steps.iter().map(|s| s.action.clone()).collect()
+    }
+
+    #[test]
+    fn causative_passive_negative_past_reaches_dictionary_form() {
+        // Arrange
+        let surface = "食べさせられなかった";
+
+        // Act
+        let results = deconjugate_with_rules(surface, &[]);
+
+        // Assert
+        let result = results
+            .iter()
+            .find(|r| {
+                r.lemma == "食べる"
+                    && chain(r)
+                        == [
+                            FormatAction::VerbToCausativePassive {},
+                            FormatAction::VerbToNai {},
+                            FormatAction::AdjectiveToKatta {},
+                        ]
+            })
+            .expect("食べる via causative-passive");
+        assert_eq!(result.steps.last().unwrap().form, surface);
+        assert_eq!(result.steps[0].form, "食べさせられる");
+    }
+
+    #[test]
+    fn compound_suru_verb_reaches_its_dictionary_form() {
+        // Act
+        let results = deconjugate_with_rules("勉強させられた", &[]);
+
+        // Assert
+        let result = results
+            .iter()
+            .find(|r| r.lemma == "勉強する")
+            .expect("勉強する via causative-passive");
+        assert_eq!(
+            chain(result),
+            [
+                FormatAction::VerbToCausativePassive {},
+                FormatAction::VerbToTa {},
+            ]
+        );
+    }
+
+    #[test]
+    fn search_for_a_compound_suru_verb_finds_its_chain() {
+        // Act
+        let result = deconjugate_to_with_rules("勉強しました", "勉強する", &[]);
+
+        // Assert
+        let result = result.expect("勉強する is reachable");
+        assert_eq!(chain(&result), [FormatAction::VerbToMashita {}]);
+    }
+
+    #[test]
+    fn search_for_a_lemma_returns_its_shortest_chain() {
+        // Act
+        let result = deconjugate_to_with_rules("食べさせられなかった", "食べる", &[]);
+
+        // Assert
+        let result = result.expect("食べる is reachable");
+        assert_eq!(
+            chain(&result),
+            [
+                FormatAction::VerbToCausativePassive {},
+                FormatAction::VerbToNai {},
+                FormatAction::AdjectiveToKatta {},
+            ]
+        );
+    }
+
+    #[test]
+    fn search_for_a_lemma_finds_irregular_stems() {
+        let result = deconjugate_to_with_rules("きませんでした", "くる", &[]);
+
+        assert_eq!(
+            result.map(|r| chain(&r)),
+            Some(vec![FormatAction::VerbToMasenDeshita {}])
+        );
+    }
+
+    #[test]
+    fn search_for_an_unrelated_lemma_finds_nothing() {
+        assert!(deconjugate_to_with_rules("行った", "食べる", &[]).is_none());
+    }
+
+    #[test]
+    fn ambiguous_godan_past_lists_every_lemma() {
+        let lemmas: HashSet<String> = deconjugate_with_rules("行った", &[])
+            .into_iter()
+            .filter(|r| r.steps.len() == 1)
+            .map(|r| r.lemma)
+            .collect();
+
+        assert!(lemmas.contains("行く"));
+        assert!(lemmas.contains("行う"));
+    }
+
+    #[test]
+    fn irregular_and_polite_forms_are_found() {
+        let results = deconjugate_with_rules("きませんでした", &[]);
+
+        assert!(
+            results
+                .iter()
+                .any(|r| r.lemma == "くる" && chain(r) == [FormatAction::VerbToMasenDeshita {}])
+        );
+    }
+
+    #[test]
+    fn colloquial_progressive_negative_is_found() {
+        let results = deconjugate_with_rules("食べてない", &[]);
+
+        assert!(results.iter().any(|r| r.lemma == "食べる"
+            && chain(r) == [FormatAction::VerbToTeru {}, FormatAction::VerbToNai {}]));
+    }
+
+    #[test]
+    fn single_action_rule_is_linked_to_step() {
+        // Arrange
+        let rule_id = Ulid::new();
+        let rule = GrammarRule::new(
+            rule_id,
+            JapaneseLevel::N4,
+            HashMap::new(),
+            Some(HashMap::from([(
+                PartOfSpeech::Verb,
+                vec![FormatAction::VerbToPassive {}],
+            )])),
+        );
+
+        // Act
+        let results = deconjugate_with_rules("読まれる", &[rule]);
+
+        // Assert
+        let result = results
+            .iter()
+            .find(|r| r.lemma == "読む")
+            .expect("読む via passive");
+        assert_eq!(result.steps[0].rule_id, Some(rule_id));
+    }
+}
@@ -1,3 +1,10 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use super::exceptions::verb_exception;
+use crate::domain::japanese::JapaneseChar;
+use crate::domain::tokenizer::{PartOfSpeech, TokenInfo, tokenize_text};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VerbGroup {
     Ichidan,
@@ -6,18 +13,94 @@ pub enum VerbGroup {
 }
 
 pub(super) const ICHIDAN_SHORT_VERBS: &[&str] = &[
-    "見る", "居る", "着る", "似る", "煮る", "射る", "鋳る", "寝る", "経る", "蹴る", "乾る",
+    "見る", "居る", "着る", "似る", "煮る", "射る", "鋳る", "寝る", "経る", "乾る",
 ];
 
+/// Classifies by the exception table, then by the conjugation type the
+/// tokenizer dictionary records for the word, and only without a loaded
+/// dictionary falls back to guessing from the kana before る.
+///
+/// Conjugation and deconjugation classify the same few bases over and over, so
+/// groups read from the dictionary are memoized per word. Guesses are not
+/// cached: the dictionary may still be loading.
 pub fn classify_verb(word: &str) -> VerbGroup {
-    if word == "する" || word == "くる" || word == "来る" {
-        return VerbGroup::Irregular;
+    if let Some(exception) = verb_exception(word) {
+        return exception.group;
+    }
+
+    if let Some(group) = cached_verb_group(word) {
+        return group;
+    }
+
+    if let Some(group) = tokenize_text(word)
+        .ok()
+        .and_then(|tokens| verb_group_from_tokens(word, &tokens))
+    {
+        cache_verb_group(word, group);
+        return group;
+    }
+
+    guess_verb_group(word)
+}
+
+/// Upper bound on memoized words; the memo is dropped when it is reached.
+const VERB_GROUP_CACHE_LIMIT: usize = 4096;
+
+fn verb_group_cache() -> &'static Mutex<HashMap<String, VerbGroup>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, VerbGroup>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cached_verb_group(word: &str) -> Option<VerbGroup> {
+    verb_group_cache().lock().ok()?.get(word).copied()
+}
+
+fn cache_verb_group(word: &str, group: VerbGroup) {
+    if let Ok(mut cache) = verb_group_cache().lock() {
+        if cache.len() >= VERB_GROUP_CACHE_LIMIT {
+            cache.clear();
+        }
+        cache.insert(word.to_string(), group);
     }
+}
 
+/// Group of the verb `word` ends with, so compounds like 勉強する take the
+/// group of their last token.
+fn verb_group_from_tokens(word: &str, tokens: &[TokenInfo]) -> Option<VerbGroup> {
+    let last = tokens.last()?;
+    if *last.part_of_speech() != PartOfSpeech::Verb
+        || !word.ends_with(last.orthographic_surface_form())
+    {
+        return None;
+    }
+    verb_group_from_conjugation_type(last.conjugation_type()?)
+}
+
+/// Maps a SudachiDict conjugation type; classical types such as
+/// `文語四段-ハ行` are left to the fallback.
+fn verb_group_from_conjugation_type(conjugation_type: &str) -> Option<VerbGroup> {
+    if conjugation_type.starts_with("五段") {
+        Some(VerbGroup::Godan)
+    } else if conjugation_type.starts_with("上一段") || conjugation_type.starts_with("下一段")
+    {
+        Some(VerbGroup::Ichidan)
+    } else if conjugation_type.starts_with("サ行変格") || conjugation_type.starts_with("カ行変格")
+    {
+        Some(VerbGroup::Irregular)
+    } else {
+        None
+    }
+}
+
+fn guess_verb_group(word: &str) -> VerbGroup {
     if ICHIDAN_SHORT_VERBS.contains(&word) {
         return VerbGroup::Ichidan;
     }
 
+    if is_compound_suru(word) {
+        return VerbGroup::Irregular;
+    }
+
     if super::godan_tables::GODAN_IRU_ERU_VERBS.contains(&word) {
         return VerbGroup::Godan;
     }
@@ -44,3 +127,97 @@ pub fn classify_verb(word: &str) -> VerbGroup {
 
     VerbGroup::Godan
 }
+
+/// A noun written in kanji or katakana followed by する (勉強する,
+/// ドライブする); kana-only words like こする are left to the other rules.
+fn is_compound_suru(word: &str) -> bool {
+    word.strip_suffix("する")
+        .and_then(|head| head.chars().last())
+        .is_some_and(|last| last.is_kanji() || last.is_katakana())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("五段-ラ行", Some(VerbGroup::Godan))]
+    #[case("上一段-カ行", Some(VerbGroup::Ichidan))]
+    #[case("下一段-バ行", Some(VerbGroup::Ichidan))]
+    #[case("サ行変格", Some(VerbGroup::Irregular))]
+    #[case("カ行変格", Some(VerbGroup::Irregular))]
+    #[case("文語四段-ハ行", None)]
+    fn maps_sudachi_conjugation_types(
+        #[case] conjugation_type: &str,
+        #[case] expected: Option<VerbGroup>,
+    ) {
+        assert_eq!(verb_group_from_conjugation_type(conjugation_type), expected);
+    }
+
+    #[test]
+    fn memoized_group_is_reused() {
+        // Arrange
+        cache_verb_group("試験用の語る", VerbGroup::Godan);
+
+        // Act
+        let group = classify_verb("試験用の語る");
+
+        // Assert
+        assert_eq!(group, VerbGroup::Godan);
+    }
+
+    #[rstest]
+    #[case("切る", "五段-ラ行", VerbGroup::Godan)]
+    #[case("着る", "上一段-カ行", VerbGroup::Ichidan)]
+    #[case("帰る", "五段-ラ行", VerbGroup::Godan)]
+    fn dictionary_type_decides_hidden_vowel(
+        #[case] word: &str,
+        #[case] conjugation_type: &str,
+        #[case] expected: VerbGroup,
+    ) {
+        // Arrange
+        let tokens = vec![TokenInfo::new_test_verb(word, conjugation_type)];
+
+        // Act
+        let group = verb_group_from_tokens(word, &tokens);
+
+        // Assert
+        assert_eq!(group, Some(expected));
+    }
+
+    #[test]
+    fn compound_takes_group_of_last_token() {
+        // Arrange
+        let tokens = vec![
+            TokenInfo::new_test("勉強", PartOfSpeech::Noun),
+            TokenInfo::new_test_verb("する", "サ行変格"),
+        ];
+
+        // Act
+        let group = verb_group_from_tokens("勉強する", &tokens);
+
+        // Assert
+        assert_eq!(group, Some(VerbGroup::Irregular));
+    }
+
+    #[rstest]
+    #[case("勉強する", VerbGroup::Irregular)]
+    #[case("ドライブする", VerbGroup::Irregular)]
+    #[case("こする", VerbGroup::Godan)]
+    fn guess_reads_noun_plus_suru_as_irregular(#[case] word: &str, #[case] expected: VerbGroup) {
+        assert_eq!(guess_verb_group(word), expected);
+    }
+
+    #[test]
+    fn non_verb_tail_is_not_classified() {
+        // Arrange
+        let tokens = vec![TokenInfo::new_test("高い", PartOfSpeech::IAdjective)];
+
+        // Act
+        let group = verb_group_from_tokens("高い", &tokens);
+
+        // Assert
+        assert_eq!(group, None);
+    }
+}
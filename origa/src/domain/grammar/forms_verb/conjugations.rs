@@ -1,3 +1,4 @@
+use super::exceptions::verb_exception;
 use super::godan_tables::{
     GODAN_TO_BA, GODAN_TO_CAUSATIVE, GODAN_TO_CAUSATIVE_PASSIVE, GODAN_TO_IMPERATIVE,
     GODAN_TO_MIZENKEI, GODAN_TO_NAI, GODAN_TO_PASSIVE, GODAN_TO_POTENTIAL, GODAN_TO_VOLITIONAL,
@@ -16,6 +17,9 @@ fn main_view_stem(word: &str) -> String {
     if let Some(result) = get_irregular_form(word, MAIN_VIEW_IRREGULAR) {
         return result;
     }
+    if let Some(stem) = verb_exception(word).and_then(|exception| exception.stem) {
+        return stem.to_string();
+    }
     if is_ichidan(word) {
         return word.strip_suffix('る').unwrap_or(word).to_string();
     }
@@ -51,6 +55,9 @@ fn apply_conjugation(
 }
 
 pub fn to_nai_form(word: &str) -> String {
+    if let Some(nai_form) = verb_exception(word).and_then(|exception| exception.nai_form) {
+        return nai_form.to_string();
+    }
     apply_conjugation(word, NAI_IRREGULAR, "ない", GODAN_TO_NAI)
 }
 
@@ -80,6 +87,9 @@ pub fn to_causative_passive_form(word: &str) -> String {
 }
 
 pub fn to_imperative_form(word: &str) -> String {
+    if let Some(imperative) = verb_exception(word).and_then(|exception| exception.imperative) {
+        return imperative.to_string();
+    }
     apply_conjugation(word, IMPERATIVE_IRREGULAR, "ろ", GODAN_TO_IMPERATIVE)
 }
 
@@ -0,0 +1,100 @@
+use super::classify::VerbGroup;
+
+/// A verb whose group or forms the regular paradigm gets wrong. Entries are
+/// checked against a reference grammar; a form left `None` follows the
+/// regular pattern of `group`.
+pub(super) struct VerbException {
+    pub(super) word: &'static str,
+    pub(super) group: VerbGroup,
+    pub(super) te_form: Option<&'static str>,
+    pub(super) nai_form: Option<&'static str>,
+    pub(super) stem: Option<&'static str>,
+    pub(super) imperative: Option<&'static str>,
+}
+
+impl VerbException {
+    const fn new(word: &'static str, group: VerbGroup) -> Self {
+        Self {
+            word,
+            group,
+            te_form: None,
+            nai_form: None,
+            stem: None,
+            imperative: None,
+        }
+    }
+
+    const fn te(self, te_form: &'static str) -> Self {
+        Self {
+            te_form: Some(te_form),
+            ..self
+        }
+    }
+
+    const fn nai(self, nai_form: &'static str) -> Self {
+        Self {
+            nai_form: Some(nai_form),
+            ..self
+        }
+    }
+
+    const fn stem(self, stem: &'static str) -> Self {
+        Self {
+            stem: Some(stem),
+            ..self
+        }
+    }
+
+    const fn imperative(self, imperative: &'static str) -> Self {
+        Self {
+            imperative: Some(imperative),
+            ..self
+        }
+    }
+}
+
+pub(super) const VERB_EXCEPTIONS: &[VerbException] = &[
+    VerbException::new("する", VerbGroup::Irregular),
+    VerbException::new("くる", VerbGroup::Irregular),
+    VerbException::new("来る", VerbGroup::Irregular),
+    // The only く-verbs with a っ te-form.
+    VerbException::new("行く", VerbGroup::Godan).te("行って"),
+    VerbException::new("いく", VerbGroup::Godan).te("いって"),
+    VerbException::new("逝く", VerbGroup::Godan).te("逝って"),
+    // う-verbs that keep the う before て: 問うて, not 問って.
+    VerbException::new("問う", VerbGroup::Godan).te("問うて"),
+    VerbException::new("乞う", VerbGroup::Godan).te("乞うて"),
+    VerbException::new("請う", VerbGroup::Godan).te("請うて"),
+    // ある is negated by the adjective ない, never あらない.
+    VerbException::new("ある", VerbGroup::Godan).nai("ない"),
+    VerbException::new("有る", VerbGroup::Godan).nai("ない"),
+    VerbException::new("在る", VerbGroup::Godan).nai("ない"),
+    // Honorific ら-row verbs drop the り: くださいます, ください.
+    VerbException::new("くださる", VerbGroup::Godan)
+        .stem("ください")
+        .imperative("ください"),
+    VerbException::new("下さる", VerbGroup::Godan)
+        .stem("下さい")
+        .imperative("下さい"),
+    VerbException::new("いらっしゃる", VerbGroup::Godan)
+        .stem("いらっしゃい")
+        .imperative("いらっしゃい"),
+    VerbException::new("なさる", VerbGroup::Godan)
+        .stem("なさい")
+        .imperative("なさい"),
+    VerbException::new("おっしゃる", VerbGroup::Godan)
+        .stem("おっしゃい")
+        .imperative("おっしゃい"),
+    VerbException::new("仰る", VerbGroup::Godan)
+        .stem("仰い")
+        .imperative("仰い"),
+    VerbException::new("ござる", VerbGroup::Godan).stem("ござい"),
+    // The kanji hides the え of える.
+    VerbException::new("得る", VerbGroup::Ichidan),
+];
+
+pub(super) fn verb_exception(word: &str) -> Option<&'static VerbException> {
+    VERB_EXCEPTIONS
+        .iter()
+        .find(|exception| exception.word == word)
+}
@@ -1,5 +1,5 @@
 pub(super) const GODAN_IRU_ERU_VERBS: &[&str] = &[
-    "要る", "入る", "減る", "茂る", "耽る", "喋る", "遮る", "罵る", "悟る",
+    "要る", "入る", "減る", "茂る", "耽る", "喋る", "遮る", "罵る", "悟る", "蹴る",
 ];
 
 pub(super) const GODAN_TO_STEM: &[(char, &str)] = &[
@@ -17,6 +17,8 @@ impl IrregularMapping {
 pub const IRREGULAR_SURU: &str = "する";
 pub const IRREGULAR_KURU: &str = "くる";
 
+pub const TE_IRREGULAR: IrregularMapping = IrregularMapping::new("して", "きて");
+pub const TA_IRREGULAR: IrregularMapping = IrregularMapping::new("した", "きた");
 pub const MAIN_VIEW_IRREGULAR: IrregularMapping = IrregularMapping::new("し", "き");
 pub const NAI_IRREGULAR: IrregularMapping = IrregularMapping::new("しない", "こない");
 pub const TARA_IRREGULAR: IrregularMapping = IrregularMapping::new("したら", "きたら");
@@ -37,11 +39,20 @@ pub const O_KUDASAI_IRREGULAR: IrregularMapping =
 pub const O_SHIMASU_IRREGULAR: IrregularMapping = IrregularMapping::new("いたす", "参る");
 
 pub fn get_irregular_form(verb: &str, mapping: IrregularMapping) -> Option<String> {
-    match verb {
-        IRREGULAR_SURU => Some(mapping.suru.to_string()),
-        IRREGULAR_KURU | "来る" => Some(mapping.kuru.to_string()),
-        _ => None,
+    let tails = [
+        (IRREGULAR_SURU, mapping.suru),
+        (IRREGULAR_KURU, mapping.kuru),
+        ("来る", mapping.kuru),
+    ];
+    let (head, form) = tails
+        .into_iter()
+        .find_map(|(tail, form)| verb.strip_suffix(tail).map(|head| (head, form)))?;
+    if head.is_empty() {
+        return Some(form.to_string());
     }
+    // Compounds such as 勉強する conjugate their tail, but こする or めくる
+    // only end in the same kana.
+    (classify_verb(verb) == VerbGroup::Irregular).then(|| format!("{head}{form}"))
 }
 
 pub fn stem_from_godan(word: &str) -> Option<String> {
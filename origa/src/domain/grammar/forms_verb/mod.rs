@@ -1,5 +1,6 @@
 mod classify;
 mod conjugations;
+mod exceptions;
 mod godan_tables;
 mod irregulars;
 mod te_ta;
@@ -168,6 +169,7 @@ mod tests {
     #[case("喋る", VerbGroup::Godan)]
     #[case("遮る", VerbGroup::Godan)]
     #[case("悟る", VerbGroup::Godan)]
+    #[case("蹴る", VerbGroup::Godan)]
     fn godan_iru_eru_exceptions(#[case] input: &str, #[case] expected: VerbGroup) {
         assert_eq!(super::classify_verb(input), expected);
     }
@@ -178,11 +180,35 @@ mod tests {
     #[case("着る", VerbGroup::Ichidan)]
     #[case("寝る", VerbGroup::Ichidan)]
     #[case("経る", VerbGroup::Ichidan)]
-    #[case("蹴る", VerbGroup::Ichidan)]
+    #[case("得る", VerbGroup::Ichidan)]
     fn ichidan_short_verbs(#[case] input: &str, #[case] expected: VerbGroup) {
         assert_eq!(super::classify_verb(input), expected);
     }
 
+    #[rstest]
+    #[case("行く", "行って", "行った")]
+    #[case("いく", "いって", "いった")]
+    #[case("問う", "問うて", "問うた")]
+    fn exception_te_ta_forms(#[case] verb: &str, #[case] te: &str, #[case] ta: &str) {
+        assert_eq!(to_te_form(verb), te);
+        assert_eq!(to_ta_form(verb), ta);
+    }
+
+    #[rstest]
+    #[case("くださる", "くださいます", "ください")]
+    #[case("いらっしゃる", "いらっしゃいます", "いらっしゃい")]
+    #[case("おっしゃる", "おっしゃいます", "おっしゃい")]
+    fn honorific_verbs_drop_ri(#[case] verb: &str, #[case] masu: &str, #[case] imperative: &str) {
+        assert_eq!(to_masu_form(verb), masu);
+        assert_eq!(to_imperative_form(verb), imperative);
+    }
+
+    #[test]
+    fn aru_negates_with_nai() {
+        assert_eq!(to_nai_form("ある"), "ない");
+        assert_eq!(to_masu_form("ある"), "あります");
+    }
+
     #[rstest]
     #[case("要る", "要って", "要ります")]
     #[case("入る", "入って", "入ります")]
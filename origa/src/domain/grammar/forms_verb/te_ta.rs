@@ -1,5 +1,7 @@
 use super::classify::{VerbGroup, classify_verb};
+use super::exceptions::verb_exception;
 use super::godan_tables::TE_TA_MAPPING;
+use super::irregulars::{TA_IRREGULAR, TE_IRREGULAR, get_irregular_form};
 
 pub fn to_te_form(word: &str) -> String {
     apply_te_ta(word, true)
@@ -10,20 +12,16 @@ pub fn to_ta_form(word: &str) -> String {
 }
 
 fn apply_te_ta(word: &str, use_te: bool) -> String {
-    let (suru_result, kuru_result, iku_result) = if use_te {
-        ("して", "きて", "行って")
-    } else {
-        ("した", "きた", "行った")
-    };
-
-    if word == "する" {
-        return suru_result.to_string();
-    }
-    if word == "くる" || word == "来る" {
-        return kuru_result.to_string();
+    let irregular = if use_te { TE_IRREGULAR } else { TA_IRREGULAR };
+    if let Some(result) = get_irregular_form(word, irregular) {
+        return result;
     }
-    if word == "行く" {
-        return iku_result.to_string();
+    if let Some(te_form) = verb_exception(word).and_then(|exception| exception.te_form) {
+        return if use_te {
+            te_form.to_string()
+        } else {
+            te_to_ta(te_form)
+        };
     }
 
     let chars: Vec<char> = word.chars().collect();
@@ -52,3 +50,13 @@ fn apply_te_ta(word: &str, use_te: bool) -> String {
 
     word.to_string()
 }
+
+fn te_to_ta(te_form: &str) -> String {
+    if let Some(head) = te_form.strip_suffix('て') {
+        format!("{head}た")
+    } else if let Some(head) = te_form.strip_suffix('で') {
+        format!("{head}だ")
+    } else {
+        te_form.to_string()
+    }
+}
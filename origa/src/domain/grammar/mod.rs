@@ -1,9 +1,15 @@
+mod conjugation_drill;
+mod deconjugate;
 pub(crate) mod forms_adjective;
 pub(crate) mod forms_verb;
 pub(crate) mod particles;
 pub mod quiz_generation;
 
-pub(crate) use particles::is_grammatical_particle;
+pub use conjugation_drill::{
+    ConjugationCheck, ConjugationStepForm, check_conjugation, conjugation_step_label,
+};
+pub use deconjugate::{Deconjugation, DeconjugationStep, deconjugate, deconjugate_to};
+pub(crate) use particles::{confusable_particles, is_grammatical_particle};
 
 use crate::dictionary::grammar::{FormatAction, GrammarRule};
 use crate::domain::grammar::forms_adjective::{
@@ -69,6 +69,35 @@ pub(crate) fn is_grammatical_particle(token: &str) -> bool {
     particle_lookup().contains(token)
 }
 
+/// Particle pairs learners routinely mix up. A particle blanked out of a
+/// phrase is offered together with every particle it shares a pair with, so
+/// the distractors test the distinction rather than particle recognition.
+pub(crate) const CONFUSABLE_PARTICLES: &[[&str; 2]] = &[
+    ["は", "が"],
+    ["が", "を"],
+    ["に", "で"],
+    ["へ", "に"],
+    ["と", "や"],
+    ["から", "より"],
+];
+
+/// Particles `particle` is confused with, in table order and without
+/// duplicates. Empty for particles outside [`CONFUSABLE_PARTICLES`].
+pub(crate) fn confusable_particles(particle: &str) -> Vec<&'static str> {
+    let mut result: Vec<&'static str> = Vec::new();
+    for pair in CONFUSABLE_PARTICLES {
+        let other = match pair {
+            [a, b] if *a == particle => b,
+            [a, b] if *b == particle => a,
+            _ => continue,
+        };
+        if !result.contains(other) {
+            result.push(other);
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,4 +183,21 @@ mod tests {
             "the OnceLock must return the same HashSet reference on repeated calls"
         );
     }
+
+    #[test]
+    fn confusable_particles_collect_every_pair() {
+        assert_eq!(confusable_particles("に"), vec!["で", "へ"]);
+        assert_eq!(confusable_particles("が"), vec!["は", "を"]);
+        assert_eq!(confusable_particles("は"), vec!["が"]);
+    }
+
+    #[test]
+    fn confusable_particles_are_grammatical_particles() {
+        for pair in CONFUSABLE_PARTICLES {
+            for p in pair {
+                assert!(is_grammatical_particle(p), "{p} should be a particle");
+            }
+        }
+        assert!(confusable_particles("ね").is_empty());
+    }
 }
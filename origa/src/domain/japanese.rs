@@ -98,6 +98,297 @@ pub fn hiragana_to_katakana(text: &str) -> String {
         .collect()
 }
 
+/// Hepburn and kunrei romaji with the usual IME spellings for small kana
+/// (`xa`/`la`, `xtsu`/`ltu`) and for sounds in loanwords (`ye`, `wi`,
+/// `tsa`, `thi`, `va`). Looked up longest key first.
+const ROMAJI_TO_HIRAGANA: &[(&str, &str)] = &[
+    ("a", "あ"),
+    ("i", "い"),
+    ("u", "う"),
+    ("e", "え"),
+    ("o", "お"),
+    ("ka", "か"),
+    ("ki", "き"),
+    ("ku", "く"),
+    ("ke", "け"),
+    ("ko", "こ"),
+    ("kya", "きゃ"),
+    ("kyu", "きゅ"),
+    ("kyo", "きょ"),
+    ("kwa", "くぁ"),
+    ("ga", "が"),
+    ("gi", "ぎ"),
+    ("gu", "ぐ"),
+    ("ge", "げ"),
+    ("go", "ご"),
+    ("gya", "ぎゃ"),
+    ("gyu", "ぎゅ"),
+    ("gyo", "ぎょ"),
+    ("gwa", "ぐぁ"),
+    ("sa", "さ"),
+    ("shi", "し"),
+    ("si", "し"),
+    ("su", "す"),
+    ("se", "せ"),
+    ("so", "そ"),
+    ("sha", "しゃ"),
+    ("shu", "しゅ"),
+    ("she", "しぇ"),
+    ("sho", "しょ"),
+    ("sya", "しゃ"),
+    ("syu", "しゅ"),
+    ("syo", "しょ"),
+    ("za", "ざ"),
+    ("ji", "じ"),
+    ("zi", "じ"),
+    ("zu", "ず"),
+    ("ze", "ぜ"),
+    ("zo", "ぞ"),
+    ("ja", "じゃ"),
+    ("ju", "じゅ"),
+    ("je", "じぇ"),
+    ("jo", "じょ"),
+    ("jya", "じゃ"),
+    ("jyu", "じゅ"),
+    ("jyo", "じょ"),
+    ("zya", "じゃ"),
+    ("zyu", "じゅ"),
+    ("zyo", "じょ"),
+    ("ta", "た"),
+    ("chi", "ち"),
+    ("ti", "ち"),
+    ("tsu", "つ"),
+    ("tu", "つ"),
+    ("te", "て"),
+    ("to", "と"),
+    ("cha", "ちゃ"),
+    ("chu", "ちゅ"),
+    ("che", "ちぇ"),
+    ("cho", "ちょ"),
+    ("tya", "ちゃ"),
+    ("tyu", "ちゅ"),
+    ("tyo", "ちょ"),
+    ("tsa", "つぁ"),
+    ("tsi", "つぃ"),
+    ("tse", "つぇ"),
+    ("tso", "つぉ"),
+    ("thi", "てぃ"),
+    ("thu", "てゅ"),
+    ("twu", "とぅ"),
+    ("da", "だ"),
+    ("di", "ぢ"),
+    ("du", "づ"),
+    ("de", "で"),
+    ("do", "ど"),
+    ("dya", "ぢゃ"),
+    ("dyu", "ぢゅ"),
+    ("dyo", "ぢょ"),
+    ("dhi", "でぃ"),
+    ("dhu", "でゅ"),
+    ("dwu", "どぅ"),
+    ("na", "な"),
+    ("ni", "に"),
+    ("nu", "ぬ"),
+    ("ne", "ね"),
+    ("no", "の"),
+    ("nya", "にゃ"),
+    ("nyu", "にゅ"),
+    ("nyo", "にょ"),
+    ("ha", "は"),
+    ("hi", "ひ"),
+    ("fu", "ふ"),
+    ("hu", "ふ"),
+    ("he", "へ"),
+    ("ho", "ほ"),
+    ("hya", "ひゃ"),
+    ("hyu", "ひゅ"),
+    ("hyo", "ひょ"),
+    ("fa", "ふぁ"),
+    ("fi", "ふぃ"),
+    ("fe", "ふぇ"),
+    ("fo", "ふぉ"),
+    ("fyu", "ふゅ"),
+    ("ba", "ば"),
+    ("bi", "び"),
+    ("bu", "ぶ"),
+    ("be", "べ"),
+    ("bo", "ぼ"),
+    ("bya", "びゃ"),
+    ("byu", "びゅ"),
+    ("byo", "びょ"),
+    ("pa", "ぱ"),
+    ("pi", "ぴ"),
+    ("pu", "ぷ"),
+    ("pe", "ぺ"),
+    ("po", "ぽ"),
+    ("pya", "ぴゃ"),
+    ("pyu", "ぴゅ"),
+    ("pyo", "ぴょ"),
+    ("ma", "ま"),
+    ("mi", "み"),
+    ("mu", "む"),
+    ("me", "め"),
+    ("mo", "も"),
+    ("mya", "みゃ"),
+    ("myu", "みゅ"),
+    ("myo", "みょ"),
+    ("ya", "や"),
+    ("yu", "ゆ"),
+    ("yo", "よ"),
+    ("ye", "いぇ"),
+    ("ra", "ら"),
+    ("ri", "り"),
+    ("ru", "る"),
+    ("re", "れ"),
+    ("ro", "ろ"),
+    ("rya", "りゃ"),
+    ("ryu", "りゅ"),
+    ("ryo", "りょ"),
+    ("wa", "わ"),
+    ("wo", "を"),
+    ("wi", "うぃ"),
+    ("we", "うぇ"),
+    ("wha", "うぁ"),
+    ("who", "うぉ"),
+    ("va", "ゔぁ"),
+    ("vi", "ゔぃ"),
+    ("vu", "ゔ"),
+    ("ve", "ゔぇ"),
+    ("vo", "ゔぉ"),
+    ("xa", "ぁ"),
+    ("xi", "ぃ"),
+    ("xu", "ぅ"),
+    ("xe", "ぇ"),
+    ("xo", "ぉ"),
+    ("la", "ぁ"),
+    ("li", "ぃ"),
+    ("lu", "ぅ"),
+    ("le", "ぇ"),
+    ("lo", "ぉ"),
+    ("xya", "ゃ"),
+    ("xyu", "ゅ"),
+    ("xyo", "ょ"),
+    ("lya", "ゃ"),
+    ("lyu", "ゅ"),
+    ("lyo", "ょ"),
+    ("xwa", "ゎ"),
+    ("lwa", "ゎ"),
+    ("xtu", "っ"),
+    ("ltu", "っ"),
+    ("xtsu", "っ"),
+    ("ltsu", "っ"),
+];
+
+const ROMAJI_VOWELS: &[char] = &['a', 'i', 'u', 'e', 'o'];
+
+/// Transliterates romaji in ``text`` to hiragana, leaving kana and anything
+/// else untouched so already-converted input can be fed back in. Double
+/// consonants become ``っ``, ``-`` becomes ``ー``, and ``n`` becomes ``ん``
+/// before a consonant, after ``'`` or ``nn``, or at the end of the text.
+/// Hepburn's ``m`` before ``b``, ``p`` or ``m`` (``shimbun``) is ``ん`` too.
+pub fn romaji_to_kana(text: &str) -> String {
+    transliterate_romaji(text, true)
+}
+
+/// Live variant of [`romaji_to_kana`] for text still being typed: letters
+/// that may start a longer syllable (``k``, ``sh``, a final ``n`` or ``nn``)
+/// are kept as romaji until the next keystroke settles them.
+pub fn romaji_to_kana_partial(text: &str) -> String {
+    transliterate_romaji(text, false)
+}
+
+fn transliterate_romaji(text: &str, finalize: bool) -> String {
+    let chars: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c == '-' {
+            out.push('ー');
+            i += 1;
+            continue;
+        }
+        if !c.is_ascii_alphabetic() {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+
+        if c == 'n' {
+            let after = chars.get(i + 2).copied();
+            match next {
+                Some('\'') => {
+                    out.push('ん');
+                    i += 2;
+                    continue;
+                },
+                // While typing, a trailing nn may still be こんに; wait for
+                // the next letter.
+                Some('n') if after.is_none() && !finalize => {
+                    out.push_str("nn");
+                    i += 2;
+                    continue;
+                },
+                // こんにちは: the first n closes the syllable, the second
+                // starts に. A doubled n before a consonant or the end is ん.
+                Some('n') if !after.is_some_and(|a| ROMAJI_VOWELS.contains(&a) || a == 'y') => {
+                    out.push('ん');
+                    i += 2;
+                    continue;
+                },
+                Some(n) if n.is_ascii_alphabetic() && !ROMAJI_VOWELS.contains(&n) && n != 'y' => {
+                    out.push('ん');
+                    i += 1;
+                    continue;
+                },
+                None if finalize => {
+                    out.push('ん');
+                    i += 1;
+                    continue;
+                },
+                _ => {},
+            }
+        }
+
+        if c == 'm' && matches!(next, Some('b' | 'p' | 'm')) {
+            out.push('ん');
+            i += 1;
+            continue;
+        }
+
+        let is_sokuon = next == Some(c) && !ROMAJI_VOWELS.contains(&c) && c != 'n'
+            || c == 't' && next == Some('c') && chars.get(i + 2) == Some(&'h');
+        if is_sokuon {
+            out.push('っ');
+            i += 1;
+            continue;
+        }
+
+        let matched = (1..=4).rev().find_map(|len| {
+            let key: String = chars.get(i..i + len)?.iter().collect();
+            ROMAJI_TO_HIRAGANA
+                .iter()
+                .find(|(romaji, _)| *romaji == key)
+                .map(|(_, kana)| (len, *kana))
+        });
+        match matched {
+            Some((len, kana)) => {
+                out.push_str(kana);
+                i += len;
+            },
+            None => {
+                out.push(c);
+                i += 1;
+            },
+        }
+    }
+
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,4 +541,75 @@ mod tests {
         assert_eq!(hiragana_to_katakana("アイウ"), "アイウ");
         assert_eq!(hiragana_to_katakana("123"), "123");
     }
+
+    #[rstest]
+    #[case("taberu", "たべる")]
+    #[case("kitte", "きって")]
+    #[case("matcha", "まっちゃ")]
+    #[case("kyou", "きょう")]
+    #[case("shinbun", "しんぶん")]
+    #[case("konnichiha", "こんにちは")]
+    #[case("onna", "おんな")]
+    #[case("kon'ya", "こんや")]
+    #[case("hon", "ほん")]
+    #[case("honn", "ほん")]
+    #[case("ko-hi-", "こーひー")]
+    #[case("tsukue", "つくえ")]
+    #[case("Sushi", "すし")]
+    #[case("たべru", "たべる")]
+    #[case("shimbun", "しんぶん")]
+    #[case("sampo", "さんぽ")]
+    #[case("amma", "あんま")]
+    #[case("yeti", "いぇち")]
+    #[case("wisuki-", "うぃすきー")]
+    #[case("wesuto", "うぇすと")]
+    #[case("tsa-ri", "つぁーり")]
+    #[case("pa-thi-", "ぱーてぃー")]
+    #[case("vaiorin", "ゔぁいおりん")]
+    fn romaji_to_kana_converts_hepburn_and_ime_spellings(
+        #[case] romaji: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(romaji_to_kana(romaji), expected);
+    }
+
+    #[rstest]
+    #[case("k", "k")]
+    #[case("kan", "かn")]
+    #[case("kanj", "かんj")]
+    #[case("sh", "sh")]
+    #[case("kanji", "かんじ")]
+    #[case("konn", "こnn")]
+    fn romaji_to_kana_partial_keeps_unfinished_syllables(
+        #[case] romaji: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(romaji_to_kana_partial(romaji), expected);
+    }
+
+    /// Converts after every keystroke and writes the result back, as the
+    /// production card's input does.
+    fn type_keys(keys: &str) -> String {
+        keys.chars().fold(String::new(), |field, key| {
+            romaji_to_kana_partial(&format!("{field}{key}"))
+        })
+    }
+
+    #[rstest]
+    #[case("konnichi", "こんにち")]
+    #[case("onna", "おんな")]
+    #[case("shinbun", "しんぶn")]
+    #[case("kannkei", "かんけい")]
+    #[case("honn", "ほnn")]
+    fn romaji_to_kana_partial_settles_syllables_key_by_key(
+        #[case] keys: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(type_keys(keys), expected);
+    }
+
+    #[test]
+    fn typed_trailing_nn_finalizes_to_n() {
+        assert_eq!(romaji_to_kana(&type_keys("honn")), "ほん");
+    }
 }
@@ -35,6 +35,8 @@ impl JlptContent {
             CardType::Vocabulary => &self.words_by_level,
             CardType::Grammar => &self.grammar_by_level,
             CardType::Phrase => &self.words_by_level,
+            // Particle cards track mistakes, not JLPT coverage.
+            CardType::Particle => return None,
         };
 
         JapaneseLevel::ALL
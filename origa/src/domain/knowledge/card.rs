@@ -1,6 +1,6 @@
 use crate::domain::{
-    OrigaError, PartOfSpeech, Rating,
-    knowledge::{GrammarRuleCard, KanjiCard, PhraseCard, VocabularyCard},
+    OrigaError, PartOfSpeech, RateMode, Rating,
+    knowledge::{GrammarRuleCard, KanjiCard, ParticleCard, PhraseCard, VocabularyCard},
     memory::{MemoryHistory, MemoryState},
     value_objects::{CardAnswer, NativeLanguage, Question},
 };
@@ -33,6 +33,13 @@ impl StudyCard {
         }
     }
 
+    pub(crate) fn with_memory(card: Card, memory_history: MemoryHistory) -> Self {
+        Self {
+            memory_history,
+            ..Self::new(card)
+        }
+    }
+
     pub fn card_id(&self) -> &Ulid {
         &self.card_id
     }
@@ -70,19 +77,43 @@ impl StudyCard {
         self.favorite_changed_at = ts;
     }
 
+    #[cfg(test)]
     pub(crate) fn apply_review(&mut self, memory_state: MemoryState, rating: Rating) {
         self.memory_history.apply_review(memory_state, rating);
     }
 
+    pub(crate) fn apply_review_at(
+        &mut self,
+        memory_state: MemoryState,
+        rating: Rating,
+        reviewed_at: DateTime<Utc>,
+    ) {
+        self.memory_history
+            .apply_review_at(memory_state, rating, reviewed_at);
+    }
+
+    pub(crate) fn record_review(&mut self, rating: Rating, mode: RateMode) {
+        self.memory_history.record_review(rating, mode);
+    }
+
     pub fn toggle_favorite(&mut self) {
+        self.toggle_favorite_at(Utc::now());
+    }
+
+    pub(crate) fn toggle_favorite_at(&mut self, changed_at: DateTime<Utc>) {
         self.is_favorite = !self.is_favorite;
-        self.favorite_changed_at = Some(Utc::now());
+        self.favorite_changed_at = Some(changed_at);
         if !self.is_favorite {
             self.favorite_easy_streak = 0;
         }
     }
 
+    #[cfg(test)]
     pub(crate) fn handle_favorite_rating(&mut self, rating: Rating) {
+        self.handle_favorite_rating_at(rating, Utc::now());
+    }
+
+    pub(crate) fn handle_favorite_rating_at(&mut self, rating: Rating, rated_at: DateTime<Utc>) {
         if !self.is_favorite {
             return;
         }
@@ -92,7 +123,7 @@ impl StudyCard {
                 self.favorite_easy_streak += 1;
                 if self.favorite_easy_streak >= 5 {
                     self.is_favorite = false;
-                    self.favorite_changed_at = Some(Utc::now());
+                    self.favorite_changed_at = Some(rated_at);
                     self.favorite_easy_streak = 0;
                 }
             },
@@ -140,6 +171,7 @@ pub enum Card {
     Kanji(KanjiCard),
     Grammar(GrammarRuleCard),
     Phrase(PhraseCard),
+    Particle(ParticleCard),
 }
 
 impl Card {
@@ -156,6 +188,7 @@ impl Card {
                     reason: e.to_string(),
                 })
             },
+            Card::Particle(card) => card.question(),
         }
     }
 
@@ -172,6 +205,7 @@ impl Card {
                     reason: e.to_string(),
                 })
             },
+            Card::Particle(card) => card.answer(lang),
         }
     }
 
@@ -181,6 +215,7 @@ impl Card {
             Card::Kanji(card) => card.kanji().text().to_string(),
             Card::Grammar(card) => card.rule_id().to_string(),
             Card::Phrase(card) => card.phrase_id().to_string(),
+            Card::Particle(card) => card.particle().to_string(),
         }
     }
 
@@ -198,6 +233,7 @@ pub enum CardType {
     Kanji,
     Grammar,
     Phrase,
+    Particle,
 }
 
 impl From<&Card> for CardType {
@@ -207,6 +243,7 @@ impl From<&Card> for CardType {
             Card::Kanji(_) => CardType::Kanji,
             Card::Grammar(_) => CardType::Grammar,
             Card::Phrase(_) => CardType::Phrase,
+            Card::Particle(_) => CardType::Particle,
         }
     }
 }
@@ -234,6 +271,10 @@ mod tests {
         PhraseCard::new_test_with_id(phrase_id)
     }
 
+    fn create_particle_card(particle: &str) -> ParticleCard {
+        ParticleCard::new(particle.to_string())
+    }
+
     mod study_card {
         use super::*;
 
@@ -245,6 +286,7 @@ mod tests {
             #[case(Card::Kanji(create_kanji_card("日")))]
             #[case(Card::Grammar(create_grammar_card(Ulid::new())))]
             #[case(Card::Phrase(create_phrase_card(Ulid::new())))]
+            #[case(Card::Particle(create_particle_card("は")))]
             fn creates_study_card_with_card_type(#[case] card: Card) {
                 let study_card = StudyCard::new(card);
 
@@ -297,6 +339,7 @@ mod tests {
             #[case(Card::Kanji(create_kanji_card("日")), CardType::Kanji)]
             #[case(Card::Grammar(create_grammar_card(Ulid::new())), CardType::Grammar)]
             #[case(Card::Phrase(create_phrase_card(Ulid::new())), CardType::Phrase)]
+            #[case(Card::Particle(create_particle_card("は")), CardType::Particle)]
             fn returns_card_reference(#[case] card: Card, #[case] expected_type: CardType) {
                 let study_card = StudyCard::new(card);
                 let returned_card = study_card.card();
@@ -638,6 +681,7 @@ mod tests {
             #[rstest]
             #[case(Card::Vocabulary(create_vocabulary_card("猫")), "猫")]
             #[case(Card::Kanji(create_kanji_card("日")), "日")]
+            #[case(Card::Particle(create_particle_card("に")), "に")]
             fn returns_question_for_card(#[case] card: Card, #[case] expected: &str) {
                 let question = card.question(&NativeLanguage::Russian);
 
@@ -713,6 +757,15 @@ mod tests {
 
                 assert_eq!(content_key, rule_id.to_string());
             }
+
+            #[test]
+            fn returns_particle_for_particle_card() {
+                let card = Card::Particle(create_particle_card("で"));
+
+                let content_key = card.content_key();
+
+                assert_eq!(content_key, "で");
+            }
         }
     }
 
@@ -727,6 +780,7 @@ mod tests {
             #[case(Card::Kanji(create_kanji_card("日")), CardType::Kanji)]
             #[case(Card::Grammar(create_grammar_card(Ulid::new())), CardType::Grammar)]
             #[case(Card::Phrase(create_phrase_card(Ulid::new())), CardType::Phrase)]
+            #[case(Card::Particle(create_particle_card("は")), CardType::Particle)]
             fn converts_card_to_type(#[case] card: Card, #[case] expected_type: CardType) {
                 let card_type = CardType::from(&card);
 
@@ -773,6 +827,11 @@ mod tests {
             let json = serde_json::to_string(&phrase).unwrap();
             let de: Card = serde_json::from_str(&json).unwrap();
             assert_eq!(phrase, de);
+
+            let particle = Card::Particle(create_particle_card("へ"));
+            let json = serde_json::to_string(&particle).unwrap();
+            let de: Card = serde_json::from_str(&json).unwrap();
+            assert_eq!(particle, de);
         }
     }
 }
@@ -48,8 +48,13 @@ impl Default for DailyHistoryItem {
 
 impl DailyHistoryItem {
     pub fn new() -> Self {
+        Self::starting_at(Utc::now())
+    }
+
+    /// Empty item for the day of `timestamp` (journal replay of past days).
+    pub(crate) fn starting_at(timestamp: DateTime<Utc>) -> Self {
         Self {
-            timestamp: Utc::now(),
+            timestamp,
             avg_stability: None,
             avg_difficulty: None,
             total_words: 0,
@@ -191,17 +196,10 @@ impl DailyHistoryItem {
 // 15% буфер для оценки даты завершения
 const ESTIMATION_SAFETY_FACTOR: f64 = 1.15;
 
-/// Оценивает дату завершения изучения всех оставшихся новых карточек
-/// на основе средней дневной скорости за последние 10 дней (исключая текущий).
-/// Возвращает `None` если нет оставшихся карточек или недостаточно данных.
-pub fn estimate_completion_date(
-    history: &[DailyHistoryItem],
-    new_cards_remaining: usize,
-) -> Option<DateTime<Utc>> {
-    if new_cards_remaining == 0 {
-        return None;
-    }
-
+/// Средняя дневная скорость изучения новых карточек за последние 10 дней
+/// (исключая текущий и дни без новых карточек).
+/// Возвращает `None` если недостаточно данных.
+pub(crate) fn average_new_cards_per_day(history: &[DailyHistoryItem]) -> Option<f64> {
     let today = Utc::now().date_naive();
 
     let studied: Vec<&DailyHistoryItem> = history
@@ -220,7 +218,21 @@ pub fn estimate_completion_date(
         .iter()
         .map(|item| item.new_cards_studied_today())
         .sum();
-    let avg = total as f64 / studied.len() as f64;
+    Some(total as f64 / studied.len() as f64)
+}
+
+/// Оценивает дату завершения изучения всех оставшихся новых карточек
+/// на основе средней дневной скорости за последние 10 дней (исключая текущий).
+/// Возвращает `None` если нет оставшихся карточек или недостаточно данных.
+pub fn estimate_completion_date(
+    history: &[DailyHistoryItem],
+    new_cards_remaining: usize,
+) -> Option<DateTime<Utc>> {
+    if new_cards_remaining == 0 {
+        return None;
+    }
+
+    let avg = average_new_cards_per_day(history)?;
     let days = (new_cards_remaining as f64 / avg * ESTIMATION_SAFETY_FACTOR).ceil() as i64;
 
     Some(Utc::now() + Duration::days(days))
@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use super::daily_history::{average_new_cards_per_day, estimate_completion_date};
+use super::{CardType, KnowledgeSet};
+use crate::domain::{ExamTarget, JapaneseLevel, JlptContent};
+
+/// How far the user is from covering the content of their target exam, and
+/// whether the current study pace gets them there in time.
+///
+/// "Target content" is every kanji, word and grammar point of the levels the
+/// exam covers (see [`ExamTarget::covers`]). Phrase cards are not JLPT
+/// content of their own and are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExamReadiness {
+    target: ExamTarget,
+    days_left: i64,
+    target_items: usize,
+    learned_items: usize,
+    new_cards_remaining: usize,
+    missing_items: usize,
+    current_pace: Option<f64>,
+    projected_completion: Option<DateTime<Utc>>,
+}
+
+impl ExamReadiness {
+    pub fn target(&self) -> &ExamTarget {
+        &self.target
+    }
+
+    /// Whole study days left before the exam day.
+    pub fn days_left(&self) -> i64 {
+        self.days_left
+    }
+
+    /// Total target-level items in the JLPT lists.
+    pub fn target_items(&self) -> usize {
+        self.target_items
+    }
+
+    /// Target-level items whose card is past the known-card threshold.
+    pub fn learned_items(&self) -> usize {
+        self.learned_items
+    }
+
+    /// Target-level cards that exist in the deck but were never studied.
+    pub fn new_cards_remaining(&self) -> usize {
+        self.new_cards_remaining
+    }
+
+    /// Target-level items with no card at all: the user has to add them
+    /// (sets, import) before lessons can pick them up.
+    pub fn missing_items(&self) -> usize {
+        self.missing_items
+    }
+
+    /// Items still to be introduced before the exam: unstarted cards plus
+    /// content that has no card yet.
+    pub fn items_to_introduce(&self) -> usize {
+        self.new_cards_remaining + self.missing_items
+    }
+
+    /// Average new cards per day over the recent history; `None` without
+    /// enough history.
+    pub fn current_pace(&self) -> Option<f64> {
+        self.current_pace
+    }
+
+    /// New cards per day needed to introduce everything before the exam;
+    /// `0` once the exam day has come, when new material no longer helps.
+    pub fn required_pace(&self) -> usize {
+        if self.days_left == 0 {
+            return 0;
+        }
+        self.items_to_introduce().div_ceil(self.days_left as usize)
+    }
+
+    /// When everything left would be introduced at the current pace.
+    pub fn projected_completion(&self) -> Option<DateTime<Utc>> {
+        self.projected_completion
+    }
+
+    /// Whether the current pace introduces all target content before the
+    /// exam day. Without pace history the answer is `false` unless nothing
+    /// is left to introduce.
+    pub fn is_reachable(&self) -> bool {
+        if self.items_to_introduce() == 0 {
+            return true;
+        }
+        self.projected_completion
+            .is_some_and(|date| date.date_naive() < self.target.exam_date())
+    }
+}
+
+/// Measures `knowledge_set` against the content of `target` as of `today`.
+pub fn assess_exam_readiness(
+    knowledge_set: &KnowledgeSet,
+    jlpt_content: &JlptContent,
+    target: &ExamTarget,
+    today: NaiveDate,
+) -> ExamReadiness {
+    let mut present: HashSet<(CardType, String)> = HashSet::new();
+    let mut learned_items = 0usize;
+    let mut new_cards_remaining = 0usize;
+
+    for study_card in knowledge_set.study_cards().values() {
+        let card = study_card.card();
+        let card_type = CardType::from(card);
+        if matches!(card_type, CardType::Phrase) {
+            continue;
+        }
+        let content_key = card.content_key();
+        let Some(level) = jlpt_content.find_level(&content_key, card_type) else {
+            continue;
+        };
+        if !target.covers(level) || !present.insert((card_type, content_key)) {
+            continue;
+        }
+
+        let memory = study_card.memory();
+        if memory.is_known_card() {
+            learned_items += 1;
+        } else if memory.is_new() {
+            new_cards_remaining += 1;
+        }
+    }
+
+    let target_items: usize = target_levels(target)
+        .map(|level| {
+            jlpt_content.total_kanji(level)
+                + jlpt_content.total_words(level)
+                + jlpt_content.total_grammar(level)
+        })
+        .sum();
+    let missing_items = target_items.saturating_sub(present.len());
+
+    let history = knowledge_set.lesson_history();
+
+    ExamReadiness {
+        target: *target,
+        days_left: target.days_left(today),
+        target_items,
+        learned_items,
+        new_cards_remaining,
+        missing_items,
+        current_pace: average_new_cards_per_day(history),
+        projected_completion: estimate_completion_date(
+            history,
+            new_cards_remaining + missing_items,
+        ),
+    }
+}
+
+fn target_levels(target: &ExamTarget) -> impl Iterator<Item = JapaneseLevel> + '_ {
+    JapaneseLevel::ALL
+        .into_iter()
+        .filter(|level| target.covers(*level))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::domain::knowledge::{Card, VocabularyCard};
+    use crate::domain::value_objects::Question;
+    use crate::domain::{RateMode, Rating};
+
+    fn content() -> JlptContent {
+        let mut content = JlptContent::new();
+        content.words_by_level.insert(
+            JapaneseLevel::N5,
+            ["水", "火", "木"].iter().map(|w| w.to_string()).collect(),
+        );
+        content.words_by_level.insert(
+            JapaneseLevel::N4,
+            ["運動"].iter().map(|w| w.to_string()).collect(),
+        );
+        content.words_by_level.insert(
+            JapaneseLevel::N3,
+            ["政治"].iter().map(|w| w.to_string()).collect(),
+        );
+        content
+    }
+
+    fn vocab(word: &str) -> Card {
+        Card::Vocabulary(VocabularyCard::new(
+            Question::new(word.to_string()).unwrap(),
+        ))
+    }
+
+    fn target(level: JapaneseLevel, days: i64) -> ExamTarget {
+        ExamTarget::new(level, Utc::now().date_naive() + Duration::days(days)).unwrap()
+    }
+
+    #[test]
+    fn counts_only_levels_covered_by_the_exam() {
+        let mut ks = KnowledgeSet::new();
+        ks.create_card(vocab("水")).unwrap();
+        ks.create_card(vocab("運動")).unwrap();
+        ks.create_card(vocab("政治")).unwrap();
+
+        let readiness = assess_exam_readiness(
+            &ks,
+            &content(),
+            &target(JapaneseLevel::N4, 60),
+            Utc::now().date_naive(),
+        );
+
+        assert_eq!(readiness.target_items(), 4);
+        assert_eq!(readiness.new_cards_remaining(), 2);
+        assert_eq!(readiness.missing_items(), 2);
+        assert_eq!(readiness.items_to_introduce(), 4);
+        assert_eq!(readiness.days_left(), 60);
+        assert_eq!(readiness.required_pace(), 1);
+    }
+
+    #[test]
+    fn studied_cards_are_neither_new_nor_missing() {
+        let mut ks = KnowledgeSet::new();
+        let card = ks.create_card(vocab("水")).unwrap();
+        ks.rate_card(*card.card_id(), Rating::Good, RateMode::StandardLesson)
+            .unwrap();
+
+        let readiness = assess_exam_readiness(
+            &ks,
+            &content(),
+            &target(JapaneseLevel::N5, 30),
+            Utc::now().date_naive(),
+        );
+
+        assert_eq!(readiness.new_cards_remaining(), 0);
+        assert_eq!(readiness.missing_items(), 2);
+    }
+
+    #[test]
+    fn unreachable_without_pace_history() {
+        let ks = KnowledgeSet::new();
+
+        let readiness = assess_exam_readiness(
+            &ks,
+            &content(),
+            &target(JapaneseLevel::N5, 30),
+            Utc::now().date_naive(),
+        );
+
+        assert!(readiness.current_pace().is_none());
+        assert!(!readiness.is_reachable());
+    }
+
+    #[test]
+    fn reachable_once_nothing_is_left_to_introduce() {
+        let readiness = assess_exam_readiness(
+            &KnowledgeSet::new(),
+            &JlptContent::new(),
+            &target(JapaneseLevel::N5, 30),
+            Utc::now().date_naive(),
+        );
+
+        assert_eq!(readiness.items_to_introduce(), 0);
+        assert!(readiness.is_reachable());
+    }
+}
@@ -0,0 +1,285 @@
+//! Append-only event journal of a [`KnowledgeSet`](super::KnowledgeSet).
+//!
+//! Every change to the set (card created, edited, deleted, reviewed, marked
+//! known, favorite toggled) is recorded as a [`KnowledgeEvent`] carrying its
+//! outcome — a review stores the resulting `MemoryState`, not the inputs FSRS
+//! needs — so applying an event never consults the clock or the scheduler
+//! and replay is a pure function of (checkpoint, events).
+//!
+//! Sync model:
+//!
+//! - The remote keeps a *checkpoint* (a full set written by some device)
+//!   plus the events recorded after it. A checkpoint names the events it
+//!   already contains by a per-replica watermark (`folded`).
+//! - Each device (replica) numbers its own events `0, 1, 2, …` and uploads
+//!   them in order, so a watermark of `n` for a replica means "its events
+//!   `< n` are inside the checkpoint".
+//! - On merge a device rebuilds its set as checkpoint + every unfolded
+//!   event it or the remote knows, sorted by [`EventId`]. Two devices that
+//!   saw the same checkpoint and the same events therefore hold identical
+//!   sets — unlike the state merge, where counters take `max()` and
+//!   concurrent reviews of one card undercount.
+//!
+//! Sets written before the journal existed carry no watermark and are not
+//! *replayable*: the first sync of such a set falls back to the state merge
+//! and then publishes a fresh checkpoint, after which replay takes over.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+
+use super::{Card, StudyCard};
+use crate::domain::{MemoryState, RateMode, Rating};
+
+/// Identity and replay position of an event: ordered by time first, then by
+/// replica and sequence so that simultaneous events still sort the same way
+/// on every device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct EventId {
+    at: DateTime<Utc>,
+    replica: Ulid,
+    seq: u64,
+}
+
+impl EventId {
+    pub fn new(at: DateTime<Utc>, replica: Ulid, seq: u64) -> Self {
+        Self { at, replica, seq }
+    }
+
+    pub fn at(&self) -> DateTime<Utc> {
+        self.at
+    }
+
+    pub fn replica(&self) -> Ulid {
+        self.replica
+    }
+
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+}
+
+/// A single change to a [`KnowledgeSet`](super::KnowledgeSet), with its
+/// outcome already decided.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum KnowledgeOp {
+    CardCreated {
+        card: StudyCard,
+    },
+    CardContentUpdated {
+        card_id: Ulid,
+        card: Card,
+    },
+    CardDeleted {
+        card_id: Ulid,
+    },
+    /// `mode` is the effective mode the card was scheduled with.
+    CardReviewed {
+        card_id: Ulid,
+        rating: Rating,
+        mode: RateMode,
+        state: MemoryState,
+        #[serde(default)]
+        logged: bool,
+    },
+    CardMarkedKnown {
+        card_id: Ulid,
+        state: MemoryState,
+    },
+    FavoriteToggled {
+        card_id: Ulid,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgeEvent {
+    id: EventId,
+    #[serde(flatten)]
+    op: KnowledgeOp,
+}
+
+impl KnowledgeEvent {
+    pub fn id(&self) -> &EventId {
+        &self.id
+    }
+
+    pub fn op(&self) -> &KnowledgeOp {
+        &self.op
+    }
+}
+
+/// Per-replica count of events folded into a checkpoint.
+pub type Watermark = BTreeMap<Ulid, u64>;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct KnowledgeJournal {
+    /// This device's replica id, minted on its first recorded event. Never
+    /// part of a checkpoint, so a profile downloaded to a new device starts
+    /// its own stream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replica: Option<Ulid>,
+    #[serde(default)]
+    next_seq: u64,
+    /// Watermark of the checkpoint the set was rebuilt from.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    folded: Watermark,
+    /// Events applied on top of that checkpoint, in [`EventId`] order.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_events"
+    )]
+    events: Vec<KnowledgeEvent>,
+    /// Whether the set equals checkpoint + `events`. `false` for sets that
+    /// predate the journal or were last merged by state.
+    #[serde(default)]
+    replayable: bool,
+}
+
+/// Reads events one by one so an event this build cannot parse (one that
+/// carries a card type added later) is dropped on its own instead of
+/// failing the whole set. The remote keeps its event row regardless.
+fn deserialize_events<'de, D>(deserializer: D) -> Result<Vec<KnowledgeEvent>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(raw
+        .into_iter()
+        .filter_map(|value| match KnowledgeEvent::deserialize(value) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                tracing::warn!("Skipping unreadable knowledge event: {}", e);
+                None
+            },
+        })
+        .collect())
+}
+
+impl KnowledgeJournal {
+    pub fn replica(&self) -> Option<Ulid> {
+        self.replica
+    }
+
+    pub fn folded(&self) -> &Watermark {
+        &self.folded
+    }
+
+    pub fn events(&self) -> &[KnowledgeEvent] {
+        &self.events
+    }
+
+    pub fn is_replayable(&self) -> bool {
+        self.replayable
+    }
+
+    /// Whether `id` is already contained in the checkpoint.
+    pub fn is_folded(&self, id: &EventId) -> bool {
+        self.folded
+            .get(&id.replica)
+            .is_some_and(|&count| id.seq < count)
+    }
+
+    /// Events not folded into `checkpoint` and absent from `known` — what
+    /// this device still has to upload.
+    pub fn unsynced<'a>(
+        &'a self,
+        checkpoint: &'a KnowledgeJournal,
+        known: &'a HashSet<EventId>,
+    ) -> impl Iterator<Item = &'a KnowledgeEvent> + 'a {
+        self.events
+            .iter()
+            .filter(move |event| !checkpoint.is_folded(&event.id) && !known.contains(&event.id))
+    }
+
+    /// Whether every event folded into `checkpoint` is folded or journaled
+    /// here too, i.e. overwriting `checkpoint` with this set loses nothing.
+    pub fn contains_checkpoint(&self, checkpoint: &KnowledgeJournal) -> bool {
+        let watermark = self.watermark();
+        checkpoint
+            .folded
+            .iter()
+            .all(|(replica, &count)| watermark.get(replica).is_some_and(|&own| own >= count))
+    }
+
+    /// Appends a new event of this replica.
+    pub(crate) fn record(&mut self, at: DateTime<Utc>, op: KnowledgeOp) -> KnowledgeEvent {
+        let replica = *self.replica.get_or_insert_with(Ulid::new);
+        let event = KnowledgeEvent {
+            id: EventId::new(at, replica, self.next_seq),
+            op,
+        };
+        self.next_seq += 1;
+        self.events.push(event.clone());
+        event
+    }
+
+    /// Watermark covering the checkpoint and every journaled event.
+    fn watermark(&self) -> Watermark {
+        let mut watermark = self.folded.clone();
+        for event in &self.events {
+            let count = watermark.entry(event.id.replica).or_insert(0);
+            *count = (*count).max(event.id.seq + 1);
+        }
+        watermark
+    }
+
+    /// The journal of a checkpoint written from this set: everything is
+    /// folded and no replica is attached.
+    pub(crate) fn to_checkpoint(&self) -> Self {
+        Self {
+            replica: None,
+            next_seq: 0,
+            folded: self.watermark(),
+            events: Vec::new(),
+            replayable: true,
+        }
+    }
+
+    /// Adopts the watermark of a checkpoint published from this set, keeping
+    /// this device's replica and sequence and the events recorded since.
+    pub(crate) fn fold_into(&mut self, checkpoint: &KnowledgeJournal) {
+        self.folded = checkpoint.folded.clone();
+        self.events.retain(|event| !checkpoint.is_folded(&event.id));
+        self.replayable = true;
+    }
+
+    /// Unfolded events of both sides, deduplicated and in replay order.
+    pub(crate) fn merged_events(
+        &self,
+        checkpoint: &KnowledgeJournal,
+        remote_events: &[KnowledgeEvent],
+    ) -> Vec<KnowledgeEvent> {
+        let mut merged: BTreeMap<EventId, KnowledgeEvent> = BTreeMap::new();
+        for event in self.events.iter().chain(remote_events) {
+            if !checkpoint.is_folded(&event.id) {
+                merged.entry(event.id).or_insert_with(|| event.clone());
+            }
+        }
+        merged.into_values().collect()
+    }
+
+    /// Adopts `checkpoint`'s watermark and `events` after a rebase,
+    /// keeping this device's replica and sequence.
+    pub(crate) fn rebased(
+        &self,
+        checkpoint: &KnowledgeJournal,
+        events: Vec<KnowledgeEvent>,
+        replayable: bool,
+    ) -> Self {
+        Self {
+            replica: self.replica,
+            next_seq: self.next_seq,
+            folded: checkpoint.folded.clone(),
+            events,
+            replayable,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "journal_tests.rs"]
+mod tests;
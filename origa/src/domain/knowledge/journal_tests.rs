@@ -0,0 +1,212 @@
+use super::*;
+use crate::domain::knowledge::{KnowledgeSet, VocabularyCard};
+use crate::domain::value_objects::Question;
+
+fn vocab(word: &str) -> Card {
+    Card::Vocabulary(VocabularyCard::new(
+        Question::new(word.to_string()).unwrap(),
+    ))
+}
+
+/// A published checkpoint holding one card, and its id.
+fn checkpoint_with_card() -> (KnowledgeSet, Ulid) {
+    let mut origin = KnowledgeSet::new();
+    let card_id = *origin.create_card(vocab("猫")).unwrap().card_id();
+    (origin.checkpoint(), card_id)
+}
+
+fn assert_same_content(left: &KnowledgeSet, right: &KnowledgeSet) {
+    assert_eq!(left.study_cards(), right.study_cards());
+    assert_eq!(left.deleted_cards(), right.deleted_cards());
+    assert_eq!(left.lesson_history(), right.lesson_history());
+}
+
+#[test]
+fn recorded_events_are_numbered_per_replica() {
+    let mut set = KnowledgeSet::new();
+
+    set.create_card(vocab("犬")).unwrap();
+    set.create_card(vocab("鳥")).unwrap();
+
+    let events = set.journal().events();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].id().replica(), events[1].id().replica());
+    assert_eq!(events[0].id().seq(), 0);
+    assert_eq!(events[1].id().seq(), 1);
+}
+
+#[test]
+fn checkpoint_folds_every_event_and_detaches_replica() {
+    let mut set = KnowledgeSet::new();
+    set.create_card(vocab("犬")).unwrap();
+    let replica = set.journal().replica().unwrap();
+
+    let checkpoint = set.checkpoint();
+
+    assert!(checkpoint.journal().is_replayable());
+    assert!(checkpoint.journal().events().is_empty());
+    assert!(checkpoint.journal().replica().is_none());
+    assert_eq!(checkpoint.journal().folded().get(&replica), Some(&1));
+    assert_eq!(checkpoint.study_cards(), set.study_cards());
+}
+
+#[test]
+fn replaying_events_reproduces_the_recording_set() {
+    let (checkpoint, card_id) = checkpoint_with_card();
+    let mut device = checkpoint.clone();
+    let extra = *device.create_card(vocab("犬")).unwrap().card_id();
+    device
+        .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+        .unwrap();
+    device.toggle_favorite(card_id).unwrap();
+    device.mark_card_as_known(extra).unwrap();
+    device.delete_card(extra).unwrap();
+
+    let mut other = checkpoint.clone();
+    other.rebase(&checkpoint, device.journal().events());
+
+    assert_same_content(&other, &device);
+}
+
+#[test]
+fn offline_devices_converge_exactly() {
+    // Arrange — both devices start from the same checkpoint and review the
+    // same card while offline.
+    let (checkpoint, card_id) = checkpoint_with_card();
+    let mut device_a = checkpoint.clone();
+    let mut device_b = checkpoint.clone();
+    device_a
+        .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+        .unwrap();
+    device_a.create_card(vocab("犬")).unwrap();
+    device_b
+        .rate_card(card_id, Rating::Again, RateMode::StandardLesson)
+        .unwrap();
+    device_b.create_card(vocab("鳥")).unwrap();
+    let events_a = device_a.journal().events().to_vec();
+    let events_b = device_b.journal().events().to_vec();
+
+    // Act
+    device_a.rebase(&checkpoint, &events_b);
+    device_b.rebase(&checkpoint, &events_a);
+
+    // Assert — identical sets, and both reviews are counted.
+    assert_same_content(&device_a, &device_b);
+    assert_eq!(device_a.study_cards().len(), 3);
+    assert_eq!(device_a.get_card(card_id).unwrap().memory().reps(), 2);
+}
+
+#[test]
+fn rebase_is_idempotent() {
+    let (checkpoint, card_id) = checkpoint_with_card();
+    let mut remote_device = checkpoint.clone();
+    remote_device
+        .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+        .unwrap();
+    let remote_events = remote_device.journal().events().to_vec();
+    let mut device = checkpoint.clone();
+
+    device.rebase(&checkpoint, &remote_events);
+    let once = device.clone();
+    device.rebase(&checkpoint, &remote_events);
+
+    assert_same_content(&device, &once);
+    assert_eq!(device.journal().events(), once.journal().events());
+}
+
+#[test]
+fn events_folded_into_a_newer_checkpoint_are_not_replayed_again() {
+    let (checkpoint, card_id) = checkpoint_with_card();
+    let mut device = checkpoint.clone();
+    device
+        .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+        .unwrap();
+    let newer_checkpoint = device.checkpoint();
+
+    let stale_events = device.journal().events().to_vec();
+    device.rebase(&newer_checkpoint, &stale_events);
+
+    assert!(device.journal().events().is_empty());
+    assert_eq!(device.get_card(card_id).unwrap().memory().reps(), 1);
+}
+
+#[test]
+fn own_events_stay_journaled_until_published() {
+    let (checkpoint, card_id) = checkpoint_with_card();
+    let mut device = checkpoint.clone();
+    device
+        .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+        .unwrap();
+
+    device.rebase(&checkpoint, &[]);
+
+    let known = HashSet::new();
+    assert_eq!(
+        device
+            .journal()
+            .unsynced(checkpoint.journal(), &known)
+            .count(),
+        1
+    );
+    let published = device.checkpoint();
+    device
+        .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+        .unwrap();
+    device.fold_journal(published.journal());
+
+    // Only the review recorded after publishing is still pending.
+    assert_eq!(device.journal().events().len(), 1);
+    assert_eq!(device.journal().events()[0].id().seq(), 1);
+    assert_eq!(device.get_card(card_id).unwrap().memory().reps(), 2);
+}
+
+#[test]
+fn set_predating_the_journal_falls_back_to_state_merge() {
+    // Arrange — a legacy local set (never rebased) with its own card.
+    let (checkpoint, _) = checkpoint_with_card();
+    let mut legacy = KnowledgeSet::new();
+    legacy.create_card(vocab("犬")).unwrap();
+    assert!(!legacy.journal().is_replayable());
+
+    // Act
+    legacy.rebase(&checkpoint, &[]);
+
+    // Assert — nothing is lost, and the set waits for a fresh checkpoint.
+    assert_eq!(legacy.study_cards().len(), 2);
+    assert!(!legacy.journal().is_replayable());
+    let published = legacy.checkpoint();
+    legacy.fold_journal(published.journal());
+    assert!(legacy.journal().is_replayable());
+}
+
+#[test]
+fn event_roundtrips_through_json() {
+    let mut set = KnowledgeSet::new();
+    let card_id = *set.create_card(vocab("犬")).unwrap().card_id();
+    set.rate_card(card_id, Rating::Hard, RateMode::StandardLesson)
+        .unwrap();
+
+    for event in set.journal().events() {
+        let json = serde_json::to_string(event).unwrap();
+        let restored: KnowledgeEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(&restored, event);
+    }
+}
+
+#[test]
+fn set_contains_only_checkpoints_it_was_rebuilt_from() {
+    let (checkpoint, card_id) = checkpoint_with_card();
+    let mut device = checkpoint.clone();
+    let mut other_device = checkpoint.clone();
+    other_device
+        .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+        .unwrap();
+    let newer = other_device.checkpoint();
+
+    assert!(device.journal().contains_checkpoint(checkpoint.journal()));
+    assert!(!device.journal().contains_checkpoint(newer.journal()));
+
+    device.rebase(&newer, &[]);
+
+    assert!(device.journal().contains_checkpoint(newer.journal()));
+}
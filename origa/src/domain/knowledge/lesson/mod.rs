@@ -2,7 +2,8 @@ mod types;
 mod view_generator;
 
 pub use types::{
-    GrammarInfo, GrammarQuizCard, LessonCard, LessonCardView, LessonData, MultiQuizResult,
-    QuizCard, QuizMode, QuizOption, YesNoCard,
+    ConjugationDrillCard, GrammarInfo, GrammarQuizCard, LessonCard, LessonCardView, LessonData,
+    MultiQuizResult, ParticleClozeCard, ProductionCard, ProductionResult, QuizCard, QuizMode,
+    QuizOption, YesNoCard,
 };
 pub use view_generator::LessonViewGenerator;
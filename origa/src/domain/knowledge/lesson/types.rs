@@ -3,9 +3,13 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
-use crate::domain::Card;
+use crate::dictionary::grammar::FormatAction;
 use crate::domain::knowledge::card::CardType;
 use crate::domain::memory::Rating;
+use crate::domain::{
+    Card, ConjugationCheck, OrigaError, PartOfSpeech, check_conjugation, conjugation_step_label,
+    katakana_to_hiragana, romaji_to_kana,
+};
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QuizOption {
     text: String,
@@ -316,6 +320,244 @@ impl GrammarQuizCard {
     }
 }
 
+/// Typed-answer drill: the learner writes `word_text` conjugated through
+/// `actions` instead of picking it from options.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConjugationDrillCard {
+    card: Card,
+    grammar_info: GrammarInfo,
+    word_text: String,
+    #[serde(default)]
+    reading: Option<String>,
+    part_of_speech: PartOfSpeech,
+    actions: Vec<FormatAction>,
+}
+
+impl ConjugationDrillCard {
+    pub fn new(
+        card: Card,
+        grammar_info: GrammarInfo,
+        word_text: String,
+        reading: Option<String>,
+        part_of_speech: PartOfSpeech,
+        actions: Vec<FormatAction>,
+    ) -> Self {
+        Self {
+            card,
+            grammar_info,
+            word_text,
+            reading,
+            part_of_speech,
+            actions,
+        }
+    }
+
+    pub fn card(&self) -> &Card {
+        &self.card
+    }
+
+    pub fn grammar_info(&self) -> &GrammarInfo {
+        &self.grammar_info
+    }
+
+    pub fn word_text(&self) -> &str {
+        &self.word_text
+    }
+
+    pub fn reading(&self) -> Option<&str> {
+        self.reading.as_deref()
+    }
+
+    pub fn part_of_speech(&self) -> &PartOfSpeech {
+        &self.part_of_speech
+    }
+
+    pub fn actions(&self) -> &[FormatAction] {
+        &self.actions
+    }
+
+    /// Step names shown as the prompt, in application order.
+    pub fn step_labels(&self) -> Vec<String> {
+        self.actions.iter().map(conjugation_step_label).collect()
+    }
+
+    pub fn check_answer(&self, answer: &str) -> Result<ConjugationCheck, OrigaError> {
+        check_conjugation(
+            &self.word_text,
+            self.reading.as_deref(),
+            &self.actions,
+            &self.part_of_speech,
+            answer,
+        )
+    }
+}
+
+/// Production review: the learner sees the meaning and types the reading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductionCard {
+    card: Card,
+    prompt: String,
+    /// Accepted readings in hiragana, the usual one first.
+    readings: Vec<String>,
+}
+
+impl ProductionCard {
+    pub fn new(card: Card, prompt: String, readings: Vec<String>) -> Self {
+        Self {
+            card,
+            prompt,
+            readings,
+        }
+    }
+
+    pub fn card(&self) -> &Card {
+        &self.card
+    }
+
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    pub fn readings(&self) -> &[String] {
+        &self.readings
+    }
+
+    /// Grades a romaji or kana answer against the closest accepted reading:
+    /// exact is `Good`, within the typo allowance is `Hard`, anything else
+    /// is `Again`.
+    pub fn check_answer(&self, answer: &str) -> ProductionResult {
+        let answer = katakana_to_hiragana(&romaji_to_kana(answer.trim()));
+        let closest = self
+            .readings
+            .iter()
+            .map(|reading| (reading, edit_distance(reading, &answer)))
+            .min_by_key(|(_, typos)| *typos);
+
+        let Some((reading, typos)) = closest else {
+            return ProductionResult {
+                rating: Rating::Again,
+                expected: String::new(),
+                typos: 0,
+            };
+        };
+        let rating = if answer.is_empty() {
+            Rating::Again
+        } else if typos == 0 {
+            Rating::Good
+        } else if typos <= allowed_typos(reading) {
+            Rating::Hard
+        } else {
+            Rating::Again
+        };
+
+        ProductionResult {
+            rating,
+            expected: reading.clone(),
+            typos,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductionResult {
+    pub rating: Rating,
+    /// Accepted reading closest to the answer.
+    pub expected: String,
+    pub typos: usize,
+}
+
+/// One slip per four kana, so short words must be exact: for きく a single
+/// typo already gives a different word.
+fn allowed_typos(reading: &str) -> usize {
+    (reading.chars().count() + 1) / 4
+}
+
+/// Levenshtein distance counted in characters, so one kana is one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Particle cloze: a known phrase with one particle blanked out, answered by
+/// picking it among the particles it is usually confused with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticleClozeCard {
+    card: Card,
+    before: String,
+    after: String,
+    particle: String,
+    #[serde(default)]
+    translation: Option<String>,
+    options: Vec<QuizOption>,
+}
+
+impl ParticleClozeCard {
+    pub fn new(
+        card: Card,
+        before: String,
+        after: String,
+        particle: String,
+        translation: Option<String>,
+        options: Vec<QuizOption>,
+    ) -> Self {
+        Self {
+            card,
+            before,
+            after,
+            particle,
+            translation,
+            options,
+        }
+    }
+
+    pub fn card(&self) -> &Card {
+        &self.card
+    }
+
+    /// Phrase text up to the blank.
+    pub fn before(&self) -> &str {
+        &self.before
+    }
+
+    /// Phrase text after the blank.
+    pub fn after(&self) -> &str {
+        &self.after
+    }
+
+    /// The particle that fills the blank.
+    pub fn particle(&self) -> &str {
+        &self.particle
+    }
+
+    pub fn translation(&self) -> Option<&str> {
+        self.translation.as_deref()
+    }
+
+    pub fn options(&self) -> &[QuizOption] {
+        &self.options
+    }
+
+    pub fn phrase_text(&self) -> String {
+        format!("{}{}{}", self.before, self.particle, self.after)
+    }
+
+    pub fn check_answer(&self, index: usize) -> bool {
+        self.options
+            .get(index)
+            .map(|o| o.is_correct())
+            .unwrap_or(false)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LessonCardView {
     Normal(Card),
@@ -334,6 +576,10 @@ pub enum LessonCardView {
     },
     KanjiReadingQuiz(QuizCard),
     GrammarQuiz(GrammarQuizCard),
+    PitchAccentQuiz(QuizCard),
+    ConjugationDrill(ConjugationDrillCard),
+    Production(ProductionCard),
+    ParticleCloze(ParticleClozeCard),
 }
 
 impl LessonCardView {
@@ -346,8 +592,13 @@ impl LessonCardView {
             | LessonCardView::PhraseListen { card, .. } => card,
             LessonCardView::Quiz(quiz) => quiz.card(),
             LessonCardView::YesNo(yc) => yc.card(),
-            LessonCardView::KanjiReadingQuiz(quiz) => quiz.card(),
+            LessonCardView::KanjiReadingQuiz(quiz) | LessonCardView::PitchAccentQuiz(quiz) => {
+                quiz.card()
+            },
             LessonCardView::GrammarQuiz(gq) => gq.card(),
+            LessonCardView::ConjugationDrill(drill) => drill.card(),
+            LessonCardView::Production(production) => production.card(),
+            LessonCardView::ParticleCloze(cloze) => cloze.card(),
         }
     }
 
@@ -355,13 +606,17 @@ impl LessonCardView {
         match self {
             LessonCardView::GrammarMutated { grammar_info, .. } => Some(grammar_info),
             LessonCardView::GrammarQuiz(gq) => Some(gq.grammar_info()),
+            LessonCardView::ConjugationDrill(drill) => Some(drill.grammar_info()),
             LessonCardView::Normal(_)
             | LessonCardView::Quiz(_)
             | LessonCardView::YesNo(_)
             | LessonCardView::Reversed(_)
             | LessonCardView::Writing(_)
             | LessonCardView::PhraseListen { .. }
-            | LessonCardView::KanjiReadingQuiz(_) => None,
+            | LessonCardView::KanjiReadingQuiz(_)
+            | LessonCardView::PitchAccentQuiz(_)
+            | LessonCardView::Production(_)
+            | LessonCardView::ParticleCloze(_) => None,
         }
     }
 }
@@ -700,6 +955,74 @@ mod tests {
         );
     }
 
+    mod production_card_tests {
+        use super::*;
+        use rstest::rstest;
+
+        fn production_card(readings: &[&str]) -> ProductionCard {
+            let card = Card::Vocabulary(VocabularyCard::new(
+                Question::new("学生".to_string()).unwrap(),
+            ));
+            ProductionCard::new(
+                card,
+                "student".to_string(),
+                readings.iter().map(|r| r.to_string()).collect(),
+            )
+        }
+
+        #[rstest]
+        #[case("gakusei", Rating::Good, 0)]
+        #[case("がくせい", Rating::Good, 0)]
+        #[case("ガクセイ", Rating::Good, 0)]
+        #[case("gakuse", Rating::Hard, 1)]
+        #[case("gakusii", Rating::Hard, 1)]
+        #[case("gakkou", Rating::Again, 3)]
+        #[case("", Rating::Again, 4)]
+        fn check_answer_grades_by_typos(
+            #[case] answer: &str,
+            #[case] rating: Rating,
+            #[case] typos: usize,
+        ) {
+            // Arrange
+            let production = production_card(&["がくせい"]);
+
+            // Act
+            let result = production.check_answer(answer);
+
+            // Assert
+            assert_eq!(result.rating, rating);
+            assert_eq!(result.typos, typos);
+            assert_eq!(result.expected, "がくせい");
+        }
+
+        #[test]
+        fn check_answer_accepts_alternative_reading() {
+            // Arrange
+            let production = production_card(&["きょう", "こんにち"]);
+
+            // Act
+            let result = production.check_answer("konnichi");
+
+            // Assert
+            assert_eq!(result.rating, Rating::Good);
+            assert_eq!(result.expected, "こんにち");
+        }
+
+        #[test]
+        fn check_answer_requires_exact_short_word() {
+            // Arrange
+            let production = production_card(&["きく"]);
+
+            // Act
+            let result = production.check_answer("kiku");
+            let typo = production.check_answer("kaku");
+
+            // Assert
+            assert_eq!(result.rating, Rating::Good);
+            assert_eq!(typo.rating, Rating::Again);
+        }
+    }
+
     mod yesno_card_deserialize_tests {
         use super::*;
 
@@ -1,16 +1,24 @@
-use crate::dictionary::grammar::get_rule_by_id;
+use crate::dictionary::furigana_dict::get_furigana_dict;
+use crate::dictionary::grammar::{FormatAction, FormatActionGroup, get_rule_by_id};
 use crate::dictionary::kanji::{KanjiInfo, get_kanji_info};
-use crate::domain::grammar::apply_format_actions;
+use crate::dictionary::phrase::{get_phrase_text, get_phrase_translation};
+use crate::dictionary::pitch_audio::pitch_accent_for_word;
+use crate::domain::grammar::{apply_format_actions, confusable_particles};
 use crate::domain::knowledge::KnowledgeSet;
+use crate::domain::tokenizer::TokenInfo;
 use crate::domain::value_objects::{CardAnswer, NativeLanguage};
 use crate::domain::{
-    Card, OrigaError, PartOfSpeech, find_known_vocab_words_for_pos, generate_grammar_distractors,
+    Card, GrammarRuleCard, JapaneseText, OrigaError, PartOfSpeech, PitchAccent, PitchAccentType,
+    find_known_vocab_words_for_pos, generate_grammar_distractors, katakana_to_hiragana,
+    tokenize_text,
 };
 use rand::{Rng, prelude::IndexedRandom, seq::SliceRandom};
 use std::collections::HashMap;
+use ulid::Ulid;
 
 use super::super::types::{
-    GrammarInfo, GrammarQuizCard, LessonCardView, QuizCard, QuizMode, QuizOption, YesNoCard,
+    ConjugationDrillCard, GrammarInfo, GrammarQuizCard, LessonCardView, ParticleClozeCard,
+    ProductionCard, QuizCard, QuizMode, QuizOption, YesNoCard,
 };
 use super::QUIZ_OPTIONS_COUNT;
 
@@ -27,7 +35,11 @@ pub(crate) fn generate_quiz(
     lang: &NativeLanguage,
 ) -> Result<LessonCardView, OrigaError> {
     match &original_card {
-        Card::Vocabulary(_) | Card::Kanji(_) | Card::Grammar(_) | Card::Phrase(_) => {},
+        Card::Vocabulary(_)
+        | Card::Kanji(_)
+        | Card::Grammar(_)
+        | Card::Phrase(_)
+        | Card::Particle(_) => {},
     }
 
     let correct_answer = original_card.answer(lang)?;
@@ -115,7 +127,11 @@ pub(crate) fn generate_yesno(
     rng: &mut impl Rng,
 ) -> Result<LessonCardView, OrigaError> {
     match &original_card {
-        Card::Vocabulary(_) | Card::Kanji(_) | Card::Grammar(_) | Card::Phrase(_) => {},
+        Card::Vocabulary(_)
+        | Card::Kanji(_)
+        | Card::Grammar(_)
+        | Card::Phrase(_)
+        | Card::Particle(_) => {},
     }
 
     let question = original_card.question(lang)?;
@@ -158,7 +174,7 @@ pub(crate) fn generate_phrase_quiz(
 ) -> Option<LessonCardView> {
     let phrase_card = match &original_card {
         Card::Phrase(pc) => pc,
-        Card::Vocabulary(_) | Card::Kanji(_) | Card::Grammar(_) => return None,
+        Card::Vocabulary(_) | Card::Kanji(_) | Card::Grammar(_) | Card::Particle(_) => return None,
     };
 
     let audio_file = format!("{}.opus", phrase_card.phrase_id());
@@ -172,7 +188,7 @@ pub(crate) fn generate_phrase_quiz(
                 .ok()
                 .map(|a| answer_display_text(&a))
                 .filter(|text| text != &correct_text),
-            Card::Vocabulary(_) | Card::Kanji(_) | Card::Grammar(_) => None,
+            Card::Vocabulary(_) | Card::Kanji(_) | Card::Grammar(_) | Card::Particle(_) => None,
         })
         .collect();
 
@@ -207,7 +223,7 @@ pub(crate) fn generate_kanji_reading_quiz(
 ) -> Result<LessonCardView, OrigaError> {
     let kanji_card = match &original_card {
         Card::Kanji(kc) => kc,
-        Card::Vocabulary(_) | Card::Grammar(_) | Card::Phrase(_) => {
+        Card::Vocabulary(_) | Card::Grammar(_) | Card::Phrase(_) | Card::Particle(_) => {
             return Ok(LessonCardView::Normal(original_card));
         },
     };
@@ -314,7 +330,7 @@ fn collect_distractors(
                 );
                 Some(readings)
             },
-            Card::Vocabulary(_) | Card::Grammar(_) | Card::Phrase(_) => None,
+            Card::Vocabulary(_) | Card::Grammar(_) | Card::Phrase(_) | Card::Particle(_) => None,
         })
         .flatten()
         .filter(|r| !target_readings.contains(r))
@@ -327,6 +343,163 @@ fn collect_distractors(
     distractors
 }
 
+/// Asks for the accent type of a vocabulary word; `None` when the pitch
+/// audio index has no accent for it.
+pub(crate) fn generate_pitch_accent_quiz(original_card: Card) -> Option<LessonCardView> {
+    let accent = match &original_card {
+        Card::Vocabulary(vc) => pitch_accent_for_word(vc.word().text())?,
+        Card::Kanji(_) | Card::Grammar(_) | Card::Phrase(_) | Card::Particle(_) => return None,
+    };
+    Some(build_pitch_accent_quiz(original_card, &accent))
+}
+
+/// Offers only the accent types the word's mora count allows: a two-mora
+/// word cannot be nakadaka, and a one-mora odaka is written as atamadaka.
+pub(crate) fn build_pitch_accent_quiz(original_card: Card, accent: &PitchAccent) -> LessonCardView {
+    let correct = accent.accent_type();
+    let mora_count = accent.morae().len();
+    let options: Vec<QuizOption> = PitchAccentType::ALL
+        .into_iter()
+        .filter(|accent_type| match accent_type {
+            PitchAccentType::Heiban | PitchAccentType::Atamadaka => true,
+            PitchAccentType::Odaka => mora_count >= 2,
+            PitchAccentType::Nakadaka => mora_count >= 3,
+        })
+        .map(|accent_type| {
+            QuizOption::new(
+                accent_type.japanese_name().to_string(),
+                accent_type == correct,
+                Some(accent_type.romaji_name().to_string()),
+            )
+        })
+        .collect();
+
+    LessonCardView::PitchAccentQuiz(QuizCard::new(original_card, options, QuizMode::Single))
+}
+
+/// Asks for the reading of a vocabulary word from its meaning. Every reading
+/// the tokenizer or the furigana dictionary knows is accepted; `None` when
+/// there is none.
+pub(crate) fn generate_production(
+    original_card: Card,
+    lang: &NativeLanguage,
+) -> Option<LessonCardView> {
+    let word = match &original_card {
+        Card::Vocabulary(vc) => vc.word().text().to_string(),
+        Card::Kanji(_) | Card::Grammar(_) | Card::Phrase(_) | Card::Particle(_) => return None,
+    };
+    let prompt = answer_display_text(&original_card.answer(lang).ok()?);
+
+    let mut readings = Vec::new();
+    if word.is_japanese() && !word.contains_kanji() {
+        readings.push(katakana_to_hiragana(&word));
+    }
+    readings.extend(kana_reading(&word));
+    if let Some(dict) = get_furigana_dict() {
+        readings.extend(
+            dict.lookup_word(&word)
+                .into_iter()
+                .map(|entry| katakana_to_hiragana(&entry.reading)),
+        );
+    }
+    build_production(original_card, prompt, readings)
+}
+
+/// Drops empty and repeated readings; the first one left is shown after a
+/// miss.
+pub(crate) fn build_production(
+    original_card: Card,
+    prompt: String,
+    readings: Vec<String>,
+) -> Option<LessonCardView> {
+    let mut accepted: Vec<String> = Vec::new();
+    for reading in readings {
+        if !reading.is_empty() && !accepted.contains(&reading) {
+            accepted.push(reading);
+        }
+    }
+    if prompt.is_empty() || accepted.is_empty() {
+        return None;
+    }
+    Some(LessonCardView::Production(ProductionCard::new(
+        original_card,
+        prompt,
+        accepted,
+    )))
+}
+
+/// Blanks a particle out of the phrase `phrase_id`. A phrase card gets a
+/// random blankable particle; a particle card only its own particle. `None`
+/// when the phrase text is not cached yet or nothing can be blanked.
+pub(crate) fn generate_particle_cloze(
+    original_card: Card,
+    phrase_id: &Ulid,
+    lang: &NativeLanguage,
+    rng: &mut impl Rng,
+) -> Option<LessonCardView> {
+    let target = match &original_card {
+        Card::Phrase(_) => None,
+        Card::Particle(pc) => Some(pc.particle().to_string()),
+        Card::Vocabulary(_) | Card::Kanji(_) | Card::Grammar(_) => return None,
+    };
+    let text = get_phrase_text(phrase_id)?;
+    let tokens = tokenize_text(&text).ok()?;
+    let translation = get_phrase_translation(phrase_id, lang);
+    build_particle_cloze(
+        original_card,
+        &text,
+        &tokens,
+        target.as_deref(),
+        translation,
+        rng,
+    )
+}
+
+/// Only particle tokens with a confusable partner are blanked, so every
+/// cloze has at least one distractor.
+pub(crate) fn build_particle_cloze(
+    original_card: Card,
+    text: &str,
+    tokens: &[TokenInfo],
+    target: Option<&str>,
+    translation: Option<String>,
+    rng: &mut impl Rng,
+) -> Option<LessonCardView> {
+    let mut blanks: Vec<(usize, &str)> = Vec::new();
+    let mut cursor = 0;
+    for token in tokens {
+        let surface = token.orthographic_surface_form();
+        let Some(offset) = text[cursor..].find(surface) else {
+            continue;
+        };
+        let start = cursor + offset;
+        cursor = start + surface.len();
+        if *token.part_of_speech() == PartOfSpeech::Particle
+            && target.map_or(true, |target| target == surface)
+            && !confusable_particles(surface).is_empty()
+        {
+            blanks.push((start, surface));
+        }
+    }
+    let &(start, particle) = blanks.choose(rng)?;
+
+    let mut options: Vec<QuizOption> = confusable_particles(particle)
+        .into_iter()
+        .map(|other| QuizOption::new_simple(other.to_string(), false))
+        .collect();
+    options.push(QuizOption::new_simple(particle.to_string(), true));
+    options.shuffle(rng);
+
+    Some(LessonCardView::ParticleCloze(ParticleClozeCard::new(
+        original_card,
+        text[..start].to_string(),
+        text[start + particle.len()..].to_string(),
+        particle.to_string(),
+        translation,
+        options,
+    )))
+}
+
 pub(crate) fn cached_kanji_info<'a>(
     kanji: &str,
     cache: &'a mut HashMap<String, &'static KanjiInfo>,
@@ -338,18 +511,20 @@ pub(crate) fn cached_kanji_info<'a>(
     Ok(cache[kanji])
 }
 
-pub(crate) fn generate_grammar_quiz(
-    original_card: Card,
-    knowledge_set: &KnowledgeSet,
-    lang: &NativeLanguage,
-) -> Result<LessonCardView, OrigaError> {
-    let grammar_rule_card = match &original_card {
-        Card::Grammar(grc) => grc,
-        Card::Vocabulary(_) | Card::Kanji(_) | Card::Phrase(_) => {
-            return Ok(LessonCardView::Normal(original_card));
-        },
-    };
+/// Rule chain of a grammar card and a known word to run it on.
+struct GrammarTarget {
+    part_of_speech: PartOfSpeech,
+    actions: Vec<FormatAction>,
+    word_text: String,
+}
 
+/// `Ok(None)` when the rule has no format map or the learner knows no word
+/// of a part of speech it applies to.
+fn pick_grammar_target(
+    grammar_rule_card: &GrammarRuleCard,
+    knowledge_set: &KnowledgeSet,
+    rng: &mut impl Rng,
+) -> Result<Option<GrammarTarget>, OrigaError> {
     let rule = get_rule_by_id(grammar_rule_card.rule_id()).ok_or_else(|| {
         OrigaError::GrammarRuleNotFound {
             rule_id: *grammar_rule_card.rule_id(),
@@ -357,7 +532,7 @@ pub(crate) fn generate_grammar_quiz(
     })?;
 
     let Some(format_map) = rule.format_map() else {
-        return Ok(LessonCardView::Normal(original_card));
+        return Ok(None);
     };
 
     let supported_pos: Vec<&PartOfSpeech> = format_map.keys().collect();
@@ -371,27 +546,63 @@ pub(crate) fn generate_grammar_quiz(
         })?;
 
     let matching_vocab = find_known_vocab_words_for_pos(knowledge_set, &applicable_pos);
-    if matching_vocab.is_empty() {
-        return Ok(LessonCardView::Normal(original_card));
-    }
+    let Some(word_text) = matching_vocab.choose(rng).cloned() else {
+        return Ok(None);
+    };
 
-    let mut rng = rand::rng();
-    let word_text = matching_vocab
-        .choose(&mut rng)
-        .unwrap_or(&matching_vocab[0])
-        .clone();
+    let Some(actions) = format_map.get(&applicable_pos) else {
+        return Ok(None);
+    };
+
+    Ok(Some(GrammarTarget {
+        part_of_speech: applicable_pos,
+        actions: actions.clone(),
+        word_text,
+    }))
+}
+
+fn grammar_info_for(grammar_rule_card: &GrammarRuleCard, lang: &NativeLanguage) -> GrammarInfo {
+    let grammar_title = grammar_rule_card
+        .title(lang)
+        .map(|q| q.text().to_string())
+        .unwrap_or_else(|_| grammar_rule_card.rule_id().to_string());
+    let grammar_desc = grammar_rule_card
+        .description(lang)
+        .map(|a| answer_display_text(&a))
+        .unwrap_or_default();
+
+    GrammarInfo::new(
+        Some(*grammar_rule_card.rule_id()),
+        grammar_title,
+        grammar_desc,
+    )
+}
 
-    let Some(rules) = format_map.get(&applicable_pos) else {
+pub(crate) fn generate_grammar_quiz(
+    original_card: Card,
+    knowledge_set: &KnowledgeSet,
+    lang: &NativeLanguage,
+) -> Result<LessonCardView, OrigaError> {
+    let grammar_rule_card = match &original_card {
+        Card::Grammar(grc) => grc,
+        Card::Vocabulary(_) | Card::Kanji(_) | Card::Phrase(_) | Card::Particle(_) => {
+            return Ok(LessonCardView::Normal(original_card));
+        },
+    };
+
+    let mut rng = rand::rng();
+    let Some(target) = pick_grammar_target(grammar_rule_card, knowledge_set, &mut rng)? else {
         return Ok(LessonCardView::Normal(original_card));
     };
 
-    let correct_text = apply_format_actions(&word_text, rules, &applicable_pos)?;
+    let correct_text =
+        apply_format_actions(&target.word_text, &target.actions, &target.part_of_speech)?;
 
     let needed_distractors = QUIZ_OPTIONS_COUNT.saturating_sub(1);
     let distractors = generate_grammar_distractors(
-        rules,
-        &word_text,
-        &applicable_pos,
+        &target.actions,
+        &target.word_text,
+        &target.part_of_speech,
         &correct_text,
         needed_distractors,
         &mut rng,
@@ -409,23 +620,82 @@ pub(crate) fn generate_grammar_quiz(
     options.shuffle(&mut rng);
 
     let quiz = QuizCard::new(original_card.clone(), options, QuizMode::Single);
+    let grammar_info = grammar_info_for(grammar_rule_card, lang);
+    let grammar_quiz = GrammarQuizCard::new(original_card, grammar_info, target.word_text, quiz);
 
-    let grammar_title = grammar_rule_card
-        .title(lang)
-        .map(|q| q.text().to_string())
-        .unwrap_or_else(|_| grammar_rule_card.rule_id().to_string());
-    let grammar_desc = grammar_rule_card
-        .description(lang)
-        .map(|a| answer_display_text(&a))
-        .unwrap_or_default();
+    Ok(LessonCardView::GrammarQuiz(grammar_quiz))
+}
 
-    let grammar_info = GrammarInfo::new(
-        Some(*grammar_rule_card.rule_id()),
-        grammar_title,
-        grammar_desc,
-    );
+/// Typed conjugation drill for a reviewed grammar card. Falls back to
+/// `Normal` for rules that do not conjugate a verb or adjective.
+pub(crate) fn generate_conjugation_drill(
+    original_card: Card,
+    knowledge_set: &KnowledgeSet,
+    lang: &NativeLanguage,
+    rng: &mut impl Rng,
+) -> Result<LessonCardView, OrigaError> {
+    let grammar_rule_card = match &original_card {
+        Card::Grammar(grc) => grc,
+        Card::Vocabulary(_) | Card::Kanji(_) | Card::Phrase(_) | Card::Particle(_) => {
+            return Ok(LessonCardView::Normal(original_card));
+        },
+    };
 
-    let grammar_quiz = GrammarQuizCard::new(original_card, grammar_info, word_text, quiz);
+    let Some(target) = pick_grammar_target(grammar_rule_card, knowledge_set, rng)? else {
+        return Ok(LessonCardView::Normal(original_card));
+    };
 
-    Ok(LessonCardView::GrammarQuiz(grammar_quiz))
+    let grammar_info = grammar_info_for(grammar_rule_card, lang);
+    let reading = kana_reading(&target.word_text);
+    Ok(build_conjugation_drill(
+        original_card.clone(),
+        grammar_info,
+        target.word_text,
+        reading,
+        target.part_of_speech,
+        target.actions,
+    )
+    .unwrap_or(LessonCardView::Normal(original_card)))
+}
+
+/// `None` unless the chain conjugates a verb or adjective: postfix-only
+/// chains have nothing to drill.
+pub(crate) fn build_conjugation_drill(
+    original_card: Card,
+    grammar_info: GrammarInfo,
+    word_text: String,
+    reading: Option<String>,
+    part_of_speech: PartOfSpeech,
+    actions: Vec<FormatAction>,
+) -> Option<LessonCardView> {
+    let conjugates = matches!(
+        part_of_speech,
+        PartOfSpeech::Verb | PartOfSpeech::IAdjective | PartOfSpeech::NaAdjective
+    ) && actions
+        .iter()
+        .any(|action| action.group() != FormatActionGroup::Universal);
+    if !conjugates || apply_format_actions(&word_text, &actions, &part_of_speech).is_err() {
+        return None;
+    }
+
+    Some(LessonCardView::ConjugationDrill(ConjugationDrillCard::new(
+        original_card,
+        grammar_info,
+        word_text,
+        reading,
+        part_of_speech,
+        actions,
+    )))
+}
+
+/// Hiragana reading of `word`, or `None` when it is already kana or the
+/// tokenizer is unavailable.
+fn kana_reading(word: &str) -> Option<String> {
+    let reading: String = tokenize_text(word)
+        .ok()?
+        .iter()
+        .map(|token| token.phonological_surface_form())
+        .collect();
+    let reading = katakana_to_hiragana(&reading);
+    (!reading.is_empty() && reading != word).then_some(reading)
 }
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 
 use crate::dictionary::kanji::KanjiInfo;
+use crate::dictionary::phrase::get_index_entry;
 use crate::domain::knowledge::KnowledgeSet;
 use crate::domain::value_objects::NativeLanguage;
 use crate::domain::{Card, CardType, GrammarRuleCard, MemoryHistory};
 use rand::Rng;
+use rand::seq::SliceRandom;
 
 use super::types::LessonCardView;
 
@@ -31,8 +33,13 @@ const PROB_NEW_VOCAB_NORMAL: f32 = 0.50;
 
 const PROB_NEW_PHRASE_NORMAL: f32 = 0.50;
 const PROB_REVIEW_PHRASE_NORMAL: f32 = 0.15;
+const PROB_PARTICLE_CLOZE: f32 = 0.30;
 
 const PROB_GRAMMAR_QUIZ: f32 = 0.50;
+const PROB_CONJUGATION_DRILL: f32 = 0.75;
+
+const PROB_PITCH_ACCENT_QUIZ: f32 = 0.15;
+const PROB_PRODUCTION_VIEW: f32 = 0.15;
 
 const EASY_REVIEWS_FOR_REVERSED: usize = 2;
 const GOOD_REVIEWS_FOR_REVERSED: usize = 4;
@@ -92,6 +99,14 @@ impl<'a> LessonViewGenerator<'a> {
                         &self.native_language,
                     )
                     .unwrap_or_else(|_| LessonCardView::Normal(card.clone()))
+                } else if rand_val < PROB_CONJUGATION_DRILL {
+                    generation::generate_conjugation_drill(
+                        card.clone(),
+                        self.knowledge_set,
+                        &self.native_language,
+                        rng,
+                    )
+                    .unwrap_or_else(|_| LessonCardView::Normal(card.clone()))
                 } else {
                     LessonCardView::Normal(card.clone())
                 }
@@ -127,6 +142,7 @@ impl<'a> LessonViewGenerator<'a> {
                 let same_type_cards = self.same_type_cards(&card_type);
                 self.select_phrase_view(card, same_type_cards, is_new, rng)
             },
+            CardType::Particle => self.select_particle_view(card, rng),
         }
     }
 
@@ -198,6 +214,18 @@ impl<'a> LessonViewGenerator<'a> {
         let eligible_for_reversed = eligible_for_advanced
             || memory.easy_review_count() > EASY_REVIEWS_FOR_REVERSED
             || memory.good_review_count() >= GOOD_REVIEWS_FOR_REVERSED;
+        if memory.is_known_card()
+            && rng.random::<f32>() < PROB_PITCH_ACCENT_QUIZ
+            && let Some(view) = generation::generate_pitch_accent_quiz(card.clone())
+        {
+            return view;
+        }
+        if eligible_for_reversed
+            && rng.random::<f32>() < PROB_PRODUCTION_VIEW
+            && let Some(view) = generation::generate_production(card.clone(), &self.native_language)
+        {
+            return view;
+        }
         let rand_val = rng.random::<f32>();
         if rand_val < PROB_NORMAL_VIEW {
             LessonCardView::Normal(card.clone())
@@ -239,10 +267,63 @@ impl<'a> LessonViewGenerator<'a> {
             return LessonCardView::Normal(card.clone());
         }
 
+        if !is_new
+            && rng.random::<f32>() < PROB_PARTICLE_CLOZE
+            && let Card::Phrase(phrase_card) = card
+            && let Some(view) = generation::generate_particle_cloze(
+                card.clone(),
+                phrase_card.phrase_id(),
+                &self.native_language,
+                rng,
+            )
+        {
+            return view;
+        }
+
         generation::generate_phrase_quiz(card.clone(), same_type_cards, &self.native_language)
             .unwrap_or_else(|| LessonCardView::Normal(card.clone()))
     }
 
+    /// A particle card is reviewed as a cloze over one of the learner's
+    /// already reviewed phrases that uses the particle.
+    fn select_particle_view<R: Rng>(&self, card: &Card, rng: &mut R) -> LessonCardView {
+        let Card::Particle(particle_card) = card else {
+            return LessonCardView::Normal(card.clone());
+        };
+        let mut phrase_ids: Vec<_> = self
+            .knowledge_set
+            .study_cards()
+            .values()
+            .filter(|study_card| !study_card.is_new())
+            .filter_map(|study_card| match study_card.card() {
+                Card::Phrase(phrase_card) => Some(*phrase_card.phrase_id()),
+                _ => None,
+            })
+            .filter(|phrase_id| {
+                get_index_entry(phrase_id).is_some_and(|entry| {
+                    entry
+                        .tokens()
+                        .iter()
+                        .any(|token| token == particle_card.particle())
+                })
+            })
+            .collect();
+        phrase_ids.sort();
+        phrase_ids.shuffle(rng);
+
+        phrase_ids
+            .iter()
+            .find_map(|phrase_id| {
+                generation::generate_particle_cloze(
+                    card.clone(),
+                    phrase_id,
+                    &self.native_language,
+                    rng,
+                )
+            })
+            .unwrap_or_else(|| LessonCardView::Normal(card.clone()))
+    }
+
     pub(crate) fn candidate_views_for_repeat<R: Rng>(
         &mut self,
         study_card: &crate::domain::StudyCard,
@@ -254,7 +335,7 @@ impl<'a> LessonViewGenerator<'a> {
             CardType::Vocabulary => self.candidate_views_for_vocab_repeat(study_card, is_new, rng),
             CardType::Kanji => self.candidate_views_for_kanji_repeat(study_card, is_new, rng),
             CardType::Grammar => self.candidate_views_for_grammar_repeat(study_card, is_new, rng),
-            CardType::Phrase => Vec::new(),
+            CardType::Phrase | CardType::Particle => Vec::new(),
         }
     }
 
@@ -302,6 +383,10 @@ impl<'a> LessonViewGenerator<'a> {
             }
             if eligible_for_reversed {
                 candidates.push(transforms::apply_reversed(card, &self.native_language));
+                candidates.extend(generation::generate_production(
+                    card.clone(),
+                    &self.native_language,
+                ));
             }
             if eligible_for_advanced {
                 candidates.push(transforms::apply_grammar_mutated(
@@ -311,6 +396,11 @@ impl<'a> LessonViewGenerator<'a> {
                     &self.native_language,
                 ));
             }
+            if memory.is_known_card()
+                && let Some(view) = generation::generate_pitch_accent_quiz(card.clone())
+            {
+                candidates.push(view);
+            }
             candidates.push(LessonCardView::Normal(card.clone()));
 
             build_distinct_views(candidates)
@@ -364,7 +454,7 @@ impl<'a> LessonViewGenerator<'a> {
         &self,
         study_card: &crate::domain::StudyCard,
         is_new: bool,
-        _rng: &mut R,
+        rng: &mut R,
     ) -> Vec<LessonCardView> {
         let card = study_card.card();
         if is_new {
@@ -377,6 +467,13 @@ impl<'a> LessonViewGenerator<'a> {
                     &self.native_language,
                 )
                 .unwrap_or_else(|_| LessonCardView::Normal(card.clone())),
+                generation::generate_conjugation_drill(
+                    card.clone(),
+                    self.knowledge_set,
+                    &self.native_language,
+                    rng,
+                )
+                .unwrap_or_else(|_| LessonCardView::Normal(card.clone())),
                 LessonCardView::Normal(card.clone()),
             ])
         }
@@ -0,0 +1,59 @@
+use super::*;
+use crate::dictionary::grammar::FormatAction;
+use crate::domain::knowledge::lesson::types::{GrammarInfo, LessonCardView};
+use crate::domain::{GrammarRuleCard, PartOfSpeech};
+
+fn drill_for(
+    word: &str,
+    part_of_speech: PartOfSpeech,
+    actions: Vec<FormatAction>,
+) -> Option<LessonCardView> {
+    let grammar_card = GrammarRuleCard::new_test();
+    let rule_id = *grammar_card.rule_id();
+    generation::build_conjugation_drill(
+        Card::Grammar(grammar_card),
+        GrammarInfo::new(Some(rule_id), "ない形".to_string(), String::new()),
+        word.to_string(),
+        None,
+        part_of_speech,
+        actions,
+    )
+}
+
+#[test]
+fn verb_chain_builds_drill_checked_against_forward_conjugation() {
+    // Arrange / Act
+    let view = drill_for(
+        "書く",
+        PartOfSpeech::Verb,
+        vec![
+            FormatAction::VerbToNai {},
+            FormatAction::ReplacePostfix {
+                old_postfix: "い".to_string(),
+                new_postfix: "かった".to_string(),
+            },
+        ],
+    );
+
+    // Assert
+    let Some(LessonCardView::ConjugationDrill(drill)) = view else {
+        panic!("expected ConjugationDrill");
+    };
+    assert_eq!(drill.step_labels(), ["negative", "い → かった"]);
+    assert!(drill.check_answer("書かなかった").unwrap().is_correct);
+    let check = drill.check_answer("書きなかった").unwrap();
+    assert_eq!(check.wrong_step, Some(0));
+    assert_eq!(check.expected, "書かなかった");
+}
+
+#[test]
+fn postfix_only_or_noun_chain_has_nothing_to_drill() {
+    // Arrange
+    let postfix = vec![FormatAction::AddPostfix {
+        postfix: "こと".to_string(),
+    }];
+
+    // Act / Assert
+    assert!(drill_for("書く", PartOfSpeech::Verb, postfix.clone()).is_none());
+    assert!(drill_for("本", PartOfSpeech::Noun, postfix).is_none());
+}
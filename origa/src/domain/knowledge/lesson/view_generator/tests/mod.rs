@@ -7,8 +7,12 @@ use chrono::Utc;
 use ulid::Ulid;
 
 mod card_views;
+mod conjugation_drill;
 mod filtering;
 mod kanji_reading_quiz;
+mod particle_cloze;
+mod pitch_accent_quiz;
+mod production;
 mod quiz;
 mod transforms;
 mod types;
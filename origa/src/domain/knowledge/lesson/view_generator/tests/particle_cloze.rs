@@ -0,0 +1,129 @@
+use super::*;
+use crate::domain::knowledge::lesson::types::{LessonCardView, ParticleClozeCard};
+use crate::domain::{PartOfSpeech, ParticleCard, PhraseCard, TokenInfo};
+use rand::{SeedableRng, rngs::StdRng};
+
+const PHRASE: &str = "学校に行きます";
+
+fn phrase_tokens() -> Vec<TokenInfo> {
+    vec![
+        TokenInfo::new_test("学校", PartOfSpeech::Noun),
+        TokenInfo::new_test("に", PartOfSpeech::Particle),
+        TokenInfo::new_test("行き", PartOfSpeech::Verb),
+        TokenInfo::new_test("ます", PartOfSpeech::AuxiliaryVerb),
+    ]
+}
+
+fn cloze(view: Option<LessonCardView>) -> ParticleClozeCard {
+    let Some(LessonCardView::ParticleCloze(cloze)) = view else {
+        panic!("expected ParticleCloze");
+    };
+    cloze
+}
+
+fn option_texts(cloze: &ParticleClozeCard) -> Vec<&str> {
+    let mut texts: Vec<&str> = cloze.options().iter().map(|o| o.text()).collect();
+    texts.sort();
+    texts
+}
+
+#[test]
+fn build_particle_cloze_blanks_particle_with_confusable_options() {
+    // Arrange
+    let card = Card::Phrase(PhraseCard::new(Ulid::new()));
+    let mut rng = StdRng::seed_from_u64(7);
+
+    // Act
+    let view = generation::build_particle_cloze(
+        card,
+        PHRASE,
+        &phrase_tokens(),
+        None,
+        Some("I go to school".to_string()),
+        &mut rng,
+    );
+
+    // Assert
+    let cloze = cloze(view);
+    assert_eq!(cloze.before(), "学校");
+    assert_eq!(cloze.after(), "行きます");
+    assert_eq!(cloze.particle(), "に");
+    assert_eq!(cloze.phrase_text(), PHRASE);
+    assert_eq!(cloze.translation(), Some("I go to school"));
+    assert_eq!(option_texts(&cloze), ["で", "に", "へ"]);
+    let correct: Vec<&str> = cloze
+        .options()
+        .iter()
+        .filter(|o| o.is_correct())
+        .map(|o| o.text())
+        .collect();
+    assert_eq!(correct, ["に"]);
+}
+
+#[test]
+fn build_particle_cloze_for_particle_card_blanks_only_that_particle() {
+    // Arrange
+    let text = "私は学校に行きます";
+    let tokens = vec![
+        TokenInfo::new_test("私", PartOfSpeech::Pronoun),
+        TokenInfo::new_test("は", PartOfSpeech::Particle),
+        TokenInfo::new_test("学校", PartOfSpeech::Noun),
+        TokenInfo::new_test("に", PartOfSpeech::Particle),
+        TokenInfo::new_test("行き", PartOfSpeech::Verb),
+        TokenInfo::new_test("ます", PartOfSpeech::AuxiliaryVerb),
+    ];
+    let card = Card::Particle(ParticleCard::new("は".to_string()));
+
+    for seed in 0..8 {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        // Act
+        let view = generation::build_particle_cloze(
+            card.clone(),
+            text,
+            &tokens,
+            Some("は"),
+            None,
+            &mut rng,
+        );
+
+        // Assert
+        let cloze = cloze(view);
+        assert_eq!(cloze.before(), "私");
+        assert_eq!(cloze.particle(), "は");
+        assert_eq!(option_texts(&cloze), ["が", "は"]);
+    }
+}
+
+#[test]
+fn build_particle_cloze_needs_confusable_particle() {
+    // Arrange
+    let text = "いいですね";
+    let tokens = vec![
+        TokenInfo::new_test("いい", PartOfSpeech::IAdjective),
+        TokenInfo::new_test("です", PartOfSpeech::AuxiliaryVerb),
+        TokenInfo::new_test("ね", PartOfSpeech::Particle),
+    ];
+    let card = Card::Phrase(PhraseCard::new(Ulid::new()));
+    let mut rng = StdRng::seed_from_u64(1);
+
+    // Act
+    let view = generation::build_particle_cloze(card, text, &tokens, None, None, &mut rng);
+
+    // Assert
+    assert!(view.is_none());
+}
+
+#[test]
+fn particle_cloze_is_phrase_or_particle_only() {
+    // Arrange
+    let card = create_vocab_card("学校");
+    let mut rng = StdRng::seed_from_u64(1);
+
+    // Act
+    let view =
+        generation::generate_particle_cloze(card, &Ulid::new(), &NativeLanguage::English, &mut rng);
+
+    // Assert
+    assert!(view.is_none());
+}
@@ -0,0 +1,57 @@
+use super::*;
+use crate::domain::knowledge::lesson::types::{LessonCardView, QuizMode};
+use crate::domain::{PitchAccent, PitchAccentType};
+
+fn quiz_for(word: &str, reading: &str, downstep: u8) -> Vec<(String, bool)> {
+    let accent = PitchAccent::new(reading, downstep).unwrap();
+    let LessonCardView::PitchAccentQuiz(quiz) =
+        generation::build_pitch_accent_quiz(create_vocab_card(word), &accent)
+    else {
+        panic!("expected PitchAccentQuiz");
+    };
+    assert_eq!(quiz.mode(), QuizMode::Single);
+    quiz.options()
+        .iter()
+        .map(|o| (o.text().to_string(), o.is_correct()))
+        .collect()
+}
+
+#[test]
+fn three_mora_word_offers_all_types_with_one_correct() {
+    // Arrange / Act
+    let options = quiz_for("卵", "タマゴ", 2);
+
+    // Assert
+    assert_eq!(options.len(), 4);
+    let correct: Vec<&str> = options
+        .iter()
+        .filter(|(_, correct)| *correct)
+        .map(|(text, _)| text.as_str())
+        .collect();
+    assert_eq!(correct, [PitchAccentType::Nakadaka.japanese_name()]);
+}
+
+#[test]
+fn short_words_skip_impossible_types() {
+    // Arrange / Act
+    let two_morae = quiz_for("犬", "いぬ", 2);
+    let one_mora = quiz_for("木", "き", 1);
+
+    // Assert
+    let nakadaka = PitchAccentType::Nakadaka.japanese_name();
+    let odaka = PitchAccentType::Odaka.japanese_name();
+    assert_eq!(two_morae.len(), 3);
+    assert!(two_morae.iter().all(|(text, _)| text != nakadaka));
+    assert!(
+        two_morae
+            .iter()
+            .any(|(text, correct)| text == odaka && *correct)
+    );
+    assert_eq!(one_mora.len(), 2);
+    assert!(one_mora.iter().any(|(_, correct)| *correct));
+}
+
+#[test]
+fn pitch_quiz_needs_index_entry() {
+    assert!(generation::generate_pitch_accent_quiz(create_vocab_card("猫")).is_none());
+}
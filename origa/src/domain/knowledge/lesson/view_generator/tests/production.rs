@@ -0,0 +1,54 @@
+use super::*;
+use crate::domain::knowledge::lesson::types::LessonCardView;
+
+fn readings(view: Option<LessonCardView>) -> Vec<String> {
+    let Some(LessonCardView::Production(production)) = view else {
+        panic!("expected Production");
+    };
+    production.readings().to_vec()
+}
+
+#[test]
+fn build_production_drops_empty_and_repeated_readings() {
+    // Arrange
+    let card = create_vocab_card("今日");
+    let candidates = vec![
+        "きょう".to_string(),
+        String::new(),
+        "こんにち".to_string(),
+        "きょう".to_string(),
+    ];
+
+    // Act
+    let view = generation::build_production(card, "today".to_string(), candidates);
+
+    // Assert
+    assert_eq!(readings(view), ["きょう", "こんにち"]);
+}
+
+#[test]
+fn build_production_needs_prompt_and_reading() {
+    // Arrange
+    let card = create_vocab_card("今日");
+
+    // Act
+    let no_prompt =
+        generation::build_production(card.clone(), String::new(), vec!["きょう".to_string()]);
+    let no_reading = generation::build_production(card, "today".to_string(), Vec::new());
+
+    // Assert
+    assert!(no_prompt.is_none());
+    assert!(no_reading.is_none());
+}
+
+#[test]
+fn production_is_vocabulary_only() {
+    // Arrange
+    let card = Card::Grammar(GrammarRuleCard::new_test());
+
+    // Act
+    let view = generation::generate_production(card, &NativeLanguage::English);
+
+    // Assert
+    assert!(view.is_none());
+}
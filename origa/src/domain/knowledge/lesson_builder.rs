@@ -137,7 +137,7 @@ pub(crate) fn interleave_core_by_type(mut lesson_data: LessonData) -> LessonData
             CardType::Vocabulary => vocab.push(card),
             CardType::Kanji => kanji.push(card),
             CardType::Grammar => grammar.push(card),
-            CardType::Phrase => other.push(card),
+            CardType::Phrase | CardType::Particle => other.push(card),
         }
     }
 
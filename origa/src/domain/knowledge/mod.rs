@@ -1,11 +1,14 @@
 mod card;
 mod daily_history;
 mod empty_diagnosis;
+mod exam_readiness;
 mod grammar;
+mod journal;
 mod kanji;
 mod kanji_companions;
 pub mod lesson;
 mod lesson_builder;
+mod particle;
 mod phrase;
 mod stats_tracker;
 mod stats_updater;
@@ -16,24 +19,30 @@ pub mod vocabulary;
 pub use card::{Card, CardType, StudyCard};
 pub use daily_history::{DailyHistoryItem, estimate_completion_date};
 pub use empty_diagnosis::{LessonEmptyDiagnosis, diagnose_empty_lesson};
+pub use exam_readiness::{ExamReadiness, assess_exam_readiness};
 pub use grammar::GrammarRuleCard;
+pub use journal::{EventId, KnowledgeEvent, KnowledgeJournal, KnowledgeOp, Watermark};
 pub use kanji::{ExampleKanjiWord, KanjiCard};
 pub use lesson::{
-    GrammarInfo, GrammarQuizCard, LessonCard, LessonCardView, LessonData, LessonViewGenerator,
-    MultiQuizResult, QuizCard, QuizMode, QuizOption, YesNoCard,
+    ConjugationDrillCard, GrammarInfo, GrammarQuizCard, LessonCard, LessonCardView, LessonData,
+    LessonViewGenerator, MultiQuizResult, ParticleClozeCard, ProductionCard, ProductionResult,
+    QuizCard, QuizMode, QuizOption, YesNoCard,
 };
+pub use particle::ParticleCard;
 pub use phrase::PhraseCard;
 pub use stats_tracker::StatsTracker;
-pub use vocabulary::VocabularyCard;
+pub use vocabulary::{ExampleContext, ExampleMedium, VocabularyCard};
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use ulid::Ulid;
 
 use crate::dictionary::kanji::get_kanji_info;
 use crate::domain::{
-    DailyBudget, JapaneseLevel, JlptContent, NativeLanguage, OrigaError, RateMode, Rating,
-    srs::rate_memory,
+    DailyBudget, JapaneseLevel, JlptContent, MemoryHistory, NativeLanguage, OrigaError, RateMode,
+    Rating, SrsSettings,
+    srs::{rate_memory, scheduling_family},
 };
 
 pub(crate) const MAX_COMPANION_WORDS: usize = 3;
@@ -63,9 +72,15 @@ where
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "KnowledgeSetRepr")]
 pub struct KnowledgeSet {
-    #[serde(deserialize_with = "deserialize_study_cards")]
     study_cards: HashMap<Ulid, StudyCard>,
+    // Cards this build cannot read, typically a card type added by a newer
+    // version. Kept verbatim and written back, so a device running an older
+    // build neither fails to load the set nor drops the card on save; every
+    // load retries them, so an up-to-date build picks them up again.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    unrecognized_cards: HashMap<Ulid, serde_json::Value>,
     #[serde(default)]
     deleted_cards: HashSet<Ulid>,
     // Words of deleted Vocabulary cards. Consulted ONLY by
@@ -77,41 +92,59 @@ pub struct KnowledgeSet {
     deleted_companion_words: HashSet<String>,
     #[serde(flatten)]
     stats: StatsTracker,
+    #[serde(default)]
+    journal: KnowledgeJournal,
 }
 
-fn deserialize_study_cards<'de, D>(deserializer: D) -> Result<HashMap<Ulid, StudyCard>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    struct StudyCardsVisitor;
+/// Wire shape of [`KnowledgeSet`]. Study cards are read as raw JSON first:
+/// a card that fails to parse can then be set aside on its own, whereas a
+/// parse error inside a streaming map leaves the reader mid-value and fails
+/// the whole set.
+#[derive(Deserialize)]
+struct KnowledgeSetRepr {
+    study_cards: HashMap<Ulid, serde_json::Value>,
+    #[serde(default)]
+    unrecognized_cards: HashMap<Ulid, serde_json::Value>,
+    #[serde(default)]
+    deleted_cards: HashSet<Ulid>,
+    #[serde(default)]
+    deleted_companion_words: HashSet<String>,
+    #[serde(flatten)]
+    stats: StatsTracker,
+    #[serde(default)]
+    journal: KnowledgeJournal,
+}
 
-    impl<'de> serde::de::Visitor<'de> for StudyCardsVisitor {
-        type Value = HashMap<Ulid, StudyCard>;
+impl From<KnowledgeSetRepr> for KnowledgeSet {
+    fn from(repr: KnowledgeSetRepr) -> Self {
+        let mut study_cards = HashMap::with_capacity(repr.study_cards.len());
+        let mut unrecognized_cards = HashMap::new();
 
-        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-            write!(f, "a map of study cards")
+        for (card_id, raw) in repr.unrecognized_cards.into_iter().chain(repr.study_cards) {
+            match StudyCard::deserialize(&raw) {
+                Ok(study_card) => {
+                    unrecognized_cards.remove(&card_id);
+                    study_cards.insert(card_id, study_card);
+                },
+                Err(e) => {
+                    tracing::warn!("Keeping unrecognized study card {}: {}", card_id, e);
+                    if !study_cards.contains_key(&card_id) && !repr.deleted_cards.contains(&card_id)
+                    {
+                        unrecognized_cards.insert(card_id, raw);
+                    }
+                },
+            }
         }
 
-        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-        where
-            A: serde::de::MapAccess<'de>,
-        {
-            let mut result = HashMap::new();
-            while let Some(key) = map.next_key::<Ulid>()? {
-                match map.next_value::<StudyCard>() {
-                    Ok(value) => {
-                        result.insert(key, value);
-                    },
-                    Err(e) => {
-                        tracing::warn!("Skipping study card {}: {}", key, e);
-                    },
-                }
-            }
-            Ok(result)
+        Self {
+            study_cards,
+            unrecognized_cards,
+            deleted_cards: repr.deleted_cards,
+            deleted_companion_words: repr.deleted_companion_words,
+            stats: repr.stats,
+            journal: repr.journal,
         }
     }
-
-    deserializer.deserialize_map(StudyCardsVisitor)
 }
 
 impl Default for KnowledgeSet {
@@ -124,15 +157,21 @@ impl KnowledgeSet {
     pub fn new() -> Self {
         Self {
             study_cards: HashMap::new(),
+            unrecognized_cards: HashMap::new(),
             deleted_cards: HashSet::new(),
             deleted_companion_words: HashSet::new(),
             stats: StatsTracker::new(),
+            journal: KnowledgeJournal::default(),
         }
     }
 
+    /// State merge: tombstones and the dismissed-companion blocklist are
+    /// unioned, card memory is last-writer-wins and counters take `max()`.
+    /// Used for sets that are not replayable yet; see [`Self::rebase`].
     pub fn merge(&mut self, new_values: &KnowledgeSet) {
         for deleted_id in &new_values.deleted_cards {
             self.study_cards.remove(deleted_id);
+            self.unrecognized_cards.remove(deleted_id);
             self.deleted_cards.insert(*deleted_id);
         }
 
@@ -155,10 +194,152 @@ impl KnowledgeSet {
             }
         }
 
+        for (id, raw) in &new_values.unrecognized_cards {
+            if !self.deleted_cards.contains(id) && !self.study_cards.contains_key(id) {
+                self.unrecognized_cards
+                    .entry(*id)
+                    .or_insert_with(|| raw.clone());
+            }
+        }
+
         self.stats.merge(&new_values.stats);
         self.recalculate_daily_stats();
     }
 
+    /// Rebuilds the set as the remote `checkpoint` plus every event that
+    /// either side recorded after it, replayed in [`EventId`] order. Devices
+    /// that saw the same checkpoint and events end up with identical sets.
+    ///
+    /// When either side predates the journal the state [`Self::merge`] is
+    /// used instead and the set stays non-replayable until it is published
+    /// as a checkpoint (see [`Self::fold_journal`]).
+    pub fn rebase(&mut self, checkpoint: &KnowledgeSet, remote_events: &[KnowledgeEvent]) {
+        let events = self
+            .journal
+            .merged_events(&checkpoint.journal, remote_events);
+
+        if self.journal.is_replayable() && checkpoint.journal.is_replayable() {
+            let mut rebuilt = checkpoint.clone();
+            rebuilt.replay(&events);
+            rebuilt.journal = self.journal.rebased(&checkpoint.journal, events, true);
+            *self = rebuilt;
+        } else {
+            let mut remote = checkpoint.clone();
+            remote.replay(
+                &KnowledgeJournal::default().merged_events(&checkpoint.journal, remote_events),
+            );
+            self.merge(&remote);
+            self.journal = self.journal.rebased(&checkpoint.journal, events, false);
+        }
+    }
+
+    /// The set as published to the remote: same content, with every
+    /// journaled event folded into the watermark.
+    pub fn checkpoint(&self) -> KnowledgeSet {
+        let mut checkpoint = self.clone();
+        checkpoint.journal = self.journal.to_checkpoint();
+        checkpoint
+    }
+
+    /// Called once `published`, a [`Self::checkpoint`] of this set, reached
+    /// the remote: drops the events it folded — events recorded since stay
+    /// journaled — and makes the set replayable.
+    pub fn fold_journal(&mut self, published: &KnowledgeJournal) {
+        self.journal.fold_into(published);
+    }
+
+    pub fn journal(&self) -> &KnowledgeJournal {
+        &self.journal
+    }
+
+    fn replay(&mut self, events: &[KnowledgeEvent]) {
+        for event in events {
+            self.apply(event);
+        }
+        self.recalculate_daily_stats();
+    }
+
+    /// Records a change on this replica and applies it.
+    fn record(&mut self, op: KnowledgeOp) {
+        let event = self.journal.record(Utc::now(), op);
+        self.apply(&event);
+    }
+
+    /// Applies one event. Deterministic: reads nothing but the set and the
+    /// event, and silently skips events whose card no longer applies (a
+    /// duplicate created on another device, a card deleted meanwhile).
+    fn apply(&mut self, event: &KnowledgeEvent) {
+        let at = event.id().at();
+        match event.op() {
+            KnowledgeOp::CardCreated { card } => {
+                let card_id = *card.card_id();
+                if self.deleted_cards.contains(&card_id)
+                    || self.study_cards.contains_key(&card_id)
+                    || self.validate_unique_card(card.card()).is_err()
+                {
+                    return;
+                }
+                // A manually (re)created Vocabulary card clears the word from the
+                // dismissed-companion blocklist: the user explicitly reintroduced it,
+                // so future companion auto-creation should not be suppressed by a stale
+                // dismissal. The companion path skips blocklisted words BEFORE reaching
+                // `create_card`, so this eviction never fires for companion cards.
+                if let Card::Vocabulary(vocab) = card.card() {
+                    self.deleted_companion_words.remove(vocab.word().text());
+                }
+                self.study_cards.insert(card_id, card.clone());
+            },
+            KnowledgeOp::CardContentUpdated { card_id, card } => {
+                if let Some(study_card) = self.study_cards.get_mut(card_id) {
+                    study_card.replace_card(card.clone());
+                }
+            },
+            KnowledgeOp::CardDeleted { card_id } => {
+                if let Some(Card::Vocabulary(vocab)) = self
+                    .study_cards
+                    .remove(card_id)
+                    .map(|removed| removed.card().clone())
+                {
+                    self.deleted_companion_words
+                        .insert(vocab.word().text().to_string());
+                }
+                self.unrecognized_cards.remove(card_id);
+                self.deleted_cards.insert(*card_id);
+            },
+            KnowledgeOp::CardReviewed {
+                card_id,
+                rating,
+                mode,
+                state,
+                logged,
+            } => {
+                let Some(card) = self.study_cards.get_mut(card_id) else {
+                    return;
+                };
+                let was_new = card.memory().is_new();
+                let is_phrase = matches!(card.card(), Card::Phrase(_));
+                card.apply_review_at(state.clone(), *rating, at);
+                if *logged {
+                    card.record_review(*rating, *mode);
+                }
+                card.handle_favorite_rating_at(*rating, at);
+                self.stats
+                    .update(&self.study_cards, *rating, was_new, is_phrase, *mode, at);
+            },
+            KnowledgeOp::CardMarkedKnown { card_id, state } => {
+                if let Some(card) = self.study_cards.get_mut(card_id) {
+                    card.apply_review_at(state.clone(), Rating::Easy, at);
+                    card.handle_favorite_rating_at(Rating::Easy, at);
+                }
+            },
+            KnowledgeOp::FavoriteToggled { card_id } => {
+                if let Some(card) = self.study_cards.get_mut(card_id) {
+                    card.toggle_favorite_at(at);
+                }
+            },
+        }
+    }
+
     pub fn get_card(&self, card_id: Ulid) -> Option<&StudyCard> {
         self.study_cards.get(&card_id)
     }
@@ -192,15 +373,10 @@ impl KnowledgeSet {
     }
 
     pub fn delete_card(&mut self, card_id: Ulid) -> Result<(), OrigaError> {
-        let removed = self
-            .study_cards
-            .remove(&card_id)
-            .ok_or(OrigaError::CardNotFound { card_id })?;
-        if let Card::Vocabulary(vocab) = removed.card() {
-            self.deleted_companion_words
-                .insert(vocab.word().text().to_string());
+        if !self.study_cards.contains_key(&card_id) {
+            return Err(OrigaError::CardNotFound { card_id });
         }
-        self.deleted_cards.insert(card_id);
+        self.record(KnowledgeOp::CardDeleted { card_id });
         self.recalculate_daily_stats();
         Ok(())
     }
@@ -215,39 +391,44 @@ impl KnowledgeSet {
     }
 
     pub fn update_card_content(&mut self, card_id: Ulid, new_card: Card) -> Result<(), OrigaError> {
-        let study_card = self
-            .study_cards
-            .get_mut(&card_id)
-            .ok_or(OrigaError::CardNotFound { card_id })?;
-        study_card.replace_card(new_card);
+        if !self.study_cards.contains_key(&card_id) {
+            return Err(OrigaError::CardNotFound { card_id });
+        }
+        self.record(KnowledgeOp::CardContentUpdated {
+            card_id,
+            card: new_card,
+        });
         Ok(())
     }
 
     pub fn create_card(&mut self, card: Card) -> Result<StudyCard, OrigaError> {
-        let study_card = StudyCard::new(card);
+        self.insert_study_card(StudyCard::new(card))
+    }
+
+    /// Creates a card that already has a review history, e.g. one carried
+    /// over from Anki.
+    pub(crate) fn create_card_with_memory(
+        &mut self,
+        card: Card,
+        memory: MemoryHistory,
+    ) -> Result<StudyCard, OrigaError> {
+        self.insert_study_card(StudyCard::with_memory(card, memory))
+    }
+
+    fn insert_study_card(&mut self, study_card: StudyCard) -> Result<StudyCard, OrigaError> {
         let card_id = *study_card.card_id();
 
         self.validate_unique_card(study_card.card())?;
 
-        if self
-            .study_cards
-            .insert(card_id, study_card.clone())
-            .is_some()
-        {
+        if self.study_cards.contains_key(&card_id) {
             return Err(OrigaError::DuplicateCard {
                 question: study_card.card().content_key(),
             });
         }
 
-        // A manually (re)created Vocabulary card clears the word from the
-        // dismissed-companion blocklist: the user explicitly reintroduced it,
-        // so future companion auto-creation should not be suppressed by a stale
-        // dismissal. The companion path skips blocklisted words BEFORE reaching
-        // `create_card`, so this eviction never fires for companion cards.
-        if let Card::Vocabulary(vocab) = study_card.card() {
-            self.deleted_companion_words.remove(vocab.word().text());
-        }
-
+        self.record(KnowledgeOp::CardCreated {
+            card: study_card.clone(),
+        });
         self.recalculate_daily_stats();
         Ok(study_card)
     }
@@ -277,6 +458,9 @@ impl KnowledgeSet {
             (Card::Phrase(phrase_card), Card::Phrase(existing_phrase_card)) => {
                 phrase_card.phrase_id() == existing_phrase_card.phrase_id()
             },
+            (Card::Particle(particle_card), Card::Particle(existing_particle_card)) => {
+                particle_card.particle() == existing_particle_card.particle()
+            },
 
             _ => false,
         }) {
@@ -319,29 +503,46 @@ impl KnowledgeSet {
         lesson_builder::redistribute_core_for_spacing(expanded)
     }
 
+    #[cfg(test)]
     pub(crate) fn rate_card(
         &mut self,
         card_id: Ulid,
         rating: Rating,
         mode: RateMode,
     ) -> Result<(), OrigaError> {
-        if let Some(card) = self.study_cards.get_mut(&card_id) {
-            let was_new = card.memory().is_new();
-            let is_phrase = matches!(card.card(), Card::Phrase(_));
+        self.rate_card_with_settings(card_id, rating, mode, &SrsSettings::default())
+    }
+
+    /// Rates a card applying the user's SRS personalization: fitted weights
+    /// of the card's family, the user's retention and interval ceiling for
+    /// the effective mode and, when enabled, appending to its review log.
+    pub(crate) fn rate_card_with_settings(
+        &mut self,
+        card_id: Ulid,
+        rating: Rating,
+        mode: RateMode,
+        settings: &SrsSettings,
+    ) -> Result<(), OrigaError> {
+        if let Some(card) = self.study_cards.get(&card_id) {
             let effective_mode = match mode {
                 RateMode::ShortTerm | RateMode::OnboardingScoring => mode,
                 _ => match card.card() {
                     Card::Phrase(_) => RateMode::PhraseReview,
-                    Card::Grammar(_) => RateMode::GrammarReview,
+                    Card::Grammar(_) | Card::Particle(_) => RateMode::GrammarReview,
                     Card::Kanji(_) => RateMode::KanjiReview,
                     Card::Vocabulary(_) => mode,
                 },
             };
 
-            let memory_state = rate_memory(effective_mode, rating, card.memory())?;
-            card.apply_review(memory_state, rating);
-            card.handle_favorite_rating(rating);
-            self.update_history(rating, was_new, is_phrase, mode);
+            let overrides = settings.overrides_for(scheduling_family(card.card()), effective_mode);
+            let state = rate_memory(effective_mode, rating, card.memory(), &overrides)?;
+            self.record(KnowledgeOp::CardReviewed {
+                card_id,
+                rating,
+                mode: effective_mode,
+                state,
+                logged: settings.review_log_enabled(),
+            });
             Ok(())
         } else {
             Err(OrigaError::CardNotFound { card_id })
@@ -349,15 +550,11 @@ impl KnowledgeSet {
     }
 
     pub(crate) fn toggle_favorite(&mut self, card_id: Ulid) -> Result<(), OrigaError> {
-        self.study_cards
-            .get_mut(&card_id)
-            .map(|card| card.toggle_favorite())
-            .ok_or(OrigaError::CardNotFound { card_id })
-    }
-
-    fn update_history(&mut self, rating: Rating, was_new: bool, is_phrase: bool, mode: RateMode) {
-        self.stats
-            .update(&self.study_cards, rating, was_new, is_phrase, mode);
+        if !self.study_cards.contains_key(&card_id) {
+            return Err(OrigaError::CardNotFound { card_id });
+        }
+        self.record(KnowledgeOp::FavoriteToggled { card_id });
+        Ok(())
     }
 
     pub fn create_companion_vocab_cards(
@@ -410,23 +607,21 @@ impl KnowledgeSet {
 
     pub fn mark_card_as_known(&mut self, card_id: Ulid) -> Result<(), OrigaError> {
         use crate::domain::memory::{
-            Difficulty, KNOWN_CARD_STABILITY_THRESHOLD, MemoryState, Rating, Stability,
+            Difficulty, KNOWN_CARD_STABILITY_THRESHOLD, MemoryState, Stability,
         };
-        use chrono::{Duration, Utc};
-
-        if let Some(card) = self.study_cards.get_mut(&card_id) {
-            let stability = KNOWN_CARD_STABILITY_THRESHOLD + 1.0;
-            let memory = MemoryState::new(
-                Stability::new(stability).unwrap(),
-                Difficulty::new(3.0).unwrap(),
-                Utc::now() - Duration::days(1),
-            );
-            card.apply_review(memory, Rating::Easy);
-            card.handle_favorite_rating(Rating::Easy);
-            Ok(())
-        } else {
-            Err(OrigaError::CardNotFound { card_id })
+        use chrono::Duration;
+
+        if !self.study_cards.contains_key(&card_id) {
+            return Err(OrigaError::CardNotFound { card_id });
         }
+        let stability = KNOWN_CARD_STABILITY_THRESHOLD + 1.0;
+        let state = MemoryState::new(
+            Stability::new(stability).unwrap(),
+            Difficulty::new(3.0).unwrap(),
+            Utc::now() - Duration::days(1),
+        );
+        self.record(KnowledgeOp::CardMarkedKnown { card_id, state });
+        Ok(())
     }
 
     #[cfg(test)]
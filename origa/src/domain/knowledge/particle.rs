@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dictionary::vocabulary::get_translations;
+use crate::domain::{CardAnswer, NativeLanguage, OrigaError, Question};
+
+/// A particle the learner confused in a phrase cloze. Kept apart from the
+/// phrase cards so a particle mix-up gets its own memory instead of
+/// lowering every phrase it appears in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticleCard {
+    particle: String,
+}
+
+impl ParticleCard {
+    pub fn new(particle: String) -> Self {
+        Self { particle }
+    }
+
+    pub fn particle(&self) -> &str {
+        &self.particle
+    }
+
+    pub fn question(&self) -> Result<Question, OrigaError> {
+        Question::new(self.particle.clone())
+    }
+
+    /// Dictionary translations of the particle, or the particle itself when
+    /// the vocabulary dictionary has no entry for it.
+    pub fn answer(&self, lang: &NativeLanguage) -> Result<CardAnswer, OrigaError> {
+        match get_translations(&self.particle, lang) {
+            Some(translations) => CardAnswer::vocabulary(translations, None),
+            None => CardAnswer::text(self.particle.clone()),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
@@ -50,6 +50,7 @@ impl StatsTracker {
         was_new: bool,
         is_phrase: bool,
         mode: RateMode,
+        at: DateTime<Utc>,
     ) {
         super::stats_updater::update_history(
             study_cards,
@@ -58,6 +59,7 @@ impl StatsTracker {
             was_new,
             is_phrase,
             mode,
+            at,
         );
     }
 
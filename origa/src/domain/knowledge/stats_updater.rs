@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use ulid::Ulid;
 
 use super::daily_history::DailyStatsUpdate;
@@ -84,17 +84,18 @@ pub(crate) fn update_history(
     was_new: bool,
     is_phrase: bool,
     mode: RateMode,
+    at: DateTime<Utc>,
 ) {
     let stats = match ComputedStats::compute(study_cards) {
         Some(s) => s,
         None => return,
     };
 
-    let today = Utc::now().date_naive();
+    let day = at.date_naive();
 
     if let Some(existing_item) = lesson_history
         .iter_mut()
-        .find(|item| item.timestamp().date_naive() == today)
+        .find(|item| item.timestamp().date_naive() == day)
     {
         if was_new && !is_phrase && mode != RateMode::OnboardingScoring {
             existing_item.increment_new_cards_studied();
@@ -117,7 +118,7 @@ pub(crate) fn update_history(
             existing_item.update(update, rating);
         }
     } else {
-        let mut item = DailyHistoryItem::new();
+        let mut item = DailyHistoryItem::starting_at(at);
         if was_new && !is_phrase && mode != RateMode::OnboardingScoring {
             item.increment_new_cards_studied();
         }
@@ -139,6 +140,8 @@ pub(crate) fn update_history(
             item.update(update, rating);
         }
         lesson_history.push(item);
+        // A replayed review may open a day older than the latest item.
+        lesson_history.sort_by_key(|item| item.timestamp());
     }
 }
 
@@ -893,6 +893,7 @@ fn new_cards_interleave_across_jlpt_levels() {
             },
             Card::Grammar(_) => panic!("No grammar cards in this test"),
             Card::Phrase(_) => panic!("No phrase cards in this test"),
+            Card::Particle(_) => panic!("No particle cards in this test"),
         }
     }
 
@@ -1668,3 +1669,76 @@ mod deleted_companion_words {
         );
     }
 }
+
+mod unrecognized_cards {
+    use super::*;
+
+    fn with_unknown_card_type(study_card: &StudyCard) -> serde_json::Value {
+        let mut raw = serde_json::to_value(study_card).unwrap();
+        raw["card"] = serde_json::json!({ "Radical": { "radical": "氵" } });
+        raw
+    }
+
+    #[test]
+    fn card_of_unknown_type_is_kept_verbatim_across_a_save() {
+        // Arrange
+        let mut ks = KnowledgeSet::new();
+        ks.create_card(create_vocab_card("水")).unwrap();
+        let other = ks.create_card(create_vocab_card("海")).unwrap();
+        let unknown_id = other.card_id().to_string();
+        let mut json = serde_json::to_value(&ks).unwrap();
+        json["study_cards"][&unknown_id] = with_unknown_card_type(&other);
+
+        // Act
+        let loaded: KnowledgeSet = serde_json::from_value(json).expect("set must still load");
+        let saved = serde_json::to_value(&loaded).unwrap();
+
+        // Assert
+        assert_eq!(loaded.study_cards().len(), 1);
+        assert_eq!(
+            saved["unrecognized_cards"][&unknown_id]["card"]["Radical"]["radical"],
+            "氵"
+        );
+    }
+
+    #[test]
+    fn unrecognized_card_is_restored_once_readable() {
+        // Arrange
+        let mut ks = KnowledgeSet::new();
+        let card = ks.create_card(create_vocab_card("水")).unwrap();
+        let card_id = card.card_id().to_string();
+        let mut json = serde_json::to_value(&ks).unwrap();
+        json["study_cards"] = serde_json::json!({});
+        json["unrecognized_cards"] = serde_json::json!({ card_id.clone(): card });
+
+        // Act
+        let loaded: KnowledgeSet = serde_json::from_value(json).unwrap();
+        let saved = serde_json::to_value(&loaded).unwrap();
+
+        // Assert
+        assert!(loaded.get_card(*card.card_id()).is_some());
+        assert!(saved.get("unrecognized_cards").is_none());
+    }
+
+    #[test]
+    fn journal_event_of_unknown_card_type_does_not_fail_the_set() {
+        // Arrange
+        let mut ks = KnowledgeSet::new();
+        let created = ks.create_card(create_vocab_card("水")).unwrap();
+        ks.create_card(create_vocab_card("海")).unwrap();
+        let mut json = serde_json::to_value(&ks).unwrap();
+        let events = json["journal"]["events"].as_array_mut().unwrap();
+        let event = events
+            .iter_mut()
+            .find(|event| event["card"]["card_id"] == created.card_id().to_string())
+            .unwrap();
+        event["card"] = with_unknown_card_type(&created);
+
+        // Act
+        let loaded: KnowledgeSet = serde_json::from_value(json).expect("set must still load");
+
+        // Assert
+        assert_eq!(loaded.study_cards().len(), 2);
+        assert_eq!(loaded.journal().events().len(), 1);
+    }
+}
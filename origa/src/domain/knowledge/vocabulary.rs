@@ -1,9 +1,11 @@
 use crate::dictionary::grammar::GrammarRule;
+use crate::dictionary::phrase::find_phrase_by_tokens;
 use crate::dictionary::vocabulary::{get_description, get_translation, get_translations};
 use crate::domain::tokenizer::{PartOfSpeech, tokenize_text};
 use crate::domain::{CardAnswer, NativeLanguage, OrigaError, Question};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
+use ulid::Ulid;
 
 /// Результат создания карточек из текста
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -12,12 +14,136 @@ pub struct CreateFromTextResult {
     pub skipped_no_translation: Vec<String>,
 }
 
+const CLOZE_BLANK: &str = "＿＿";
+
+/// Kind of input a sentence was read from when it has no name of its own.
+/// Stored instead of a display label so the UI can localize it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExampleMedium {
+    Image,
+    Audio,
+}
+
+/// Sentence a word was mined from, kept so the card can be reviewed in context.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExampleContext {
+    sentence: String,
+    /// Where the sentence came from: deck, episode, book or image name.
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    medium: Option<ExampleMedium>,
+    #[serde(default)]
+    timestamp_ms: Option<u64>,
+    #[serde(default)]
+    phrase_id: Option<Ulid>,
+}
+
+impl ExampleContext {
+    pub fn new(sentence: String) -> Self {
+        Self {
+            sentence,
+            source: None,
+            medium: None,
+            timestamp_ms: None,
+            phrase_id: None,
+        }
+    }
+
+    pub fn with_source(mut self, source: String) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn with_timestamp_ms(mut self, timestamp_ms: u64) -> Self {
+        self.timestamp_ms = Some(timestamp_ms);
+        self
+    }
+
+    pub fn with_medium(mut self, medium: ExampleMedium) -> Self {
+        self.medium = Some(medium);
+        self
+    }
+
+    /// Links the sentence to the phrase dataset entry with the same
+    /// vocabulary words, so it can be reviewed as a phrase card later. A
+    /// sentence that fails to tokenize or matches no phrase is left as is.
+    pub fn with_matching_phrase(mut self) -> Self {
+        let Ok(tokens) = tokenize_text(&self.sentence) else {
+            return self;
+        };
+        let bases: Vec<String> = tokens
+            .iter()
+            .filter(|token| token.part_of_speech().is_vocabulary_word())
+            .map(|token| token.orthographic_base_form().to_string())
+            .collect();
+        if let Some(entry) = find_phrase_by_tokens(&bases) {
+            self.phrase_id = Some(*entry.id());
+        }
+        self
+    }
+
+    pub fn sentence(&self) -> &str {
+        &self.sentence
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    pub fn medium(&self) -> Option<ExampleMedium> {
+        self.medium
+    }
+
+    /// Position of the sentence in the source media (subtitle cue start).
+    pub fn timestamp_ms(&self) -> Option<u64> {
+        self.timestamp_ms
+    }
+
+    /// Phrase from the phrase dataset the sentence matches, if any.
+    pub fn phrase_id(&self) -> Option<&Ulid> {
+        self.phrase_id.as_ref()
+    }
+
+    /// The sentence with every occurrence of `word` blanked out. Inflected
+    /// forms are matched through their dictionary form (`食べた` for
+    /// `食べる`); a failed tokenization falls back to a literal match.
+    pub fn cloze(&self, word: &str) -> String {
+        let Ok(tokens) = tokenize_text(&self.sentence) else {
+            return self.sentence.replace(word, CLOZE_BLANK);
+        };
+
+        let mut result = String::with_capacity(self.sentence.len());
+        let mut previous_blank = false;
+        for token in &tokens {
+            let is_word =
+                token.orthographic_base_form() == word || token.orthographic_surface_form() == word;
+            if is_word && !previous_blank {
+                result.push_str(CLOZE_BLANK);
+            } else if !is_word {
+                result.push_str(token.orthographic_surface_form());
+            }
+            previous_blank = is_word;
+        }
+
+        if result.contains(CLOZE_BLANK) {
+            result
+        } else {
+            self.sentence.replace(word, CLOZE_BLANK)
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VocabularyCard {
     word: Question,
     reverse_side: Option<Question>,
     #[serde(default)]
     pos: Option<PartOfSpeech>,
+    /// Boxed: most cards have no context, and `Card` is sized by its
+    /// largest variant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    context: Option<Box<ExampleContext>>,
 }
 
 impl VocabularyCard {
@@ -28,6 +154,7 @@ impl VocabularyCard {
             word,
             reverse_side: None,
             pos: None,
+            context: None,
         }
     }
 
@@ -46,6 +173,7 @@ impl VocabularyCard {
             word,
             reverse_side,
             pos,
+            context: None,
         }
     }
 
@@ -62,6 +190,7 @@ impl VocabularyCard {
             word: question,
             reverse_side: None,
             pos,
+            context: None,
         })
     }
 
@@ -164,6 +293,23 @@ impl VocabularyCard {
         self
     }
 
+    pub fn context(&self) -> Option<&ExampleContext> {
+        self.context.as_deref()
+    }
+
+    pub fn with_context(mut self, context: ExampleContext) -> Self {
+        self.context = Some(Box::new(context));
+        self
+    }
+
+    /// The example sentence with the Japanese word blanked out. Reversed
+    /// cards keep the Japanese word in `reverse_side`.
+    pub fn example_cloze(&self) -> Option<String> {
+        let context = self.context.as_ref()?;
+        let japanese = self.reverse_side.as_ref().unwrap_or(&self.word);
+        Some(context.cloze(japanese.text()))
+    }
+
     /// Replaces the card's word in place (dictionary-lemma migration).
     ///
     /// Keeps the reverse side and drops the stale POS: the new lemma may be a
@@ -193,6 +339,7 @@ impl VocabularyCard {
             word: Question::new(formatted_word)?,
             reverse_side: Some(answer_text),
             pos: self.pos.clone(),
+            context: None,
         };
 
         Ok((card, grammar_description))
@@ -207,6 +354,7 @@ impl VocabularyCard {
             word: Question::new(meaning_text)?,
             reverse_side: Some(self.word.clone()),
             pos: self.pos.clone(),
+            context: self.context.clone(),
         })
     }
 }
@@ -222,6 +370,7 @@ mod tests {
             word: Question::new(word.to_string()).unwrap(),
             reverse_side: None,
             pos: None,
+            context: None,
         }
     }
 
@@ -300,6 +449,7 @@ mod tests {
             word: question,
             reverse_side: Some(reverse_side),
             pos: None,
+            context: None,
         };
 
         let answer = card.answer(&NativeLanguage::Russian);
@@ -476,6 +626,7 @@ mod tests {
             word: question,
             reverse_side: Some(reverse_side),
             pos: None,
+            context: None,
         };
 
         let json = serde_json::to_string(&card).unwrap();
@@ -484,6 +635,76 @@ mod tests {
         assert_eq!(card, deserialized);
     }
 
+    #[test]
+    fn serialization_roundtrip_with_context() {
+        let card = create_vocab_card("猫").with_context(
+            ExampleContext::new("猫が好きだ".to_string())
+                .with_source("Episode 1".to_string())
+                .with_timestamp_ms(83_500),
+        );
+
+        let json = serde_json::to_string(&card).unwrap();
+        let deserialized: VocabularyCard = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(card, deserialized);
+        let context = deserialized.context().unwrap();
+        assert_eq!(context.sentence(), "猫が好きだ");
+        assert_eq!(context.source(), Some("Episode 1"));
+        assert_eq!(context.timestamp_ms(), Some(83_500));
+        assert!(context.phrase_id().is_none());
+        assert!(context.medium().is_none());
+    }
+
+    #[test]
+    fn serialization_roundtrip_keeps_medium() {
+        let context =
+            ExampleContext::new("猫が好きだ".to_string()).with_medium(ExampleMedium::Audio);
+
+        let json = serde_json::to_string(&context).unwrap();
+        let deserialized: ExampleContext = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.medium(), Some(ExampleMedium::Audio));
+        assert!(deserialized.source().is_none());
+    }
+
+    #[test]
+    fn cloze_blanks_inflected_word() {
+        init_real_dictionaries();
+        let context = ExampleContext::new("昨日パンを食べた。".to_string());
+
+        assert_eq!(context.cloze("食べる"), "昨日パンを＿＿た。");
+    }
+
+    #[test]
+    fn example_cloze_uses_japanese_side_of_reversed_card() {
+        let card = VocabularyCard::new_with_pos(
+            Question::new("кошка".to_string()).unwrap(),
+            None,
+            Some(Question::new("猫".to_string()).unwrap()),
+        )
+        .with_context(ExampleContext::new("猫".to_string()));
+
+        assert!(card.example_cloze().unwrap().contains(CLOZE_BLANK));
+    }
+
+    #[test]
+    fn deserializes_legacy_card_without_context() {
+        let json = r#"{"word":{"text":"猫"},"reverse_side":null}"#;
+
+        let card: VocabularyCard = serde_json::from_str(json).unwrap();
+
+        assert!(card.context().is_none());
+    }
+
+    #[test]
+    fn card_without_context_serializes_without_the_field() {
+        let card = create_vocab_card("猫");
+
+        let json = serde_json::to_string(&card).unwrap();
+
+        assert!(!json.contains("context"));
+    }
+
     #[test]
     fn serialization_contains_expected_fields() {
         let card = create_vocab_card("猫");
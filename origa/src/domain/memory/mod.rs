@@ -1,7 +1,11 @@
+mod review_log;
 mod value;
 
+pub use review_log::{ReviewLog, ReviewLogEntry};
 pub use value::{CardState, Difficulty, MemoryState, Rating, Stability};
 
+use crate::domain::RateMode;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -23,10 +27,18 @@ const HIGH_DIFFICULTY_STABILITY_CAP: f64 = 7.0;
 /// `current_state`. The `easy_count`/`good_count` scalars feed only the
 /// lesson view generator's reversed-card heuristic.
 ///
-/// Cross-device merge uses `max()` for counters (known limitation: may
+/// Cross-device sync normally replays the knowledge journal (see
+/// `knowledge::journal`), which applies every device's reviews in order and
+/// counts them exactly. The state merge below is the fallback for sets that
+/// predate the journal: it uses `max()` for counters (known limitation: may
 /// undercount by ±N during offline→offline divergence). `current_state`
 /// merges via LWW by `last_review_date` (unchanged from the array-based
 /// `select_later_state` logic).
+///
+/// `review_log` is the opt-in exception to the counters-only rule: when the
+/// user enables it, a bounded compact log (see [`ReviewLog`]) is kept so the
+/// FSRS optimizer can fit per-user weights. It is empty — and absent from the
+/// wire — for everyone else.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MemoryHistory {
     current_state: Option<MemoryState>,
@@ -42,6 +54,8 @@ pub struct MemoryHistory {
     last_review_date: Option<DateTime<Utc>>,
     #[serde(default)]
     last_rating: Option<Rating>,
+    #[serde(default, skip_serializing_if = "ReviewLog::is_empty")]
+    review_log: ReviewLog,
 }
 
 impl Default for MemoryHistory {
@@ -60,6 +74,30 @@ impl MemoryHistory {
             good_count: 0,
             last_review_date: None,
             last_rating: None,
+            review_log: ReviewLog::default(),
+        }
+    }
+
+    /// History of a card reviewed outside Origa (an Anki import): the
+    /// current memory and review counters are taken as is, and `reviews`
+    /// seeds the opt-in log (only the latest entries survive the cap).
+    pub(crate) fn imported(
+        memory_state: MemoryState,
+        reps: u32,
+        lapses: u32,
+        last_review: Option<(DateTime<Utc>, Rating)>,
+        reviews: &[ReviewLogEntry],
+    ) -> Self {
+        let mut review_log = ReviewLog::default();
+        review_log.extend(reviews.iter().copied());
+        Self {
+            current_state: Some(memory_state),
+            reps,
+            lapses,
+            last_review_date: last_review.map(|(at, _)| at),
+            last_rating: last_review.map(|(_, rating)| rating),
+            review_log,
+            ..Self::new()
         }
     }
 
@@ -97,9 +135,21 @@ impl MemoryHistory {
         self.good_count as usize
     }
 
+    #[cfg(test)]
     pub(crate) fn apply_review(&mut self, memory_state: MemoryState, rating: Rating) {
+        self.apply_review_at(memory_state, rating, Utc::now());
+    }
+
+    /// [`Self::apply_review`] for a review that happened at `reviewed_at`
+    /// (journal replay).
+    pub(crate) fn apply_review_at(
+        &mut self,
+        memory_state: MemoryState,
+        rating: Rating,
+        reviewed_at: DateTime<Utc>,
+    ) {
         self.current_state = Some(memory_state);
-        self.last_review_date = Some(Utc::now());
+        self.last_review_date = Some(reviewed_at);
         self.last_rating = Some(rating);
         self.reps += 1;
         match rating {
@@ -110,6 +160,18 @@ impl MemoryHistory {
         }
     }
 
+    /// Appends the review just applied by [`Self::apply_review`] to the
+    /// opt-in log. Called only when the user enabled review logging.
+    pub(crate) fn record_review(&mut self, rating: Rating, mode: RateMode) {
+        let reviewed_at = self.last_review_date.unwrap_or_else(Utc::now);
+        self.review_log
+            .push(ReviewLogEntry::new(reviewed_at, rating, mode));
+    }
+
+    pub fn review_log(&self) -> &ReviewLog {
+        &self.review_log
+    }
+
     pub fn last_review_date(&self) -> Option<DateTime<Utc>> {
         self.last_review_date
     }
@@ -168,14 +230,13 @@ impl MemoryHistory {
         self.lapses = self.lapses.max(other.lapses);
         self.easy_count = self.easy_count.max(other.easy_count);
         self.good_count = self.good_count.max(other.good_count);
+        self.review_log.merge(&other.review_log);
 
         // last_review_date + last_rating: take from whichever side is newer.
         match (self.last_review_date, other.last_review_date) {
-            (Some(self_ts), Some(other_ts)) => {
-                if other_ts >= self_ts {
-                    self.last_review_date = other.last_review_date;
-                    self.last_rating = other.last_rating;
-                }
+            (Some(self_ts), Some(other_ts)) if other_ts >= self_ts => {
+                self.last_review_date = other.last_review_date;
+                self.last_rating = other.last_rating;
             },
             (None, Some(_)) => {
                 self.last_review_date = other.last_review_date;
@@ -533,4 +594,48 @@ mod tests {
 
         assert_eq!(history_a.last_rating(), Some(Rating::Easy));
     }
+
+    // --- review log ---
+
+    #[test]
+    fn review_log_is_empty_unless_recorded() {
+        let mut history = MemoryHistory::new();
+        history.apply_review(make_state(), Rating::Good);
+
+        assert!(history.review_log().is_empty());
+        let json = serde_json::to_string(&history).unwrap();
+        assert!(
+            !json.contains("review_log"),
+            "empty log must stay off the wire"
+        );
+    }
+
+    #[test]
+    fn record_review_uses_last_review_date() {
+        let mut history = MemoryHistory::new();
+        history.apply_review(make_state(), Rating::Hard);
+        history.record_review(Rating::Hard, RateMode::StandardLesson);
+
+        let entry = history.review_log().entries()[0];
+        assert_eq!(entry.rating(), Rating::Hard);
+        assert_eq!(
+            entry.reviewed_at().timestamp(),
+            history.last_review_date().unwrap().timestamp()
+        );
+    }
+
+    #[test]
+    fn merge_unions_review_logs() {
+        let mut history_a = MemoryHistory::new();
+        history_a.apply_review(make_state(), Rating::Good);
+        history_a.record_review(Rating::Good, RateMode::StandardLesson);
+
+        let mut history_b = MemoryHistory::new();
+        history_b.apply_review(make_state(), Rating::Again);
+        history_b.record_review(Rating::Again, RateMode::ShortTerm);
+
+        history_a.merge(&history_b);
+
+        assert_eq!(history_a.review_log().len(), 2);
+    }
 }
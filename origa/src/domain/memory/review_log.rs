@@ -0,0 +1,249 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::RateMode;
+use crate::domain::memory::Rating;
+
+/// Upper bound of entries kept per card. FSRS parameter fitting gains almost
+/// nothing from reviews older than the last few dozen (stability saturates),
+/// while every entry is paid for on each `save_sync`. 32 entries × ~20 bytes
+/// keeps a heavy user's set well under the pre-ADR-034 payload.
+pub(crate) const MAX_REVIEW_LOG_ENTRIES: usize = 32;
+
+/// One review as the optimizer needs it: when, how, and under which
+/// scheduling mode. Interval and memory state are NOT stored — they are
+/// replayed from the ratings by the optimizer, which is exactly what the
+/// dropped `VecDeque<ReviewLog>` used to waste bytes on (ADR-034).
+///
+/// Serialized as a compact tuple `[unix_seconds, rating, mode]` instead of
+/// a keyed object: field names would triple the wire size of the log.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(into = "WireEntry", try_from = "WireEntry")]
+pub struct ReviewLogEntry {
+    reviewed_at: DateTime<Utc>,
+    rating: Rating,
+    mode: RateMode,
+}
+
+type WireEntry = (i64, u8, u8);
+
+impl ReviewLogEntry {
+    pub fn new(reviewed_at: DateTime<Utc>, rating: Rating, mode: RateMode) -> Self {
+        Self {
+            // Second precision is what the wire keeps; truncating up front
+            // makes a roundtripped entry compare equal to the original, which
+            // the union in `ReviewLog::merge` relies on.
+            reviewed_at: DateTime::from_timestamp(reviewed_at.timestamp(), 0)
+                .unwrap_or(reviewed_at),
+            rating,
+            mode,
+        }
+    }
+
+    pub fn reviewed_at(&self) -> DateTime<Utc> {
+        self.reviewed_at
+    }
+
+    pub fn rating(&self) -> Rating {
+        self.rating
+    }
+
+    pub fn mode(&self) -> RateMode {
+        self.mode
+    }
+}
+
+impl From<ReviewLogEntry> for WireEntry {
+    fn from(entry: ReviewLogEntry) -> Self {
+        (
+            entry.reviewed_at.timestamp(),
+            rating_to_wire(entry.rating),
+            mode_to_wire(entry.mode),
+        )
+    }
+}
+
+impl TryFrom<WireEntry> for ReviewLogEntry {
+    type Error = String;
+
+    fn try_from((seconds, rating, mode): WireEntry) -> Result<Self, Self::Error> {
+        let reviewed_at = DateTime::from_timestamp(seconds, 0)
+            .ok_or_else(|| format!("review timestamp out of range: {seconds}"))?;
+        Ok(Self {
+            reviewed_at,
+            rating: rating_from_wire(rating)?,
+            mode: mode_from_wire(mode)?,
+        })
+    }
+}
+
+/// Anki/FSRS numbering (1 = Again … 4 = Easy) so exported logs need no
+/// translation table.
+fn rating_to_wire(rating: Rating) -> u8 {
+    match rating {
+        Rating::Again => 1,
+        Rating::Hard => 2,
+        Rating::Good => 3,
+        Rating::Easy => 4,
+    }
+}
+
+fn rating_from_wire(value: u8) -> Result<Rating, String> {
+    match value {
+        1 => Ok(Rating::Again),
+        2 => Ok(Rating::Hard),
+        3 => Ok(Rating::Good),
+        4 => Ok(Rating::Easy),
+        other => Err(format!("unknown review rating: {other}")),
+    }
+}
+
+// Wire codes are append-only: never renumber an existing mode.
+fn mode_to_wire(mode: RateMode) -> u8 {
+    match mode {
+        RateMode::ShortTerm => 0,
+        RateMode::StandardLesson => 1,
+        RateMode::PhraseReview => 2,
+        RateMode::OnboardingScoring => 3,
+        RateMode::GrammarReview => 4,
+        RateMode::KanjiReview => 5,
+    }
+}
+
+fn mode_from_wire(value: u8) -> Result<RateMode, String> {
+    match value {
+        0 => Ok(RateMode::ShortTerm),
+        1 => Ok(RateMode::StandardLesson),
+        2 => Ok(RateMode::PhraseReview),
+        3 => Ok(RateMode::OnboardingScoring),
+        4 => Ok(RateMode::GrammarReview),
+        5 => Ok(RateMode::KanjiReview),
+        other => Err(format!("unknown review mode: {other}")),
+    }
+}
+
+/// Opt-in, bounded per-card review log (see [`MAX_REVIEW_LOG_ENTRIES`]).
+///
+/// Kept chronologically sorted. Unlike the `MemoryHistory` counters it is a
+/// G-Set: cross-device merge is a union of entries, so offline→offline
+/// divergence does not lose reviews (up to the cap).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReviewLog {
+    entries: Vec<ReviewLogEntry>,
+}
+
+impl ReviewLog {
+    pub fn entries(&self) -> &[ReviewLogEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn push(&mut self, entry: ReviewLogEntry) {
+        self.entries.push(entry);
+        self.normalize();
+    }
+
+    pub(crate) fn merge(&mut self, other: &ReviewLog) {
+        self.extend(other.entries.iter().copied());
+    }
+
+    pub(crate) fn extend(&mut self, entries: impl IntoIterator<Item = ReviewLogEntry>) {
+        self.entries.extend(entries);
+        self.normalize();
+    }
+
+    fn normalize(&mut self) {
+        self.entries.sort_by_key(|e| {
+            (
+                e.reviewed_at,
+                rating_to_wire(e.rating),
+                mode_to_wire(e.mode),
+            )
+        });
+        self.entries.dedup();
+        if self.entries.len() > MAX_REVIEW_LOG_ENTRIES {
+            let overflow = self.entries.len() - MAX_REVIEW_LOG_ENTRIES;
+            self.entries.drain(..overflow);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry_at(days_ago: i64, rating: Rating) -> ReviewLogEntry {
+        ReviewLogEntry::new(
+            Utc::now() - Duration::days(days_ago),
+            rating,
+            RateMode::StandardLesson,
+        )
+    }
+
+    #[test]
+    fn entry_serializes_as_compact_tuple() {
+        let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let entry = ReviewLogEntry::new(at, Rating::Good, RateMode::KanjiReview);
+
+        let json = serde_json::to_string(&entry).unwrap();
+
+        assert_eq!(json, "[1700000000,3,5]");
+        let back: ReviewLogEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, entry);
+    }
+
+    #[test]
+    fn entry_rejects_unknown_rating_code() {
+        let result = serde_json::from_str::<ReviewLogEntry>("[1700000000,9,1]");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn push_keeps_entries_sorted() {
+        let mut log = ReviewLog::default();
+        log.push(entry_at(1, Rating::Good));
+        log.push(entry_at(5, Rating::Again));
+
+        assert_eq!(log.entries()[0].rating(), Rating::Again);
+        assert_eq!(log.entries()[1].rating(), Rating::Good);
+    }
+
+    #[test]
+    fn push_drops_oldest_entries_past_cap() {
+        let mut log = ReviewLog::default();
+        for day in 0..(MAX_REVIEW_LOG_ENTRIES as i64 + 5) {
+            log.push(entry_at(day, Rating::Good));
+        }
+
+        assert_eq!(log.len(), MAX_REVIEW_LOG_ENTRIES);
+        let oldest_kept = log.entries()[0].reviewed_at();
+        assert!(oldest_kept > Utc::now() - Duration::days(MAX_REVIEW_LOG_ENTRIES as i64));
+    }
+
+    #[test]
+    fn merge_unions_divergent_logs_without_duplicates() {
+        let shared = entry_at(10, Rating::Good);
+        let mut device_a = ReviewLog::default();
+        device_a.push(shared);
+        device_a.push(entry_at(3, Rating::Hard));
+        let mut device_b = ReviewLog::default();
+        device_b.push(shared);
+        device_b.push(entry_at(2, Rating::Easy));
+
+        device_a.merge(&device_b);
+
+        assert_eq!(device_a.len(), 3);
+        let ratings: Vec<Rating> = device_a.entries().iter().map(|e| e.rating()).collect();
+        assert_eq!(ratings, vec![Rating::Good, Rating::Hard, Rating::Easy]);
+    }
+}
@@ -1,3 +1,4 @@
+mod book;
 mod error;
 mod furigana;
 mod furigana_annotator;
@@ -8,37 +9,50 @@ mod jlpt_content;
 mod jlpt_progress;
 mod knowledge;
 mod memory;
+mod pitch_accent;
 mod score_content;
 mod serde_utils;
 mod srs;
 mod stats;
+mod subtitles;
 mod tokenizer;
 mod user;
 pub(crate) mod value_objects;
 mod well_known_set;
 
+pub use book::{Book, BookChapter, split_sentences};
 pub use error::{ErrorCategory, OrigaError};
-pub use furigana::{FuriganaSegment, furiganize_segments, furiganize_text, furiganize_text_html};
-pub use furigana_annotator::{AnnotatedSpan, annotate_text};
-pub use grammar::apply_format_actions;
+pub use furigana::{
+    FuriganaSegment, furiganize_segments, furiganize_segments_with_hints, furiganize_text,
+    furiganize_text_html,
+};
+pub use furigana_annotator::{AnnotatedSpan, ReadingHint, annotate_text, annotate_text_with_hints};
 pub use grammar::quiz_generation::{
     GrammarPracticeQuestion, apply_mutated_pattern, find_known_vocab_words_for_pos,
     generate_grammar_distractors, generate_grammar_practice_questions,
 };
+pub use grammar::{
+    ConjugationCheck, ConjugationStepForm, Deconjugation, DeconjugationStep, apply_format_actions,
+    check_conjugation, conjugation_step_label, deconjugate, deconjugate_to,
+};
 pub use grammar::{detect_format_map_rules, detect_grammar_rules_in_text, detect_keyword_rules};
 pub use import_preview::{WordImportClassifier, WordImportOutcome, WordImportPreview};
 pub use japanese::{JapaneseChar, JapaneseText};
-pub use japanese::{hiragana_to_katakana, katakana_to_hiragana};
+pub use japanese::{
+    hiragana_to_katakana, katakana_to_hiragana, romaji_to_kana, romaji_to_kana_partial,
+};
 pub use jlpt_content::{JlptContent, JlptContentError};
 pub use jlpt_progress::{
     CategoryCounts, CategoryProgress, JlptProgress, LevelProgressDetail, ProgressUpdate,
 };
 pub use knowledge::{
-    Card, CardType, DailyHistoryItem, ExampleKanjiWord, GrammarInfo, GrammarQuizCard,
-    GrammarRuleCard, KanjiCard, KnowledgeSet, LessonCard, LessonCardView, LessonData,
-    LessonEmptyDiagnosis, LessonViewGenerator, MultiQuizResult, PhraseCard, QuizCard, QuizMode,
-    QuizOption, StudyCard, VocabularyCard, YesNoCard, diagnose_empty_lesson,
-    estimate_completion_date,
+    Card, CardType, ConjugationDrillCard, DailyHistoryItem, EventId, ExamReadiness, ExampleContext,
+    ExampleKanjiWord, ExampleMedium, GrammarInfo, GrammarQuizCard, GrammarRuleCard, KanjiCard,
+    KnowledgeEvent, KnowledgeJournal, KnowledgeOp, KnowledgeSet, LessonCard, LessonCardView,
+    LessonData, LessonEmptyDiagnosis, LessonViewGenerator, MultiQuizResult, ParticleCard,
+    ParticleClozeCard, PhraseCard, ProductionCard, ProductionResult, QuizCard, QuizMode,
+    QuizOption, StudyCard, VocabularyCard, Watermark, YesNoCard, assess_exam_readiness,
+    diagnose_empty_lesson, estimate_completion_date,
 };
 
 /// Re-exported so the UI can stay layering-clean: presentation code reaches
@@ -49,10 +63,17 @@ pub use knowledge::{
 pub use crate::dictionary::kanji::RARE_READING_MAX_FREQ;
 
 pub(crate) use knowledge::collect_known_vocabulary_words;
-pub use memory::{CardState, Difficulty, MemoryHistory, MemoryState, Rating, Stability};
+pub use memory::{
+    CardState, Difficulty, MemoryHistory, MemoryState, Rating, ReviewLog, ReviewLogEntry, Stability,
+};
+pub use pitch_accent::{PitchAccent, PitchAccentType, split_morae};
 pub use score_content::ScoreContentResult;
-pub use srs::RateMode;
+pub use srs::{
+    ExamTarget, FsrsWeights, MAX_INTERVAL_DAYS, MAX_REQUEST_RETENTION, MIN_REQUEST_RETENTION,
+    ModeSchedule, OptimizedWeights, RateMode, SrsOptimization, SrsOptimizationReport, SrsSettings,
+};
 pub use stats::{RatingRatio, TodayOverview, compute_rating_ratio, compute_today_overview};
+pub use subtitles::{SubtitleFormat, SubtitleLine, parse_subtitle_file, parse_subtitles};
 pub use tokenizer::{
     DictionaryData, PartOfSpeech, TokenInfo, TokenTranslation, init_dictionary,
     is_dictionary_loaded, lookup_tokens_translations, tokenize_text,
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+use super::japanese::katakana_to_hiragana;
+
+/// Small kana that merge with the preceding kana into one mora (きゃ, ファ).
+/// っ and ー are full morae of their own and are not listed here.
+const SMALL_KANA: &[char] = &[
+    'ゃ', 'ゅ', 'ょ', 'ぁ', 'ぃ', 'ぅ', 'ぇ', 'ぉ', 'ゎ', 'ャ', 'ュ', 'ョ', 'ァ', 'ィ', 'ゥ', 'ェ',
+    'ォ', 'ヮ',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PitchAccentType {
+    /// Low first mora, high to the end and on the following particle.
+    Heiban,
+    /// High first mora, low afterwards.
+    Atamadaka,
+    /// Drops after a mora in the middle of the word.
+    Nakadaka,
+    /// High to the last mora, low on the following particle.
+    Odaka,
+}
+
+impl PitchAccentType {
+    pub const ALL: [PitchAccentType; 4] = [
+        PitchAccentType::Heiban,
+        PitchAccentType::Atamadaka,
+        PitchAccentType::Nakadaka,
+        PitchAccentType::Odaka,
+    ];
+
+    pub fn japanese_name(&self) -> &'static str {
+        match self {
+            PitchAccentType::Heiban => "平板",
+            PitchAccentType::Atamadaka => "頭高",
+            PitchAccentType::Nakadaka => "中高",
+            PitchAccentType::Odaka => "尾高",
+        }
+    }
+
+    pub fn romaji_name(&self) -> &'static str {
+        match self {
+            PitchAccentType::Heiban => "heiban",
+            PitchAccentType::Atamadaka => "atamadaka",
+            PitchAccentType::Nakadaka => "nakadaka",
+            PitchAccentType::Odaka => "odaka",
+        }
+    }
+}
+
+/// Pitch accent of one reading: the mora after which pitch drops
+/// (0 for none, as in the pitch audio index) and the reading split into morae.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PitchAccent {
+    downstep: u8,
+    morae: Vec<String>,
+}
+
+impl PitchAccent {
+    /// `None` when the downstep points past the end of the reading, which
+    /// means the index entry belongs to a different reading.
+    pub fn new(reading: &str, downstep: u8) -> Option<Self> {
+        let morae = split_morae(&katakana_to_hiragana(reading));
+        if morae.is_empty() || downstep as usize > morae.len() {
+            return None;
+        }
+        Some(Self { downstep, morae })
+    }
+
+    pub fn downstep(&self) -> u8 {
+        self.downstep
+    }
+
+    pub fn morae(&self) -> &[String] {
+        &self.morae
+    }
+
+    pub fn accent_type(&self) -> PitchAccentType {
+        match self.downstep as usize {
+            0 => PitchAccentType::Heiban,
+            1 => PitchAccentType::Atamadaka,
+            n if n == self.morae.len() => PitchAccentType::Odaka,
+            _ => PitchAccentType::Nakadaka,
+        }
+    }
+
+    /// High (`true`) or low pitch of every mora followed by the pitch of an
+    /// attached particle, which tells heiban and odaka apart.
+    pub fn contour(&self) -> Vec<bool> {
+        let downstep = self.downstep as usize;
+        (0..=self.morae.len())
+            .map(|index| match downstep {
+                0 => index > 0,
+                1 => index == 0,
+                _ => index > 0 && index < downstep,
+            })
+            .collect()
+    }
+}
+
+/// Splits kana into morae: small ya/yu/yo and vowels join the previous
+/// kana, while っ, ん and ー each count as a mora.
+pub fn split_morae(reading: &str) -> Vec<String> {
+    let mut morae: Vec<String> = Vec::new();
+    for c in reading.chars() {
+        match morae.last_mut() {
+            Some(last) if SMALL_KANA.contains(&c) => last.push(c),
+            _ => morae.push(c.to_string()),
+        }
+    }
+    morae
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("きょう", &["きょ", "う"])]
+    #[case("がっこう", &["が", "っ", "こ", "う"])]
+    #[case("コーヒー", &["こ", "ー", "ひ", "ー"])]
+    #[case("しんぶん", &["し", "ん", "ぶ", "ん"])]
+    fn splits_reading_into_morae(#[case] reading: &str, #[case] expected: &[&str]) {
+        let accent = PitchAccent::new(reading, 0).unwrap();
+
+        assert_eq!(accent.morae(), expected);
+    }
+
+    #[rstest]
+    #[case("さくら", 0, PitchAccentType::Heiban, &[false, true, true, true])]
+    #[case("ねこ", 1, PitchAccentType::Atamadaka, &[true, false, false])]
+    #[case("たまご", 2, PitchAccentType::Nakadaka, &[false, true, false, false])]
+    #[case("いぬ", 2, PitchAccentType::Odaka, &[false, true, false])]
+    fn classifies_accent_and_builds_contour(
+        #[case] reading: &str,
+        #[case] downstep: u8,
+        #[case] accent_type: PitchAccentType,
+        #[case] contour: &[bool],
+    ) {
+        let accent = PitchAccent::new(reading, downstep).unwrap();
+
+        assert_eq!(accent.accent_type(), accent_type);
+        assert_eq!(accent.contour(), contour);
+    }
+
+    #[test]
+    fn downstep_past_reading_end_is_rejected() {
+        assert!(PitchAccent::new("ねこ", 3).is_none());
+        assert!(PitchAccent::new("", 0).is_none());
+    }
+}
@@ -28,6 +28,16 @@ impl ScoreContentResult {
     pub fn unknown_kanji(&self) -> &[String] {
         &self.unknown_kanji
     }
+
+    /// Share of distinct vocabulary words already known, in percent. Text
+    /// without vocabulary words counts as fully understood.
+    pub fn comprehension_percentage(&self) -> f32 {
+        let total = self.known_words.len() + self.unknown_words.len();
+        if total == 0 {
+            return 100.0;
+        }
+        self.known_words.len() as f32 / total as f32 * 100.0
+    }
 }
 
 fn classify_items<T: Eq + Hash>(
@@ -328,6 +338,25 @@ mod tests {
         assert_eq!(cat_count, 1);
     }
 
+    #[test]
+    fn comprehension_percentage_counts_known_share_of_words() {
+        let result = ScoreContentResult {
+            unknown_words: vec!["犬".to_string()],
+            unknown_kanji: vec![],
+            known_words: vec!["猫".to_string(), "水".to_string(), "本".to_string()],
+            known_kanji: vec![],
+        };
+        let empty = ScoreContentResult {
+            unknown_words: vec![],
+            unknown_kanji: vec![],
+            known_words: vec![],
+            known_kanji: vec![],
+        };
+
+        assert_eq!(result.comprehension_percentage(), 75.0);
+        assert_eq!(empty.comprehension_percentage(), 100.0);
+    }
+
     #[test]
     fn score_content_unknown_kanji_card_not_counted_as_known() {
         init_real_dictionaries();
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{JapaneseLevel, OrigaError};
+
+/// A JLPT sitting the user is preparing for.
+///
+/// The target covers the whole level stack up to `level`: the N3 exam also
+/// tests N4 and N5 material, so "target-level content" always means every
+/// level from N5 down to `level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExamTarget {
+    level: JapaneseLevel,
+    exam_date: NaiveDate,
+}
+
+impl ExamTarget {
+    pub fn new(level: JapaneseLevel, exam_date: NaiveDate) -> Result<Self, OrigaError> {
+        let today = Utc::now().date_naive();
+        if exam_date <= today {
+            return Err(OrigaError::InvalidSrsSettings {
+                reason: format!("exam date {exam_date} must be after today ({today})"),
+            });
+        }
+        Ok(Self { level, exam_date })
+    }
+
+    pub fn level(&self) -> JapaneseLevel {
+        self.level
+    }
+
+    pub fn exam_date(&self) -> NaiveDate {
+        self.exam_date
+    }
+
+    /// Whether `level` material is tested at this exam.
+    pub fn covers(&self, level: JapaneseLevel) -> bool {
+        level.as_number() >= self.level.as_number()
+    }
+
+    /// Whether the exam is still ahead of `today`. From the exam day on the
+    /// target no longer steers scheduling or intake.
+    pub fn is_upcoming(&self, today: NaiveDate) -> bool {
+        self.exam_date > today
+    }
+
+    /// Whole study days left before the exam day itself; `0` once the exam
+    /// is today or over.
+    pub fn days_left(&self, today: NaiveDate) -> i64 {
+        (self.exam_date - today).num_days().max(0)
+    }
+
+    /// Latest moment a review may be scheduled for so it still happens
+    /// before the exam: the start of the day before it. `None` when that
+    /// moment is already past, so the cap never schedules into the past.
+    pub(crate) fn review_deadline(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let deadline = (self.exam_date - Duration::days(1))
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        (deadline > now).then_some(deadline)
+    }
+}
+
+/// Pulls `due`, which falls after `deadline`, back before it. Instead of
+/// stacking every capped review on the deadline, the remaining window is
+/// shared out by the uncapped interval: `window * interval / (interval +
+/// window)` keeps the order of the due dates and lands them in the second
+/// half of the window, the longest intervals nearest the deadline.
+pub(crate) fn spread_before_deadline(
+    now: DateTime<Utc>,
+    due: DateTime<Utc>,
+    deadline: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let window = (deadline - now).num_seconds().max(0) as f64;
+    let interval = (due - now).num_seconds().max(0) as f64;
+    if interval <= window {
+        return due;
+    }
+    let offset = window * interval / (interval + window);
+    now + Duration::seconds(offset as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target_in(days: i64) -> ExamTarget {
+        ExamTarget::new(
+            JapaneseLevel::N3,
+            Utc::now().date_naive() + Duration::days(days),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn new_rejects_past_and_today_dates() {
+        let today = Utc::now().date_naive();
+
+        assert!(ExamTarget::new(JapaneseLevel::N4, today).is_err());
+        assert!(ExamTarget::new(JapaneseLevel::N4, today - Duration::days(3)).is_err());
+    }
+
+    #[test]
+    fn covers_target_and_lower_levels_only() {
+        let target = target_in(30);
+
+        assert!(target.covers(JapaneseLevel::N5));
+        assert!(target.covers(JapaneseLevel::N3));
+        assert!(!target.covers(JapaneseLevel::N2));
+    }
+
+    #[test]
+    fn review_deadline_is_start_of_the_day_before_exam() {
+        let target = target_in(10);
+        let now = Utc::now();
+
+        let deadline = target.review_deadline(now).unwrap();
+
+        assert_eq!(
+            deadline.date_naive(),
+            target.exam_date() - Duration::days(1)
+        );
+        assert!(deadline > now);
+    }
+
+    #[test]
+    fn exam_is_no_longer_upcoming_on_the_exam_day() {
+        let target = target_in(5);
+
+        assert!(target.is_upcoming(target.exam_date() - Duration::days(1)));
+        assert!(!target.is_upcoming(target.exam_date()));
+        assert!(!target.is_upcoming(target.exam_date() + Duration::days(7)));
+    }
+
+    #[test]
+    fn spread_keeps_order_of_capped_due_dates_within_the_window() {
+        let now = Utc::now();
+        let deadline = now + Duration::days(10);
+
+        let short = spread_before_deadline(now, now + Duration::days(15), deadline);
+        let long = spread_before_deadline(now, now + Duration::days(200), deadline);
+
+        assert!(short > now + Duration::days(5));
+        assert!(short < long);
+        assert!(long < deadline);
+    }
+
+    #[test]
+    fn spread_leaves_due_dates_before_the_deadline_alone() {
+        let now = Utc::now();
+        let due = now + Duration::days(3);
+
+        assert_eq!(
+            spread_before_deadline(now, due, now + Duration::days(10)),
+            due
+        );
+    }
+
+    #[test]
+    fn review_deadline_is_none_on_the_eve_of_the_exam() {
+        let target = target_in(1);
+
+        assert!(target.review_deadline(Utc::now()).is_none());
+    }
+}
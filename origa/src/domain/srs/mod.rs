@@ -1,6 +1,18 @@
+mod exam;
+mod optimizer;
+mod settings;
+
+pub use exam::ExamTarget;
+pub use optimizer::{FsrsWeights, SrsOptimization, SrsOptimizationReport};
+pub(crate) use settings::SrsOverrides;
+pub use settings::{
+    MAX_INTERVAL_DAYS, MAX_REQUEST_RETENTION, MIN_REQUEST_RETENTION, ModeSchedule,
+    OptimizedWeights, SrsSettings,
+};
+
 use crate::domain::OrigaError;
 use crate::domain::Rating;
-use crate::domain::{CardState, Difficulty, MemoryHistory, MemoryState, Stability};
+use crate::domain::{Card, CardState, Difficulty, MemoryHistory, MemoryState, Stability};
 use chrono::Utc;
 use rs_fsrs::{Card as FsrsCard, FSRS, Parameters, Rating as FsrsRating, State as FsrsState};
 use serde::Deserialize;
@@ -32,6 +44,27 @@ const ALL_RATE_MODES: [RateMode; 6] = [
     RateMode::KanjiReview,
 ];
 
+/// Long-term modes whose reviews share one set of fitted FSRS weights.
+/// `ShortTerm` and `OnboardingScoring` reviews of a card still feed the
+/// family of its card type (see [`scheduling_family`]).
+pub(crate) const SCHEDULING_FAMILIES: [RateMode; 4] = [
+    RateMode::StandardLesson,
+    RateMode::PhraseReview,
+    RateMode::GrammarReview,
+    RateMode::KanjiReview,
+];
+
+/// The weights family a card belongs to, mirroring the long-term mode
+/// `KnowledgeSet::rate_card` picks for its card type.
+pub(crate) fn scheduling_family(card: &Card) -> RateMode {
+    match card {
+        Card::Vocabulary(_) => RateMode::StandardLesson,
+        Card::Phrase(_) => RateMode::PhraseReview,
+        Card::Grammar(_) | Card::Particle(_) => RateMode::GrammarReview,
+        Card::Kanji(_) => RateMode::KanjiReview,
+    }
+}
+
 struct SrsConfig {
     request_retention: f64,
     maximum_interval: i32,
@@ -115,17 +148,61 @@ fn to_card_state(fsrs_state: FsrsState) -> CardState {
     }
 }
 
-pub fn rate_memory(
+fn to_fsrs_rating(rating: Rating) -> FsrsRating {
+    match rating {
+        Rating::Again => FsrsRating::Again,
+        Rating::Hard => FsrsRating::Hard,
+        Rating::Good => FsrsRating::Good,
+        Rating::Easy => FsrsRating::Easy,
+    }
+}
+
+/// Schedules the next review. Without weight or schedule overrides the
+/// shared per-mode engine is used; otherwise a one-off engine is built on
+/// top of the mode's `SrsConfig` (constructing `FSRS` is a plain struct
+/// copy). An exam deadline only moves the due date, spread over the days
+/// before it: stability and difficulty stay what FSRS computed, so the
+/// post-exam schedule recovers.
+pub(crate) fn rate_memory(
     mode: RateMode,
     rating: Rating,
     memory_history: &MemoryHistory,
+    overrides: &SrsOverrides,
 ) -> Result<MemoryState, OrigaError> {
-    let srs_service = FSRS_SERVICE.get_or_init(FsrsSrsService::new);
-    let engine = srs_service
-        .engines
-        .get(&mode)
-        .expect("all RateMode variants are pre-initialized in FsrsSrsService");
-    schedule_next_review(engine, rating, memory_history)
+    let memory_state = if overrides.weights.is_none() && overrides.schedule.is_none() {
+        let srs_service = FSRS_SERVICE.get_or_init(FsrsSrsService::new);
+        let engine = srs_service
+            .engines
+            .get(&mode)
+            .expect("all RateMode variants are pre-initialized in FsrsSrsService");
+        schedule_next_review(engine, rating, memory_history)?
+    } else {
+        let mut parameters = SrsConfig::for_mode(mode).to_parameters();
+        if let Some(weights) = overrides.weights {
+            parameters.w = *weights;
+        }
+        if let Some(schedule) = overrides.schedule {
+            parameters.request_retention = schedule.request_retention();
+            parameters.maximum_interval = schedule.maximum_interval();
+        }
+        schedule_next_review(&FSRS::new(parameters), rating, memory_history)?
+    };
+
+    Ok(match overrides.due_before {
+        Some(deadline) if *memory_state.next_review_date() > deadline => {
+            MemoryState::with_card_state(
+                *memory_state.stability(),
+                *memory_state.difficulty(),
+                exam::spread_before_deadline(
+                    Utc::now(),
+                    *memory_state.next_review_date(),
+                    deadline,
+                ),
+                memory_state.card_state(),
+            )
+        },
+        _ => memory_state,
+    })
 }
 
 fn schedule_next_review(
@@ -166,14 +243,7 @@ fn schedule_next_review(
         FsrsCard::new()
     };
 
-    let fsrs_rating = match rating {
-        Rating::Again => FsrsRating::Again,
-        Rating::Hard => FsrsRating::Hard,
-        Rating::Good => FsrsRating::Good,
-        Rating::Easy => FsrsRating::Easy,
-    };
-
-    let scheduling_info = engine.next(card, now, fsrs_rating);
+    let scheduling_info = engine.next(card, now, to_fsrs_rating(rating));
 
     let next_review_date = scheduling_info.card.due;
     let stability = Stability::new(scheduling_info.card.stability)?;
@@ -205,7 +275,13 @@ mod tests {
         let memory_history = MemoryHistory::new();
         let before = Utc::now();
 
-        let result = rate_memory(RateMode::StandardLesson, Rating::Again, &memory_history).unwrap();
+        let result = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Again,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         let after = Utc::now();
 
@@ -223,7 +299,13 @@ mod tests {
         let memory_history = MemoryHistory::new();
         let now = Utc::now();
 
-        let result = rate_memory(mode, Rating::Good, &memory_history).unwrap();
+        let result = rate_memory(
+            mode,
+            Rating::Good,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         assert!(
             *result.next_review_date() > now,
@@ -236,7 +318,13 @@ mod tests {
         let memory_history = MemoryHistory::new();
         let before = Utc::now();
 
-        let result = rate_memory(RateMode::PhraseReview, Rating::Again, &memory_history).unwrap();
+        let result = rate_memory(
+            RateMode::PhraseReview,
+            Rating::Again,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         let after = Utc::now();
 
@@ -249,9 +337,20 @@ mod tests {
     fn phrase_review_easy_gives_longer_interval_than_standard() {
         let memory_history = MemoryHistory::new();
 
-        let standard =
-            rate_memory(RateMode::StandardLesson, Rating::Easy, &memory_history).unwrap();
-        let phrase = rate_memory(RateMode::PhraseReview, Rating::Easy, &memory_history).unwrap();
+        let standard = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Easy,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
+        let phrase = rate_memory(
+            RateMode::PhraseReview,
+            Rating::Easy,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         assert!(*phrase.next_review_date() > *standard.next_review_date());
     }
@@ -277,7 +376,13 @@ mod tests {
         let memory_history = MemoryHistory::new();
         let before = Utc::now();
 
-        let result = rate_memory(RateMode::GrammarReview, Rating::Again, &memory_history).unwrap();
+        let result = rate_memory(
+            RateMode::GrammarReview,
+            Rating::Again,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         let after = Utc::now();
 
@@ -290,7 +395,13 @@ mod tests {
         let memory_history = MemoryHistory::new();
         let before = Utc::now();
 
-        let result = rate_memory(RateMode::KanjiReview, Rating::Again, &memory_history).unwrap();
+        let result = rate_memory(
+            RateMode::KanjiReview,
+            Rating::Again,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         let after = Utc::now();
 
@@ -302,9 +413,20 @@ mod tests {
     fn grammar_review_easy_gives_shorter_or_equal_interval_than_standard() {
         let memory_history = MemoryHistory::new();
 
-        let standard =
-            rate_memory(RateMode::StandardLesson, Rating::Easy, &memory_history).unwrap();
-        let grammar = rate_memory(RateMode::GrammarReview, Rating::Easy, &memory_history).unwrap();
+        let standard = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Easy,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
+        let grammar = rate_memory(
+            RateMode::GrammarReview,
+            Rating::Easy,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         assert!(*grammar.next_review_date() <= *standard.next_review_date());
     }
@@ -346,7 +468,13 @@ mod tests {
     #[test]
     fn new_card_good_transitions_to_learning() {
         let memory_history = MemoryHistory::new();
-        let result = rate_memory(RateMode::StandardLesson, Rating::Good, &memory_history).unwrap();
+        let result = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Good,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         assert_eq!(result.card_state(), CardState::Learning);
     }
@@ -354,7 +482,13 @@ mod tests {
     #[test]
     fn new_card_easy_transitions_to_review() {
         let memory_history = MemoryHistory::new();
-        let result = rate_memory(RateMode::StandardLesson, Rating::Easy, &memory_history).unwrap();
+        let result = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Easy,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         assert_eq!(result.card_state(), CardState::Review);
     }
@@ -370,7 +504,13 @@ mod tests {
         );
         history.apply_review(state, Rating::Good);
 
-        let result = rate_memory(RateMode::StandardLesson, Rating::Again, &history).unwrap();
+        let result = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Again,
+            &history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         assert_eq!(result.card_state(), CardState::Relearning);
     }
@@ -386,7 +526,13 @@ mod tests {
         );
         history.apply_review(state, Rating::Good);
 
-        let result = rate_memory(RateMode::StandardLesson, Rating::Good, &history).unwrap();
+        let result = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Good,
+            &history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         assert_eq!(result.card_state(), CardState::Review);
     }
@@ -402,8 +548,102 @@ mod tests {
         );
         history.apply_review(state, Rating::Again);
 
-        let result = rate_memory(RateMode::StandardLesson, Rating::Good, &history).unwrap();
+        let result = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Good,
+            &history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
 
         assert_eq!(result.card_state(), CardState::Review);
     }
+
+    #[test]
+    fn weight_overrides_change_the_scheduled_interval() {
+        let memory_history = MemoryHistory::new();
+        let mut weights = SrsConfig::for_mode(RateMode::StandardLesson)
+            .to_parameters()
+            .w;
+        weights[3] = 60.0;
+        let overrides = SrsOverrides {
+            weights: Some(&weights),
+            ..Default::default()
+        };
+
+        let stock = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Easy,
+            &memory_history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
+        let personal = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Easy,
+            &memory_history,
+            &overrides,
+        )
+        .unwrap();
+
+        assert!(personal.stability().value() > stock.stability().value());
+        assert!(*personal.next_review_date() > *stock.next_review_date());
+    }
+
+    #[test]
+    fn schedule_override_caps_interval_at_user_maximum() {
+        let memory_history = MemoryHistory::new();
+        let overrides = SrsOverrides {
+            schedule: Some(ModeSchedule::new(0.80, 2).unwrap()),
+            ..Default::default()
+        };
+
+        let result = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Easy,
+            &memory_history,
+            &overrides,
+        )
+        .unwrap();
+
+        let interval = result
+            .next_review_date()
+            .signed_duration_since(Utc::now())
+            .num_hours();
+        assert!(interval <= 48, "interval {interval}h exceeds the 2-day cap");
+    }
+
+    #[test]
+    fn exam_deadline_pulls_due_date_before_the_exam() {
+        let mut history = MemoryHistory::new();
+        history.apply_review(
+            MemoryState::with_card_state(
+                Stability::new(60.0).unwrap(),
+                Difficulty::new(4.0).unwrap(),
+                Utc::now(),
+                CardState::Review,
+            ),
+            Rating::Good,
+        );
+        let deadline = Utc::now() + Duration::days(5);
+        let overrides = SrsOverrides {
+            due_before: Some(deadline),
+            ..Default::default()
+        };
+
+        let stock = rate_memory(
+            RateMode::StandardLesson,
+            Rating::Good,
+            &history,
+            &SrsOverrides::default(),
+        )
+        .unwrap();
+        let capped =
+            rate_memory(RateMode::StandardLesson, Rating::Good, &history, &overrides).unwrap();
+
+        assert!(*stock.next_review_date() > deadline);
+        assert!(*capped.next_review_date() <= deadline);
+        assert!(*capped.next_review_date() > Utc::now() + Duration::days(2));
+        assert_eq!(capped.stability(), stock.stability());
+    }
 }
@@ -0,0 +1,523 @@
+//! Per-user FSRS weight fitting from the opt-in review log.
+//!
+//! The fit replays every card's log through the FSRS-4.5 memory model (the
+//! same formulas `rs-fsrs` schedules with), predicts recall probability for
+//! each review that happened at least one day after the previous one, and
+//! minimizes the binary cross-entropy against the actual outcome
+//! (`Again` = forgotten, anything else = recalled).
+//!
+//! `rs-fsrs` ships no optimizer and the model is only 19 scalars, so the fit
+//! is a plain bounded Adam over finite-difference gradients: deterministic,
+//! dependency-free and fast enough for a few thousand reviews on WASM.
+
+use rs_fsrs::{Parameters, Rating as FsrsRating};
+
+use super::{RateMode, to_fsrs_rating};
+use crate::domain::ReviewLogEntry;
+use crate::domain::memory::Rating;
+
+pub type FsrsWeights = [f64; 19];
+
+/// Below this many graded reviews the stock weights generalize better than
+/// anything fitted on the user's data.
+pub(crate) const MIN_REVIEWS_FOR_OPTIMIZATION: usize = 100;
+
+const ITERATIONS: usize = 150;
+const LEARNING_RATE: f64 = 0.02;
+const GRADIENT_STEP: f64 = 1e-4;
+const ADAM_BETA1: f64 = 0.9;
+const ADAM_BETA2: f64 = 0.999;
+const ADAM_EPSILON: f64 = 1e-8;
+/// Pull towards the stock weights, scaled down as the log grows so a large
+/// log can move the weights freely while a small one stays near defaults.
+const REGULARIZATION: f64 = 2.0;
+const MIN_STABILITY: f64 = 0.01;
+const MAX_STABILITY: f64 = 36_500.0;
+
+/// Parameter ranges of the reference FSRS optimizer; anything outside them
+/// makes the model degenerate (negative stabilities, inverted ratings).
+const WEIGHT_BOUNDS: [(f64, f64); 19] = [
+    (0.001, 100.0),
+    (0.001, 100.0),
+    (0.001, 100.0),
+    (0.001, 100.0),
+    (1.0, 10.0),
+    (0.001, 4.0),
+    (0.001, 4.0),
+    (0.001, 0.75),
+    (0.0, 4.5),
+    (0.0, 0.8),
+    (0.001, 3.5),
+    (0.001, 5.0),
+    (0.001, 0.25),
+    (0.001, 0.9),
+    (0.0, 4.0),
+    (0.0, 1.0),
+    (1.0, 6.0),
+    (0.0, 2.0),
+    (0.0, 2.0),
+];
+
+/// Outcome of optimizing one scheduling family, returned to the UI so it can
+/// tell the user why their weights did or did not change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SrsOptimizationReport {
+    family: RateMode,
+    review_count: usize,
+    default_loss: Option<f64>,
+    fitted_loss: Option<f64>,
+    applied: bool,
+}
+
+impl SrsOptimizationReport {
+    pub(crate) fn skipped(family: RateMode, review_count: usize) -> Self {
+        Self {
+            family,
+            review_count,
+            default_loss: None,
+            fitted_loss: None,
+            applied: false,
+        }
+    }
+
+    pub(crate) fn fitted(family: RateMode, fit: &WeightsFit, applied: bool) -> Self {
+        Self {
+            family,
+            review_count: fit.review_count,
+            default_loss: Some(fit.default_loss),
+            fitted_loss: Some(fit.fitted_loss),
+            applied,
+        }
+    }
+
+    pub fn family(&self) -> RateMode {
+        self.family
+    }
+
+    pub fn review_count(&self) -> usize {
+        self.review_count
+    }
+
+    /// Log loss of the stock weights; `None` when too few reviews to fit.
+    pub fn default_loss(&self) -> Option<f64> {
+        self.default_loss
+    }
+
+    pub fn fitted_loss(&self) -> Option<f64> {
+        self.fitted_loss
+    }
+
+    /// Whether the fitted weights were stored. Fits that do not beat the
+    /// stock weights on the user's own log are discarded.
+    pub fn applied(&self) -> bool {
+        self.applied
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct WeightsFit {
+    pub(crate) weights: FsrsWeights,
+    pub(crate) review_count: usize,
+    pub(crate) default_loss: f64,
+    pub(crate) fitted_loss: f64,
+}
+
+pub(crate) fn default_weights() -> FsrsWeights {
+    Parameters::default().w
+}
+
+/// Number of reviews in `logs` that carry a recall signal, i.e. that
+/// happened at least one whole day after the previous review of the card.
+pub(crate) fn graded_review_count(logs: &[&[ReviewLogEntry]]) -> usize {
+    logs.iter()
+        .map(|log| {
+            log.windows(2)
+                .filter(|pair| elapsed_days(&pair[0], &pair[1]) >= 1.0)
+                .count()
+        })
+        .sum()
+}
+
+/// Mean binary cross-entropy of `weights` on `logs`, or `None` when the logs
+/// contain no graded review.
+pub(crate) fn log_loss(weights: &FsrsWeights, logs: &[&[ReviewLogEntry]]) -> Option<f64> {
+    let (total, count) = replay(weights, logs);
+    (count > 0).then(|| total / count as f64)
+}
+
+/// A fit of the FSRS weights to a set of review logs, advanced a chunk of
+/// iterations at a time so a single-threaded caller can yield between chunks.
+///
+/// Each log is treated as starting with the card's first review. For logs
+/// truncated at `MAX_REVIEW_LOG_ENTRIES` this is an approximation, but the
+/// memory state converges within a handful of reviews so the bias is small.
+#[derive(Debug, Clone)]
+pub(crate) struct WeightsOptimizer {
+    logs: Vec<Vec<ReviewLogEntry>>,
+    review_count: usize,
+    point: FsrsWeights,
+    first_moment: FsrsWeights,
+    second_moment: FsrsWeights,
+    step: usize,
+}
+
+impl WeightsOptimizer {
+    /// `None` when there are fewer than [`MIN_REVIEWS_FOR_OPTIMIZATION`]
+    /// graded reviews.
+    pub(crate) fn new(logs: Vec<Vec<ReviewLogEntry>>) -> Option<Self> {
+        let review_count = graded_review_count(&as_slices(&logs));
+        if review_count < MIN_REVIEWS_FOR_OPTIMIZATION {
+            return None;
+        }
+        Some(Self {
+            logs,
+            review_count,
+            point: normalize(&default_weights()),
+            first_moment: [0.0; 19],
+            second_moment: [0.0; 19],
+            step: 0,
+        })
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.step >= ITERATIONS
+    }
+
+    /// Runs up to `iterations` more Adam steps.
+    pub(crate) fn advance(&mut self, iterations: usize) {
+        let logs = as_slices(&self.logs);
+        let defaults = normalize(&default_weights());
+        let regularization = REGULARIZATION / self.review_count as f64;
+        let objective = |normalized: &FsrsWeights| {
+            let weights = denormalize(normalized);
+            let (total, count) = replay(&weights, &logs);
+            let penalty: f64 = defaults
+                .iter()
+                .zip(normalized)
+                .map(|(d, w)| (w - d).powi(2))
+                .sum();
+            total / count as f64 + regularization * penalty
+        };
+
+        let last_step = self.step.saturating_add(iterations).min(ITERATIONS);
+        while self.step < last_step {
+            self.step += 1;
+            let step = self.step as i32;
+            let gradient = finite_difference_gradient(&objective, &self.point);
+            for (i, slope) in gradient.iter().enumerate() {
+                self.first_moment[i] =
+                    ADAM_BETA1 * self.first_moment[i] + (1.0 - ADAM_BETA1) * slope;
+                self.second_moment[i] =
+                    ADAM_BETA2 * self.second_moment[i] + (1.0 - ADAM_BETA2) * slope.powi(2);
+                let m_hat = self.first_moment[i] / (1.0 - ADAM_BETA1.powi(step));
+                let v_hat = self.second_moment[i] / (1.0 - ADAM_BETA2.powi(step));
+                self.point[i] = (self.point[i]
+                    - LEARNING_RATE * m_hat / (v_hat.sqrt() + ADAM_EPSILON))
+                    .clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    /// The weights reached so far and their loss against the stock weights.
+    pub(crate) fn finish(self) -> Option<WeightsFit> {
+        let logs = as_slices(&self.logs);
+        let weights = denormalize(&self.point);
+        Some(WeightsFit {
+            weights,
+            review_count: self.review_count,
+            default_loss: log_loss(&default_weights(), &logs)?,
+            fitted_loss: log_loss(&weights, &logs)?,
+        })
+    }
+}
+
+/// A per-family FSRS fit in progress: built from the cards' review logs by
+/// `User::start_srs_optimization`, advanced chunk by chunk with
+/// [`Self::advance`] and stored by `User::finish_srs_optimization`.
+#[derive(Debug, Clone)]
+pub struct SrsOptimization {
+    families: Vec<(RateMode, FamilyFit)>,
+}
+
+#[derive(Debug, Clone)]
+enum FamilyFit {
+    /// Too few graded reviews to fit; carries their count.
+    Skipped(usize),
+    Fitting(Box<WeightsOptimizer>),
+}
+
+impl SrsOptimization {
+    pub(crate) fn new(
+        logs_by_family: impl IntoIterator<Item = (RateMode, Vec<Vec<ReviewLogEntry>>)>,
+    ) -> Self {
+        let families = logs_by_family
+            .into_iter()
+            .map(|(family, logs)| {
+                let review_count = graded_review_count(&as_slices(&logs));
+                let fit = match WeightsOptimizer::new(logs) {
+                    Some(optimizer) => FamilyFit::Fitting(Box::new(optimizer)),
+                    None => FamilyFit::Skipped(review_count),
+                };
+                (family, fit)
+            })
+            .collect();
+        Self { families }
+    }
+
+    /// Runs up to `iterations` optimizer steps on the first unfinished
+    /// family. Returns `false`, doing nothing, once every family is fitted.
+    pub fn advance(&mut self, iterations: usize) -> bool {
+        let pending = self.families.iter_mut().find_map(|(_, fit)| match fit {
+            FamilyFit::Fitting(optimizer) if !optimizer.is_done() => Some(optimizer),
+            FamilyFit::Fitting(_) | FamilyFit::Skipped(_) => None,
+        });
+        match pending {
+            Some(optimizer) => {
+                optimizer.advance(iterations);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Each family's fit, or the number of graded reviews it was skipped
+    /// with.
+    pub(crate) fn into_fits(self) -> impl Iterator<Item = (RateMode, Result<WeightsFit, usize>)> {
+        self.families.into_iter().map(|(family, fit)| {
+            let fit = match fit {
+                FamilyFit::Skipped(review_count) => Err(review_count),
+                FamilyFit::Fitting(optimizer) => {
+                    let review_count = optimizer.review_count;
+                    optimizer.finish().ok_or(review_count)
+                },
+            };
+            (family, fit)
+        })
+    }
+}
+
+fn as_slices(logs: &[Vec<ReviewLogEntry>]) -> Vec<&[ReviewLogEntry]> {
+    logs.iter().map(Vec::as_slice).collect()
+}
+
+fn finite_difference_gradient(
+    objective: &impl Fn(&FsrsWeights) -> f64,
+    point: &FsrsWeights,
+) -> FsrsWeights {
+    let mut gradient = [0.0; 19];
+    let mut probe = *point;
+    for i in 0..point.len() {
+        probe[i] = point[i] + GRADIENT_STEP;
+        let upper = objective(&probe);
+        probe[i] = point[i] - GRADIENT_STEP;
+        let lower = objective(&probe);
+        probe[i] = point[i];
+        gradient[i] = (upper - lower) / (2.0 * GRADIENT_STEP);
+    }
+    gradient
+}
+
+/// Maps weights onto `[0, 1]` per their bounds so one learning rate fits
+/// parameters whose natural scales differ by four orders of magnitude.
+fn normalize(weights: &FsrsWeights) -> FsrsWeights {
+    std::array::from_fn(|i| {
+        let (lo, hi) = WEIGHT_BOUNDS[i];
+        ((weights[i] - lo) / (hi - lo)).clamp(0.0, 1.0)
+    })
+}
+
+fn denormalize(normalized: &FsrsWeights) -> FsrsWeights {
+    std::array::from_fn(|i| {
+        let (lo, hi) = WEIGHT_BOUNDS[i];
+        lo + normalized[i] * (hi - lo)
+    })
+}
+
+fn elapsed_days(previous: &ReviewLogEntry, current: &ReviewLogEntry) -> f64 {
+    current
+        .reviewed_at()
+        .signed_duration_since(previous.reviewed_at())
+        .num_days() as f64
+}
+
+/// Replays all logs and returns the summed cross-entropy and the number of
+/// graded reviews it was summed over.
+fn replay(weights: &FsrsWeights, logs: &[&[ReviewLogEntry]]) -> (f64, usize) {
+    let parameters = Parameters {
+        w: *weights,
+        ..Default::default()
+    };
+
+    let mut total = 0.0;
+    let mut count = 0;
+    for log in logs {
+        let Some((first, rest)) = log.split_first() else {
+            continue;
+        };
+        let first_rating = to_fsrs_rating(first.rating());
+        let mut stability = parameters.init_stability(first_rating);
+        let mut difficulty = parameters.init_difficulty(first_rating);
+        let mut previous = first;
+
+        for entry in rest {
+            let rating = to_fsrs_rating(entry.rating());
+            let elapsed = elapsed_days(previous, entry);
+
+            if elapsed < 1.0 {
+                stability = parameters.short_term_stability(stability, rating);
+            } else {
+                let retrievability =
+                    Parameters::forgetting_curve(elapsed, stability).clamp(0.0001, 0.9999);
+                let recalled = entry.rating() != Rating::Again;
+                total -= if recalled {
+                    retrievability.ln()
+                } else {
+                    (1.0 - retrievability).ln()
+                };
+                count += 1;
+
+                stability = if rating == FsrsRating::Again {
+                    parameters.next_forget_stability(difficulty, stability, retrievability)
+                } else {
+                    parameters.next_recall_stability(difficulty, stability, retrievability, rating)
+                };
+            }
+            difficulty = parameters.next_difficulty(difficulty, rating);
+            stability = stability.clamp(MIN_STABILITY, MAX_STABILITY);
+            previous = entry;
+        }
+    }
+    (total, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration};
+
+    fn optimize_weights(logs: &[&[ReviewLogEntry]]) -> Option<WeightsFit> {
+        let mut optimizer = WeightsOptimizer::new(logs.iter().map(|log| log.to_vec()).collect())?;
+        optimizer.advance(ITERATIONS);
+        optimizer.finish()
+    }
+
+    /// Simulates a learner whose memory follows `weights`: each card is
+    /// reviewed whenever its true recall probability drops to 85%, and the
+    /// outcome is drawn from that probability with a fixed-seed LCG.
+    fn simulate_logs(
+        weights: &FsrsWeights,
+        cards: usize,
+        reviews: usize,
+    ) -> Vec<Vec<ReviewLogEntry>> {
+        let parameters = Parameters {
+            w: *weights,
+            request_retention: 0.85,
+            maximum_interval: 365,
+            enable_fuzz: false,
+            ..Default::default()
+        };
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut seed: u64 = 42;
+        let mut next_random = move || {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        (0..cards)
+            .map(|card| {
+                let mut at = start + Duration::hours(card as i64);
+                let mut log = vec![ReviewLogEntry::new(
+                    at,
+                    Rating::Good,
+                    RateMode::StandardLesson,
+                )];
+                let mut stability = parameters.init_stability(FsrsRating::Good);
+                let mut difficulty = parameters.init_difficulty(FsrsRating::Good);
+                for _ in 1..reviews {
+                    let interval = parameters.next_interval(stability, 0) as i64;
+                    at += Duration::days(interval);
+                    let retrievability = Parameters::forgetting_curve(interval as f64, stability);
+                    let rating = if next_random() < retrievability {
+                        Rating::Good
+                    } else {
+                        Rating::Again
+                    };
+                    let fsrs_rating = to_fsrs_rating(rating);
+                    stability = if rating == Rating::Again {
+                        parameters.next_forget_stability(difficulty, stability, retrievability)
+                    } else {
+                        parameters.next_recall_stability(
+                            difficulty,
+                            stability,
+                            retrievability,
+                            fsrs_rating,
+                        )
+                    };
+                    difficulty = parameters.next_difficulty(difficulty, fsrs_rating);
+                    log.push(ReviewLogEntry::new(at, rating, RateMode::StandardLesson));
+                }
+                log
+            })
+            .collect()
+    }
+
+    #[test]
+    fn optimize_returns_none_below_minimum_reviews() {
+        let logs = simulate_logs(&default_weights(), 5, 5);
+
+        assert!(graded_review_count(&as_slices(&logs)) < MIN_REVIEWS_FOR_OPTIMIZATION);
+        assert!(optimize_weights(&as_slices(&logs)).is_none());
+    }
+
+    #[test]
+    fn same_day_reviews_carry_no_recall_signal() {
+        let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let log = [
+            ReviewLogEntry::new(at, Rating::Again, RateMode::ShortTerm),
+            ReviewLogEntry::new(at + Duration::minutes(5), Rating::Good, RateMode::ShortTerm),
+        ];
+
+        assert_eq!(graded_review_count(&[&log]), 0);
+        assert_eq!(log_loss(&default_weights(), &[&log]), None);
+    }
+
+    #[test]
+    fn optimize_fits_a_learner_who_forgets_faster_than_default() {
+        let mut forgetful = default_weights();
+        forgetful[2] = 0.8;
+        forgetful[8] = 0.6;
+        forgetful[10] = 0.5;
+        let logs = simulate_logs(&forgetful, 60, 8);
+
+        let fit = optimize_weights(&as_slices(&logs)).unwrap();
+
+        assert_eq!(fit.review_count, 60 * 7);
+        assert!(
+            fit.fitted_loss < fit.default_loss,
+            "fitted loss {} should beat default loss {}",
+            fit.fitted_loss,
+            fit.default_loss
+        );
+        assert!(fit.weights[2] < default_weights()[2]);
+        for (weight, (lo, hi)) in fit.weights.iter().zip(WEIGHT_BOUNDS) {
+            assert!((lo..=hi).contains(weight));
+        }
+    }
+
+    #[test]
+    fn chunked_fit_matches_the_one_shot_fit() {
+        let logs = simulate_logs(&default_weights(), 40, 6);
+        let mut optimizer = WeightsOptimizer::new(logs.clone()).unwrap();
+
+        let mut chunks = 0;
+        while !optimizer.is_done() {
+            optimizer.advance(7);
+            chunks += 1;
+        }
+
+        assert_eq!(chunks, ITERATIONS.div_ceil(7));
+        assert_eq!(optimizer.finish(), optimize_weights(&as_slices(&logs)));
+    }
+}
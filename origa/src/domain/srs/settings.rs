@@ -0,0 +1,524 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::exam::ExamTarget;
+use super::optimizer::FsrsWeights;
+use super::{RateMode, SCHEDULING_FAMILIES, SrsConfig};
+use crate::domain::OrigaError;
+
+/// FSRS is calibrated for retention targets in this range; below it reviews
+/// mostly fail, above it the workload grows without bound.
+pub const MIN_REQUEST_RETENTION: f64 = 0.70;
+pub const MAX_REQUEST_RETENTION: f64 = 0.97;
+/// Upper bound for `maximum_interval`, the same 100 years `rs-fsrs` allows.
+pub const MAX_INTERVAL_DAYS: i32 = 36_500;
+
+/// Desired retention and interval ceiling for one long-term review mode.
+/// Deserialization goes through [`ModeSchedule::new`], so a stored value is
+/// held to the same ranges as one set from the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ModeScheduleRaw")]
+pub struct ModeSchedule {
+    request_retention: f64,
+    maximum_interval: i32,
+}
+
+#[derive(Deserialize)]
+struct ModeScheduleRaw {
+    request_retention: f64,
+    maximum_interval: i32,
+}
+
+impl TryFrom<ModeScheduleRaw> for ModeSchedule {
+    type Error = OrigaError;
+
+    fn try_from(raw: ModeScheduleRaw) -> Result<Self, Self::Error> {
+        Self::new(raw.request_retention, raw.maximum_interval)
+    }
+}
+
+impl ModeSchedule {
+    pub fn new(request_retention: f64, maximum_interval: i32) -> Result<Self, OrigaError> {
+        if !(MIN_REQUEST_RETENTION..=MAX_REQUEST_RETENTION).contains(&request_retention) {
+            return Err(OrigaError::InvalidSrsSettings {
+                reason: format!(
+                    "request retention {request_retention} must be within \
+                     {MIN_REQUEST_RETENTION}..={MAX_REQUEST_RETENTION}"
+                ),
+            });
+        }
+        if !(1..=MAX_INTERVAL_DAYS).contains(&maximum_interval) {
+            return Err(OrigaError::InvalidSrsSettings {
+                reason: format!(
+                    "maximum interval {maximum_interval} must be within 1..={MAX_INTERVAL_DAYS} days"
+                ),
+            });
+        }
+        Ok(Self {
+            request_retention,
+            maximum_interval,
+        })
+    }
+
+    /// The built-in schedule of `mode`, i.e. what every user gets until they
+    /// change it.
+    pub fn default_for(mode: RateMode) -> Self {
+        let config = SrsConfig::for_mode(mode);
+        Self {
+            request_retention: config.request_retention,
+            maximum_interval: config.maximum_interval,
+        }
+    }
+
+    pub fn request_retention(&self) -> f64 {
+        self.request_retention
+    }
+
+    pub fn maximum_interval(&self) -> i32 {
+        self.maximum_interval
+    }
+}
+
+/// FSRS weights fitted from the user's own review log for one scheduling
+/// family (see `scheduling_family`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptimizedWeights {
+    weights: FsrsWeights,
+    review_count: usize,
+    log_loss: f64,
+    optimized_at: DateTime<Utc>,
+}
+
+impl OptimizedWeights {
+    pub(crate) fn new(
+        weights: FsrsWeights,
+        review_count: usize,
+        log_loss: f64,
+        optimized_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            weights,
+            review_count,
+            log_loss,
+            optimized_at,
+        }
+    }
+
+    pub fn weights(&self) -> &FsrsWeights {
+        &self.weights
+    }
+
+    /// Number of graded reviews (elapsed ≥ 1 day) the weights were fitted on.
+    pub fn review_count(&self) -> usize {
+        self.review_count
+    }
+
+    /// Mean binary cross-entropy of the fitted weights on their training log.
+    pub fn log_loss(&self) -> f64 {
+        self.log_loss
+    }
+
+    pub fn optimized_at(&self) -> DateTime<Utc> {
+        self.optimized_at
+    }
+}
+
+/// Per-user SRS personalization stored on [`crate::domain::User`].
+///
+/// Everything defaults to "off": a user who never touches these settings is
+/// scheduled exactly like before, by `SrsConfig::for_mode` and the stock
+/// FSRS weights. Only deviations from the defaults are stored, so tuning a
+/// built-in default later still reaches users who never customized it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SrsSettings {
+    /// Opt-in switch for the per-card review log (ADR-034 dropped the
+    /// unconditional log for payload reasons).
+    #[serde(default)]
+    review_log_enabled: bool,
+    #[serde(default)]
+    review_log_changed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    optimized_weights: HashMap<RateMode, OptimizedWeights>,
+    #[serde(default)]
+    mode_schedules: HashMap<RateMode, ModeSchedule>,
+    #[serde(default)]
+    mode_schedules_changed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    exam_target: Option<ExamTarget>,
+    #[serde(default)]
+    exam_target_changed_at: Option<DateTime<Utc>>,
+}
+
+impl SrsSettings {
+    pub fn review_log_enabled(&self) -> bool {
+        self.review_log_enabled
+    }
+
+    pub(crate) fn set_review_log_enabled(&mut self, enabled: bool) {
+        self.review_log_enabled = enabled;
+        self.review_log_changed_at = Some(Utc::now());
+    }
+
+    pub fn optimized_weights(&self, family: RateMode) -> Option<&OptimizedWeights> {
+        self.optimized_weights.get(&family)
+    }
+
+    pub(crate) fn set_optimized_weights(&mut self, family: RateMode, weights: OptimizedWeights) {
+        self.optimized_weights.insert(family, weights);
+    }
+
+    pub(crate) fn clear_optimized_weights(&mut self) {
+        self.optimized_weights.clear();
+    }
+
+    /// Modes whose retention and interval ceiling the user may change.
+    /// `ShortTerm` and `OnboardingScoring` are fixed: their parameters are
+    /// part of the lesson and onboarding mechanics, not a study preference.
+    pub fn configurable_modes() -> &'static [RateMode] {
+        &SCHEDULING_FAMILIES
+    }
+
+    /// Effective schedule of `mode`: the user's choice or the built-in one.
+    pub fn mode_schedule(&self, mode: RateMode) -> ModeSchedule {
+        self.mode_schedules
+            .get(&mode)
+            .copied()
+            .unwrap_or_else(|| ModeSchedule::default_for(mode))
+    }
+
+    pub fn is_mode_schedule_customized(&self, mode: RateMode) -> bool {
+        self.mode_schedules.contains_key(&mode)
+    }
+
+    pub(crate) fn set_mode_schedule(
+        &mut self,
+        mode: RateMode,
+        schedule: ModeSchedule,
+    ) -> Result<(), OrigaError> {
+        if !Self::configurable_modes().contains(&mode) {
+            return Err(OrigaError::InvalidSrsSettings {
+                reason: format!("{mode:?} schedule is not user-configurable"),
+            });
+        }
+        if schedule == ModeSchedule::default_for(mode) {
+            self.mode_schedules.remove(&mode);
+        } else {
+            self.mode_schedules.insert(mode, schedule);
+        }
+        self.mode_schedules_changed_at = Some(Utc::now());
+        Ok(())
+    }
+
+    pub(crate) fn reset_mode_schedules(&mut self) {
+        self.mode_schedules.clear();
+        self.mode_schedules_changed_at = Some(Utc::now());
+    }
+
+    /// The exam the user is preparing for, as long as it is still ahead. A
+    /// target whose day has come counts as absent, so a lapsed exam stops
+    /// steering reviews and intake without anyone clearing it.
+    pub fn exam_target(&self) -> Option<&ExamTarget> {
+        let today = Utc::now().date_naive();
+        self.exam_target
+            .as_ref()
+            .filter(|target| target.is_upcoming(today))
+    }
+
+    /// Sets or clears (`None`) the exam the user is preparing for.
+    pub(crate) fn set_exam_target(&mut self, target: Option<ExamTarget>) {
+        self.exam_target = target;
+        self.exam_target_changed_at = Some(Utc::now());
+    }
+
+    /// Scheduling adjustments for a review in `mode` of a card whose
+    /// long-term family is `family`.
+    ///
+    /// The exam deadline applies to every card, not only target-level ones:
+    /// rating has no access to `JlptContent`, and an extra pre-exam review of
+    /// above-level material costs little compared to missing a tested word.
+    pub(crate) fn overrides_for(&self, family: RateMode, mode: RateMode) -> SrsOverrides<'_> {
+        SrsOverrides {
+            weights: self.optimized_weights(family).map(|w| w.weights()),
+            schedule: self.mode_schedules.get(&mode).copied(),
+            due_before: self
+                .exam_target()
+                .and_then(|target| target.review_deadline(Utc::now())),
+        }
+    }
+
+    /// Cross-device merge. The review-log switch, the mode schedules and the
+    /// exam target are each last-writer-wins by their change timestamp
+    /// (schedules as a whole, so a reset on one device is not undone by stale
+    /// entries of another);
+    /// weights are kept per family from whichever device optimized more
+    /// recently.
+    pub(crate) fn merge(&mut self, other: &SrsSettings) {
+        if is_newer(self.review_log_changed_at, other.review_log_changed_at) {
+            self.review_log_enabled = other.review_log_enabled;
+            self.review_log_changed_at = other.review_log_changed_at;
+        }
+
+        if is_newer(
+            self.mode_schedules_changed_at,
+            other.mode_schedules_changed_at,
+        ) {
+            self.mode_schedules = other.mode_schedules.clone();
+            self.mode_schedules_changed_at = other.mode_schedules_changed_at;
+        }
+
+        if is_newer(self.exam_target_changed_at, other.exam_target_changed_at) {
+            self.exam_target = other.exam_target;
+            self.exam_target_changed_at = other.exam_target_changed_at;
+        }
+
+        for (family, theirs) in &other.optimized_weights {
+            let take_theirs = match self.optimized_weights.get(family) {
+                Some(ours) => theirs.optimized_at > ours.optimized_at,
+                None => true,
+            };
+            if take_theirs {
+                self.optimized_weights.insert(*family, theirs.clone());
+            }
+        }
+    }
+}
+
+fn is_newer(ours: Option<DateTime<Utc>>, theirs: Option<DateTime<Utc>>) -> bool {
+    match (ours, theirs) {
+        (Some(ours), Some(theirs)) => theirs > ours,
+        (None, Some(_)) => true,
+        _ => false,
+    }
+}
+
+/// Per-user adjustments layered on top of `SrsConfig::for_mode` for a single
+/// `rate_memory` call. The default value schedules with the stock engine.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SrsOverrides<'a> {
+    pub(crate) weights: Option<&'a FsrsWeights>,
+    pub(crate) schedule: Option<ModeSchedule>,
+    /// Latest allowed due date (exam mode).
+    pub(crate) due_before: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl SrsOverrides<'_> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.weights.is_none() && self.schedule.is_none() && self.due_before.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use rstest::rstest;
+
+    fn weights_at(first: f64, optimized_at: DateTime<Utc>) -> OptimizedWeights {
+        let mut weights = rs_fsrs::Parameters::default().w;
+        weights[0] = first;
+        OptimizedWeights::new(weights, 500, 0.3, optimized_at)
+    }
+
+    #[test]
+    fn default_settings_produce_no_overrides() {
+        let settings = SrsSettings::default();
+
+        assert!(!settings.review_log_enabled());
+        assert!(
+            settings
+                .overrides_for(RateMode::StandardLesson, RateMode::StandardLesson)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn overrides_pick_weights_of_the_requested_family_only() {
+        let mut settings = SrsSettings::default();
+        settings.set_optimized_weights(RateMode::KanjiReview, weights_at(1.5, Utc::now()));
+
+        let kanji = settings.overrides_for(RateMode::KanjiReview, RateMode::KanjiReview);
+        let vocab = settings.overrides_for(RateMode::StandardLesson, RateMode::StandardLesson);
+        assert!(kanji.weights.is_some());
+        assert!(vocab.is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_most_recently_optimized_weights() {
+        let now = Utc::now();
+        let mut local = SrsSettings::default();
+        local.set_optimized_weights(RateMode::StandardLesson, weights_at(1.0, now));
+        let mut remote = SrsSettings::default();
+        remote.set_optimized_weights(
+            RateMode::StandardLesson,
+            weights_at(2.0, now - Duration::days(3)),
+        );
+        remote.set_optimized_weights(RateMode::GrammarReview, weights_at(3.0, now));
+
+        local.merge(&remote);
+
+        let standard = local.optimized_weights(RateMode::StandardLesson).unwrap();
+        assert_eq!(standard.weights()[0], 1.0);
+        assert!(local.optimized_weights(RateMode::GrammarReview).is_some());
+    }
+
+    #[test]
+    fn merge_review_log_switch_is_last_writer_wins() {
+        let mut local = SrsSettings::default();
+        local.set_review_log_enabled(true);
+        let mut remote = SrsSettings::default();
+        remote.set_review_log_enabled(false);
+        remote.review_log_changed_at = Some(Utc::now() + Duration::seconds(5));
+
+        local.merge(&remote);
+
+        assert!(!local.review_log_enabled());
+    }
+
+    #[rstest]
+    #[case(RateMode::StandardLesson, 0.85, 180)]
+    #[case(RateMode::PhraseReview, 0.70, 365)]
+    #[case(RateMode::GrammarReview, 0.90, 60)]
+    #[case(RateMode::KanjiReview, 0.85, 90)]
+    fn mode_schedule_defaults_to_built_in_values(
+        #[case] mode: RateMode,
+        #[case] retention: f64,
+        #[case] max_interval: i32,
+    ) {
+        let schedule = SrsSettings::default().mode_schedule(mode);
+
+        assert_eq!(schedule.request_retention(), retention);
+        assert_eq!(schedule.maximum_interval(), max_interval);
+    }
+
+    #[rstest]
+    #[case(0.5, 180)]
+    #[case(0.99, 180)]
+    #[case(0.9, 0)]
+    #[case(0.9, MAX_INTERVAL_DAYS + 1)]
+    fn mode_schedule_rejects_out_of_range_values(#[case] retention: f64, #[case] interval: i32) {
+        let result = ModeSchedule::new(retention, interval);
+
+        assert!(matches!(result, Err(OrigaError::InvalidSrsSettings { .. })));
+    }
+
+    #[rstest]
+    #[case(r#"{"request_retention":0.5,"maximum_interval":30}"#)]
+    #[case(r#"{"request_retention":0.9,"maximum_interval":0}"#)]
+    fn mode_schedule_deserialize_rejects_out_of_range_values(#[case] json: &str) {
+        let result = serde_json::from_str::<ModeSchedule>(json);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mode_schedule_serde_roundtrip_preserves_valid_values() {
+        let schedule = ModeSchedule::new(0.9, 45).unwrap();
+
+        let json = serde_json::to_string(&schedule).unwrap();
+        let restored: ModeSchedule = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, schedule);
+    }
+
+    #[test]
+    fn set_mode_schedule_rejects_fixed_modes() {
+        let mut settings = SrsSettings::default();
+        let schedule = ModeSchedule::new(0.9, 10).unwrap();
+
+        let result = settings.set_mode_schedule(RateMode::ShortTerm, schedule);
+
+        assert!(matches!(result, Err(OrigaError::InvalidSrsSettings { .. })));
+    }
+
+    #[test]
+    fn setting_the_default_schedule_stores_nothing() {
+        let mut settings = SrsSettings::default();
+        settings
+            .set_mode_schedule(
+                RateMode::GrammarReview,
+                ModeSchedule::new(0.95, 30).unwrap(),
+            )
+            .unwrap();
+        assert!(settings.is_mode_schedule_customized(RateMode::GrammarReview));
+
+        settings
+            .set_mode_schedule(
+                RateMode::GrammarReview,
+                ModeSchedule::default_for(RateMode::GrammarReview),
+            )
+            .unwrap();
+
+        assert!(!settings.is_mode_schedule_customized(RateMode::GrammarReview));
+        assert!(
+            settings
+                .overrides_for(RateMode::GrammarReview, RateMode::GrammarReview)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn merge_mode_schedules_is_last_writer_wins_as_a_whole() {
+        let mut local = SrsSettings::default();
+        local
+            .set_mode_schedule(RateMode::PhraseReview, ModeSchedule::new(0.8, 200).unwrap())
+            .unwrap();
+        let mut remote = SrsSettings::default();
+        remote.reset_mode_schedules();
+        remote.mode_schedules_changed_at = Some(Utc::now() + Duration::seconds(5));
+
+        local.merge(&remote);
+
+        assert!(!local.is_mode_schedule_customized(RateMode::PhraseReview));
+        assert_eq!(
+            local.mode_schedule(RateMode::PhraseReview),
+            ModeSchedule::default_for(RateMode::PhraseReview)
+        );
+    }
+
+    #[test]
+    fn exam_target_adds_review_deadline_to_overrides() {
+        let mut settings = SrsSettings::default();
+        let exam_date = Utc::now().date_naive() + Duration::days(20);
+        settings.set_exam_target(Some(
+            ExamTarget::new(crate::domain::JapaneseLevel::N4, exam_date).unwrap(),
+        ));
+
+        let overrides = settings.overrides_for(RateMode::KanjiReview, RateMode::KanjiReview);
+
+        let deadline = overrides.due_before.unwrap();
+        assert!(deadline.date_naive() < exam_date);
+    }
+
+    #[test]
+    fn exam_target_dated_in_the_past_counts_as_absent() {
+        let exam_date = Utc::now().date_naive() - Duration::days(3);
+        let settings: SrsSettings = serde_json::from_value(serde_json::json!({
+            "exam_target": { "level": "N4", "exam_date": exam_date },
+        }))
+        .unwrap();
+
+        let overrides = settings.overrides_for(RateMode::KanjiReview, RateMode::KanjiReview);
+
+        assert!(settings.exam_target().is_none());
+        assert!(overrides.due_before.is_none());
+    }
+
+    #[test]
+    fn merge_propagates_cleared_exam_target() {
+        let exam_date = Utc::now().date_naive() + Duration::days(20);
+        let mut local = SrsSettings::default();
+        local.set_exam_target(Some(
+            ExamTarget::new(crate::domain::JapaneseLevel::N2, exam_date).unwrap(),
+        ));
+        let mut remote = SrsSettings::default();
+        remote.set_exam_target(None);
+        remote.exam_target_changed_at = Some(Utc::now() + Duration::seconds(5));
+
+        local.merge(&remote);
+
+        assert!(local.exam_target().is_none());
+    }
+}
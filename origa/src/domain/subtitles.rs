@@ -0,0 +1,392 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::OrigaError;
+use crate::domain::book::decode_japanese_text;
+use tracing::warn;
+
+const ASS_DEFAULT_FORMAT: [&str; 10] = [
+    "layer", "start", "end", "style", "name", "marginl", "marginr", "marginv", "effect", "text",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubtitleFormat {
+    Srt,
+    Ass,
+    WebVtt,
+}
+
+impl SubtitleFormat {
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = file_name.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "srt" => Some(Self::Srt),
+            "ass" | "ssa" => Some(Self::Ass),
+            "vtt" => Some(Self::WebVtt),
+            _ => None,
+        }
+    }
+
+    /// Sniffs the format from the file header, falling back to SRT.
+    pub fn detect(content: &str) -> Self {
+        let content = content.trim_start_matches('\u{feff}').trim_start();
+        if content.starts_with("WEBVTT") {
+            Self::WebVtt
+        } else if content.starts_with("[Script Info]") || content.contains("\nDialogue:") {
+            Self::Ass
+        } else {
+            Self::Srt
+        }
+    }
+}
+
+/// One subtitle cue with markup stripped; multi-line cues keep their line breaks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubtitleLine {
+    start_ms: u64,
+    end_ms: u64,
+    text: String,
+}
+
+impl SubtitleLine {
+    pub fn new(start_ms: u64, end_ms: u64, text: String) -> Self {
+        Self {
+            start_ms,
+            end_ms,
+            text,
+        }
+    }
+
+    pub fn start_ms(&self) -> u64 {
+        self.start_ms
+    }
+
+    pub fn end_ms(&self) -> u64 {
+        self.end_ms
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Cue start as `H:MM:SS`, the way players display it.
+    pub fn timestamp(&self) -> String {
+        let total_seconds = self.start_ms / 1000;
+        format!(
+            "{}:{:02}:{:02}",
+            total_seconds / 3600,
+            total_seconds / 60 % 60,
+            total_seconds % 60
+        )
+    }
+}
+
+/// Parses subtitle cues ordered by start time. Cues whose text is empty after
+/// markup removal (signs, karaoke drawings) are dropped, and so are malformed
+/// cues as long as at least one cue parses.
+pub fn parse_subtitles(
+    content: &str,
+    format: SubtitleFormat,
+) -> Result<Vec<SubtitleLine>, OrigaError> {
+    let content = content
+        .trim_start_matches('\u{feff}')
+        .replace("\r\n", "\n")
+        .replace('\r', "\n");
+
+    let mut lines = match format {
+        SubtitleFormat::Srt | SubtitleFormat::WebVtt => parse_cue_blocks(&content)?,
+        SubtitleFormat::Ass => parse_ass(&content)?,
+    };
+    lines.sort_by_key(|line| line.start_ms);
+    Ok(lines)
+}
+
+/// Parses an uploaded subtitle file: the text may be UTF-8, UTF-16 or
+/// Shift_JIS, and the format comes from the extension or, failing that,
+/// from the content.
+pub fn parse_subtitle_file(file_name: &str, data: &[u8]) -> Result<Vec<SubtitleLine>, OrigaError> {
+    let content = decode_japanese_text(data).ok_or_else(|| OrigaError::SubtitleParseError {
+        reason: format!("{file_name} is not UTF-8, Shift_JIS or EUC-JP text"),
+    })?;
+    let format = SubtitleFormat::from_file_name(file_name)
+        .unwrap_or_else(|| SubtitleFormat::detect(&content));
+    parse_subtitles(&content, format)
+}
+
+/// SRT and WebVTT share the same shape: blank-line separated blocks with a
+/// `start --> end` line followed by the cue text. Blocks without a timing
+/// line (counters, `WEBVTT` header, `NOTE`, `STYLE`) carry no dialogue.
+fn parse_cue_blocks(content: &str) -> Result<Vec<SubtitleLine>, OrigaError> {
+    collect_cues(content.split("\n\n").map(parse_cue_block))
+}
+
+fn parse_cue_block(block: &str) -> Result<Option<SubtitleLine>, OrigaError> {
+    let mut rows = block.lines().skip_while(|row| !row.contains("-->"));
+    let Some(timing) = rows.next() else {
+        return Ok(None);
+    };
+    let (start, end) = timing.split_once("-->").unwrap_or_default();
+    let start_ms = parse_timestamp(start.trim())?;
+    let end_ms = parse_timestamp(end.split_whitespace().next().unwrap_or_default())?;
+
+    let text = rows
+        .map(strip_markup)
+        .filter(|row| !row.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok((!text.is_empty()).then(|| SubtitleLine::new(start_ms, end_ms, text)))
+}
+
+fn parse_ass(content: &str) -> Result<Vec<SubtitleLine>, OrigaError> {
+    let mut cues = Vec::new();
+    let mut in_events = false;
+    let mut format: Vec<String> = ASS_DEFAULT_FORMAT.iter().map(|f| f.to_string()).collect();
+
+    for row in content.lines() {
+        let row = row.trim();
+        if row.starts_with('[') {
+            in_events = row.eq_ignore_ascii_case("[Events]");
+            continue;
+        }
+        if !in_events {
+            continue;
+        }
+
+        if let Some(fields) = row.strip_prefix("Format:") {
+            format = fields
+                .split(',')
+                .map(|f| f.trim().to_ascii_lowercase())
+                .collect();
+            continue;
+        }
+        if let Some(values) = row.strip_prefix("Dialogue:") {
+            cues.push(parse_dialogue(row, values, &format));
+        }
+    }
+
+    collect_cues(cues)
+}
+
+fn parse_dialogue(
+    row: &str,
+    values: &str,
+    format: &[String],
+) -> Result<Option<SubtitleLine>, OrigaError> {
+    // Text is always the last field and may itself contain commas.
+    let values: Vec<&str> = values.splitn(format.len(), ',').collect();
+    let field = |name: &str| -> Result<&str, OrigaError> {
+        format
+            .iter()
+            .position(|f| f == name)
+            .and_then(|i| values.get(i))
+            .map(|v| v.trim())
+            .ok_or_else(|| OrigaError::SubtitleParseError {
+                reason: format!("Dialogue line is missing field '{name}': {row}"),
+            })
+    };
+
+    let start_ms = parse_timestamp(field("start")?)?;
+    let end_ms = parse_timestamp(field("end")?)?;
+    let raw_text = field("text")?;
+    if is_drawing(raw_text) {
+        return Ok(None);
+    }
+    let text = raw_text
+        .replace("\\N", "\n")
+        .replace("\\n", "\n")
+        .replace("\\h", " ");
+    let text = text
+        .lines()
+        .map(strip_markup)
+        .filter(|row| !row.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok((!text.is_empty()).then(|| SubtitleLine::new(start_ms, end_ms, text)))
+}
+
+/// A malformed cue is skipped so one broken timestamp does not lose the
+/// whole episode; the file is rejected only when no cue could be read.
+fn collect_cues(
+    cues: impl IntoIterator<Item = Result<Option<SubtitleLine>, OrigaError>>,
+) -> Result<Vec<SubtitleLine>, OrigaError> {
+    let mut lines = Vec::new();
+    let mut first_error = None;
+    for cue in cues {
+        match cue {
+            Ok(Some(line)) => lines.push(line),
+            Ok(None) => {},
+            Err(e) => {
+                warn!(error = %e, "Skipping malformed subtitle cue");
+                first_error.get_or_insert(e);
+            },
+        }
+    }
+    match first_error {
+        Some(e) if lines.is_empty() => Err(e),
+        _ => Ok(lines),
+    }
+}
+
+/// `{\p1}` switches ASS into vector drawing mode: the "text" is path data.
+fn is_drawing(text: &str) -> bool {
+    text.match_indices("\\p")
+        .any(|(i, _)| matches!(text[i + 2..].chars().next(), Some('1'..='9')))
+}
+
+/// Accepts `HH:MM:SS,mmm` (SRT), `[HH:]MM:SS.mmm` (WebVTT) and
+/// `H:MM:SS.cc` (ASS centiseconds).
+fn parse_timestamp(value: &str) -> Result<u64, OrigaError> {
+    let invalid = || OrigaError::SubtitleParseError {
+        reason: format!("Invalid timestamp '{value}'"),
+    };
+
+    let (clock, fraction) = value
+        .replace(',', ".")
+        .split_once('.')
+        .map(|(c, f)| (c.to_string(), f.to_string()))
+        .unwrap_or_else(|| (value.to_string(), String::new()));
+
+    let parts = clock
+        .split(':')
+        .map(|p| p.parse::<u64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let seconds = match parts.as_slice() {
+        [m, s] => m * 60 + s,
+        [h, m, s] => h * 3600 + m * 60 + s,
+        _ => return Err(invalid()),
+    };
+
+    let millis = if fraction.is_empty() {
+        0
+    } else {
+        if !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let padded = format!("{fraction:0<3}");
+        padded[..3].parse::<u64>().map_err(|_| invalid())?
+    };
+
+    Ok(seconds * 1000 + millis)
+}
+
+/// Removes HTML-like tags (`<i>`, WebVTT `<c.x>` and inline timestamps), ASS
+/// override blocks (`{\an8}`) and the common HTML entities.
+fn strip_markup(row: &str) -> String {
+    let mut result = String::with_capacity(row.len());
+    let mut closing: Option<char> = None;
+
+    for c in row.chars() {
+        match closing {
+            Some(end) if c == end => closing = None,
+            Some(_) => {},
+            None if c == '<' => closing = Some('>'),
+            None if c == '{' => closing = Some('}'),
+            None => result.push(c),
+        }
+    }
+
+    result
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_srt_cues_and_strips_tags() {
+        let content = "1\r\n00:00:01,500 --> 00:00:03,000\r\n<i>猫が好きだ</i>\r\n\r\n2\r\n00:01:02,000 --> 00:01:04,250\r\n{\\an8}明日\r\n行く\r\n";
+
+        let lines = parse_subtitles(content, SubtitleFormat::Srt).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].start_ms(), 1_500);
+        assert_eq!(lines[0].end_ms(), 3_000);
+        assert_eq!(lines[0].text(), "猫が好きだ");
+        assert_eq!(lines[1].start_ms(), 62_000);
+        assert_eq!(lines[1].text(), "明日\n行く");
+        assert_eq!(lines[1].timestamp(), "0:01:02");
+    }
+
+    #[test]
+    fn parses_webvtt_skipping_header_and_notes() {
+        let content = "WEBVTT\n\nNOTE translated by fans\n\nintro\n00:05.000 --> 00:07.500 align:start\n<c.yellow>こんにちは</c>\n\n01:00:00.000 --> 01:00:01.000\n<00:00:00.500>さようなら\n";
+
+        let lines = parse_subtitles(content, SubtitleFormat::WebVtt).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].start_ms(), 5_000);
+        assert_eq!(lines[0].end_ms(), 7_500);
+        assert_eq!(lines[0].text(), "こんにちは");
+        assert_eq!(lines[1].start_ms(), 3_600_000);
+        assert_eq!(lines[1].text(), "さようなら");
+    }
+
+    #[test]
+    fn parses_ass_dialogue_skipping_comments_and_drawings() {
+        let content = "[Script Info]\nTitle: test\n\n[V4+ Styles]\nFormat: Name, Fontname\nStyle: Default,Arial\n\n[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\nDialogue: 0,0:00:10.50,0:00:12.00,Default,,0,0,0,,{\\i1}ええ、そうです\\N本当に\nComment: 0,0:00:11.00,0:00:12.00,Default,,0,0,0,,ignored\nDialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,{\\p1}m 0 0 l 10 10{\\p0}\n";
+
+        let lines = parse_subtitles(content, SubtitleFormat::Ass).unwrap();
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].start_ms(), 10_500);
+        assert_eq!(lines[0].end_ms(), 12_000);
+        assert_eq!(lines[0].text(), "ええ、そうです\n本当に");
+    }
+
+    #[test]
+    fn malformed_cues_are_skipped() {
+        let srt = "1\n00:00:xx,000 --> 00:00:02,000\n猫\n\n2\n00:00:03,000 --> 00:00:04,000\n犬\n";
+        let ass = "[Events]\nDialogue: 0,0:00:xx.00,0:00:02.00,Default,,0,0,0,,猫\nDialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,犬\n";
+
+        let srt_lines = parse_subtitles(srt, SubtitleFormat::Srt).unwrap();
+        let ass_lines = parse_subtitles(ass, SubtitleFormat::Ass).unwrap();
+
+        assert_eq!(srt_lines.len(), 1);
+        assert_eq!(srt_lines[0].text(), "犬");
+        assert_eq!(ass_lines.len(), 1);
+        assert_eq!(ass_lines[0].text(), "犬");
+    }
+
+    #[test]
+    fn invalid_timestamp_is_reported_when_no_cue_parses() {
+        let content = "1\n00:00:xx,000 --> 00:00:02,000\n猫\n";
+
+        let error = parse_subtitles(content, SubtitleFormat::Srt).unwrap_err();
+
+        assert!(matches!(error, OrigaError::SubtitleParseError { .. }));
+    }
+
+    #[test]
+    fn parses_shift_jis_file_with_detected_format() {
+        let content = "1\r\n00:00:01,000 --> 00:00:02,000\r\n猫が好きだ\r\n";
+        let (data, _, _) = encoding_rs::SHIFT_JIS.encode(content);
+
+        let lines = parse_subtitle_file("episode.txt", &data).unwrap();
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].text(), "猫が好きだ");
+    }
+
+    #[test]
+    fn detects_format_from_name_and_content() {
+        assert_eq!(
+            SubtitleFormat::from_file_name("Episode 01.ASS"),
+            Some(SubtitleFormat::Ass)
+        );
+        assert_eq!(SubtitleFormat::from_file_name("notes.txt"), None);
+        assert_eq!(
+            SubtitleFormat::detect("\u{feff}WEBVTT\n\n"),
+            SubtitleFormat::WebVtt
+        );
+        assert_eq!(
+            SubtitleFormat::detect("1\n00:00:01,000 --> 00:00:02,000\n猫\n"),
+            SubtitleFormat::Srt
+        );
+    }
+}
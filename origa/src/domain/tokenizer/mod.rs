@@ -15,6 +15,7 @@ pub struct TokenInfo {
     orthographic_surface_form: String,
     phonological_surface_form: String,
     part_of_speech: PartOfSpeech,
+    conjugation_type: Option<String>,
 }
 
 impl TokenInfo {
@@ -37,6 +38,12 @@ impl TokenInfo {
     pub fn part_of_speech(&self) -> &PartOfSpeech {
         &self.part_of_speech
     }
+
+    /// SudachiDict conjugation type (活用型) such as `五段-ラ行` or
+    /// `下一段-バ行`; `None` for words that do not inflect.
+    pub fn conjugation_type(&self) -> Option<&str> {
+        self.conjugation_type.as_deref()
+    }
 }
 
 #[cfg(test)]
@@ -52,6 +59,14 @@ impl TokenInfo {
             orthographic_surface_form: base.to_string(),
             phonological_surface_form: reading.to_string(),
             part_of_speech: pos,
+            conjugation_type: None,
+        }
+    }
+
+    pub fn new_test_verb(base: &str, conjugation_type: &str) -> Self {
+        Self {
+            conjugation_type: Some(conjugation_type.to_string()),
+            ..Self::new_test(base, PartOfSpeech::Verb)
         }
     }
 }
@@ -232,12 +247,17 @@ fn token_to_token_info(token: &mut lindera::token::Token) -> TokenInfo {
     let orthographic_surface_form = surface.clone();
 
     let reading_raw = token.get("reading").unwrap_or_default().to_string();
-    let phonological_base_form = if reading_raw != "*" && !reading_raw.is_empty() {
+    let phonological_surface_form = if reading_raw != "*" && !reading_raw.is_empty() {
         reading_raw.clone()
     } else {
         reading_fallback_for_surface(&surface)
     };
-    let phonological_surface_form = phonological_base_form.clone();
+    let phonological_base_form = base_form_reading(
+        &surface,
+        &phonological_surface_form,
+        &orthographic_base_form,
+    )
+    .unwrap_or_else(|| phonological_surface_form.clone());
 
     let pos_sub1 = token
         .get("part_of_speech_subcategory_1")
@@ -250,6 +270,11 @@ fn token_to_token_info(token: &mut lindera::token::Token) -> TokenInfo {
         .parse()
         .unwrap_or(PartOfSpeech::Unspecified);
 
+    let conjugation_type = token
+        .get("conjugation_type")
+        .filter(|value| *value != "*" && !value.is_empty())
+        .map(str::to_string);
+
     if part_of_speech == PartOfSpeech::Noun && pos_sub1 == "固有名詞" {
         part_of_speech = PartOfSpeech::ProperNoun;
     }
@@ -275,6 +300,7 @@ fn token_to_token_info(token: &mut lindera::token::Token) -> TokenInfo {
         orthographic_surface_form,
         phonological_surface_form,
         part_of_speech,
+        conjugation_type,
     }
 }
 
@@ -297,6 +323,73 @@ fn reading_fallback_for_surface(surface: &str) -> String {
     }
 }
 
+/// Derives the katakana reading of the lemma from the inflected surface.
+///
+/// SudachiDict only carries the reading of the surface (食べ → タベ), so the
+/// lemma reading is rebuilt by swapping the kana okurigana that differ between
+/// surface and lemma: 食べ/タベ/食べる → タベル, 書か/カカ/書く → カク,
+/// し/シ/する → スル. Returns `None` when the two spellings differ in
+/// anything other than a kana tail (e.g. a kana surface with a kanji lemma),
+/// A kana surface of a kanji lemma (たべ/食べる) is aligned on the first kana
+/// of the lemma's okurigana instead. Returns `None` when the spellings cannot
+/// be aligned, e.g. when the okurigana is inflected away entirely (かか/書く).
+fn base_form_reading(surface: &str, surface_reading: &str, lemma: &str) -> Option<String> {
+    if surface == lemma {
+        return Some(surface_reading.to_string());
+    }
+    if lemma == "来る" {
+        return Some("クル".to_string());
+    }
+
+    let shared = surface
+        .chars()
+        .zip(lemma.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum::<usize>();
+    let surface_tail = &surface[shared..];
+    let lemma_tail = &lemma[shared..];
+    let is_kana = |tail: &str| tail.chars().all(|c| c.is_hiragana() || c.is_katakana());
+    if !is_kana(surface_tail) {
+        return None;
+    }
+    if !is_kana(lemma_tail) {
+        return if shared == 0 && is_kana(surface) {
+            kana_surface_base_reading(surface_reading, lemma)
+        } else {
+            None
+        };
+    }
+
+    let stem_reading = surface_reading.strip_suffix(&hiragana_to_katakana(surface_tail))?;
+    Some(format!(
+        "{stem_reading}{}",
+        hiragana_to_katakana(lemma_tail)
+    ))
+}
+
+fn kana_surface_base_reading(surface_reading: &str, lemma: &str) -> Option<String> {
+    let okurigana: String = lemma
+        .chars()
+        .rev()
+        .take_while(|c| c.is_hiragana() || c.is_katakana())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let okurigana = hiragana_to_katakana(&okurigana);
+    let first = okurigana.chars().next()?;
+    if okurigana.chars().count() == lemma.chars().count() {
+        return None;
+    }
+
+    let (stem_end, _) = surface_reading
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == first)?;
+    Some(format!("{}{okurigana}", &surface_reading[..stem_end]))
+}
+
 fn non_japanese_token(segment: &str, is_whitespace: bool) -> TokenInfo {
     let pos = if is_whitespace {
         PartOfSpeech::Whitespace
@@ -309,6 +402,7 @@ fn non_japanese_token(segment: &str, is_whitespace: bool) -> TokenInfo {
         orthographic_surface_form: segment.to_string(),
         phonological_surface_form: segment.to_string(),
         part_of_speech: pos,
+        conjugation_type: None,
     }
 }
 
@@ -388,6 +482,7 @@ fn split_at_suffix(
             orthographic_surface_form: prefix.to_string(),
             phonological_surface_form: prefix_reading,
             part_of_speech: PartOfSpeech::Noun,
+            conjugation_type: None,
         },
         TokenInfo {
             orthographic_base_form: suffix.to_string(),
@@ -395,6 +490,7 @@ fn split_at_suffix(
             orthographic_surface_form: suffix.to_string(),
             phonological_surface_form: suffix_reading,
             part_of_speech: suffix_pos,
+            conjugation_type: None,
         },
     ]
 }
@@ -475,9 +571,45 @@ mod tests {
         let tokens = tokenize_text("食べます").unwrap();
         assert_eq!(tokens.len(), 2);
         assert_eq!(tokens[0].orthographic_base_form, "食べる");
-        // SudachiDict exposes a single katakana reading (surface pronunciation);
-        // the separate base-reading field UniDic had does not exist.
-        assert_eq!(tokens[0].phonological_base_form, "タベ");
+        assert_eq!(tokens[0].phonological_base_form, "タベル");
+        assert_eq!(tokens[0].phonological_surface_form, "タベ");
+    }
+
+    #[test]
+    fn base_form_reading_swaps_the_inflected_kana_tail() {
+        assert_eq!(
+            base_form_reading("食べ", "タベ", "食べる").as_deref(),
+            Some("タベル")
+        );
+        assert_eq!(
+            base_form_reading("書か", "カカ", "書く").as_deref(),
+            Some("カク")
+        );
+        assert_eq!(
+            base_form_reading("行っ", "イッ", "行く").as_deref(),
+            Some("イク")
+        );
+        assert_eq!(
+            base_form_reading("し", "シ", "する").as_deref(),
+            Some("スル")
+        );
+        assert_eq!(
+            base_form_reading("来", "キ", "来る").as_deref(),
+            Some("クル")
+        );
+    }
+
+    #[test]
+    fn base_form_reading_aligns_kana_surface_on_the_okurigana() {
+        assert_eq!(
+            base_form_reading("たべ", "タベ", "食べる").as_deref(),
+            Some("タベル")
+        );
+        assert_eq!(
+            base_form_reading("わかっ", "ワカッ", "分かる").as_deref(),
+            Some("ワカル")
+        );
+        assert_eq!(base_form_reading("かか", "カカ", "書く"), None);
     }
 
     #[test]
@@ -504,7 +636,7 @@ mod tests {
         let tokens = tokenize_text("たべます").unwrap();
         assert_eq!(tokens.len(), 2);
         assert_eq!(tokens[0].orthographic_base_form, "食べる");
-        assert_eq!(tokens[0].phonological_base_form, "タベ");
+        assert_eq!(tokens[0].phonological_base_form, "タベル");
     }
 
     #[test]
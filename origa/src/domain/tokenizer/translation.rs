@@ -2,10 +2,13 @@ use serde::Serialize;
 
 use super::{PartOfSpeech, TokenInfo};
 use crate::dictionary::grammar::{GRAMMAR_RULES, GrammarRule};
-use crate::dictionary::vocabulary::get_translation;
+use crate::dictionary::pitch_audio::get_pitch_accent;
+use crate::dictionary::vocabulary::{get_translation, get_translation_for_reading};
 use crate::domain::JapaneseChar;
 use crate::domain::NativeLanguage;
+use crate::domain::PitchAccent;
 use crate::domain::grammar::find_format_map_matches;
+use crate::domain::grammar::{Deconjugation, deconjugate_to};
 use crate::domain::katakana_to_hiragana;
 
 #[derive(Debug, Clone, Serialize)]
@@ -17,6 +20,11 @@ pub struct TokenTranslation {
     pub translation: Option<String>,
     pub grammar_label: Option<String>,
     pub grammar_description: Option<String>,
+    /// Accent of the dictionary form, from the pitch audio index.
+    pub pitch_accent: Option<PitchAccent>,
+    /// How an inflected verb or adjective, together with the auxiliaries
+    /// after it, derives from `base_form`.
+    pub conjugation: Option<Deconjugation>,
 }
 
 /// A grammar rule match carried through the resolution pipeline: the rule
@@ -105,7 +113,11 @@ pub fn lookup_tokens_translations(
                 get_translation(&surface_form, native_language)
                     .or_else(|| get_translation(&base_form, native_language))
             } else {
-                get_translation(&base_form, native_language)
+                get_translation_for_reading(
+                    &base_form,
+                    Some(token.phonological_base_form()).filter(|reading| !reading.is_empty()),
+                    native_language,
+                )
             };
 
             let grammar = resolve_sou_da_match(token, index, tokens, native_language)
@@ -114,6 +126,8 @@ pub fn lookup_tokens_translations(
                 })
                 .or(masu_stem_match);
             let (grammar_label, grammar_description) = split_grammar_fields(grammar);
+            let pitch_accent = get_pitch_accent(&base_form, token.phonological_base_form());
+            let conjugation = resolve_conjugation(token, index, tokens);
 
             TokenTranslation {
                 surface_form,
@@ -123,11 +137,51 @@ pub fn lookup_tokens_translations(
                 translation,
                 grammar_label,
                 grammar_description,
+                pitch_accent,
+                conjugation,
             }
         })
         .collect()
 }
 
+/// Particles that attach to a conjugated form and belong to its inflection
+/// (食べ|て, 食べれ|ば).
+const CONJUNCTIVE_PARTICLES: &[&str] = &["て", "で", "ば"];
+
+/// Deconjugates a verb or i-adjective token together with the auxiliaries
+/// that follow it (食べ|させ|られ|なかっ|た) and keeps the shortest chain
+/// leading back to the token's own lemma.
+fn resolve_conjugation(
+    token: &TokenInfo,
+    index: usize,
+    tokens: &[TokenInfo],
+) -> Option<Deconjugation> {
+    if !matches!(
+        token.part_of_speech(),
+        PartOfSpeech::Verb | PartOfSpeech::IAdjective
+    ) {
+        return None;
+    }
+
+    let mut inflected = token.orthographic_surface_form().to_string();
+    for next in &tokens[index + 1..] {
+        let surface = next.orthographic_surface_form();
+        let attached = next.part_of_speech() == &PartOfSpeech::AuxiliaryVerb
+            || (next.part_of_speech() == &PartOfSpeech::Particle
+                && CONJUNCTIVE_PARTICLES.contains(&surface));
+        if !attached {
+            break;
+        }
+        inflected.push_str(surface);
+    }
+
+    let lemma = token.orthographic_base_form();
+    if inflected == lemma {
+        return None;
+    }
+    deconjugate_to(&inflected, lemma)
+}
+
 fn match_grammar_keyword(
     rules: &'static [GrammarRule],
     surface: &str,
@@ -488,6 +542,7 @@ const HONORIFIC_VERB_BASES: &[&str] = &[
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dictionary::grammar::FormatAction;
 
     fn make_token(base: &str, surface: &str, reading: &str, pos: PartOfSpeech) -> TokenInfo {
         TokenInfo {
@@ -496,6 +551,7 @@ mod tests {
             orthographic_surface_form: surface.to_string(),
             phonological_surface_form: reading.to_string(),
             part_of_speech: pos,
+            conjugation_type: None,
         }
     }
 
@@ -507,6 +563,7 @@ mod tests {
             orthographic_surface_form: "食べ".to_string(),
             phonological_surface_form: "タベ".to_string(),
             part_of_speech: PartOfSpeech::Verb,
+            conjugation_type: None,
         }];
 
         let result = lookup_tokens_translations(&tokens, &NativeLanguage::English, "");
@@ -518,6 +575,39 @@ mod tests {
         assert_eq!(result[0].pos, PartOfSpeech::Verb);
     }
 
+    #[test]
+    fn verb_with_auxiliaries_carries_conjugation_chain() {
+        // Arrange
+        let tokens = vec![
+            make_token("食べる", "食べ", "タベ", PartOfSpeech::Verb),
+            make_token("させる", "させ", "サセ", PartOfSpeech::AuxiliaryVerb),
+            make_token("られる", "られ", "ラレ", PartOfSpeech::AuxiliaryVerb),
+            make_token("ない", "なかっ", "ナカッ", PartOfSpeech::AuxiliaryVerb),
+            make_token("た", "た", "タ", PartOfSpeech::AuxiliaryVerb),
+        ];
+
+        // Act
+        let result = lookup_tokens_translations(&tokens, &NativeLanguage::English, "");
+
+        // Assert
+        let conjugation = result[0].conjugation.as_ref().expect("verb is inflected");
+        let actions: Vec<FormatAction> = conjugation
+            .steps
+            .iter()
+            .map(|step| step.action.clone())
+            .collect();
+        assert_eq!(conjugation.lemma, "食べる");
+        assert_eq!(
+            actions,
+            [
+                FormatAction::VerbToCausativePassive {},
+                FormatAction::VerbToNai {},
+                FormatAction::AdjectiveToKatta {},
+            ]
+        );
+        assert!(result[1].conjugation.is_none());
+    }
+
     #[test]
     fn should_return_none_translation_for_unknown_word() {
         let tokens = vec![make_token("未知語", "未知語", "ミチゴ", PartOfSpeech::Noun)];
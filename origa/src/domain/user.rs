@@ -6,8 +6,11 @@ use ulid::Ulid;
 
 use crate::dictionary::vocabulary::get_translation;
 use crate::domain::{
-    Card, CardType, DailyLoad, JapaneseLevel, JlptContent, JlptProgress, KnowledgeSet,
-    NativeLanguage, OrigaError, RateMode, Rating, ScoreContentResult, StudyCard, score_content,
+    Card, CardType, DailyBudget, DailyLoad, ExamReadiness, ExamTarget, JapaneseLevel, JlptContent,
+    JlptProgress, KnowledgeEvent, KnowledgeJournal, KnowledgeSet, MemoryHistory, ModeSchedule,
+    NativeLanguage, OrigaError, RateMode, Rating, ReviewLogEntry, ScoreContentResult,
+    SrsOptimization, SrsOptimizationReport, SrsSettings, StudyCard, score_content,
+    srs::{OptimizedWeights, SCHEDULING_FAMILIES, scheduling_family},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +61,9 @@ pub struct User {
     /// finishes.
     #[serde(default)]
     onboarding_scoring_skipped: HashSet<Ulid>,
+
+    #[serde(default)]
+    srs_settings: SrsSettings,
 }
 
 impl User {
@@ -79,6 +85,7 @@ impl User {
             daily_load: DailyLoad::default(),
             known_vocab_hash: 0,
             onboarding_scoring_skipped: HashSet::new(),
+            srs_settings: SrsSettings::default(),
         }
     }
 
@@ -95,6 +102,7 @@ impl User {
         imported_sets: HashSet<String>,
         daily_load: DailyLoad,
         known_vocab_hash: u32,
+        srs_settings: SrsSettings,
     ) -> Self {
         Self {
             id,
@@ -109,10 +117,36 @@ impl User {
             daily_load,
             known_vocab_hash,
             onboarding_scoring_skipped: HashSet::new(),
+            srs_settings,
         }
     }
 
     pub fn merge(&mut self, another_user: &User) {
+        self.merge_profile(another_user, |knowledge_set| {
+            knowledge_set.merge(&another_user.knowledge_set)
+        });
+    }
+
+    /// Like [`merge`](Self::merge), but rebuilds the knowledge set from the
+    /// remote checkpoint and the events recorded after it, so concurrent
+    /// reviews on several devices are all counted.
+    pub fn rebase(&mut self, remote: &User, remote_events: &[KnowledgeEvent]) {
+        self.merge_profile(remote, |knowledge_set| {
+            knowledge_set.rebase(&remote.knowledge_set, remote_events)
+        });
+    }
+
+    /// Drops the knowledge events folded into `published`, a checkpoint of
+    /// this user's knowledge set that reached the remote.
+    pub fn fold_knowledge_journal(&mut self, published: &KnowledgeJournal) {
+        self.knowledge_set.fold_journal(published);
+    }
+
+    fn merge_profile(
+        &mut self,
+        another_user: &User,
+        merge_knowledge: impl FnOnce(&mut KnowledgeSet),
+    ) {
         // Remote is the source of truth for identity: a local record must not
         // override the canonical user id, otherwise saves on the same browser
         // get attributed to different ids and break cross-device sync.
@@ -133,7 +167,7 @@ impl User {
         self.telegram_user_id = another_user.telegram_user_id;
         self.daily_load = another_user.daily_load;
 
-        self.knowledge_set.merge(&another_user.knowledge_set);
+        merge_knowledge(&mut self.knowledge_set);
 
         for set_id in &another_user.imported_sets {
             self.imported_sets.insert(set_id.clone());
@@ -143,6 +177,8 @@ impl User {
             self.onboarding_scoring_skipped.insert(*card_id);
         }
 
+        self.srs_settings.merge(&another_user.srs_settings);
+
         self.touch();
     }
 
@@ -204,6 +240,134 @@ impl User {
         self.touch();
     }
 
+    pub fn srs_settings(&self) -> &SrsSettings {
+        &self.srs_settings
+    }
+
+    /// Turns the per-card review log on or off. Disabling keeps already
+    /// recorded entries and fitted weights; only new reviews stop being logged.
+    pub fn set_review_log_enabled(&mut self, enabled: bool) {
+        self.srs_settings.set_review_log_enabled(enabled);
+        self.touch();
+    }
+
+    /// Sets the desired retention and interval ceiling of a long-term review
+    /// mode. Takes effect from the next review of each card.
+    pub fn set_mode_schedule(
+        &mut self,
+        mode: RateMode,
+        schedule: ModeSchedule,
+    ) -> Result<(), OrigaError> {
+        self.srs_settings.set_mode_schedule(mode, schedule)?;
+        self.touch();
+        Ok(())
+    }
+
+    pub fn reset_mode_schedules(&mut self) {
+        self.srs_settings.reset_mode_schedules();
+        self.touch();
+    }
+
+    /// Sets or clears (`None`) the JLPT exam the user prepares for. While
+    /// set, reviews are pulled before the exam and lessons take in more new
+    /// target-level cards when the pace falls short.
+    pub fn set_exam_target(&mut self, target: Option<ExamTarget>) {
+        self.srs_settings.set_exam_target(target);
+        self.touch();
+    }
+
+    /// Readiness report for the current exam target; `None` without one.
+    pub fn exam_readiness(&self, jlpt_content: &JlptContent) -> Option<ExamReadiness> {
+        self.srs_settings.exam_target().map(|target| {
+            crate::domain::assess_exam_readiness(
+                &self.knowledge_set,
+                jlpt_content,
+                target,
+                Utc::now().date_naive(),
+            )
+        })
+    }
+
+    /// Lesson limits for today: the daily load, raised to the exam pace when
+    /// an exam target still has unstarted target-level cards.
+    pub fn lesson_budget(&self, jlpt_content: &JlptContent) -> DailyBudget {
+        let budget = DailyBudget::from_load(self.daily_load);
+        match self.exam_readiness(jlpt_content) {
+            Some(readiness) => budget.with_exam_intake(
+                readiness
+                    .required_pace()
+                    .min(readiness.new_cards_remaining()),
+            ),
+            None => budget,
+        }
+    }
+
+    /// Drops fitted weights so every family schedules with stock FSRS again.
+    pub fn reset_optimized_weights(&mut self) {
+        self.srs_settings.clear_optimized_weights();
+        self.touch();
+    }
+
+    /// Fits FSRS weights per scheduling family from the cards' review logs.
+    /// A family's weights are replaced only when the fit beats the stock
+    /// weights on the same log; families with too few reviews are skipped.
+    pub fn optimize_srs_weights(&mut self) -> Vec<SrsOptimizationReport> {
+        let mut optimization = self.start_srs_optimization();
+        while optimization.advance(usize::MAX) {}
+        self.finish_srs_optimization(optimization)
+    }
+
+    /// First half of [`Self::optimize_srs_weights`] for callers that must
+    /// yield between chunks of the fit: snapshots each family's review logs.
+    pub fn start_srs_optimization(&self) -> SrsOptimization {
+        SrsOptimization::new(SCHEDULING_FAMILIES.into_iter().map(|family| {
+            let logs = self
+                .knowledge_set
+                .study_cards()
+                .values()
+                .filter(|sc| scheduling_family(sc.card()) == family)
+                .map(|sc| sc.memory().review_log().entries())
+                .filter(|entries| !entries.is_empty())
+                .map(<[ReviewLogEntry]>::to_vec)
+                .collect();
+            (family, logs)
+        }))
+    }
+
+    /// Second half of [`Self::optimize_srs_weights`]: stores the weights of
+    /// every family whose fit beats the stock weights.
+    pub fn finish_srs_optimization(
+        &mut self,
+        optimization: SrsOptimization,
+    ) -> Vec<SrsOptimizationReport> {
+        let now = Utc::now();
+        let mut reports = Vec::with_capacity(SCHEDULING_FAMILIES.len());
+
+        for (family, fit) in optimization.into_fits() {
+            let fit = match fit {
+                Ok(fit) => fit,
+                Err(review_count) => {
+                    reports.push(SrsOptimizationReport::skipped(family, review_count));
+                    continue;
+                },
+            };
+
+            let applied = fit.fitted_loss < fit.default_loss;
+            if applied {
+                self.srs_settings.set_optimized_weights(
+                    family,
+                    OptimizedWeights::new(fit.weights, fit.review_count, fit.fitted_loss, now),
+                );
+            }
+            reports.push(SrsOptimizationReport::fitted(family, &fit, applied));
+        }
+
+        if reports.iter().any(SrsOptimizationReport::applied) {
+            self.touch();
+        }
+        reports
+    }
+
     pub fn updated_at(&self) -> &DateTime<Utc> {
         &self.updated_at
     }
@@ -310,7 +474,8 @@ impl User {
         rating: Rating,
         mode: RateMode,
     ) -> Result<(), OrigaError> {
-        self.knowledge_set.rate_card(card_id, rating, mode)
+        self.knowledge_set
+            .rate_card_with_settings(card_id, rating, mode, &self.srs_settings)
     }
 
     pub fn mark_card_as_known(&mut self, card_id: Ulid) -> Result<(), OrigaError> {
@@ -325,6 +490,14 @@ impl User {
         self.knowledge_set.create_card(card)
     }
 
+    pub(crate) fn create_card_with_memory(
+        &mut self,
+        card: Card,
+        memory: MemoryHistory,
+    ) -> Result<StudyCard, OrigaError> {
+        self.knowledge_set.create_card_with_memory(card, memory)
+    }
+
     pub fn update_card_content(&mut self, card_id: Ulid, new_card: Card) -> Result<(), OrigaError> {
         self.knowledge_set.update_card_content(card_id, new_card)
     }
@@ -371,7 +544,9 @@ impl User {
             let (learned_map, projected_map) = match card {
                 Card::Kanji(_) => (&mut learned.kanji, &mut projected.kanji),
                 Card::Vocabulary(_) | Card::Phrase(_) => (&mut learned.words, &mut projected.words),
-                Card::Grammar(_) => (&mut learned.grammar, &mut projected.grammar),
+                Card::Grammar(_) | Card::Particle(_) => {
+                    (&mut learned.grammar, &mut projected.grammar)
+                },
             };
 
             if is_learned {
@@ -680,6 +855,28 @@ mod tests {
         assert_eq!(user1.imported_sets().len(), 3);
     }
 
+    #[test]
+    fn user_merge_adopts_mode_schedule_changed_on_other_device() {
+        let mut local = User::new(
+            "user@example.com".to_string(),
+            NativeLanguage::Russian,
+            None,
+        );
+        let mut remote = local.clone();
+        remote
+            .set_mode_schedule(
+                RateMode::GrammarReview,
+                ModeSchedule::new(0.95, 30).unwrap(),
+            )
+            .unwrap();
+
+        local.merge(&remote);
+
+        let schedule = local.srs_settings().mode_schedule(RateMode::GrammarReview);
+        assert_eq!(schedule.request_retention(), 0.95);
+        assert_eq!(schedule.maximum_interval(), 30);
+    }
+
     /// Regression guard for the cross-device onboarding-repeat bug: a user
     /// who completed/skipped onboarding on device 1 has the sentinel inside
     /// `imported_sets`. When device 2 merges the remote record into its
@@ -750,6 +947,7 @@ mod tests {
             HashSet::new(),
             DailyLoad::default(),
             0,
+            SrsSettings::default(),
         );
 
         local.merge(&remote);
@@ -785,6 +983,7 @@ mod tests {
             HashSet::new(),
             DailyLoad::default(),
             0,
+            SrsSettings::default(),
         );
 
         local.merge(&nil_remote);
@@ -795,6 +994,52 @@ mod tests {
         assert_eq!(local.email(), "remote@example.com");
     }
 
+    #[test]
+    fn user_rebase_counts_reviews_from_both_devices() {
+        // Arrange — both devices start from the published checkpoint and
+        // review the same card offline.
+        let mut origin = User::new(
+            "test@example.com".to_string(),
+            NativeLanguage::Russian,
+            None,
+        );
+        let card_id = *origin
+            .create_card(create_test_vocab_card("猫"))
+            .unwrap()
+            .card_id();
+        let remote = User::from_row(
+            origin.id(),
+            origin.email().to_string(),
+            origin.username().to_string(),
+            JlptProgress::new(),
+            NativeLanguage::Russian,
+            None,
+            origin.knowledge_set().checkpoint(),
+            Utc::now(),
+            HashSet::new(),
+            DailyLoad::default(),
+            0,
+            SrsSettings::default(),
+        );
+        let mut device1 = remote.clone();
+        let mut device2 = remote.clone();
+        device1
+            .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+            .unwrap();
+        device2
+            .rate_card(card_id, Rating::Hard, RateMode::StandardLesson)
+            .unwrap();
+
+        // Act
+        device2.rebase(&remote, device1.knowledge_set().journal().events());
+
+        // Assert
+        assert_eq!(device2.id(), remote.id());
+        let card = device2.knowledge_set().get_card(card_id).unwrap();
+        assert_eq!(card.memory().reps(), 2);
+        assert_eq!(device2.knowledge_set().journal().events().len(), 2);
+    }
+
     #[test]
     fn user_merge_preserves_jlpt_progress() {
         // Arrange
@@ -1125,4 +1370,35 @@ mod tests {
         assert_eq!(deserialized.email(), "legacy@example.com");
         assert!(deserialized.onboarding_scoring_skipped().is_empty());
     }
+
+    #[test]
+    fn lesson_budget_ignores_exam_target_whose_day_has_passed() {
+        // Arrange
+        let mut user = User::new(
+            "exam@example.com".to_string(),
+            NativeLanguage::Russian,
+            None,
+        );
+        user.set_daily_load(DailyLoad::Minimal);
+        for word in ["水", "火", "木"] {
+            user.create_card(create_test_vocab_card(word)).unwrap();
+        }
+        let content = create_test_content_with_words(&[
+            ("水", JapaneseLevel::N5),
+            ("火", JapaneseLevel::N5),
+            ("木", JapaneseLevel::N5),
+        ]);
+        let exam_date = Utc::now().date_naive() - chrono::Duration::days(2);
+        user.srs_settings = serde_json::from_value(serde_json::json!({
+            "exam_target": { "level": "N5", "exam_date": exam_date },
+        }))
+        .unwrap();
+
+        // Act
+        let budget = user.lesson_budget(&content);
+
+        // Assert
+        assert!(user.exam_readiness(&content).is_none());
+        assert_eq!(budget, DailyBudget::from_load(DailyLoad::Minimal));
+    }
 }
@@ -347,6 +347,18 @@ impl DailyBudget {
         Self::new(cards, cards * PHRASES_PER_NEW_CARD)
     }
 
+    /// Raises the new-card allowance to the pace an exam target requires,
+    /// capped at the [`DailyLoad::Maximum`] pace. Never lowers the user's own
+    /// load. The extra cards go to target-level content first because new
+    /// cards are already picked easiest JLPT level first.
+    pub fn with_exam_intake(self, required_per_day: usize) -> Self {
+        let boosted = required_per_day.min(DailyLoad::Maximum.new_cards_per_day());
+        Self {
+            new_cards_per_day: self.new_cards_per_day.max(boosted),
+            ..self
+        }
+    }
+
     pub fn new_cards_per_day(&self) -> usize {
         self.new_cards_per_day
     }
@@ -783,4 +795,21 @@ mod tests_daily_load {
             "per-lesson phrase cap must equal the former first-lesson daily allowance"
         );
     }
+
+    #[rstest]
+    #[case::raises_to_required(DailyLoad::Light, 14, 14)]
+    #[case::never_lowers(DailyLoad::Hard, 4, 15)]
+    #[case::capped_at_maximum(DailyLoad::Medium, 500, 30)]
+    fn daily_budget_with_exam_intake(
+        #[case] load: DailyLoad,
+        #[case] required: usize,
+        #[case] expected_cards: usize,
+    ) {
+        let budget = DailyBudget::from_load(load).with_exam_intake(required);
+        assert_eq!(budget.new_cards_per_day(), expected_cards);
+        assert_eq!(
+            budget.new_phrases_per_lesson(),
+            load.new_phrases_per_lesson()
+        );
+    }
 }
@@ -2,6 +2,7 @@ use crate::domain::OrigaError;
 use image::DynamicImage;
 
 use super::parseq_wasm::ParseqRecognizer;
+use super::types::Recognition;
 use super::vocab::Vocabulary;
 
 const EPSILON: f32 = 0.1;
@@ -37,7 +38,7 @@ impl CascadeRecognizer {
         })
     }
 
-    pub async fn recognize(&self, line_img: &DynamicImage, pred_char_cnt: f32) -> String {
+    pub async fn recognize(&self, line_img: &DynamicImage, pred_char_cnt: f32) -> Recognition {
         let initial_rec = if approx_eq(pred_char_cnt, 3.0) {
             &self.rec30
         } else if approx_eq(pred_char_cnt, 2.0) {
@@ -48,9 +49,9 @@ impl CascadeRecognizer {
 
         let text = initial_rec.read(line_img).await;
 
-        if text.len() >= 25 {
+        if text.text.len() >= 25 {
             let text50 = self.rec50.read(line_img).await;
-            if text50.len() >= 45 {
+            if text50.text.len() >= 45 {
                 self.rec100.read(line_img).await
             } else {
                 text50
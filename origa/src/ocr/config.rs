@@ -1,7 +1,10 @@
+use super::preprocess::PreprocessingConfig;
+
 #[derive(Debug, Clone)]
 pub struct ModelConfig {
     pub ndlocr_base_url: String,
     pub ndlocr_cache_dir: String,
+    pub preprocessing: PreprocessingConfig,
 }
 
 impl Default for ModelConfig {
@@ -9,6 +12,7 @@ impl Default for ModelConfig {
         Self {
             ndlocr_base_url: String::new(),
             ndlocr_cache_dir: "ndlocr-model-".to_string(),
+            preprocessing: PreprocessingConfig::default(),
         }
     }
 }
@@ -18,9 +22,15 @@ impl ModelConfig {
         Self {
             ndlocr_base_url: ndlocr_base_url.into(),
             ndlocr_cache_dir: ndlocr_cache_dir.into(),
+            preprocessing: PreprocessingConfig::default(),
         }
     }
 
+    pub fn with_preprocessing(mut self, preprocessing: PreprocessingConfig) -> Self {
+        self.preprocessing = preprocessing;
+        self
+    }
+
     pub fn ndlocr_file_names() -> &'static [&'static str] {
         &[
             "deim.onnx",
@@ -58,6 +68,14 @@ mod tests {
         let config = ModelConfig::default();
         assert_eq!(config.ndlocr_base_url, "");
         assert_eq!(config.ndlocr_cache_dir, "ndlocr-model-");
+        assert_eq!(config.preprocessing, PreprocessingConfig::default());
+    }
+
+    #[test]
+    fn model_config_with_preprocessing() {
+        let config = ModelConfig::default().with_preprocessing(PreprocessingConfig::photo());
+        assert!(config.preprocessing.deskew);
+        assert!(config.preprocessing.binarize);
     }
 
     // model_url tests
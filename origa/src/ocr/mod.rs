@@ -1,5 +1,7 @@
 mod config;
+mod preprocess;
 mod reading_order;
+mod ruby;
 mod shared;
 mod types;
 mod vocab;
@@ -34,9 +36,10 @@ impl From<ort::Error> for crate::domain::OrigaError {
 }
 
 pub use config::ModelConfig;
-pub use reading_order::sort_reading_order;
+pub use preprocess::PreprocessingConfig;
+pub use reading_order::{group_reading_order, sort_reading_order};
 pub use shared::ModelFiles;
-pub use types::BoundingBox;
+pub use types::{BoundingBox, OcrLine, OcrResult, Recognition, RubyText, TextOrientation};
 pub use vocab::Vocabulary;
 
 #[cfg(not(target_arch = "wasm32"))]
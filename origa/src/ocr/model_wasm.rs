@@ -3,12 +3,17 @@ use tracing::info;
 
 use super::cascade_wasm::CascadeRecognizer;
 use super::deim_wasm::DeimDetector;
+use super::preprocess::{PreprocessingConfig, preprocess};
+use super::reading_order::group_reading_order;
+use super::ruby::{ruby_base, ruby_of, split_ruby};
 use super::shared::{ModelFiles, crop_bbox};
+use super::types::{BoundingBox, OcrLine, OcrResult, RubyText};
 use crate::domain::OrigaError;
 
 pub struct JapaneseOCRModel {
     detector: DeimDetector,
     recognizer: CascadeRecognizer,
+    preprocessing: PreprocessingConfig,
 }
 
 impl JapaneseOCRModel {
@@ -27,34 +32,101 @@ impl JapaneseOCRModel {
         Ok(Self {
             detector,
             recognizer,
+            preprocessing: PreprocessingConfig::default(),
         })
     }
 
     pub async fn run(&self, img: &DynamicImage) -> Result<String, OrigaError> {
+        Ok(self.run_structured(img).await?.text())
+    }
+
+    /// Steps run on every image before detection; see `ModelConfig`.
+    pub fn with_preprocessing(mut self, preprocessing: PreprocessingConfig) -> Self {
+        self.preprocessing = preprocessing;
+        self
+    }
+
+    /// Detected lines in reading order with their boxes, detector and
+    /// recognizer confidences and orientation. Ruby (furigana) is read
+    /// separately and attached to its line. Regions read as empty are
+    /// dropped. Boxes and size always refer to `img`: with preprocessing on,
+    /// regions found on the cleaned-up page are mapped back onto it.
+    pub async fn run_structured(&self, img: &DynamicImage) -> Result<OcrResult, OrigaError> {
         info!("Running OCR (NDLOCR-Lite)");
 
-        let mut boxes = self.detector.detect(img).await?;
+        let (img, mapping) = preprocess(img, &self.preprocessing);
+        let img = img.as_ref();
+        let (boxes, ruby_boxes) = split_ruby(self.detector.detect(img).await?);
         if boxes.is_empty() {
             info!("No text detected");
-            return Ok(String::new());
         }
 
-        super::reading_order::sort_reading_order(&mut boxes, img.height(), img.width());
+        let line_count = boxes.len();
+        let blocks = group_reading_order(boxes, img.height(), img.width());
 
-        let mut results = Vec::with_capacity(boxes.len());
-        for bbox in boxes {
-            let line_img = crop_bbox(img, &bbox);
-            let text = self
-                .recognizer
-                .recognize(&line_img, bbox.pred_char_cnt)
-                .await;
-            if !text.is_empty() {
-                results.push(text);
+        let mut lines = Vec::with_capacity(line_count);
+        for (block, block_boxes) in blocks.into_iter().enumerate() {
+            for bbox in block_boxes {
+                let ruby = ruby_of(&bbox, &ruby_boxes);
+                let line = self.recognize_region(img, bbox).await;
+                if !line.text.is_empty() {
+                    let ruby = self.read_ruby(img, &line, ruby).await;
+                    lines.push(OcrLine {
+                        block,
+                        ruby,
+                        ..line
+                    });
+                }
+            }
+        }
+
+        for line in &mut lines {
+            line.bbox = mapping.map_box(&line.bbox);
+            for ruby in &mut line.ruby {
+                ruby.bbox = mapping.map_box(&ruby.bbox);
             }
         }
 
-        let result = results.join("\n");
-        info!(result_length = result.len(), "OCR completed");
-        Ok(result)
+        info!(line_count = lines.len(), "OCR completed");
+        let (width, height) = mapping.source_size();
+        Ok(OcrResult {
+            width,
+            height,
+            lines,
+        })
+    }
+
+    /// Reads a single region, e.g. one the user redrew after a bad read.
+    pub async fn recognize_region(&self, img: &DynamicImage, bbox: BoundingBox) -> OcrLine {
+        let line_img = crop_bbox(img, &bbox);
+        let recognition = self
+            .recognizer
+            .recognize(&line_img, bbox.pred_char_cnt)
+            .await;
+        OcrLine::new(bbox, recognition)
+    }
+
+    async fn read_ruby(
+        &self,
+        img: &DynamicImage,
+        line: &OcrLine,
+        ruby: Vec<&BoundingBox>,
+    ) -> Vec<RubyText> {
+        let mut texts = Vec::with_capacity(ruby.len());
+        for bbox in ruby {
+            let reading = self
+                .recognizer
+                .recognize(&crop_bbox(img, bbox), bbox.pred_char_cnt)
+                .await
+                .text;
+            if !reading.is_empty() {
+                texts.push(RubyText {
+                    base: ruby_base(line, bbox),
+                    reading,
+                    bbox: bbox.clone(),
+                });
+            }
+        }
+        texts
     }
 }
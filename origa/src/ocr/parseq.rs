@@ -1,4 +1,5 @@
 use super::shared::{parseq_postprocess, parseq_preprocess};
+use super::types::Recognition;
 use super::vocab::Vocabulary;
 use crate::domain::OrigaError;
 use image::DynamicImage;
@@ -40,16 +41,16 @@ impl ParseqRecognizer {
         })
     }
 
-    pub fn read(&self, image: &DynamicImage) -> String {
+    pub fn read(&self, image: &DynamicImage) -> Recognition {
         if image.width() == 0 || image.height() == 0 {
-            return String::new();
+            return Recognition::default();
         }
 
         let input_array = match parseq_preprocess(image, self.input_width) {
             Ok(t) => t,
             Err(e) => {
                 tracing::warn!("PARSeq preprocessing failed: {}", e);
-                return String::new();
+                return Recognition::default();
             },
         };
 
@@ -57,7 +58,7 @@ impl ParseqRecognizer {
             Ok(t) => t,
             Err(e) => {
                 tracing::warn!("Failed to create tensor: {}", e);
-                return String::new();
+                return Recognition::default();
             },
         };
 
@@ -66,12 +67,12 @@ impl ParseqRecognizer {
                 Ok(outputs) => parseq_postprocess(&outputs, &self.vocab),
                 Err(e) => {
                     tracing::warn!("PARSeq inference failed: {:?}", e);
-                    String::new()
+                    Recognition::default()
                 },
             },
             Err(e) => {
                 tracing::warn!("Session lock failed: {:?}", e);
-                String::new()
+                Recognition::default()
             },
         }
     }
@@ -1,4 +1,5 @@
 use super::shared::{parseq_postprocess, parseq_preprocess};
+use super::types::Recognition;
 use super::vocab::Vocabulary;
 use crate::domain::OrigaError;
 use crate::ort_init;
@@ -51,16 +52,16 @@ impl ParseqRecognizer {
         })
     }
 
-    pub async fn read(&self, image: &DynamicImage) -> String {
+    pub async fn read(&self, image: &DynamicImage) -> Recognition {
         if image.width() == 0 || image.height() == 0 {
-            return String::new();
+            return Recognition::default();
         }
 
         let input_array = match parseq_preprocess(image, self.input_width) {
             Ok(t) => t,
             Err(e) => {
                 tracing::warn!("PARSeq preprocessing failed: {}", e);
-                return String::new();
+                return Recognition::default();
             },
         };
 
@@ -71,7 +72,7 @@ impl ParseqRecognizer {
             Ok(t) => t.into_dyn(),
             Err(e) => {
                 tracing::warn!("Failed to create tensor: {:?}", e);
-                return String::new();
+                return Recognition::default();
             },
         };
 
@@ -80,7 +81,7 @@ impl ParseqRecognizer {
             Ok(o) => o,
             Err(e) => {
                 tracing::warn!("Failed to create run options: {:?}", e);
-                return String::new();
+                return Recognition::default();
             },
         };
         let mut outputs = match session
@@ -90,14 +91,14 @@ impl ParseqRecognizer {
             Ok(o) => o,
             Err(e) => {
                 tracing::warn!("PARSeq inference failed: {:?}", e);
-                return String::new();
+                return Recognition::default();
             },
         };
 
         for (_name, mut output) in outputs.iter_mut() {
             if let Err(e) = output.sync(ort_web::SyncDirection::Rust).await {
                 tracing::warn!("Failed to sync output: {:?}", e);
-                return String::new();
+                return Recognition::default();
             }
         }
 
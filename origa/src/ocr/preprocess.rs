@@ -0,0 +1,738 @@
+use std::borrow::Cow;
+
+use image::{DynamicImage, GrayImage, Luma, Rgb, RgbImage, imageops};
+
+use super::types::BoundingBox;
+
+/// Size of the downscaled copy the page outline and skew are measured on.
+const PAGE_ANALYSIS_SIZE: u32 = 512;
+const SKEW_ANALYSIS_SIZE: u32 = 1024;
+/// Pages cover at least this share of the photo, or the outline is noise.
+const MIN_PAGE_AREA: f32 = 0.2;
+/// Corners this close to the photo corners mean the page already fills it.
+const FRAME_CORNER_MARGIN: f32 = 0.02;
+const MAX_SKEW_DEGREES: f32 = 10.0;
+const SKEW_STEP_DEGREES: f32 = 0.25;
+const MIN_SKEW_DEGREES: f32 = 0.3;
+/// Median glyph component height (px) below which the page is upscaled.
+const MIN_TEXT_HEIGHT: f32 = 16.0;
+const TARGET_TEXT_HEIGHT: f32 = 32.0;
+const MAX_UPSCALE: f32 = 4.0;
+const MAX_UPSCALED_SIDE: u32 = 4096;
+/// Sauvola parameters; `R` is the dynamic range of the standard deviation.
+const SAUVOLA_K: f32 = 0.2;
+const SAUVOLA_R: f32 = 128.0;
+
+/// Clean-up steps for camera photos of pages, run before detection. All
+/// steps are off by default: scans and screenshots need none of them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreprocessingConfig {
+    /// Crops the page outline and warps it to a rectangle.
+    pub perspective: bool,
+    /// Rotates the page so text lines are axis-aligned.
+    pub deskew: bool,
+    /// Enlarges pages whose glyphs are only a few pixels tall.
+    pub upscale: bool,
+    /// Local (Sauvola) thresholding; removes shadows and low contrast.
+    pub binarize: bool,
+}
+
+impl PreprocessingConfig {
+    /// Every step on, for phone photos of printed pages.
+    pub fn photo() -> Self {
+        Self {
+            perspective: true,
+            deskew: true,
+            upscale: true,
+            binarize: true,
+        }
+    }
+}
+
+/// Runs the enabled steps in order: perspective, deskew, upscale, binarize.
+/// Boxes detected on the returned image map back onto `image` through the
+/// returned [`PageMapping`].
+pub(crate) fn preprocess<'a>(
+    image: &'a DynamicImage,
+    config: &PreprocessingConfig,
+) -> (Cow<'a, DynamicImage>, PageMapping) {
+    let mut current = Cow::Borrowed(image);
+    let mut mapping = PageMapping {
+        width: image.width(),
+        height: image.height(),
+        steps: Vec::new(),
+    };
+
+    if config.perspective
+        && let Some((corrected, homography)) = correct_perspective(&current)
+    {
+        current = Cow::Owned(corrected);
+        mapping.steps.push(InverseStep::Homography(homography));
+    }
+    if config.deskew {
+        let angle = estimate_skew(&current.to_luma8());
+        if angle.abs() >= MIN_SKEW_DEGREES {
+            let (width, height) = (current.width(), current.height());
+            current = Cow::Owned(rotate(&current.to_rgb8(), angle));
+            mapping.steps.push(InverseStep::Rotation {
+                degrees: angle,
+                centre: (width as f32 / 2.0, height as f32 / 2.0),
+            });
+        }
+    }
+    if config.upscale
+        && let Some(upscaled) = upscale_small_text(&current)
+    {
+        mapping.steps.push(InverseStep::Scale(
+            current.width() as f32 / upscaled.width() as f32,
+            current.height() as f32 / upscaled.height() as f32,
+        ));
+        current = Cow::Owned(upscaled);
+    }
+    if config.binarize {
+        current = Cow::Owned(DynamicImage::ImageLuma8(binarize(&current.to_luma8())));
+    }
+
+    (current, mapping)
+}
+
+/// Maps points of a preprocessed image back onto the input image: each
+/// geometric step stores how its output pixels were sampled from its input.
+#[derive(Debug, Clone)]
+pub(crate) struct PageMapping {
+    width: u32,
+    height: u32,
+    steps: Vec<InverseStep>,
+}
+
+#[derive(Debug, Clone)]
+enum InverseStep {
+    /// Output-to-source homography of the perspective warp.
+    Homography([f32; 8]),
+    Rotation {
+        degrees: f32,
+        centre: (f32, f32),
+    },
+    Scale(f32, f32),
+}
+
+impl PageMapping {
+    /// Size of the input image.
+    pub(crate) fn source_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn to_source(&self, (mut x, mut y): (f32, f32)) -> (f32, f32) {
+        for step in self.steps.iter().rev() {
+            (x, y) = match *step {
+                InverseStep::Homography(h) => {
+                    let w = h[6] * x + h[7] * y + 1.0;
+                    (
+                        (h[0] * x + h[1] * y + h[2]) / w,
+                        (h[3] * x + h[4] * y + h[5]) / w,
+                    )
+                },
+                InverseStep::Rotation {
+                    degrees,
+                    centre: (cx, cy),
+                } => {
+                    let (sin, cos) = degrees.to_radians().sin_cos();
+                    let (dx, dy) = (x - cx, y - cy);
+                    (dx * cos - dy * sin + cx, dx * sin + dy * cos + cy)
+                },
+                InverseStep::Scale(sx, sy) => (x * sx, y * sy),
+            };
+        }
+        (x, y)
+    }
+
+    /// The input-image box enclosing `bbox`. After rotation or a perspective
+    /// warp the region is no longer axis-aligned, so this is the bounding
+    /// rectangle of its four mapped corners, clamped to the image.
+    pub(crate) fn map_box(&self, bbox: &BoundingBox) -> BoundingBox {
+        if self.steps.is_empty() {
+            return bbox.clone();
+        }
+        let corners = [
+            (bbox.x0, bbox.y0),
+            (bbox.x1, bbox.y0),
+            (bbox.x1, bbox.y1),
+            (bbox.x0, bbox.y1),
+        ]
+        .map(|(x, y)| self.to_source((x as f32, y as f32)));
+        let (mut x0, mut y0) = (f32::MAX, f32::MAX);
+        let (mut x1, mut y1) = (f32::MIN, f32::MIN);
+        for (x, y) in corners {
+            (x0, y0) = (x0.min(x), y0.min(y));
+            (x1, y1) = (x1.max(x), y1.max(y));
+        }
+        let clamp_x = |value: f32| (value.round() as i32).clamp(0, self.width as i32);
+        let clamp_y = |value: f32| (value.round() as i32).clamp(0, self.height as i32);
+        BoundingBox {
+            x0: clamp_x(x0),
+            y0: clamp_y(y0),
+            x1: clamp_x(x1),
+            y1: clamp_y(y1),
+            ..bbox.clone()
+        }
+    }
+}
+
+/// Finds the page as the largest bright region and warps its four corners
+/// onto a rectangle, returned with the output-to-source homography. `None`
+/// when no page outline stands out.
+fn correct_perspective(image: &DynamicImage) -> Option<(DynamicImage, [f32; 8])> {
+    let (small, scale) = downscale(&image.to_luma8(), PAGE_ANALYSIS_SIZE);
+    let (width, height) = small.dimensions();
+    let threshold = otsu_threshold(&small);
+    let mask: Vec<bool> = small.pixels().map(|p| p[0] > threshold).collect();
+
+    let (labels, components) = connected_components(&mask, width, height);
+    let page = components.iter().max_by_key(|c| c.area)?;
+    if (page.area as f32) < MIN_PAGE_AREA * (width * height) as f32 {
+        return None;
+    }
+
+    // Extreme points along the diagonals are the page corners.
+    let mut corners = [(0.0f32, 0.0f32); 4];
+    let mut best = [f32::MIN; 4];
+    for (index, _) in labels.iter().enumerate().filter(|(_, l)| **l == page.label) {
+        let (x, y) = ((index as u32 % width) as f32, (index as u32 / width) as f32);
+        for (corner, score) in [-x - y, x - y, x + y, y - x].into_iter().enumerate() {
+            if score > best[corner] {
+                best[corner] = score;
+                corners[corner] = (x, y);
+            }
+        }
+    }
+
+    let frame = [
+        (0.0, 0.0),
+        (width as f32, 0.0),
+        (width as f32, height as f32),
+        (0.0, height as f32),
+    ];
+    let margin = FRAME_CORNER_MARGIN * width.max(height) as f32;
+    if corners
+        .iter()
+        .zip(frame)
+        .all(|(&(x, y), (fx, fy))| (x - fx).abs() <= margin && (y - fy).abs() <= margin)
+    {
+        return None;
+    }
+
+    let corners = corners.map(|(x, y)| ((x + 0.5) * scale, (y + 0.5) * scale));
+    let [top_left, top_right, bottom_right, bottom_left] = corners;
+    let out_width = distance(top_left, top_right).max(distance(bottom_left, bottom_right));
+    let out_height = distance(top_left, bottom_left).max(distance(top_right, bottom_right));
+    let (out_width, out_height) = (out_width.round() as u32, out_height.round() as u32);
+    if out_width < 2 || out_height < 2 {
+        return None;
+    }
+
+    let target = [
+        (0.0, 0.0),
+        (out_width as f32, 0.0),
+        (out_width as f32, out_height as f32),
+        (0.0, out_height as f32),
+    ];
+    let homography = solve_homography(&target, &corners)?;
+
+    let source = image.to_rgb8();
+    let warped = RgbImage::from_fn(out_width, out_height, |u, v| {
+        let (u, v) = (u as f32 + 0.5, v as f32 + 0.5);
+        let w = homography[6] * u + homography[7] * v + 1.0;
+        let x = (homography[0] * u + homography[1] * v + homography[2]) / w;
+        let y = (homography[3] * u + homography[4] * v + homography[5]) / w;
+        sample_bilinear(&source, x - 0.5, y - 0.5)
+    });
+    Some((DynamicImage::ImageRgb8(warped), homography))
+}
+
+/// Projection-profile skew estimate in degrees. Text rows (or tategaki
+/// columns) give the sharpest ink profile when measured along their angle.
+fn estimate_skew(gray: &GrayImage) -> f32 {
+    let (small, _) = downscale(gray, SKEW_ANALYSIS_SIZE);
+    let (width, height) = small.dimensions();
+    let threshold = otsu_threshold(&small);
+    let ink: Vec<(f32, f32)> = small
+        .enumerate_pixels()
+        .filter(|(_, _, p)| p[0] <= threshold)
+        .map(|(x, y, _)| (x as f32, y as f32))
+        .collect();
+    if ink.is_empty() || ink.len() == (width * height) as usize {
+        return 0.0;
+    }
+
+    let diagonal = (width as f32).hypot(height as f32).ceil() as usize;
+    let mut rows = vec![0u32; 2 * diagonal + 1];
+    let mut columns = vec![0u32; 2 * diagonal + 1];
+    let steps = (MAX_SKEW_DEGREES / SKEW_STEP_DEGREES) as i32;
+
+    let mut best = (0.0f32, 0u64);
+    for step in -steps..=steps {
+        let angle = step as f32 * SKEW_STEP_DEGREES;
+        let (sin, cos) = angle.to_radians().sin_cos();
+        rows.fill(0);
+        columns.fill(0);
+        let bin = |position: f32| (position.round() as isize + diagonal as isize) as usize;
+        for &(x, y) in &ink {
+            rows[bin(y * cos - x * sin)] += 1;
+            columns[bin(x * cos + y * sin)] += 1;
+        }
+        let sharpness: u64 = rows
+            .iter()
+            .chain(&columns)
+            .map(|&count| count as u64 * count as u64)
+            .sum();
+        // Ties (blank-ish pages) keep the angle closest to zero.
+        if sharpness > best.1 || (sharpness == best.1 && angle.abs() < best.0.abs()) {
+            best = (angle, sharpness);
+        }
+    }
+    best.0
+}
+
+/// Rotates content sloping down by `degrees` back to level around the
+/// centre, keeping the size and filling the uncovered corners with white.
+fn rotate(image: &RgbImage, degrees: f32) -> DynamicImage {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (width, height) = image.dimensions();
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+
+    let rotated = RgbImage::from_fn(width, height, |x, y| {
+        let (dx, dy) = (x as f32 - cx, y as f32 - cy);
+        let source_x = dx * cos - dy * sin + cx;
+        let source_y = dx * sin + dy * cos + cy;
+        sample_bilinear(image, source_x, source_y)
+    });
+    DynamicImage::ImageRgb8(rotated)
+}
+
+/// Upscales so the median glyph component reaches `TARGET_TEXT_HEIGHT`.
+/// `None` when the text is already large enough.
+fn upscale_small_text(image: &DynamicImage) -> Option<DynamicImage> {
+    let gray = image.to_luma8();
+    let (width, height) = gray.dimensions();
+    let threshold = otsu_threshold(&gray);
+    let mask: Vec<bool> = gray.pixels().map(|p| p[0] <= threshold).collect();
+
+    let (_, components) = connected_components(&mask, width, height);
+    let mut heights: Vec<u32> = components
+        .iter()
+        .filter(|c| c.area >= 4 && c.height() < height / 4)
+        .map(|c| c.height())
+        .collect();
+    if heights.is_empty() {
+        return None;
+    }
+    let middle = heights.len() / 2;
+    let median = *heights.select_nth_unstable(middle).1 as f32;
+    if median >= MIN_TEXT_HEIGHT {
+        return None;
+    }
+
+    let factor = (TARGET_TEXT_HEIGHT / median)
+        .min(MAX_UPSCALE)
+        .min(MAX_UPSCALED_SIDE as f32 / width.max(height) as f32);
+    if factor <= 1.0 {
+        return None;
+    }
+    Some(image.resize_exact(
+        (width as f32 * factor).round() as u32,
+        (height as f32 * factor).round() as u32,
+        imageops::FilterType::CatmullRom,
+    ))
+}
+
+/// Sauvola thresholding over a window of about 1/40 of the page, so shadows
+/// and uneven lighting shift the threshold with the local background.
+fn binarize(gray: &GrayImage) -> GrayImage {
+    let (width, height) = gray.dimensions();
+    let (w, h) = (width as usize, height as usize);
+    let half = (width.min(height) / 80).clamp(7, 50) as usize;
+
+    // Integral images of values and squares, one row/column of padding.
+    let mut sum = vec![0u64; (w + 1) * (h + 1)];
+    let mut squares = vec![0u64; (w + 1) * (h + 1)];
+    for y in 0..h {
+        let (mut row_sum, mut row_squares) = (0u64, 0u64);
+        for x in 0..w {
+            let value = gray.get_pixel(x as u32, y as u32)[0] as u64;
+            row_sum += value;
+            row_squares += value * value;
+            sum[(y + 1) * (w + 1) + x + 1] = sum[y * (w + 1) + x + 1] + row_sum;
+            squares[(y + 1) * (w + 1) + x + 1] = squares[y * (w + 1) + x + 1] + row_squares;
+        }
+    }
+    let area_sum = |table: &[u64], x0: usize, y0: usize, x1: usize, y1: usize| {
+        table[y1 * (w + 1) + x1] + table[y0 * (w + 1) + x0]
+            - table[y0 * (w + 1) + x1]
+            - table[y1 * (w + 1) + x0]
+    };
+
+    GrayImage::from_fn(width, height, |x, y| {
+        let (x, y) = (x as usize, y as usize);
+        let (x0, y0) = (x.saturating_sub(half), y.saturating_sub(half));
+        let (x1, y1) = ((x + half + 1).min(w), (y + half + 1).min(h));
+        let count = ((x1 - x0) * (y1 - y0)) as f32;
+        let mean = area_sum(&sum, x0, y0, x1, y1) as f32 / count;
+        let variance = area_sum(&squares, x0, y0, x1, y1) as f32 / count - mean * mean;
+        let threshold = mean * (1.0 + SAUVOLA_K * (variance.max(0.0).sqrt() / SAUVOLA_R - 1.0));
+
+        let value = gray.get_pixel(x as u32, y as u32)[0] as f32;
+        Luma([if value > threshold { 255 } else { 0 }])
+    })
+}
+
+fn downscale(gray: &GrayImage, max_side: u32) -> (GrayImage, f32) {
+    let (width, height) = gray.dimensions();
+    let longest = width.max(height);
+    if longest <= max_side {
+        return (gray.clone(), 1.0);
+    }
+    let scale = longest as f32 / max_side as f32;
+    let resized = imageops::resize(
+        gray,
+        ((width as f32 / scale).round() as u32).max(1),
+        ((height as f32 / scale).round() as u32).max(1),
+        imageops::FilterType::Triangle,
+    );
+    (resized, scale)
+}
+
+/// Global threshold maximising the between-class variance of the histogram.
+fn otsu_threshold(gray: &GrayImage) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in gray.pixels() {
+        histogram[pixel[0] as usize] += 1;
+    }
+    let total: u64 = histogram.iter().sum();
+    let weighted_total: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(value, &count)| value as f64 * count as f64)
+        .sum();
+
+    let (mut background, mut background_sum) = (0u64, 0f64);
+    let mut best = (0u8, 0f64);
+    for (value, &count) in histogram.iter().enumerate() {
+        background += count;
+        background_sum += value as f64 * count as f64;
+        let foreground = total - background;
+        if background == 0 || foreground == 0 {
+            continue;
+        }
+        let background_mean = background_sum / background as f64;
+        let foreground_mean = (weighted_total - background_sum) / foreground as f64;
+        let between =
+            background as f64 * foreground as f64 * (background_mean - foreground_mean).powi(2);
+        if between > best.1 {
+            best = (value as u8, between);
+        }
+    }
+    best.0
+}
+
+struct Component {
+    label: u32,
+    area: usize,
+    min_y: u32,
+    max_y: u32,
+}
+
+impl Component {
+    fn height(&self) -> u32 {
+        self.max_y - self.min_y + 1
+    }
+}
+
+/// 4-connected components of `mask`; label 0 is background.
+fn connected_components(mask: &[bool], width: u32, height: u32) -> (Vec<u32>, Vec<Component>) {
+    let (w, h) = (width as usize, height as usize);
+    let mut labels = vec![0u32; mask.len()];
+    let mut components = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0..mask.len() {
+        if !mask[start] || labels[start] != 0 {
+            continue;
+        }
+        let label = components.len() as u32 + 1;
+        let mut component = Component {
+            label,
+            area: 0,
+            min_y: u32::MAX,
+            max_y: 0,
+        };
+        labels[start] = label;
+        stack.push(start);
+
+        while let Some(index) = stack.pop() {
+            let (x, y) = (index % w, index / w);
+            component.area += 1;
+            component.min_y = component.min_y.min(y as u32);
+            component.max_y = component.max_y.max(y as u32);
+
+            let neighbours = [
+                (x > 0).then(|| index - 1),
+                (x + 1 < w).then(|| index + 1),
+                (y > 0).then(|| index - w),
+                (y + 1 < h).then(|| index + w),
+            ];
+            for next in neighbours.into_iter().flatten() {
+                if mask[next] && labels[next] == 0 {
+                    labels[next] = label;
+                    stack.push(next);
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    (labels, components)
+}
+
+/// Projective transform mapping each `from` point onto the matching `to`
+/// point, as the 8 free entries of the 3x3 matrix (the last one is 1).
+fn solve_homography(from: &[(f32, f32); 4], to: &[(f32, f32); 4]) -> Option<[f32; 8]> {
+    let mut system = [[0f64; 9]; 8];
+    for (i, (&(u, v), &(x, y))) in from.iter().zip(to).enumerate() {
+        let (u, v, x, y) = (u as f64, v as f64, x as f64, y as f64);
+        system[2 * i] = [u, v, 1.0, 0.0, 0.0, 0.0, -u * x, -v * x, x];
+        system[2 * i + 1] = [0.0, 0.0, 0.0, u, v, 1.0, -u * y, -v * y, y];
+    }
+
+    // Gaussian elimination with partial pivoting.
+    for column in 0..8 {
+        let pivot = (column..8)
+            .max_by(|&a, &b| system[a][column].abs().total_cmp(&system[b][column].abs()))?;
+        if system[pivot][column].abs() < 1e-9 {
+            return None;
+        }
+        system.swap(column, pivot);
+        let pivot_row = system[column];
+        for (index, row) in system.iter_mut().enumerate() {
+            if index != column {
+                let factor = row[column] / pivot_row[column];
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(column) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+
+    let mut solution = [0f32; 8];
+    for (i, value) in solution.iter_mut().enumerate() {
+        *value = (system[i][8] / system[i][i]) as f32;
+    }
+    Some(solution)
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// Bilinear sample at pixel-centre coordinates; outside the image is white.
+fn sample_bilinear(image: &RgbImage, x: f32, y: f32) -> Rgb<u8> {
+    let (width, height) = image.dimensions();
+    if x < -0.5 || y < -0.5 || x > width as f32 - 0.5 || y > height as f32 - 0.5 {
+        return Rgb([255, 255, 255]);
+    }
+    let (x, y) = (
+        x.clamp(0.0, (width - 1) as f32),
+        y.clamp(0.0, (height - 1) as f32),
+    );
+    let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+
+    let mut pixel = [0u8; 3];
+    for (channel, value) in pixel.iter_mut().enumerate() {
+        let top = image.get_pixel(x0, y0)[channel] as f32 * (1.0 - fx)
+            + image.get_pixel(x1, y0)[channel] as f32 * fx;
+        let bottom = image.get_pixel(x0, y1)[channel] as f32 * (1.0 - fx)
+            + image.get_pixel(x1, y1)[channel] as f32 * fx;
+        *value = (top * (1.0 - fy) + bottom * fy).round() as u8;
+    }
+    Rgb(pixel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// White page with dark 3 px lines every 20 px, sloping by `degrees`.
+    fn ruled_page(degrees: f32) -> DynamicImage {
+        let slope = degrees.to_radians().tan();
+        DynamicImage::ImageLuma8(GrayImage::from_fn(400, 300, |x, y| {
+            let offset = y as f32 - x as f32 * slope;
+            let inside = (40..360).contains(&x) && (30.0..270.0).contains(&offset);
+            Luma([if inside && offset.rem_euclid(20.0) < 3.0 {
+                20
+            } else {
+                240
+            }])
+        }))
+    }
+
+    #[test]
+    fn disabled_config_leaves_image_untouched() {
+        let image = ruled_page(3.0);
+
+        let (result, mapping) = preprocess(&image, &PreprocessingConfig::default());
+
+        assert!(matches!(result, Cow::Borrowed(_)));
+        assert!(mapping.steps.is_empty());
+    }
+
+    #[test]
+    fn deskew_levels_sloped_lines() {
+        let image = ruled_page(3.0);
+
+        assert!((estimate_skew(&image.to_luma8()) - 3.0).abs() <= 0.5);
+
+        let config = PreprocessingConfig {
+            deskew: true,
+            ..PreprocessingConfig::default()
+        };
+        let (levelled, _) = preprocess(&image, &config);
+        assert!(estimate_skew(&levelled.to_luma8()).abs() <= 0.5);
+    }
+
+    fn bbox(x0: i32, y0: i32, x1: i32, y1: i32) -> BoundingBox {
+        BoundingBox {
+            x0,
+            y0,
+            x1,
+            y1,
+            confidence: 1.0,
+            class_index: 0,
+            pred_char_cnt: 1.0,
+        }
+    }
+
+    /// Box around the red pixels of `image`.
+    fn red_region(image: &DynamicImage) -> BoundingBox {
+        let red: Vec<(u32, u32)> = image
+            .to_rgb8()
+            .enumerate_pixels()
+            .filter(|(_, _, p)| p[0] > 180 && p[1] < 90 && p[2] < 90)
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        let xs = red.iter().map(|&(x, _)| x as i32);
+        let ys = red.iter().map(|&(_, y)| y as i32);
+        bbox(
+            xs.clone().min().unwrap(),
+            ys.clone().min().unwrap(),
+            xs.max().unwrap() + 1,
+            ys.max().unwrap() + 1,
+        )
+    }
+
+    #[test]
+    fn deskewed_boxes_map_back_onto_the_original_image() {
+        // Arrange: sloped ruled page with a red mark far from the centre, so
+        // the rotation moves it by several pixels.
+        let mut page = ruled_page(3.0).to_rgb8();
+        for y in 250..262 {
+            for x in 330..350 {
+                page.put_pixel(x, y, Rgb([220, 30, 30]));
+            }
+        }
+        let image = DynamicImage::ImageRgb8(page);
+        let config = PreprocessingConfig {
+            deskew: true,
+            upscale: true,
+            ..PreprocessingConfig::default()
+        };
+
+        // Act
+        let (levelled, mapping) = preprocess(&image, &config);
+        let detected = red_region(&levelled);
+        let mapped = mapping.map_box(&detected);
+
+        // Assert
+        assert!(!mapping.steps.is_empty());
+        let original = red_region(&image);
+        let moved = (detected.x0 - original.x0).abs() + (detected.y0 - original.y0).abs();
+        assert!(moved > 4, "rotation should move the mark ({moved} px)");
+        for (got, want) in [
+            (mapped.x0, original.x0),
+            (mapped.y0, original.y0),
+            (mapped.x1, original.x1),
+            (mapped.y1, original.y1),
+        ] {
+            assert!((got - want).abs() <= 3, "mapped {mapped:?} vs {original:?}");
+        }
+        assert_eq!(mapping.source_size(), (400, 300));
+    }
+
+    #[test]
+    fn binarization_removes_shadow_gradient() {
+        // Background fades from 110 (shadow) to 230; text is 70 darker.
+        let text = |x: u32, y: u32| (20..30).contains(&(y % 40)) && (x % 30) < 12;
+        let gray = GrayImage::from_fn(300, 200, |x, y| {
+            let background = 110 + (x * 120 / 300) as u8;
+            Luma([if text(x, y) {
+                background - 70
+            } else {
+                background
+            }])
+        });
+
+        let binary = binarize(&gray);
+
+        assert_eq!(binary.get_pixel(5, 5)[0], 255);
+        assert_eq!(binary.get_pixel(295, 5)[0], 255);
+        assert_eq!(binary.get_pixel(5, 25)[0], 0);
+        assert_eq!(binary.get_pixel(275, 25)[0], 0);
+    }
+
+    #[test]
+    fn perspective_crops_page_outline_to_rectangle() {
+        let corners = [(60.0, 40.0), (340.0, 70.0), (360.0, 350.0), (40.0, 330.0)];
+        let inside = |x: f32, y: f32| {
+            (0..4).all(|i| {
+                let (ax, ay) = corners[i];
+                let (bx, by) = corners[(i + 1) % 4];
+                (bx - ax) * (y - ay) - (by - ay) * (x - ax) >= 0.0
+            })
+        };
+        let photo = DynamicImage::ImageRgb8(RgbImage::from_fn(400, 400, |x, y| {
+            if inside(x as f32 + 0.5, y as f32 + 0.5) {
+                Rgb([230, 230, 220])
+            } else {
+                Rgb([40, 40, 50])
+            }
+        }));
+
+        let (page, _) = correct_perspective(&photo).expect("page outline");
+
+        assert!((page.width() as i32 - 322).abs() <= 4);
+        assert!((page.height() as i32 - 291).abs() <= 4);
+        let rgb = page.to_rgb8();
+        for (x, y) in [(2, 2), (page.width() - 3, page.height() - 3)] {
+            assert!(rgb.get_pixel(x, y)[0] > 150);
+        }
+    }
+
+    #[test]
+    fn only_small_text_is_upscaled() {
+        let dots = |size: u32| {
+            DynamicImage::ImageLuma8(GrayImage::from_fn(200, 200, move |x, y| {
+                let period = size * 2;
+                Luma([if x % period < size && y % period < size {
+                    0
+                } else {
+                    255
+                }])
+            }))
+        };
+
+        let upscaled = upscale_small_text(&dots(8)).expect("small text");
+
+        assert_eq!(upscaled.width(), 800);
+        assert!(upscale_small_text(&dots(20)).is_none());
+    }
+}
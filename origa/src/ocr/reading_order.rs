@@ -1,26 +1,319 @@
-use super::types::BoundingBox;
+use super::types::{BoundingBox, TextOrientation};
 
-pub fn sort_reading_order(boxes: &mut [BoundingBox], _img_height: u32, _img_width: u32) {
+/// Lines further apart than this many line thicknesses start a new block.
+const LINE_GAP_RATIO: f32 = 1.0;
+/// Neighbouring lines must share this much of the shorter line's length.
+const MIN_LINE_OVERLAP: f32 = 0.3;
+/// Lines whose thickness (glyph size) differs more than this never group,
+/// so a title does not swallow the body text next to it.
+const MAX_SIZE_RATIO: f32 = 2.0;
+
+/// Orders lines the way a reader would: text blocks (speech bubbles,
+/// captions, paragraphs) are found first, the page is then cut recursively
+/// along empty rows and columns into panels, and panels in a row are read
+/// right-to-left on vertical (manga) pages and left-to-right otherwise.
+pub fn sort_reading_order(boxes: &mut [BoundingBox], img_height: u32, img_width: u32) {
+    let ordered: Vec<BoundingBox> = group_reading_order(boxes.to_vec(), img_height, img_width)
+        .into_iter()
+        .flatten()
+        .collect();
+    for (slot, bbox) in boxes.iter_mut().zip(ordered) {
+        *slot = bbox;
+    }
+}
+
+/// Like `sort_reading_order`, but keeps the text blocks: each inner vector
+/// is one speech bubble or paragraph with its lines in reading order.
+pub fn group_reading_order(
+    boxes: Vec<BoundingBox>,
+    _img_height: u32,
+    _img_width: u32,
+) -> Vec<Vec<BoundingBox>> {
     if boxes.is_empty() {
-        return;
+        return Vec::new();
     }
 
     let vertical_count = boxes
         .iter()
-        .filter(|b| (b.y1 - b.y0) > (b.x1 - b.x0))
+        .filter(|b| b.orientation() == TextOrientation::Vertical)
         .count();
+    let vertical_page = vertical_count > boxes.len() / 2;
+
+    let blocks = group_blocks(boxes)
+        .into_iter()
+        .map(Block::new)
+        .collect::<Vec<_>>();
+
+    xy_cut(blocks, vertical_page)
+        .into_iter()
+        .map(|block| block.lines)
+        .collect()
+}
+
+struct Block {
+    lines: Vec<BoundingBox>,
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+}
+
+impl Block {
+    fn new(mut lines: Vec<BoundingBox>) -> Self {
+        let vertical = lines
+            .iter()
+            .filter(|b| b.orientation() == TextOrientation::Vertical)
+            .count()
+            * 2
+            > lines.len();
+        if vertical {
+            lines.sort_by(|a, b| (b.x0 + b.x1).cmp(&(a.x0 + a.x1)).then(a.y0.cmp(&b.y0)));
+        } else {
+            lines.sort_by(|a, b| (a.y0 + a.y1).cmp(&(b.y0 + b.y1)).then(a.x0.cmp(&b.x0)));
+        }
+
+        Self {
+            x0: lines.iter().map(|b| b.x0).min().unwrap_or(0),
+            y0: lines.iter().map(|b| b.y0).min().unwrap_or(0),
+            x1: lines.iter().map(|b| b.x1).max().unwrap_or(0),
+            y1: lines.iter().map(|b| b.y1).max().unwrap_or(0),
+            lines,
+        }
+    }
+
+    fn span(&self, axis: Axis) -> (i32, i32) {
+        match axis {
+            Axis::X => (self.x0, self.x1),
+            Axis::Y => (self.y0, self.y1),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+}
+
+/// Joins lines that sit next to each other across their reading direction
+/// with similar glyph size and the same orientation.
+fn group_blocks(boxes: Vec<BoundingBox>) -> Vec<Vec<BoundingBox>> {
+    let mut parent: Vec<usize> = (0..boxes.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for i in 0..boxes.len() {
+        for j in i + 1..boxes.len() {
+            if same_block(&boxes[i], &boxes[j]) {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a] = b;
+            }
+        }
+    }
+
+    let mut groups: Vec<(usize, Vec<BoundingBox>)> = Vec::new();
+    for (i, bbox) in boxes.into_iter().enumerate() {
+        let group = root(&mut parent, i);
+        match groups.iter_mut().find(|(g, _)| *g == group) {
+            Some((_, lines)) => lines.push(bbox),
+            None => groups.push((group, vec![bbox])),
+        }
+    }
+    groups.into_iter().map(|(_, lines)| lines).collect()
+}
+
+fn same_block(a: &BoundingBox, b: &BoundingBox) -> bool {
+    let orientation = a.orientation();
+    if orientation != b.orientation() {
+        return false;
+    }
+
+    // Thickness is across the reading direction, length along it.
+    let (across, along) = match orientation {
+        TextOrientation::Vertical => (Axis::X, Axis::Y),
+        TextOrientation::Horizontal => (Axis::Y, Axis::X),
+    };
+    let (a_lo, a_hi) = span(a, across);
+    let (b_lo, b_hi) = span(b, across);
+    let (a_thickness, b_thickness) = ((a_hi - a_lo).max(1) as f32, (b_hi - b_lo).max(1) as f32);
+    if a_thickness.max(b_thickness) / a_thickness.min(b_thickness) > MAX_SIZE_RATIO {
+        return false;
+    }
+
+    let gap = (a_lo.max(b_lo) - a_hi.min(b_hi)).max(0) as f32;
+    if gap > LINE_GAP_RATIO * a_thickness.min(b_thickness) {
+        return false;
+    }
+
+    let (a_start, a_end) = span(a, along);
+    let (b_start, b_end) = span(b, along);
+    let overlap = (a_end.min(b_end) - a_start.max(b_start)) as f32;
+    let shorter = (a_end - a_start).min(b_end - b_start).max(1) as f32;
+    overlap >= MIN_LINE_OVERLAP * shorter
+}
+
+fn span(bbox: &BoundingBox, axis: Axis) -> (i32, i32) {
+    match axis {
+        Axis::X => (bbox.x0, bbox.x1),
+        Axis::Y => (bbox.y0, bbox.y1),
+    }
+}
 
-    let is_vertical = vertical_count > boxes.len() / 2;
+/// Recursive XY-cut: split on empty horizontal bands first (rows of panels,
+/// top to bottom), then on empty vertical bands (panels within a row).
+fn xy_cut(blocks: Vec<Block>, vertical_page: bool) -> Vec<Block> {
+    if blocks.len() <= 1 {
+        return blocks;
+    }
 
-    if is_vertical {
-        boxes.sort_by(|a, b| match b.x0.cmp(&a.x0) {
-            std::cmp::Ordering::Equal => a.y0.cmp(&b.y0),
-            other => other,
-        });
+    let rows = split_on_gaps(blocks, Axis::Y);
+    if rows.len() > 1 {
+        return rows
+            .into_iter()
+            .flat_map(|row| xy_cut(row, vertical_page))
+            .collect();
+    }
+    let row = rows.into_iter().next().unwrap_or_default();
+
+    let mut columns = split_on_gaps(row, Axis::X);
+    if columns.len() > 1 {
+        if vertical_page {
+            columns.reverse();
+        }
+        return columns
+            .into_iter()
+            .flat_map(|column| xy_cut(column, vertical_page))
+            .collect();
+    }
+
+    // Overlapping blocks that no straight cut separates.
+    let mut blocks = columns.into_iter().next().unwrap_or_default();
+    if vertical_page {
+        blocks.sort_by(|a, b| b.x1.cmp(&a.x1).then(a.y0.cmp(&b.y0)));
     } else {
-        boxes.sort_by(|a, b| match a.y0.cmp(&b.y0) {
-            std::cmp::Ordering::Equal => a.x0.cmp(&b.x0),
-            other => other,
-        });
+        blocks.sort_by(|a, b| a.y0.cmp(&b.y0).then(a.x0.cmp(&b.x0)));
+    }
+    blocks
+}
+
+/// Groups blocks whose projections on `axis` overlap; groups come out in
+/// increasing coordinate order.
+fn split_on_gaps(mut blocks: Vec<Block>, axis: Axis) -> Vec<Vec<Block>> {
+    blocks.sort_by_key(|b| b.span(axis).0);
+
+    let mut groups: Vec<Vec<Block>> = Vec::new();
+    let mut group_end = i32::MIN;
+    for block in blocks {
+        let (start, end) = block.span(axis);
+        match groups.last_mut() {
+            Some(group) if start < group_end => {
+                group.push(block);
+                group_end = group_end.max(end);
+            },
+            _ => {
+                groups.push(vec![block]);
+                group_end = end;
+            },
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(x0: i32, y0: i32, x1: i32, y1: i32) -> BoundingBox {
+        BoundingBox {
+            x0,
+            y0,
+            x1,
+            y1,
+            confidence: 1.0,
+            class_index: 0,
+            pred_char_cnt: 3.0,
+        }
+    }
+
+    fn order(boxes: &[BoundingBox]) -> Vec<(i32, i32)> {
+        let mut sorted = boxes.to_vec();
+        sort_reading_order(&mut sorted, 1000, 1000);
+        sorted.iter().map(|b| (b.x0, b.y0)).collect()
+    }
+
+    #[test]
+    fn vertical_bubble_lines_read_right_to_left() {
+        let boxes = [line(100, 10, 120, 200), line(125, 10, 145, 150)];
+
+        assert_eq!(order(&boxes), vec![(125, 10), (100, 10)]);
+    }
+
+    #[test]
+    fn manga_panels_read_by_row_then_right_to_left() {
+        // Two rows of panels, each with one two-line bubble; lower row
+        // bubbles sit further right than the upper-left bubble.
+        let top_right = [line(800, 50, 820, 300), line(825, 50, 845, 300)];
+        let top_left = [line(200, 60, 220, 300), line(225, 60, 245, 280)];
+        let bottom_right = [line(700, 600, 720, 900)];
+        let bottom_left = [line(100, 620, 120, 900), line(125, 620, 145, 880)];
+        let boxes: Vec<BoundingBox> = [bottom_left, top_left]
+            .concat()
+            .into_iter()
+            .chain(bottom_right)
+            .chain(top_right)
+            .collect();
+
+        let blocks = group_reading_order(boxes, 1000, 1000);
+
+        let starts: Vec<(i32, i32)> = blocks.iter().map(|b| (b[0].x0, b[0].y0)).collect();
+        assert_eq!(starts, vec![(825, 50), (225, 60), (700, 600), (125, 620)]);
+        assert_eq!(
+            blocks.iter().map(Vec::len).collect::<Vec<_>>(),
+            [2, 2, 1, 2]
+        );
+    }
+
+    #[test]
+    fn tall_right_panel_is_read_before_left_column() {
+        let tall = line(800, 0, 820, 900);
+        let left_top = line(100, 0, 120, 300);
+        let left_bottom = line(100, 500, 120, 800);
+
+        assert_eq!(
+            order(&[left_bottom, left_top, tall]),
+            vec![(800, 0), (100, 0), (100, 500)]
+        );
+    }
+
+    #[test]
+    fn horizontal_paragraph_stays_together_and_title_is_separate() {
+        let title = line(10, 10, 400, 60);
+        let first = line(10, 80, 500, 100);
+        let second = line(10, 104, 480, 124);
+        let boxes = vec![second.clone(), title, first];
+
+        let blocks = group_reading_order(boxes, 1000, 1000);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0][0].y0, 10);
+        assert_eq!(
+            blocks[1].iter().map(|b| b.y0).collect::<Vec<_>>(),
+            [80, 104]
+        );
+    }
+
+    #[test]
+    fn mixed_orientation_lines_never_share_a_block() {
+        let vertical = line(100, 10, 120, 200);
+        let horizontal = line(121, 10, 300, 30);
+
+        let blocks = group_reading_order(vec![vertical, horizontal], 1000, 1000);
+
+        assert_eq!(blocks.len(), 2);
     }
 }
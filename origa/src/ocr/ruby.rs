@@ -0,0 +1,178 @@
+use crate::domain::JapaneseChar;
+
+use super::types::{BoundingBox, OcrLine, TextOrientation};
+
+/// Ruby glyphs are about half the size of the text they annotate.
+const MAX_RUBY_SIZE_RATIO: f32 = 0.65;
+/// Furthest a ruby line sits from its base line, in base line thicknesses.
+const MAX_RUBY_GAP_RATIO: f32 = 0.5;
+/// How far a ruby box may reach into its base line (detector boxes overlap).
+const MAX_RUBY_OVERLAP_RATIO: f32 = 0.25;
+/// Share of the ruby length that must lie alongside the base line.
+const MIN_RUBY_ALONGSIDE: f32 = 0.8;
+
+/// Separates ruby (furigana) boxes from main text. Ruby sits to the right
+/// of a vertical line or above a horizontal one and is much thinner; such
+/// boxes would otherwise be read inline and pollute the text.
+pub(crate) fn split_ruby(boxes: Vec<BoundingBox>) -> (Vec<BoundingBox>, Vec<BoundingBox>) {
+    let is_ruby: Vec<bool> = boxes
+        .iter()
+        .enumerate()
+        .map(|(i, candidate)| {
+            boxes
+                .iter()
+                .enumerate()
+                .any(|(j, base)| i != j && is_ruby_of(candidate, base))
+        })
+        .collect();
+
+    let mut main = Vec::new();
+    let mut ruby = Vec::new();
+    for (bbox, ruby_box) in boxes.into_iter().zip(is_ruby) {
+        if ruby_box {
+            ruby.push(bbox);
+        } else {
+            main.push(bbox);
+        }
+    }
+    (main, ruby)
+}
+
+/// Ruby boxes annotating `base`, in its reading direction.
+pub(crate) fn ruby_of<'a>(base: &BoundingBox, ruby: &'a [BoundingBox]) -> Vec<&'a BoundingBox> {
+    let mut attached: Vec<&BoundingBox> = ruby.iter().filter(|r| is_ruby_of(r, base)).collect();
+    match base.orientation() {
+        TextOrientation::Vertical => attached.sort_by_key(|r| r.y0),
+        TextOrientation::Horizontal => attached.sort_by_key(|r| r.x0),
+    }
+    attached
+}
+
+fn is_ruby_of(ruby: &BoundingBox, base: &BoundingBox) -> bool {
+    // (thickness, gap from base, ruby span along the line, base span)
+    let (ruby_thickness, base_thickness, gap, ruby_span, base_span) = match base.orientation() {
+        TextOrientation::Vertical => (
+            ruby.x1 - ruby.x0,
+            base.x1 - base.x0,
+            ruby.x0 - base.x1,
+            (ruby.y0, ruby.y1),
+            (base.y0, base.y1),
+        ),
+        TextOrientation::Horizontal => (
+            ruby.y1 - ruby.y0,
+            base.y1 - base.y0,
+            base.y0 - ruby.y1,
+            (ruby.x0, ruby.x1),
+            (base.x0, base.x1),
+        ),
+    };
+    let base_thickness = base_thickness.max(1) as f32;
+
+    if ruby_thickness as f32 > MAX_RUBY_SIZE_RATIO * base_thickness {
+        return false;
+    }
+    let gap = gap as f32;
+    if gap > MAX_RUBY_GAP_RATIO * base_thickness || gap < -MAX_RUBY_OVERLAP_RATIO * base_thickness {
+        return false;
+    }
+
+    let alongside = ruby_span.1.min(base_span.1) - ruby_span.0.max(base_span.0);
+    let ruby_length = (ruby_span.1 - ruby_span.0).max(1) as f32;
+    alongside as f32 >= MIN_RUBY_ALONGSIDE * ruby_length
+}
+
+/// The kanji run of `line` that `ruby` most likely reads: the run nearest
+/// to where the ruby's centre falls along the line. Empty when the line has
+/// no kanji.
+pub(crate) fn ruby_base(line: &OcrLine, ruby: &BoundingBox) -> String {
+    let chars: Vec<char> = line.text.chars().collect();
+    let (start, end, ruby_center) = match line.orientation {
+        TextOrientation::Vertical => (line.bbox.y0, line.bbox.y1, (ruby.y0 + ruby.y1) / 2),
+        TextOrientation::Horizontal => (line.bbox.x0, line.bbox.x1, (ruby.x0 + ruby.x1) / 2),
+    };
+    let position = (ruby_center - start) as f32 / (end - start).max(1) as f32 * chars.len() as f32;
+
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (index, c) in chars.iter().enumerate() {
+        if !c.is_kanji() {
+            continue;
+        }
+        match runs.last_mut() {
+            Some((_, run_end)) if *run_end == index => *run_end = index + 1,
+            _ => runs.push((index, index + 1)),
+        }
+    }
+
+    let distance = |&(run_start, run_end): &(usize, usize)| {
+        if position < run_start as f32 {
+            run_start as f32 - position
+        } else {
+            (position - run_end as f32).max(0.0)
+        }
+    };
+    runs.iter()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .map(|&(run_start, run_end)| chars[run_start..run_end].iter().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::types::Recognition;
+
+    fn bbox(x0: i32, y0: i32, x1: i32, y1: i32) -> BoundingBox {
+        BoundingBox {
+            x0,
+            y0,
+            x1,
+            y1,
+            confidence: 1.0,
+            class_index: 0,
+            pred_char_cnt: 3.0,
+        }
+    }
+
+    #[test]
+    fn ruby_beside_vertical_column_is_split_off() {
+        let column = bbox(100, 0, 140, 400);
+        let ruby = bbox(142, 40, 160, 110);
+        let next_column = bbox(50, 0, 90, 400);
+
+        let (main, split) = split_ruby(vec![column.clone(), ruby, next_column]);
+
+        assert_eq!(main.len(), 2);
+        assert_eq!(split.len(), 1);
+        assert_eq!(split[0].x0, 142);
+        assert_eq!(ruby_of(&column, &split).len(), 1);
+        assert!(ruby_of(&main[1], &split).is_empty());
+    }
+
+    #[test]
+    fn ruby_above_horizontal_line_is_split_off() {
+        let line = bbox(0, 100, 400, 140);
+        let ruby = bbox(30, 80, 90, 98);
+        let below = bbox(40, 145, 100, 163);
+
+        let (main, split) = split_ruby(vec![line, ruby, below]);
+
+        assert_eq!(main.len(), 2);
+        assert_eq!(split.len(), 1);
+        assert_eq!(split[0].y0, 80);
+    }
+
+    #[test]
+    fn ruby_reads_nearest_kanji_run() {
+        // 明日は雨が降る, 7 characters over 0..350: 降 is at 250..300.
+        let line = OcrLine::new(
+            bbox(100, 0, 140, 350),
+            Recognition {
+                text: "明日は雨が降る".to_string(),
+                confidence: 1.0,
+            },
+        );
+
+        assert_eq!(ruby_base(&line, &bbox(142, 5, 160, 95)), "明日");
+        assert_eq!(ruby_base(&line, &bbox(142, 260, 160, 290)), "降");
+    }
+}
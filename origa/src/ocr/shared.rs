@@ -2,7 +2,7 @@ use crate::domain::OrigaError;
 use image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgb};
 use ort::session::SessionOutputs;
 
-use super::types::BoundingBox;
+use super::types::{BoundingBox, Recognition};
 use super::vocab::Vocabulary;
 
 pub(crate) const DEIM_CONF_THRESHOLD: f32 = 0.25;
@@ -198,7 +198,7 @@ pub(crate) fn parseq_preprocess(
     Ok(tensor)
 }
 
-pub(crate) fn parseq_postprocess(outputs: &SessionOutputs, vocab: &Vocabulary) -> String {
+pub(crate) fn parseq_postprocess(outputs: &SessionOutputs, vocab: &Vocabulary) -> Recognition {
     let logits_value = &outputs[0];
 
     let (shape, logits_data): (&ort::value::Shape, &[f32]) = match logits_value.try_extract_tensor()
@@ -206,36 +206,39 @@ pub(crate) fn parseq_postprocess(outputs: &SessionOutputs, vocab: &Vocabulary) -
         Ok(t) => t,
         Err(e) => {
             tracing::warn!("Failed to extract PARSeq output tensor: {:?}", e);
-            return String::new();
+            return Recognition::default();
         },
     };
 
     if shape.len() < 3 {
         tracing::warn!("Invalid PARSeq output shape: {:?}", shape);
-        return String::new();
+        return Recognition::default();
     }
 
     if shape[1] <= 0 || shape[2] <= 0 {
         tracing::warn!(?shape, "PARSeq output has dynamic or invalid dimensions");
-        return String::new();
+        return Recognition::default();
     }
 
     let seq_len = shape[1] as usize;
     let vocab_size = shape[2] as usize;
 
     let mut indices = Vec::with_capacity(seq_len);
+    let mut probabilities = Vec::with_capacity(seq_len);
 
     for t in 0..seq_len {
+        let row = &logits_data[t * vocab_size..(t + 1) * vocab_size];
         let mut max_idx = 0;
         let mut max_val = f32::NEG_INFINITY;
-        for v in 0..vocab_size {
-            let val = logits_data[t * vocab_size + v];
+        for (v, &val) in row.iter().enumerate() {
             if val > max_val {
                 max_val = val;
                 max_idx = v;
             }
         }
+        let sum: f32 = row.iter().map(|&val| (val - max_val).exp()).sum();
         indices.push(max_idx as i64);
+        probabilities.push(1.0 / sum);
     }
 
     let end_pos = indices
@@ -245,7 +248,23 @@ pub(crate) fn parseq_postprocess(outputs: &SessionOutputs, vocab: &Vocabulary) -
 
     let valid_indices: Vec<i64> = indices[..end_pos].to_vec();
 
-    vocab.decode(&valid_indices)
+    // The end token counts too: a confident early stop is part of the read.
+    let scored = &probabilities[..(end_pos + 1).min(seq_len)];
+    let confidence = if scored.is_empty() {
+        0.0
+    } else {
+        (scored
+            .iter()
+            .map(|p| p.max(f32::MIN_POSITIVE).ln())
+            .sum::<f32>()
+            / scored.len() as f32)
+            .exp()
+    };
+
+    Recognition {
+        text: vocab.decode(&valid_indices),
+        confidence,
+    }
 }
 
 pub struct ModelFiles {
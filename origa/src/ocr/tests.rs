@@ -104,4 +104,12 @@ async fn japanese_ocr_e2e() {
     assert!(result.contains("じゅう"), "should contain 'じゅう'");
     assert!(result.contains("入る"), "should contain '入る'");
     assert!(result.contains("どれですか"), "should contain 'どれですか'");
+
+    let structured = model.run_structured(&img).expect("Failed to run OCR");
+    assert_eq!(structured.text(), result);
+    assert!(structured.lines.iter().all(|line| {
+        line.recognition_confidence > 0.0
+            && line.recognition_confidence <= 1.0
+            && line.bbox.x1 > line.bbox.x0
+    }));
 }
@@ -1,4 +1,8 @@
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ReadingHint;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoundingBox {
     pub x0: i32,
     pub y0: i32,
@@ -8,3 +12,281 @@ pub struct BoundingBox {
     pub class_index: usize,
     pub pred_char_cnt: f32,
 }
+
+impl BoundingBox {
+    /// Taller-than-wide boxes hold vertical (tategaki) text; the recognizer
+    /// rotates those crops before reading.
+    pub fn orientation(&self) -> TextOrientation {
+        if (self.y1 - self.y0) > (self.x1 - self.x0) {
+            TextOrientation::Vertical
+        } else {
+            TextOrientation::Horizontal
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextOrientation {
+    Horizontal,
+    Vertical,
+}
+
+/// Text read from one line crop. `confidence` is the geometric mean of the
+/// per-character probabilities, so short and long lines compare fairly.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recognition {
+    pub text: String,
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrLine {
+    pub text: String,
+    pub bbox: BoundingBox,
+    pub recognition_confidence: f32,
+    pub orientation: TextOrientation,
+    /// Text block (speech bubble, caption, paragraph) the line belongs to;
+    /// lines of one block are read as one continuous sentence.
+    #[serde(default)]
+    pub block: usize,
+    /// Furigana printed beside the line; kept out of `text`.
+    #[serde(default)]
+    pub ruby: Vec<RubyText>,
+}
+
+/// A ruby (furigana) line and the kanji run it most likely reads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubyText {
+    pub reading: String,
+    pub base: String,
+    pub bbox: BoundingBox,
+}
+
+impl OcrLine {
+    pub(crate) fn new(bbox: BoundingBox, recognition: Recognition) -> Self {
+        Self {
+            text: recognition.text,
+            orientation: bbox.orientation(),
+            recognition_confidence: recognition.confidence,
+            bbox,
+            block: 0,
+            ruby: Vec::new(),
+        }
+    }
+}
+
+/// Lines of one image in reading order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OcrResult {
+    pub width: u32,
+    pub height: u32,
+    pub lines: Vec<OcrLine>,
+}
+
+impl OcrResult {
+    /// Joined text, one line per text block, as `JapaneseOCRModel::run`
+    /// returns it. Lines wrapped inside a speech bubble are glued back into
+    /// one sentence.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        let mut previous_block = None;
+        for line in &self.lines {
+            if previous_block.is_some_and(|block| block != line.block) {
+                text.push('\n');
+            }
+            text.push_str(&line.text);
+            previous_block = Some(line.block);
+        }
+        text
+    }
+
+    /// Drops regions the user marked as wrong (by index into `lines`).
+    pub fn without_lines(mut self, excluded: &[usize]) -> Self {
+        let mut index = 0;
+        self.lines.retain(|_| {
+            let keep = !excluded.contains(&index);
+            index += 1;
+            keep
+        });
+        self
+    }
+
+    /// Index of the line whose text contains `word`, to point the user at
+    /// the region a mined word came from.
+    pub fn line_containing(&self, word: &str) -> Option<usize> {
+        self.lines.iter().position(|line| line.text.contains(word))
+    }
+
+    /// Readings printed as ruby in the image, for
+    /// `annotate_text_with_hints`.
+    pub fn reading_hints(&self) -> Vec<ReadingHint> {
+        collect_hints(self.lines.iter())
+    }
+
+    /// Readings for text taken from line `index`: the ruby printed beside
+    /// that line first, then the rest of the page. The annotator takes the
+    /// first hint whose base matches, so a kanji read differently elsewhere
+    /// on the page keeps the reading printed next to it.
+    pub fn reading_hints_for_line(&self, index: usize) -> Vec<ReadingHint> {
+        let own = self.lines.get(index).into_iter();
+        let others = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(_, line)| line);
+        collect_hints(own.chain(others))
+    }
+
+    /// Replaces one line with a re-run result, e.g. after the user adjusted
+    /// its box. The line stays in its text block.
+    pub fn replace_line(&mut self, index: usize, line: OcrLine) {
+        if let Some(slot) = self.lines.get_mut(index) {
+            *slot = OcrLine {
+                block: slot.block,
+                ..line
+            };
+        }
+    }
+}
+
+fn collect_hints<'a>(lines: impl Iterator<Item = &'a OcrLine>) -> Vec<ReadingHint> {
+    let mut hints: Vec<ReadingHint> = Vec::new();
+    for ruby in lines.flat_map(|line| &line.ruby) {
+        let hint = ReadingHint {
+            base: ruby.base.clone(),
+            reading: ruby.reading.clone(),
+        };
+        if !hint.base.is_empty() && !hints.contains(&hint) {
+            hints.push(hint);
+        }
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str, block: usize, x0: i32, y0: i32, x1: i32, y1: i32) -> OcrLine {
+        let line = OcrLine::new(
+            BoundingBox {
+                x0,
+                y0,
+                x1,
+                y1,
+                confidence: 0.9,
+                class_index: 0,
+                pred_char_cnt: 3.0,
+            },
+            Recognition {
+                text: text.to_string(),
+                confidence: 0.8,
+            },
+        );
+        OcrLine { block, ..line }
+    }
+
+    fn result() -> OcrResult {
+        OcrResult {
+            width: 100,
+            height: 100,
+            lines: vec![
+                line("猫が好き", 0, 80, 0, 90, 60),
+                line("###", 1, 0, 0, 50, 5),
+                line("犬も好き", 2, 60, 0, 70, 60),
+            ],
+        }
+    }
+
+    #[test]
+    fn orientation_follows_box_shape() {
+        let result = result();
+
+        assert_eq!(result.lines[0].orientation, TextOrientation::Vertical);
+        assert_eq!(result.lines[1].orientation, TextOrientation::Horizontal);
+    }
+
+    #[test]
+    fn excluded_lines_leave_text_and_lookup() {
+        let result = result().without_lines(&[1]);
+
+        assert_eq!(result.text(), "猫が好き\n犬も好き");
+        assert_eq!(result.line_containing("犬"), Some(1));
+        assert_eq!(result.line_containing("鳥"), None);
+    }
+
+    #[test]
+    fn replaced_line_keeps_position() {
+        let mut result = result();
+
+        result.replace_line(1, line("鳥", 0, 0, 0, 50, 5));
+
+        assert_eq!(result.text(), "猫が好き\n鳥\n犬も好き");
+        assert_eq!(result.lines[1].block, 1);
+    }
+
+    #[test]
+    fn lines_of_one_bubble_join_into_one_sentence() {
+        let result = OcrResult {
+            width: 100,
+            height: 100,
+            lines: vec![
+                line("今日は", 0, 80, 0, 90, 60),
+                line("いい天気だね", 0, 60, 0, 70, 60),
+                line("うん", 1, 20, 0, 30, 30),
+            ],
+        };
+
+        assert_eq!(result.text(), "今日はいい天気だね\nうん");
+    }
+
+    #[test]
+    fn ruby_becomes_reading_hints_and_stays_out_of_text() {
+        let mut result = result();
+        let bbox = result.lines[1].bbox.clone();
+        let ruby = |reading: &str, base: &str| RubyText {
+            reading: reading.to_string(),
+            base: base.to_string(),
+            bbox: bbox.clone(),
+        };
+        result.lines[0].ruby = vec![ruby("ねこ", "猫"), ruby("す", "好")];
+        result.lines[2].ruby = vec![ruby("いぬ", "犬"), ruby("ねこ", "猫"), ruby("x", "")];
+
+        let hints = result.reading_hints();
+
+        assert_eq!(result.text(), "猫が好き\n###\n犬も好き");
+        assert_eq!(
+            hints
+                .iter()
+                .map(|h| (h.base.as_str(), h.reading.as_str()))
+                .collect::<Vec<_>>(),
+            [("猫", "ねこ"), ("好", "す"), ("犬", "いぬ")]
+        );
+    }
+
+    #[test]
+    fn line_hints_put_the_lines_own_ruby_first() {
+        let mut result = result();
+        let bbox = result.lines[1].bbox.clone();
+        let ruby = |reading: &str, base: &str| RubyText {
+            reading: reading.to_string(),
+            base: base.to_string(),
+            bbox: bbox.clone(),
+        };
+        result.lines[0].ruby = vec![ruby("じょうず", "上手")];
+        result.lines[2].ruby = vec![ruby("うわて", "上手"), ruby("いぬ", "犬")];
+
+        let readings =
+            |hints: Vec<ReadingHint>| hints.into_iter().map(|h| h.reading).collect::<Vec<_>>();
+
+        assert_eq!(
+            readings(result.reading_hints_for_line(2)),
+            ["うわて", "いぬ", "じょうず"]
+        );
+        assert_eq!(
+            readings(result.reading_hints_for_line(0)),
+            ["じょうず", "うわて", "いぬ"]
+        );
+    }
+}
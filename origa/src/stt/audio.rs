@@ -1,181 +1,122 @@
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
 
+use rustfft::{FftPlanner, num_complex::Complex32};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
 const TARGET_RATE: u32 = 16000;
 pub const TARGET_SAMPLES: usize = 480_000; // 30s × 16000 Hz
 
-#[cfg(not(target_arch = "wasm32"))]
-pub fn load_wav(path: &Path) -> Result<Vec<f32>, String> {
-    let mut reader =
-        hound::WavReader::open(path).map_err(|e| format!("Failed to open WAV: {}", e))?;
-    let spec = reader.spec();
-
-    tracing::info!(
-        sample_rate = spec.sample_rate,
-        channels = spec.channels,
-        bits_per_sample = spec.bits_per_sample,
-        "Loading WAV"
-    );
-
-    if spec.channels > 2 {
-        tracing::warn!(
-            channels = spec.channels,
-            "Unsupported channel count, only mono and stereo are supported. Using first channels."
-        );
-    }
+/// Zero crossings of the windowed-sinc kernel on each side of a sample.
+const RESAMPLE_ZERO_CROSSINGS: f64 = 8.0;
+/// Most filter phases precomputed; rates without a small common divisor
+/// with 16 kHz snap to the nearest of this many phases.
+const RESAMPLE_MAX_PHASES: u64 = 1024;
 
-    let samples = read_samples(&mut reader, &spec)?;
-    let mono = downmix_to_mono(&samples, spec.channels);
-    let resampled = resample(&mono, spec.sample_rate);
-    let result = pad_or_trim(&resampled, TARGET_SAMPLES);
+const VAD_FRAME: usize = 480; // 30 ms
+const VAD_FFT: usize = 512;
+const VAD_MIN_DB: f32 = -55.0;
+const VAD_FLOOR_MARGIN_DB: f32 = 12.0;
+const VAD_MIN_BAND_RATIO: f32 = 0.4;
+const VAD_MERGE_GAP_FRAMES: usize = 10; // 300 ms
+const VAD_MIN_SPEECH_FRAMES: usize = 8; // 240 ms
+const VAD_PAD_SAMPLES: usize = 3200; // 200 ms
+const VAD_MIN_CONFIDENCE: f32 = 0.6;
+const SPEECH_BAND_HZ: (f32, f32) = (300.0, 3400.0);
 
-    Ok(result)
+/// Loads a whole recording from disk as 16 kHz mono, in any format
+/// `load_audio_bytes` understands; the extension only helps the format probe.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_audio(path: &Path) -> Result<Vec<f32>, String> {
+    let data = std::fs::read(path).map_err(|e| format!("Failed to open audio: {}", e))?;
+    let extension = path.extension().and_then(|e| e.to_str());
+    decode_audio(data, extension)
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-fn read_samples(
-    reader: &mut hound::WavReader<std::io::BufReader<std::fs::File>>,
-    spec: &hound::WavSpec,
-) -> Result<Vec<f32>, String> {
-    match spec.sample_format {
-        hound::SampleFormat::Float => {
-            Ok(reader.samples::<f32>().map(|s| s.unwrap_or(0.0)).collect())
-        },
-        hound::SampleFormat::Int => {
-            let max_val = match spec.bits_per_sample {
-                8 => 128.0_f32,
-                16 => 32768.0,
-                24 => 8388608.0,
-                _ => 2147483648.0,
-            };
-            Ok(reader
-                .samples::<i32>()
-                .map(|s| s.map(|v| v as f32 / max_val).unwrap_or(0.0))
-                .collect())
-        },
-    }
-}
-
-#[cfg(target_arch = "wasm32")]
+/// Decodes WAV, MP3, AAC/M4A, FLAC or Ogg Vorbis to 16 kHz mono.
 pub fn load_audio_bytes(data: &[u8]) -> Result<Vec<f32>, String> {
-    if data.len() < 44 {
-        return Err("Audio data too short".to_string());
-    }
+    decode_audio(data.to_vec(), None)
+}
 
-    if &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
-        return Err("Not a WAV file".to_string());
+fn decode_audio(data: Vec<u8>, extension: Option<&str>) -> Result<Vec<f32>, String> {
+    let mut hint = Hint::new();
+    if let Some(extension) = extension {
+        hint.with_extension(extension);
     }
 
-    let mut fmt_offset: Option<usize> = None;
-    let mut data_offset: Option<usize> = None;
-    let mut data_len: usize = 0;
+    let source = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| format!("Unrecognized audio format: {}", e))?;
+    let mut format = probed.format;
 
-    let mut pos = 12usize;
-    while pos + 8 <= data.len() {
-        let chunk_id = &data[pos..pos + 4];
-        let chunk_size =
-            u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]])
-                as usize;
-        if chunk_size > data.len().saturating_sub(pos + 8) {
-            break;
-        }
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or("No audio track found")?;
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or("Audio track has no sample rate")?;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Unsupported audio codec: {}", e))?;
 
-        if chunk_id == b"fmt " {
-            fmt_offset = Some(pos + 8);
-        } else if chunk_id == b"data" {
-            data_offset = Some(pos + 8);
-            data_len = chunk_size;
-            break;
-        }
+    tracing::info!(
+        codec = ?track.codec_params.codec,
+        sample_rate,
+        channels = ?track.codec_params.channels,
+        "Decoding audio"
+    );
 
-        pos += 8 + chunk_size;
-        if pos % 2 != 0 {
-            pos += 1;
+    let mut mono = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            },
+            Err(e) => return Err(format!("Failed to read audio: {}", e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
         }
-    }
-
-    let fmt_off = fmt_offset.ok_or("No fmt chunk found in WAV")?;
-    let data_off = data_offset.ok_or("No data chunk found in WAV")?;
 
-    if data_off >= data.len() {
-        return Err("Audio data offset beyond file bounds".to_string());
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::DecodeError(reason)) => {
+                tracing::warn!(reason, "Skipping corrupt audio packet");
+                continue;
+            },
+            Err(e) => return Err(format!("Failed to decode audio: {}", e)),
+        };
+        let spec = *decoded.spec();
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buffer.copy_interleaved_ref(decoded);
+        mono.extend(downmix_to_mono(
+            buffer.samples(),
+            spec.channels.count() as u16,
+        ));
     }
 
-    if fmt_off + 16 > data.len() {
-        return Err("fmt chunk too short".to_string());
+    if mono.is_empty() {
+        return Err("Audio contains no samples".to_string());
     }
 
-    let audio_format = u16::from_le_bytes([data[fmt_off], data[fmt_off + 1]]);
-    let channels = u16::from_le_bytes([data[fmt_off + 2], data[fmt_off + 3]]);
-    let sample_rate = u32::from_le_bytes([
-        data[fmt_off + 4],
-        data[fmt_off + 5],
-        data[fmt_off + 6],
-        data[fmt_off + 7],
-    ]);
-    let bits_per_sample = u16::from_le_bytes([data[fmt_off + 14], data[fmt_off + 15]]);
-
-    tracing::info!(
-        format = audio_format,
-        channels = channels,
-        sample_rate = sample_rate,
-        bits_per_sample = bits_per_sample,
-        "Parsing WAV from bytes"
-    );
-
-    let samples_end = (data_off + data_len).min(data.len());
-    let sample_data = &data[data_off..samples_end];
-
-    let samples = match audio_format {
-        1 => decode_pcm(sample_data, bits_per_sample)?,
-        3 => sample_data
-            .chunks_exact(4)
-            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
-            .collect(),
-        _ => return Err(format!("Unsupported audio format: {}", audio_format)),
-    };
-
-    let mono = downmix_to_mono(&samples, channels);
-    let resampled = resample(&mono, sample_rate);
-    let result = pad_or_trim(&resampled, TARGET_SAMPLES);
-
-    Ok(result)
-}
-
-#[cfg(target_arch = "wasm32")]
-fn decode_pcm(sample_data: &[u8], bits_per_sample: u16) -> Result<Vec<f32>, String> {
-    match bits_per_sample {
-        8 => Ok(sample_data
-            .iter()
-            .map(|&b| (b as f32 - 128.0) / 128.0)
-            .collect()),
-        16 => {
-            let max_val = 32768.0_f32;
-            Ok(sample_data
-                .chunks_exact(2)
-                .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / max_val)
-                .collect())
-        },
-        24 => {
-            let max_val = 8388608.0_f32;
-            Ok(sample_data
-                .chunks_exact(3)
-                .map(|c| {
-                    let val = (c[0] as i32) | ((c[1] as i32) << 8) | ((c[2] as i32) << 16);
-                    let val = if val >= 0x800000 {
-                        val - 0x1000000
-                    } else {
-                        val
-                    };
-                    val as f32 / max_val
-                })
-                .collect())
-        },
-        _ => Err(format!(
-            "Unsupported PCM bits_per_sample: {}",
-            bits_per_sample
-        )),
-    }
+    Ok(resample(&mono, sample_rate))
 }
 
 pub(crate) fn downmix_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
@@ -189,26 +130,109 @@ pub(crate) fn downmix_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
     }
 }
 
+/// Band-limited (Hann-windowed sinc) resampling to 16 kHz. When
+/// downsampling, the cutoff drops to the new Nyquist frequency so
+/// content above 8 kHz does not alias into the speech band.
+///
+/// Output samples fall on a fixed set of fractional input positions, so the
+/// kernel is computed once per position (a polyphase filter table) instead
+/// of once per tap.
 pub(crate) fn resample(samples: &[f32], orig_rate: u32) -> Vec<f32> {
-    if orig_rate == TARGET_RATE {
+    if orig_rate == TARGET_RATE || samples.is_empty() {
         return samples.to_vec();
     }
 
-    let ratio = orig_rate as f64 / TARGET_RATE as f64;
-    let new_len = (samples.len() as f64 / ratio).round() as usize;
-    let last = samples.len() - 1;
+    // Output i sits at input position i * down / up.
+    let divisor = gcd(orig_rate as u64, TARGET_RATE as u64);
+    let up = TARGET_RATE as u64 / divisor;
+    let down = orig_rate as u64 / divisor;
+    let filter = PolyphaseFilter::new(orig_rate, up.min(RESAMPLE_MAX_PHASES));
+
+    let new_len = (samples.len() as u64 * up + down / 2) / down;
+    let last = samples.len() as isize - 1;
 
     (0..new_len)
         .map(|i| {
-            let src_idx = i as f64 * ratio;
-            let idx0 = src_idx.floor() as usize;
-            let idx1 = (idx0 + 1).min(last);
-            let frac = src_idx - idx0 as f64;
-            samples[idx0] * (1.0 - frac as f32) + samples[idx1] * frac as f32
+            let position = i * down;
+            let mut base = (position / up) as isize;
+            let mut phase = ((position % up) * filter.phases + up / 2) / up;
+            if phase == filter.phases {
+                base += 1;
+                phase = 0;
+            }
+
+            filter
+                .taps(phase as usize)
+                .iter()
+                .enumerate()
+                .map(|(k, weight)| {
+                    let j = base + k as isize - filter.reach;
+                    weight * samples[j.clamp(0, last) as usize]
+                })
+                .sum()
         })
         .collect()
 }
 
+/// Normalized kernel weights for each fractional input position.
+struct PolyphaseFilter {
+    phases: u64,
+    /// Taps on each side of the output position.
+    reach: isize,
+    table: Vec<f32>,
+}
+
+impl PolyphaseFilter {
+    fn new(orig_rate: u32, phases: u64) -> Self {
+        let ratio = orig_rate as f64 / TARGET_RATE as f64;
+        let cutoff = (1.0 / ratio).min(1.0);
+        let half_width = RESAMPLE_ZERO_CROSSINGS / cutoff;
+        let reach = half_width.ceil() as isize;
+        let width = 2 * reach as usize + 1;
+
+        let mut table = Vec::with_capacity(phases as usize * width);
+        for phase in 0..phases {
+            let fraction = phase as f64 / phases as f64;
+            let weights: Vec<f64> = (-reach..=reach)
+                .map(|k| {
+                    let distance = k as f64 - fraction;
+                    if distance.abs() > half_width {
+                        return 0.0;
+                    }
+                    let window = 0.5 * (1.0 + (std::f64::consts::PI * distance / half_width).cos());
+                    sinc(cutoff * distance) * window
+                })
+                .collect();
+            let sum: f64 = weights.iter().sum();
+            table.extend(weights.iter().map(|w| (w / sum) as f32));
+        }
+
+        Self {
+            phases,
+            reach,
+            table,
+        }
+    }
+
+    fn taps(&self, phase: usize) -> &[f32] {
+        let width = 2 * self.reach as usize + 1;
+        &self.table[phase * width..(phase + 1) * width]
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        let px = std::f64::consts::PI * x;
+        px.sin() / px
+    }
+}
+
 pub(crate) fn pad_or_trim(samples: &[f32], target_len: usize) -> Vec<f32> {
     if samples.len() >= target_len {
         samples[..target_len].to_vec()
@@ -218,3 +242,149 @@ pub(crate) fn pad_or_trim(samples: &[f32], target_len: usize) -> Vec<f32> {
         out
     }
 }
+
+/// A stretch of audio that looks like speech, in samples at 16 kHz.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechRegion {
+    start: usize,
+    end: usize,
+    confidence: f32,
+}
+
+impl SpeechRegion {
+    pub(crate) fn new(start: usize, end: usize, confidence: f32) -> Self {
+        Self {
+            start,
+            end,
+            confidence,
+        }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// 0..1: how much of the energy sits in the voice band, and how strongly
+    /// the loudness moves with syllables. Steady tones and music score low.
+    pub fn confidence(&self) -> f32 {
+        self.confidence
+    }
+}
+
+struct VadFrame {
+    energy_db: f32,
+    rms: f32,
+    band_ratio: f32,
+}
+
+/// Energy and spectral voice activity detection. Frames louder than the
+/// recording's noise floor with most energy in the voice band count as
+/// speech; short gaps are bridged, short blips dropped, and regions whose
+/// confidence stays below the threshold (music, hum) are discarded.
+pub(crate) fn detect_speech(samples: &[f32]) -> Vec<SpeechRegion> {
+    let frames = analyze_frames(samples);
+    if frames.is_empty() {
+        return Vec::new();
+    }
+
+    let mut sorted_db: Vec<f32> = frames.iter().map(|f| f.energy_db).collect();
+    sorted_db.sort_by(f32::total_cmp);
+    let noise_floor = sorted_db[sorted_db.len() / 10];
+    let threshold = (noise_floor + VAD_FLOOR_MARGIN_DB).max(VAD_MIN_DB);
+
+    let is_speech: Vec<bool> = frames
+        .iter()
+        .map(|f| f.energy_db >= threshold && f.band_ratio >= VAD_MIN_BAND_RATIO)
+        .collect();
+
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (index, _) in is_speech.iter().enumerate().filter(|(_, s)| **s) {
+        match runs.last_mut() {
+            Some((_, end)) if index - *end <= VAD_MERGE_GAP_FRAMES => *end = index + 1,
+            _ => runs.push((index, index + 1)),
+        }
+    }
+
+    let mut regions: Vec<SpeechRegion> = Vec::new();
+    for (first, last) in runs {
+        if last - first < VAD_MIN_SPEECH_FRAMES {
+            continue;
+        }
+        let confidence = region_confidence(&frames[first..last], &is_speech[first..last]);
+        if confidence < VAD_MIN_CONFIDENCE {
+            continue;
+        }
+
+        let start = (first * VAD_FRAME).saturating_sub(VAD_PAD_SAMPLES);
+        let end = (last * VAD_FRAME + VAD_PAD_SAMPLES).min(samples.len());
+        match regions.last_mut() {
+            Some(previous) if start <= previous.end => {
+                previous.end = end;
+                previous.confidence = previous.confidence.min(confidence);
+            },
+            _ => regions.push(SpeechRegion::new(start, end, confidence)),
+        }
+    }
+
+    tracing::debug!(
+        noise_floor,
+        regions = regions.len(),
+        "Voice activity detected"
+    );
+    regions
+}
+
+fn analyze_frames(samples: &[f32]) -> Vec<VadFrame> {
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(VAD_FFT);
+    let bin_hz = TARGET_RATE as f32 / VAD_FFT as f32;
+    let low_bin = (SPEECH_BAND_HZ.0 / bin_hz) as usize;
+    let high_bin = (SPEECH_BAND_HZ.1 / bin_hz) as usize;
+
+    samples
+        .chunks(VAD_FRAME)
+        .map(|frame| {
+            let power = frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32;
+
+            let mut buf = vec![Complex32::new(0.0, 0.0); VAD_FFT];
+            for (slot, &sample) in buf.iter_mut().zip(frame) {
+                slot.re = sample;
+            }
+            fft.process(&mut buf);
+            let spectrum: Vec<f32> = buf[..VAD_FFT / 2].iter().map(|c| c.norm_sqr()).collect();
+            let total: f32 = spectrum.iter().sum();
+            let band: f32 = spectrum[low_bin..=high_bin].iter().sum();
+
+            VadFrame {
+                energy_db: 10.0 * (power + 1e-10).log10(),
+                rms: power.sqrt(),
+                band_ratio: if total > 0.0 { band / total } else { 0.0 },
+            }
+        })
+        .collect()
+}
+
+/// Averages voice-band share over speech frames and the syllabic loudness
+/// modulation (coefficient of variation of frame RMS) over the region.
+fn region_confidence(frames: &[VadFrame], is_speech: &[bool]) -> f32 {
+    let speech_frames: Vec<&VadFrame> = frames
+        .iter()
+        .zip(is_speech)
+        .filter(|(_, s)| **s)
+        .map(|(f, _)| f)
+        .collect();
+    let band = speech_frames.iter().map(|f| f.band_ratio).sum::<f32>() / speech_frames.len() as f32;
+
+    let mean = frames.iter().map(|f| f.rms).sum::<f32>() / frames.len() as f32;
+    let variance = frames.iter().map(|f| (f.rms - mean).powi(2)).sum::<f32>() / frames.len() as f32;
+    let modulation = if mean > 0.0 {
+        (variance.sqrt() / mean).min(1.0)
+    } else {
+        0.0
+    };
+
+    (band + modulation) / 2.0
+}
@@ -26,21 +26,54 @@ fn resample_same_rate() {
 
 #[test]
 fn resample_downsample() {
-    let samples = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let samples: Vec<f32> = (0..480).map(|i| i as f32).collect();
     let result = resample(&samples, 48000);
-    assert_eq!(result.len(), 2);
-    assert!((result[0] - 0.0).abs() < 0.001);
-    assert!((result[1] - 3.0).abs() < 0.001);
+    assert_eq!(result.len(), 160);
+    assert!((result[0] - 0.0).abs() < 1.0);
+    assert!((result[80] - 240.0).abs() < 0.001);
 }
 
 #[test]
 fn resample_upsample() {
-    let samples = vec![0.0, 2.0, 4.0];
+    let samples: Vec<f32> = (0..100).map(|i| i as f32 * 2.0).collect();
     let result = resample(&samples, 8000);
-    assert_eq!(result.len(), 6);
-    assert!((result[0] - 0.0).abs() < 0.001);
-    assert!((result[1] - 1.0).abs() < 0.001);
-    assert!((result[2] - 2.0).abs() < 0.001);
+    assert_eq!(result.len(), 200);
+    assert!((result[100] - 100.0).abs() < 0.001);
+    assert!((result[101] - 101.0).abs() < 0.001);
+}
+
+#[test]
+fn resample_follows_fractional_positions_at_uneven_rates() {
+    for rate in [44100, 44101] {
+        let samples: Vec<f32> = (0..4410).map(|i| i as f32).collect();
+        let ratio = rate as f32 / 16000.0;
+
+        let result = resample(&samples, rate);
+
+        for i in [200, 777, 1200] {
+            assert!(
+                (result[i] - i as f32 * ratio).abs() < 0.01,
+                "{rate} Hz sample {i}: {}",
+                result[i]
+            );
+        }
+    }
+}
+
+#[test]
+fn resample_keeps_speech_band_and_removes_aliases() {
+    let rms = |s: &[f32]| (s.iter().map(|x| x * x).sum::<f32>() / s.len() as f32).sqrt();
+    let sine = |freq: f32| -> Vec<f32> {
+        (0..48000)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / 48000.0).sin())
+            .collect()
+    };
+
+    let voice = resample(&sine(1000.0), 48000);
+    let hiss = resample(&sine(12000.0), 48000);
+
+    assert!((rms(&voice[100..15900]) - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.02);
+    assert!(rms(&hiss[100..15900]) < 0.05);
 }
 
 #[test]
@@ -67,3 +100,101 @@ fn pad_or_trim_exact() {
     let result = pad_or_trim(&samples, 3);
     assert_eq!(result, vec![1.0, 2.0, 3.0]);
 }
+
+fn tone(freq: f32, seconds: f32, amplitude: f32) -> Vec<f32> {
+    (0..(seconds * 16000.0) as usize)
+        .map(|i| amplitude * (2.0 * std::f32::consts::PI * freq * i as f32 / 16000.0).sin())
+        .collect()
+}
+
+/// Voice-band tone switched on and off at syllable rate (~4 Hz).
+fn syllables(seconds: f32) -> Vec<f32> {
+    let mut samples = tone(800.0, seconds, 0.3);
+    for (i, s) in samples.iter_mut().enumerate() {
+        if (i / 2000) % 2 == 1 {
+            *s *= 0.02;
+        }
+    }
+    samples
+}
+
+#[test]
+fn detect_speech_finds_region_between_silences() {
+    let mut samples = vec![0.0; 32000];
+    samples.extend(syllables(2.0));
+    samples.extend(vec![0.0; 32000]);
+
+    let regions = detect_speech(&samples);
+
+    assert_eq!(regions.len(), 1);
+    assert!(regions[0].start() <= 32000 && regions[0].start() >= 32000 - 3200 - 480);
+    assert!(regions[0].end() >= 64000 && regions[0].end() <= 64000 + 3200 + 480);
+    assert!(regions[0].confidence() > 0.6);
+}
+
+#[test]
+fn detect_speech_ignores_silence() {
+    let samples = vec![0.0; 48000];
+
+    assert!(detect_speech(&samples).is_empty());
+}
+
+#[test]
+fn detect_speech_drops_steady_tone_and_bass() {
+    let mut samples = tone(1000.0, 2.0, 0.3);
+    samples.extend(vec![0.0; 16000]);
+    samples.extend(tone(80.0, 2.0, 0.3));
+
+    assert!(detect_speech(&samples).is_empty());
+}
+
+#[test]
+fn detect_speech_separates_distant_phrases() {
+    let mut samples = syllables(1.0);
+    samples.extend(vec![0.0; 32000]);
+    samples.extend(syllables(1.0));
+
+    let regions = detect_speech(&samples);
+
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[1].start() / 16, 2800);
+}
+
+fn wav_bytes(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::new();
+    bytes.extend(b"RIFF");
+    bytes.extend((36 + data_len).to_le_bytes());
+    bytes.extend(b"WAVEfmt ");
+    bytes.extend(16u32.to_le_bytes());
+    bytes.extend(1u16.to_le_bytes());
+    bytes.extend(channels.to_le_bytes());
+    bytes.extend(sample_rate.to_le_bytes());
+    bytes.extend((sample_rate * channels as u32 * 2).to_le_bytes());
+    bytes.extend((channels * 2).to_le_bytes());
+    bytes.extend(16u16.to_le_bytes());
+    bytes.extend(b"data");
+    bytes.extend(data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend(sample.to_le_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn load_audio_bytes_decodes_stereo_wav_to_16k_mono() {
+    let frames = 44100;
+    let samples: Vec<i16> = (0..frames).flat_map(|_| [16384, 0]).collect();
+
+    let result = load_audio_bytes(&wav_bytes(44100, 2, &samples)).unwrap();
+
+    assert_eq!(result.len(), 16000);
+    assert!((result[8000] - 0.25).abs() < 0.001);
+}
+
+#[test]
+fn load_audio_bytes_rejects_unknown_data() {
+    let result = load_audio_bytes(b"definitely not an audio file at all");
+
+    assert!(result.is_err());
+}
@@ -1,9 +1,16 @@
 use crate::domain::OrigaError;
+use crate::stt::decoding::TimestampRules;
+use crate::stt::kv_cache::CachedTensor;
 use crate::stt::tokenizer::WhisperTokenizer;
 
 pub const MAX_DECODE_TOKENS: usize = 220;
 
-pub fn build_prompt_tokens(tokenizer: &WhisperTokenizer) -> Result<Vec<i64>, OrigaError> {
+/// Prompt for Japanese transcription. With `timestamps` the model brackets
+/// each phrase in `<|t|>` tokens instead of emitting plain text.
+pub fn build_prompt_tokens(
+    tokenizer: &WhisperTokenizer,
+    timestamps: bool,
+) -> Result<Vec<i64>, OrigaError> {
     let lookup = |name: &str| {
         tokenizer
             .token_to_id(name)
@@ -12,15 +19,95 @@ pub fn build_prompt_tokens(tokenizer: &WhisperTokenizer) -> Result<Vec<i64>, Ori
             })
     };
 
-    Ok(vec![
+    let mut tokens = vec![
         lookup("<|startoftranscript|>")?,
         lookup("<|ja|>")?,
         lookup("<|transcribe|>")?,
-        lookup("<|notimestamps|>")?,
-    ])
+    ];
+    if !timestamps {
+        tokens.push(lookup("<|notimestamps|>")?);
+    }
+    Ok(tokens)
+}
+
+/// Id of `<|0.00|>`; every later id is a timestamp. Older tokenizer exports
+/// lack these tokens, in which case transcription falls back to plain text.
+pub fn timestamp_begin(tokenizer: &WhisperTokenizer) -> Option<i64> {
+    tokenizer.token_to_id("<|0.00|>")
+}
+
+/// Timestamp rules for the tokenizer, when it has timestamp tokens.
+pub fn timestamp_rules(tokenizer: &WhisperTokenizer) -> Option<TimestampRules> {
+    Some(TimestampRules {
+        begin: timestamp_begin(tokenizer)?,
+        no_timestamps: tokenizer.token_to_id("<|notimestamps|>"),
+    })
 }
 
-pub fn argmax_last_position(logits: &ort::value::Value) -> Result<i64, OrigaError> {
+/// Builds an input tensor; shared by both runtimes.
+pub fn tensor_value<T>(shape: Vec<usize>, data: Vec<T>) -> Result<ort::value::DynValue, OrigaError>
+where
+    T: ort::value::PrimitiveTensorElementType + Clone + std::fmt::Debug + 'static,
+{
+    ort::value::Tensor::from_array((shape, data))
+        .map(|tensor| tensor.into_dyn())
+        .map_err(|e| OrigaError::SttError {
+            reason: format!("Tensor: {:?}", e),
+        })
+}
+
+/// The `present.*` key/value outputs of a decoder run, for `KvCache`.
+pub fn present_tensors(
+    outputs: &ort::session::SessionOutputs,
+) -> Result<Vec<CachedTensor>, OrigaError> {
+    outputs
+        .iter()
+        .filter(|(name, _)| name.starts_with("present."))
+        .map(|(name, value)| {
+            let (shape, data): (&ort::value::Shape, &[f32]) =
+                value
+                    .try_extract_tensor()
+                    .map_err(|e| OrigaError::SttError {
+                        reason: format!("Extract {}: {:?}", name, e),
+                    })?;
+            Ok(CachedTensor {
+                name: name.to_string(),
+                shape: shape.iter().map(|&d| d as usize).collect(),
+                data: data.to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// Encoder output repeated for a batch of `rows` beams. Decoders build it
+/// only when the beam count changes, not every step.
+pub fn hidden_states_value(
+    hidden_states: &ndarray::Array3<f32>,
+    rows: usize,
+) -> Result<ort::value::DynValue, OrigaError> {
+    let mut shape = hidden_states.shape().to_vec();
+    shape[0] = rows;
+    let data = hidden_states
+        .iter()
+        .copied()
+        .collect::<Vec<f32>>()
+        .repeat(rows);
+    tensor_value(shape, data)
+}
+
+/// Named decoder inputs for cached key/value tensors.
+pub fn cache_values(
+    tensors: Vec<CachedTensor>,
+) -> Result<Vec<(String, ort::value::DynValue)>, OrigaError> {
+    tensors
+        .into_iter()
+        .map(|tensor| Ok((tensor.name, tensor_value(tensor.shape, tensor.data)?)))
+        .collect()
+}
+
+/// Logits of the last position for every row of a `[batch, seq, vocab]`
+/// decoder output.
+pub fn last_position_logits(logits: &ort::value::Value) -> Result<Vec<Vec<f32>>, OrigaError> {
     let (shape, data): (&ort::value::Shape, &[f32]) =
         logits
             .try_extract_tensor()
@@ -28,25 +115,21 @@ pub fn argmax_last_position(logits: &ort::value::Value) -> Result<i64, OrigaErro
                 reason: format!("Extract logits: {:?}", e),
             })?;
 
+    let batch = shape[0] as usize;
     let seq_len = shape[1] as usize;
     let vocab_size = shape[2] as usize;
-    if seq_len == 0 || vocab_size == 0 {
+    if batch == 0 || seq_len == 0 || vocab_size == 0 {
         return Err(OrigaError::SttError {
             reason: "Invalid logits shape".to_string(),
         });
     }
-    let offset = (seq_len - 1) * vocab_size;
-
-    let mut best_id = 0i64;
-    let mut best_val = f32::NEG_INFINITY;
-    for (i, &val) in data[offset..].iter().enumerate() {
-        if val > best_val {
-            best_val = val;
-            best_id = i as i64;
-        }
-    }
 
-    Ok(best_id)
+    Ok((0..batch)
+        .map(|row| {
+            let offset = (row * seq_len + seq_len - 1) * vocab_size;
+            data[offset..offset + vocab_size].to_vec()
+        })
+        .collect())
 }
 
 pub fn strip_trailing_repeats(text: &str) -> String {
@@ -0,0 +1,544 @@
+use std::borrow::Cow;
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::common::MAX_DECODE_TOKENS;
+
+/// Fixed seed so sampled fallbacks give the same text on every target.
+const SAMPLING_SEED: u64 = 0x5EED;
+
+/// Latest timestamp allowed as the first token, in 20 ms steps (1 s), as in
+/// reference Whisper's `max_initial_timestamp`.
+const MAX_INITIAL_TIMESTAMP_STEPS: usize = 50;
+
+/// Decoding settings, defaulting to the reference Whisper transcribe values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodingOptions {
+    /// Beams kept at temperature 0; 1 is plain greedy decoding.
+    pub beam_size: usize,
+    /// Tried in order until a result passes both thresholds.
+    pub temperatures: Vec<f32>,
+    /// Results with a lower average token log-probability are retried.
+    pub logprob_threshold: f32,
+    /// Results whose text gzips better than this (repetition loops) are retried.
+    pub compression_ratio_threshold: f32,
+    pub max_tokens: usize,
+}
+
+impl Default for DecodingOptions {
+    fn default() -> Self {
+        Self {
+            beam_size: 5,
+            temperatures: vec![0.0, 0.2, 0.4, 0.6, 0.8, 1.0],
+            logprob_threshold: -1.0,
+            compression_ratio_threshold: 2.4,
+            max_tokens: MAX_DECODE_TOKENS,
+        }
+    }
+}
+
+impl DecodingOptions {
+    /// Whether a decoded text should be thrown away and retried hotter.
+    pub(crate) fn needs_fallback(&self, text: &str, avg_logprob: f32) -> bool {
+        compression_ratio(text) > self.compression_ratio_threshold
+            || avg_logprob < self.logprob_threshold
+    }
+}
+
+/// Whisper's constraints on timestamp tokens (`ApplyTimestampRules` in the
+/// reference decoder): the first token is a timestamp within the first
+/// second, timestamps come in pairs closing and opening a segment, never go
+/// backwards, and win outright once their summed probability beats every
+/// single text token. Without them beam search happily emits text with no
+/// timestamps or segments that end before they start.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TimestampRules {
+    /// Id of `<|0.00|>`; every later id is a timestamp.
+    pub begin: i64,
+    pub no_timestamps: Option<i64>,
+}
+
+impl TimestampRules {
+    /// Masks the logits of one row whose generated tokens (prompt excluded)
+    /// are `generated`.
+    fn apply(&self, generated: &[i64], eos_id: i64, logits: &mut [f32]) {
+        let len = logits.len();
+        let begin = (self.begin as usize).min(len);
+        let is_timestamp = |token: i64| token >= self.begin;
+
+        if let Some(slot) = self
+            .no_timestamps
+            .and_then(|id| logits.get_mut(id as usize))
+        {
+            *slot = f32::NEG_INFINITY;
+        }
+
+        let last_was_timestamp = generated.last().is_some_and(|&t| is_timestamp(t));
+        let penultimate_was_timestamp =
+            generated.len() < 2 || is_timestamp(generated[generated.len() - 2]);
+        if last_was_timestamp {
+            if penultimate_was_timestamp {
+                logits[begin..].fill(f32::NEG_INFINITY);
+            } else {
+                logits[..(eos_id as usize).min(len)].fill(f32::NEG_INFINITY);
+            }
+        }
+
+        if let Some(&last) = generated.iter().rev().find(|&&t| is_timestamp(t)) {
+            let end = if last_was_timestamp && !penultimate_was_timestamp {
+                last
+            } else {
+                last + 1
+            };
+            logits[begin..(end as usize).clamp(begin, len)].fill(f32::NEG_INFINITY);
+        }
+
+        if generated.is_empty() {
+            logits[..begin].fill(f32::NEG_INFINITY);
+            let last_allowed = begin + MAX_INITIAL_TIMESTAMP_STEPS;
+            logits[(last_allowed + 1).min(len)..].fill(f32::NEG_INFINITY);
+        }
+
+        let logprobs = log_softmax(logits, 1.0);
+        let timestamp_logprob = log_sum_exp(&logprobs[begin..]);
+        let max_text_logprob = logprobs[..begin]
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        if timestamp_logprob > max_text_logprob {
+            logits[..begin].fill(f32::NEG_INFINITY);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Beam {
+    tokens: Vec<i64>,
+    sum_logprob: f32,
+}
+
+impl Beam {
+    fn score(&self, prompt_len: usize) -> f32 {
+        self.sum_logprob / (self.tokens.len() - prompt_len + 1) as f32
+    }
+}
+
+/// Generated tokens without the prompt, with their average log-probability.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DecodedSequence {
+    pub tokens: Vec<i64>,
+    pub avg_logprob: f32,
+}
+
+/// Model-agnostic decoding state for one temperature. The caller runs the
+/// decoder on `sequences()` as one batch and feeds the last-position logits
+/// of every row back into `step()` until `is_done()`. Temperature 0 is beam
+/// search; higher temperatures sample a single sequence. A caller keeping a
+/// KV cache reorders it by `parents()` after every step and feeds only
+/// `last_tokens()`.
+pub(crate) struct Decoder {
+    beams: Vec<Beam>,
+    /// For every beam, the index of the beam it extended in the last step.
+    parents: Vec<usize>,
+    finished: Vec<Beam>,
+    timestamp_rules: Option<TimestampRules>,
+    prompt_len: usize,
+    eos_id: i64,
+    temperature: f32,
+    beam_size: usize,
+    max_tokens: usize,
+    steps: usize,
+    rng: StdRng,
+}
+
+impl Decoder {
+    pub fn new(prompt: Vec<i64>, eos_id: i64, options: &DecodingOptions, temperature: f32) -> Self {
+        let beam_size = if temperature > 0.0 {
+            1
+        } else {
+            options.beam_size.max(1)
+        };
+        Self {
+            prompt_len: prompt.len(),
+            beams: vec![Beam {
+                tokens: prompt,
+                sum_logprob: 0.0,
+            }],
+            parents: vec![0],
+            finished: Vec::new(),
+            timestamp_rules: None,
+            eos_id,
+            temperature,
+            beam_size,
+            max_tokens: options.max_tokens,
+            steps: 0,
+            rng: StdRng::seed_from_u64(SAMPLING_SEED),
+        }
+    }
+
+    pub fn with_timestamp_rules(mut self, rules: TimestampRules) -> Self {
+        self.timestamp_rules = Some(rules);
+        self
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.beams.is_empty()
+            || self.finished.len() >= self.beam_size
+            || self.steps >= self.max_tokens
+    }
+
+    /// Current sequences; all have the same length, so they form one batch.
+    pub fn sequences(&self) -> Vec<&[i64]> {
+        self.beams.iter().map(|b| b.tokens.as_slice()).collect()
+    }
+
+    /// Newest token of every beam: the only decoder input once earlier
+    /// positions are held in a KV cache.
+    pub fn last_tokens(&self) -> Vec<i64> {
+        self.beams
+            .iter()
+            .map(|b| b.tokens.last().copied().unwrap_or_default())
+            .collect()
+    }
+
+    /// Row of the previous batch each current beam continues; a KV cache is
+    /// gathered by these indices before the next step.
+    pub fn parents(&self) -> &[usize] {
+        &self.parents
+    }
+
+    pub fn step(&mut self, logits: &[Vec<f32>]) {
+        self.steps += 1;
+
+        let mut candidates: Vec<(usize, i64, f32)> = Vec::new();
+        for (index, (beam, row)) in self.beams.iter().zip(logits).enumerate() {
+            let row = match &self.timestamp_rules {
+                Some(rules) => {
+                    let mut masked = row.clone();
+                    rules.apply(&beam.tokens[self.prompt_len..], self.eos_id, &mut masked);
+                    Cow::Owned(masked)
+                },
+                None => Cow::Borrowed(row.as_slice()),
+            };
+            let logprobs = log_softmax(&row, 1.0);
+            if self.temperature > 0.0 {
+                let token = sample(&log_softmax(&row, self.temperature), &mut self.rng);
+                candidates.push((index, token, beam.sum_logprob + logprobs[token as usize]));
+            } else {
+                for token in top_k(&logprobs, self.beam_size + 1) {
+                    candidates.push((index, token, beam.sum_logprob + logprobs[token as usize]));
+                }
+            }
+        }
+        // Tokens masked by the timestamp rules can still fill out a top-k.
+        candidates.retain(|candidate| candidate.2.is_finite());
+        candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+        let mut next = Vec::with_capacity(self.beam_size);
+        let mut parents = Vec::with_capacity(self.beam_size);
+        for (index, token, sum_logprob) in candidates {
+            let mut tokens = self.beams[index].tokens.clone();
+            if token == self.eos_id {
+                if self.finished.len() < self.beam_size {
+                    self.finished.push(Beam {
+                        tokens,
+                        sum_logprob,
+                    });
+                }
+                continue;
+            }
+            tokens.push(token);
+            next.push(Beam {
+                tokens,
+                sum_logprob,
+            });
+            parents.push(index);
+            if next.len() == self.beam_size {
+                break;
+            }
+        }
+        self.beams = next;
+        self.parents = parents;
+    }
+
+    /// Best sequence by length-normalised log-probability; unfinished beams
+    /// compete too when the token limit was hit before enough beams ended.
+    pub fn finish(self) -> DecodedSequence {
+        let prompt_len = self.prompt_len;
+        let mut pool = self.finished;
+        if pool.len() < self.beam_size {
+            pool.extend(self.beams);
+        }
+
+        pool.into_iter()
+            .max_by(|a, b| a.score(prompt_len).total_cmp(&b.score(prompt_len)))
+            .map(|best| DecodedSequence {
+                avg_logprob: best.score(prompt_len),
+                tokens: best.tokens[prompt_len..].to_vec(),
+            })
+            .unwrap_or(DecodedSequence {
+                tokens: Vec::new(),
+                avg_logprob: 0.0,
+            })
+    }
+}
+
+/// Text length over its zlib-compressed length; repetition loops compress
+/// unusually well.
+pub(crate) fn compression_ratio(text: &str) -> f32 {
+    if text.is_empty() {
+        return 0.0;
+    }
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    if encoder.write_all(text.as_bytes()).is_err() {
+        return 0.0;
+    }
+    match encoder.finish() {
+        Ok(compressed) if !compressed.is_empty() => text.len() as f32 / compressed.len() as f32,
+        _ => 0.0,
+    }
+}
+
+fn log_softmax(logits: &[f32], temperature: f32) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let sum: f32 = logits
+        .iter()
+        .map(|&l| ((l - max) / temperature).exp())
+        .sum();
+    let log_sum = sum.ln();
+    logits
+        .iter()
+        .map(|&l| (l - max) / temperature - log_sum)
+        .collect()
+}
+
+fn log_sum_exp(logprobs: &[f32]) -> f32 {
+    let max = logprobs.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max == f32::NEG_INFINITY {
+        return max;
+    }
+    max + logprobs.iter().map(|&l| (l - max).exp()).sum::<f32>().ln()
+}
+
+fn top_k(logprobs: &[f32], k: usize) -> Vec<i64> {
+    let mut indices: Vec<usize> = (0..logprobs.len()).collect();
+    let k = k.min(indices.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    indices.select_nth_unstable_by(k - 1, |&a, &b| logprobs[b].total_cmp(&logprobs[a]));
+    indices.truncate(k);
+    indices.into_iter().map(|i| i as i64).collect()
+}
+
+fn sample(logprobs: &[f32], rng: &mut StdRng) -> i64 {
+    let mut target: f32 = rng.random();
+    for (index, &logprob) in logprobs.iter().enumerate() {
+        target -= logprob.exp();
+        if target <= 0.0 {
+            return index as i64;
+        }
+    }
+    logprobs.len() as i64 - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EOS: i64 = 3;
+
+    fn run(decoder: &mut Decoder, script: impl Fn(&[i64]) -> Vec<f32>) {
+        while !decoder.is_done() {
+            let logits: Vec<Vec<f32>> = decoder.sequences().into_iter().map(&script).collect();
+            decoder.step(&logits);
+        }
+    }
+
+    #[test]
+    fn beam_search_finds_sequence_greedy_misses() {
+        // Greedy takes 1 (0.55) and then never gets a confident ending; 2
+        // (0.43) is followed by a near-certain EOS and wins overall.
+        let script = |tokens: &[i64]| match tokens.get(1) {
+            None => [0.01f32, 0.55, 0.43, 0.01].map(f32::ln).to_vec(),
+            Some(1) => [0.4f32, 0.3, 0.2, 0.1].map(f32::ln).to_vec(),
+            _ => [0.0033f32, 0.0033, 0.0034, 0.99].map(f32::ln).to_vec(),
+        };
+        let options = DecodingOptions {
+            max_tokens: 5,
+            ..DecodingOptions::default()
+        };
+
+        let mut greedy = Decoder::new(
+            vec![0],
+            EOS,
+            &DecodingOptions {
+                beam_size: 1,
+                ..options.clone()
+            },
+            0.0,
+        );
+        run(&mut greedy, script);
+        let mut beam = Decoder::new(vec![0], EOS, &options, 0.0);
+        run(&mut beam, script);
+
+        assert_eq!(greedy.finish().tokens, vec![1, 0, 0, 0, 0]);
+        let best = beam.finish();
+        assert_eq!(best.tokens, vec![2]);
+        assert!(best.avg_logprob > -0.5);
+    }
+
+    #[test]
+    fn decoding_stops_at_token_limit() {
+        let options = DecodingOptions {
+            max_tokens: 4,
+            ..DecodingOptions::default()
+        };
+        let mut decoder = Decoder::new(vec![0], EOS, &options, 0.0);
+
+        run(&mut decoder, |_| vec![-10.0, 0.0, -10.0, -10.0]);
+
+        assert_eq!(decoder.finish().tokens, vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn sampling_is_reproducible() {
+        let options = DecodingOptions::default();
+        let script = |_: &[i64]| vec![0.0, 0.0, 0.0, -1.0];
+
+        let mut first = Decoder::new(vec![0], EOS, &options, 1.0);
+        run(&mut first, script);
+        let mut second = Decoder::new(vec![0], EOS, &options, 1.0);
+        run(&mut second, script);
+
+        assert_eq!(first.finish(), second.finish());
+    }
+
+    #[test]
+    fn repetition_loop_triggers_fallback() {
+        let options = DecodingOptions::default();
+
+        assert!(options.needs_fallback(&"ですです".repeat(30), -0.2));
+        assert!(options.needs_fallback("猫が好きです", -1.5));
+        assert!(!options.needs_fallback("猫が好きです", -0.2));
+    }
+
+    const BEGIN: i64 = 4;
+    const RULES: TimestampRules = TimestampRules {
+        begin: BEGIN,
+        no_timestamps: None,
+    };
+
+    /// Text tokens 0..4 (3 is EOS) clearly ahead of 100 timestamp tokens, so
+    /// only the masking rules, not the probability-mass rule, decide.
+    fn masked(generated: &[i64]) -> Vec<bool> {
+        let mut logits = vec![10.0f32; BEGIN as usize];
+        logits.extend(vec![0.0f32; 100]);
+        RULES.apply(generated, EOS, &mut logits);
+        logits.iter().map(|l| *l == f32::NEG_INFINITY).collect()
+    }
+
+    #[test]
+    fn first_token_is_a_timestamp_within_the_first_second() {
+        let masked = masked(&[]);
+
+        assert!(masked[..BEGIN as usize].iter().all(|&m| m));
+        assert!(!masked[BEGIN as usize + MAX_INITIAL_TIMESTAMP_STEPS]);
+        assert!(masked[BEGIN as usize + MAX_INITIAL_TIMESTAMP_STEPS + 1]);
+    }
+
+    #[test]
+    fn timestamps_never_go_backwards() {
+        let masked = masked(&[BEGIN + 10, 1]);
+
+        assert!(masked[BEGIN as usize + 10]);
+        assert!(!masked[BEGIN as usize + 11]);
+        assert!(!masked[1]);
+    }
+
+    #[test]
+    fn closing_timestamp_is_followed_by_an_opening_one_or_eos() {
+        let masked = masked(&[BEGIN, 1, BEGIN + 12]);
+
+        assert!(masked[..EOS as usize].iter().all(|&m| m));
+        assert!(!masked[EOS as usize]);
+        assert!(masked[BEGIN as usize + 11]);
+        assert!(!masked[BEGIN as usize + 12]);
+    }
+
+    #[test]
+    fn timestamp_pair_is_followed_by_text() {
+        let masked = masked(&[BEGIN, 1, BEGIN + 12, BEGIN + 12]);
+
+        assert!(masked[BEGIN as usize..].iter().all(|&m| m));
+        assert!(!masked[1]);
+    }
+
+    #[test]
+    fn timestamp_mass_beats_single_text_token() {
+        let mut logits = vec![1.0f32; BEGIN as usize];
+        logits.extend(vec![0.5f32; 100]);
+
+        RULES.apply(&[BEGIN, 1], EOS, &mut logits);
+
+        assert!(
+            logits[..BEGIN as usize]
+                .iter()
+                .all(|l| *l == f32::NEG_INFINITY)
+        );
+    }
+
+    #[test]
+    fn beam_search_with_timestamp_rules_brackets_text() {
+        // Unconstrained, the model would answer text and stop at once.
+        let options = DecodingOptions {
+            max_tokens: 6,
+            ..DecodingOptions::default()
+        };
+        let mut decoder = Decoder::new(vec![0], EOS, &options, 0.0).with_timestamp_rules(RULES);
+        let vocab = BEGIN as usize + 100;
+
+        run(&mut decoder, |tokens| {
+            let mut logits = vec![-5.0f32; vocab];
+            logits[1] = if tokens.len() < 3 { 2.0 } else { -5.0 };
+            logits[EOS as usize] = if tokens.len() < 3 { 1.0 } else { 3.0 };
+            logits[BEGIN as usize + 20] = 0.0;
+            logits
+        });
+
+        let tokens = decoder.finish().tokens;
+        assert_eq!(tokens.first(), Some(&(BEGIN + 20)));
+        assert!(tokens.contains(&1));
+        assert!(
+            tokens
+                .windows(2)
+                .all(|pair| pair[0] < BEGIN || pair[1] < BEGIN || pair[0] <= pair[1])
+        );
+    }
+
+    #[test]
+    fn parents_track_the_beam_each_row_extends() {
+        let options = DecodingOptions {
+            beam_size: 2,
+            max_tokens: 2,
+            ..DecodingOptions::default()
+        };
+        let mut decoder = Decoder::new(vec![0], EOS, &options, 0.0);
+
+        decoder.step(&[[-9.0f32, -0.5, -1.0, -9.0].to_vec()]);
+        assert_eq!(decoder.parents(), [0, 0]);
+        assert_eq!(decoder.last_tokens(), [1, 2]);
+
+        // Both best continuations grow the second beam.
+        decoder.step(&[
+            [-9.0f32, -9.0, -9.0, -9.0].to_vec(),
+            [-9.0f32, -0.1, -0.2, -9.0].to_vec(),
+        ]);
+        assert_eq!(decoder.parents(), [1, 1]);
+        assert_eq!(decoder.last_tokens(), [1, 2]);
+    }
+}
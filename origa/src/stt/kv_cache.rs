@@ -0,0 +1,186 @@
+use crate::domain::OrigaError;
+
+/// One `past_key_values.*` decoder input: a `[batch, heads, positions,
+/// head_dim]` tensor, batch first.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CachedTensor {
+    pub name: String,
+    pub shape: Vec<usize>,
+    pub data: Vec<f32>,
+}
+
+impl CachedTensor {
+    fn row_len(&self) -> usize {
+        self.shape.iter().skip(1).product()
+    }
+
+    /// The batch rows `rows`, in that order.
+    fn gather(&self, rows: &[usize]) -> Self {
+        let row_len = self.row_len();
+        let mut data = Vec::with_capacity(rows.len() * row_len);
+        for &row in rows {
+            data.extend_from_slice(&self.data[row * row_len..(row + 1) * row_len]);
+        }
+        let mut shape = self.shape.clone();
+        shape[0] = rows.len();
+        Self {
+            name: self.name.clone(),
+            shape,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Attention {
+    SelfAttention,
+    Cross,
+}
+
+/// Input name for a decoder output: `present.3.decoder.key` feeds
+/// `past_key_values.3.decoder.key`. Outputs that are not cache entries
+/// (the logits) give `None`.
+fn past_input(present: &str) -> Option<(String, Attention)> {
+    let rest = present.strip_prefix("present.")?;
+    let attention = if rest.contains(".decoder.") {
+        Attention::SelfAttention
+    } else if rest.contains(".encoder.") {
+        Attention::Cross
+    } else {
+        return None;
+    };
+    Some((format!("past_key_values.{}", rest), attention))
+}
+
+/// Keys and values the decoder already computed for one window, so each
+/// step feeds it only the newest token per beam. Self-attention entries
+/// grow by a position every step and follow the beams; cross-attention
+/// entries depend only on the audio, are computed once with the prompt and
+/// shared by every beam.
+pub(crate) struct KvCache {
+    self_attention: Vec<CachedTensor>,
+    cross_attention: Vec<CachedTensor>,
+}
+
+impl KvCache {
+    /// Seeds the cache from the `present.*` outputs of the first, full-prompt
+    /// decoder run.
+    pub fn from_presents(presents: Vec<CachedTensor>) -> Result<Self, OrigaError> {
+        let mut cache = Self {
+            self_attention: Vec::new(),
+            cross_attention: Vec::new(),
+        };
+        for tensor in presents {
+            match past_input(&tensor.name) {
+                Some((name, Attention::SelfAttention)) => {
+                    cache.self_attention.push(CachedTensor { name, ..tensor })
+                },
+                Some((name, Attention::Cross)) => {
+                    cache.cross_attention.push(CachedTensor { name, ..tensor })
+                },
+                None => {},
+            }
+        }
+        if cache.self_attention.is_empty() || cache.cross_attention.is_empty() {
+            return Err(OrigaError::SttError {
+                reason: "Decoder does not export present key/values".into(),
+            });
+        }
+        Ok(cache)
+    }
+
+    /// Replaces the self-attention entries with the `present.*` outputs of a
+    /// cached step; the decoder returns them one position longer.
+    pub fn update(&mut self, presents: Vec<CachedTensor>) {
+        self.self_attention = presents
+            .into_iter()
+            .filter_map(|tensor| match past_input(&tensor.name) {
+                Some((name, Attention::SelfAttention)) => Some(CachedTensor { name, ..tensor }),
+                Some((_, Attention::Cross)) | None => None,
+            })
+            .collect();
+    }
+
+    /// Keeps the self-attention rows of the beams that survived a step, in
+    /// the new beam order (`Decoder::parents`).
+    pub fn reorder(&mut self, parents: &[usize]) {
+        for tensor in &mut self.self_attention {
+            *tensor = tensor.gather(parents);
+        }
+    }
+
+    /// Hands the self-attention entries to the next step's inputs; `update`
+    /// puts the grown ones back.
+    pub fn take_self_attention(&mut self) -> Vec<CachedTensor> {
+        std::mem::take(&mut self.self_attention)
+    }
+
+    /// Cross-attention entries for a batch of `rows` beams. Callers build
+    /// their tensors from this only when the beam count changes.
+    pub fn cross_attention(&self, rows: usize) -> Vec<CachedTensor> {
+        let parents = vec![0; rows];
+        self.cross_attention
+            .iter()
+            .map(|tensor| tensor.gather(&parents))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tensor(name: &str, rows: &[f32]) -> CachedTensor {
+        CachedTensor {
+            name: name.to_string(),
+            shape: vec![rows.len(), 1, 1, 2],
+            data: rows.iter().flat_map(|&row| [row, row]).collect(),
+        }
+    }
+
+    #[test]
+    fn presents_split_into_self_and_cross_attention() {
+        let mut cache = KvCache::from_presents(vec![
+            tensor("present.0.decoder.key", &[1.0]),
+            tensor("present.0.encoder.key", &[2.0]),
+            tensor("logits", &[3.0]),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            cache.take_self_attention()[0].name,
+            "past_key_values.0.decoder.key"
+        );
+        let cross = cache.cross_attention(3);
+        assert_eq!(cross[0].name, "past_key_values.0.encoder.key");
+        assert_eq!(cross[0].shape, [3, 1, 1, 2]);
+        assert_eq!(cross[0].data, [2.0; 6]);
+    }
+
+    #[test]
+    fn decoder_without_presents_is_an_error() {
+        let result = KvCache::from_presents(vec![tensor("logits", &[1.0])]);
+
+        assert!(matches!(result, Err(OrigaError::SttError { .. })));
+    }
+
+    #[test]
+    fn reorder_follows_beam_parents() {
+        let mut cache = KvCache::from_presents(vec![
+            tensor("present.0.decoder.value", &[1.0]),
+            tensor("present.0.encoder.value", &[9.0]),
+        ])
+        .unwrap();
+        cache.update(vec![
+            tensor("present.0.decoder.value", &[1.0, 2.0, 3.0]),
+            tensor("present.0.encoder.value", &[7.0]),
+        ]);
+
+        cache.reorder(&[2, 2, 0]);
+
+        let tensor = &cache.take_self_attention()[0];
+        assert_eq!(tensor.shape, [3, 1, 1, 2]);
+        assert_eq!(tensor.data, [3.0, 3.0, 3.0, 3.0, 1.0, 1.0]);
+        assert_eq!(cache.cross_attention(1)[0].data, [9.0, 9.0]);
+    }
+}
@@ -1,7 +1,10 @@
 mod audio;
 mod common;
+mod decoding;
+mod kv_cache;
 mod mel_spectrogram;
 mod tokenizer;
+mod transcript;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod whisper;
@@ -15,7 +18,9 @@ pub use whisper::WhisperTranscriber;
 #[cfg(target_arch = "wasm32")]
 pub use whisper_wasm::WhisperTranscriber;
 
-#[cfg(target_arch = "wasm32")]
+pub use decoding::DecodingOptions;
+pub use transcript::TranscriptSegment;
+
 pub use audio::load_audio_bytes;
 
 #[cfg(test)]
@@ -61,30 +61,39 @@ fn whisper_transcribe_audio() {
     }
 
     let transcriber = super::whisper::WhisperTranscriber::new(model_dir).unwrap();
-    let text = transcriber.transcribe(wav_path);
-    assert!(text.is_ok(), "Transcription failed: {:?}", text.err());
+    let segments = transcriber.transcribe(wav_path);
+    assert!(
+        segments.is_ok(),
+        "Transcription failed: {:?}",
+        segments.err()
+    );
 
-    let result = text.unwrap();
+    let result = segments.unwrap();
     assert!(!result.is_empty(), "Transcription should not be empty");
-    println!("Transcription result: {}", result);
+    assert!(
+        result
+            .windows(2)
+            .all(|w| w[0].start_ms() <= w[1].start_ms()),
+        "Segments should be ordered by time"
+    );
+    println!("Transcription result: {:?}", result);
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 #[test]
-fn audio_load_wav_sample() {
+fn audio_load_audio_sample() {
     let wav_path = std::path::Path::new("standard_sample1.wav");
     if !wav_path.exists() {
         println!("Skipping: test WAV not found at {:?}", wav_path);
         return;
     }
 
-    let samples = super::audio::load_wav(wav_path);
+    let samples = super::audio::load_audio(wav_path);
     assert!(samples.is_ok(), "Failed to load WAV: {:?}", samples.err());
 
     let result = samples.unwrap();
-    assert_eq!(
-        result.len(),
-        480_000,
-        "Should be padded/trimmed to 30s at 16kHz"
+    assert!(
+        !result.is_empty(),
+        "Should keep the whole recording at 16kHz"
     );
 }
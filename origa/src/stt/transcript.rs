@@ -0,0 +1,336 @@
+use serde::{Deserialize, Serialize};
+
+use super::audio::{SpeechRegion, TARGET_SAMPLES, pad_or_trim};
+use super::common::strip_trailing_repeats;
+
+/// Whisper timestamp tokens count up from `<|0.00|>` in 20 ms steps.
+const TIMESTAMP_STEP_MS: u64 = 20;
+const SAMPLES_PER_MS: usize = 16;
+
+/// One span of recognised speech, positioned in the whole recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    start_ms: u64,
+    end_ms: u64,
+    text: String,
+    confidence: f32,
+}
+
+impl TranscriptSegment {
+    pub fn new(start_ms: u64, end_ms: u64, text: String) -> Self {
+        Self {
+            start_ms,
+            end_ms,
+            text,
+            confidence: 1.0,
+        }
+    }
+
+    pub fn start_ms(&self) -> u64 {
+        self.start_ms
+    }
+
+    pub fn end_ms(&self) -> u64 {
+        self.end_ms
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Voice activity confidence of the speech region the segment came from.
+    pub fn confidence(&self) -> f32 {
+        self.confidence
+    }
+}
+
+/// Speech regions laid end to end, so that short regions share a 30-second
+/// window instead of each being padded out to one.
+pub(crate) struct PackedSpeech {
+    samples: Vec<f32>,
+    spans: Vec<PackedSpan>,
+}
+
+struct PackedSpan {
+    /// Where the region's samples start in the packed audio.
+    packed_start: usize,
+    region: SpeechRegion,
+}
+
+impl PackedSpeech {
+    pub(crate) fn new(samples: &[f32], regions: &[SpeechRegion]) -> Self {
+        let mut packed = Vec::new();
+        let mut spans = Vec::with_capacity(regions.len());
+        for region in regions {
+            spans.push(PackedSpan {
+                packed_start: packed.len(),
+                region: region.clone(),
+            });
+            packed.extend_from_slice(&samples[region.start()..region.end()]);
+        }
+        Self {
+            samples: packed,
+            spans,
+        }
+    }
+
+    pub(crate) fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Moves a segment timed on the packed audio onto the recording's
+    /// timeline. Each end lands in the region it was spoken in, so a segment
+    /// running across a region boundary also spans the silence between.
+    pub(crate) fn place(&self, segment: TranscriptSegment) -> TranscriptSegment {
+        let start = segment.start_ms as usize * SAMPLES_PER_MS;
+        let end = segment.end_ms as usize * SAMPLES_PER_MS;
+        let start_span = self.last_span_where(|span| span.packed_start <= start);
+        let end_span = self.last_span_where(|span| span.packed_start < end);
+
+        TranscriptSegment {
+            start_ms: start_span.map_or(segment.start_ms, |span| span.to_recording(start)),
+            end_ms: end_span.map_or(segment.end_ms, |span| span.to_recording(end)),
+            confidence: start_span.map_or(segment.confidence, |span| span.region.confidence()),
+            ..segment
+        }
+    }
+
+    /// Last span satisfying `started`, which must hold for a prefix of the
+    /// spans; the first span when none does.
+    fn last_span_where(&self, started: impl Fn(&PackedSpan) -> bool) -> Option<&PackedSpan> {
+        let count = self.spans.partition_point(started);
+        self.spans.get(count.saturating_sub(1))
+    }
+}
+
+impl PackedSpan {
+    fn to_recording(&self, packed: usize) -> u64 {
+        let region_len = self.region.end() - self.region.start();
+        let within = packed.saturating_sub(self.packed_start).min(region_len);
+        samples_to_ms(self.region.start() + within)
+    }
+}
+
+/// Segments decoded from one 30-second window.
+#[derive(Debug)]
+pub(crate) struct WindowTranscript {
+    pub segments: Vec<TranscriptSegment>,
+    /// How far to move the window. It stops at the end of the last finished
+    /// segment, so a phrase cut by the window edge is decoded again, whole,
+    /// at the start of the next overlapping window.
+    pub advance: usize,
+}
+
+/// The 30-second model input starting at `offset`, zero-padded at the end.
+pub(crate) fn window_at(samples: &[f32], offset: usize) -> Vec<f32> {
+    pad_or_trim(&samples[offset.min(samples.len())..], TARGET_SAMPLES)
+}
+
+/// Turns the tokens generated for the window at `offset` into segments.
+///
+/// Text between a pair of timestamp tokens is one segment. Text left open at
+/// the end is kept only for the final window; otherwise it is dropped and
+/// the next window starts where the last closed segment ended.
+pub(crate) fn split_timed_tokens(
+    tokens: &[i64],
+    timestamp_begin: i64,
+    offset: usize,
+    total_len: usize,
+    decode: impl Fn(&[i64]) -> String,
+) -> WindowTranscript {
+    let window_len = total_len.saturating_sub(offset).min(TARGET_SAMPLES);
+    let is_last = offset + TARGET_SAMPLES >= total_len;
+    let offset_ms = samples_to_ms(offset);
+
+    let mut segments = Vec::new();
+    let mut start: Option<u64> = None;
+    let mut text_tokens: Vec<i64> = Vec::new();
+    let mut last_end_ms: u64 = 0;
+
+    for &token in tokens {
+        if token < timestamp_begin {
+            text_tokens.push(token);
+            continue;
+        }
+
+        let time_ms = (token - timestamp_begin) as u64 * TIMESTAMP_STEP_MS;
+        match start {
+            Some(start_ms) if !text_tokens.is_empty() => {
+                push_segment(
+                    &mut segments,
+                    offset_ms + start_ms,
+                    offset_ms + time_ms,
+                    &decode(&text_tokens),
+                );
+                text_tokens.clear();
+                last_end_ms = time_ms;
+                start = None;
+            },
+            _ => start = Some(time_ms),
+        }
+    }
+
+    let window_end_ms = samples_to_ms(window_len);
+    if !text_tokens.is_empty() && (is_last || segments.is_empty()) {
+        let start_ms = start.unwrap_or(last_end_ms);
+        push_segment(
+            &mut segments,
+            offset_ms + start_ms,
+            offset_ms + window_end_ms.max(start_ms),
+            &decode(&text_tokens),
+        );
+        last_end_ms = window_end_ms;
+    }
+
+    let advance = if is_last || last_end_ms == 0 {
+        window_len
+    } else {
+        (last_end_ms as usize * SAMPLES_PER_MS).min(window_len)
+    };
+
+    WindowTranscript {
+        segments,
+        advance: advance.max(1),
+    }
+}
+
+/// Fallback for tokenizers without timestamp tokens: the whole window
+/// becomes one segment and windows follow each other without overlap.
+pub(crate) fn untimed_window(text: &str, offset: usize, total_len: usize) -> WindowTranscript {
+    let window_len = total_len.saturating_sub(offset).min(TARGET_SAMPLES);
+    let mut segments = Vec::new();
+    push_segment(
+        &mut segments,
+        samples_to_ms(offset),
+        samples_to_ms(offset + window_len),
+        text,
+    );
+
+    WindowTranscript {
+        segments,
+        advance: window_len.max(1),
+    }
+}
+
+fn push_segment(segments: &mut Vec<TranscriptSegment>, start_ms: u64, end_ms: u64, text: &str) {
+    let text = strip_trailing_repeats(text.trim());
+    if !text.is_empty() {
+        segments.push(TranscriptSegment::new(start_ms, end_ms, text));
+    }
+}
+
+fn samples_to_ms(samples: usize) -> u64 {
+    (samples / SAMPLES_PER_MS) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TS: i64 = 1000;
+    const MINUTE: usize = 60 * 16_000;
+
+    fn decode(tokens: &[i64]) -> String {
+        tokens
+            .iter()
+            .map(|&t| char::from_u32(0x3040 + t as u32).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn closed_segments_get_absolute_times_and_window_seeks_to_last_end() {
+        // <|0.00|> あ <|2.00|><|2.00|> い <|5.00|> う (unfinished)
+        let tokens = [TS, 2, TS + 100, TS + 100, 4, TS + 250, 6];
+
+        let window = split_timed_tokens(&tokens, TS, MINUTE, 3 * MINUTE, decode);
+
+        assert_eq!(window.segments.len(), 2);
+        assert_eq!(window.segments[0].start_ms(), 60_000);
+        assert_eq!(window.segments[0].end_ms(), 62_000);
+        assert_eq!(window.segments[0].text(), "\u{3042}");
+        assert_eq!(window.segments[1].start_ms(), 62_000);
+        assert_eq!(window.segments[1].end_ms(), 65_000);
+        assert_eq!(window.advance, 5 * 16_000);
+    }
+
+    #[test]
+    fn last_window_keeps_open_segment_until_audio_ends() {
+        let tokens = [TS, 2, TS + 100, 4];
+
+        let window = split_timed_tokens(&tokens, TS, 0, 4 * 16_000, decode);
+
+        assert_eq!(window.segments.len(), 2);
+        assert_eq!(window.segments[1].start_ms(), 2_000);
+        assert_eq!(window.segments[1].end_ms(), 4_000);
+        assert_eq!(window.advance, 4 * 16_000);
+    }
+
+    #[test]
+    fn window_without_timestamps_advances_by_full_window() {
+        let tokens = [2, 4];
+
+        let window = split_timed_tokens(&tokens, TS, 0, 2 * MINUTE, decode);
+
+        assert_eq!(window.segments.len(), 1);
+        assert_eq!(window.segments[0].start_ms(), 0);
+        assert_eq!(window.segments[0].end_ms(), 30_000);
+        assert_eq!(window.advance, TARGET_SAMPLES);
+    }
+
+    #[test]
+    fn untimed_window_spans_remaining_audio() {
+        let window = untimed_window("猫です", 30 * 16_000, 40 * 16_000);
+
+        assert_eq!(window.segments[0].start_ms(), 30_000);
+        assert_eq!(window.segments[0].end_ms(), 40_000);
+        assert_eq!(window.advance, 10 * 16_000);
+    }
+
+    #[test]
+    fn short_regions_share_one_window_and_keep_their_offsets() {
+        // Arrange: two 5-second regions a minute apart.
+        let samples = vec![0.5; 3 * MINUTE];
+        let regions = [
+            SpeechRegion::new(10 * 16_000, 15 * 16_000, 0.9),
+            SpeechRegion::new(75 * 16_000, 80 * 16_000, 0.7),
+        ];
+
+        // Act
+        let packed = PackedSpeech::new(&samples, &regions);
+        let first = packed.place(TranscriptSegment::new(1_000, 4_000, "あ".to_string()));
+        let second = packed.place(TranscriptSegment::new(6_000, 9_000, "い".to_string()));
+        let across = packed.place(TranscriptSegment::new(4_000, 7_000, "う".to_string()));
+
+        // Assert
+        assert_eq!(packed.samples().len(), 10 * 16_000);
+        assert_eq!((first.start_ms(), first.end_ms()), (11_000, 14_000));
+        assert_eq!((second.start_ms(), second.end_ms()), (76_000, 79_000));
+        assert_eq!(second.confidence(), 0.7);
+        assert_eq!((across.start_ms(), across.end_ms()), (14_000, 77_000));
+    }
+
+    #[test]
+    fn segment_ending_at_a_region_boundary_stays_in_that_region() {
+        let samples = vec![0.5; 2 * MINUTE];
+        let regions = [
+            SpeechRegion::new(0, 5 * 16_000, 0.9),
+            SpeechRegion::new(MINUTE, MINUTE + 5 * 16_000, 0.9),
+        ];
+
+        let packed = PackedSpeech::new(&samples, &regions);
+        let segment = packed.place(TranscriptSegment::new(2_000, 5_000, "あ".to_string()));
+
+        assert_eq!(segment.end_ms(), 5_000);
+    }
+
+    #[test]
+    fn window_at_pads_tail_to_thirty_seconds() {
+        let samples = vec![0.5; 40 * 16_000];
+
+        let window = window_at(&samples, 30 * 16_000);
+
+        assert_eq!(window.len(), TARGET_SAMPLES);
+        assert_eq!(window[0], 0.5);
+        assert_eq!(window[TARGET_SAMPLES - 1], 0.0);
+    }
+}
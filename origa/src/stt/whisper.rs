@@ -2,20 +2,30 @@ use std::path::Path;
 use std::sync::Mutex;
 
 use crate::domain::OrigaError;
-use ndarray::Array2;
-use ort::session::{Session, builder::GraphOptimizationLevel};
-use ort::value::Value;
+use ort::session::{Session, SessionInputValue, SessionOutputs, builder::GraphOptimizationLevel};
+use ort::value::{DynValue, Value};
 
-pub use super::audio::load_wav;
+use super::audio::detect_speech;
+pub use super::audio::load_audio;
 use super::common::{
-    MAX_DECODE_TOKENS, argmax_last_position, build_prompt_tokens, strip_trailing_repeats,
+    build_prompt_tokens, cache_values, hidden_states_value, last_position_logits, present_tensors,
+    tensor_value, timestamp_begin, timestamp_rules,
 };
+use super::decoding::{Decoder, DecodingOptions, TimestampRules};
+use super::kv_cache::{CachedTensor, KvCache};
 pub use super::tokenizer::WhisperTokenizer;
+use super::transcript::{
+    PackedSpeech, TranscriptSegment, split_timed_tokens, untimed_window, window_at,
+};
 
 pub struct WhisperTranscriber {
     encoder_session: Mutex<Session>,
     decoder_session: Mutex<Session>,
+    /// `decoder_with_past_model.onnx`: one token per beam against a KV
+    /// cache. Older exports lack it and decode the whole sequence each step.
+    decoder_with_past_session: Option<Mutex<Session>>,
     tokenizer: WhisperTokenizer,
+    options: DecodingOptions,
 }
 
 impl WhisperTranscriber {
@@ -36,6 +46,17 @@ impl WhisperTranscriber {
         let encoder_session = create_session(&encoder_bytes, "encoder")?;
         let decoder_session = create_session(&decoder_bytes, "decoder")?;
 
+        let with_past_path = model_dir.join("decoder_with_past_model.onnx");
+        let decoder_with_past_session = if with_past_path.exists() {
+            let bytes = std::fs::read(&with_past_path).map_err(|e| OrigaError::SttError {
+                reason: format!("Read decoder with past: {}", e),
+            })?;
+            Some(create_session(&bytes, "decoder_with_past")?)
+        } else {
+            tracing::warn!("No decoder_with_past model, decoding without a KV cache");
+            None
+        };
+
         tracing::info!(inputs = ?encoder_session.inputs(), outputs = ?encoder_session.outputs(), "Encoder");
         tracing::info!(inputs = ?decoder_session.inputs(), outputs = ?decoder_session.outputs(), "Decoder");
 
@@ -45,24 +66,64 @@ impl WhisperTranscriber {
         Ok(Self {
             encoder_session: Mutex::new(encoder_session),
             decoder_session: Mutex::new(decoder_session),
+            decoder_with_past_session: decoder_with_past_session.map(Mutex::new),
             tokenizer,
+            options: DecodingOptions::default(),
         })
     }
 
-    pub fn transcribe(&self, wav_path: &Path) -> Result<String, OrigaError> {
-        tracing::info!(path = ?wav_path, "Transcribing audio");
+    pub fn with_decoding_options(mut self, options: DecodingOptions) -> Self {
+        self.options = options;
+        self
+    }
 
-        let samples = load_wav(wav_path).map_err(|reason| OrigaError::SttError { reason })?;
-        let mel = super::mel_spectrogram::compute_mel_spectrogram(&samples)
-            .map_err(|reason| OrigaError::SttError { reason })?;
+    /// Transcribes the detected speech regions of the file, packed end to
+    /// end into overlapping 30-second windows; silence and music are skipped.
+    pub fn transcribe(&self, audio_path: &Path) -> Result<Vec<TranscriptSegment>, OrigaError> {
+        tracing::info!(path = ?audio_path, "Transcribing audio");
+
+        let samples = load_audio(audio_path).map_err(|reason| OrigaError::SttError { reason })?;
+        let timestamp_begin = timestamp_begin(&self.tokenizer);
+        let timestamp_rules = timestamp_rules(&self.tokenizer);
 
-        let hidden_states = run_encoder(&self.encoder_session, &mel)?;
-        let tokens = decode_autoregressive(&self.decoder_session, &self.tokenizer, &hidden_states)?;
-        let text = self.tokenizer.decode(&tokens);
-        let text = strip_trailing_repeats(&text);
+        let regions = detect_speech(&samples);
+        tracing::debug!(regions = regions.len(), "Speech regions");
 
-        tracing::info!(text = %text, "Transcription complete");
-        Ok(text)
+        let packed = PackedSpeech::new(&samples, &regions);
+        let speech = packed.samples();
+
+        let mut segments = Vec::new();
+        let mut offset = 0;
+        while offset < speech.len() {
+            let mel = super::mel_spectrogram::compute_mel_spectrogram(&window_at(speech, offset))
+                .map_err(|reason| OrigaError::SttError { reason })?;
+            let hidden_states = run_encoder(&self.encoder_session, &mel)?;
+            let tokens = decode_autoregressive(
+                &self.decoder_session,
+                self.decoder_with_past_session.as_ref(),
+                &self.tokenizer,
+                &hidden_states,
+                timestamp_rules,
+                &self.options,
+            )?;
+
+            let window = match timestamp_begin {
+                Some(begin) => split_timed_tokens(&tokens, begin, offset, speech.len(), |t| {
+                    self.tokenizer.decode(t)
+                }),
+                None => untimed_window(&self.tokenizer.decode(&tokens), offset, speech.len()),
+            };
+            tracing::debug!(
+                offset,
+                segments = window.segments.len(),
+                "Transcribed window"
+            );
+            segments.extend(window.segments.into_iter().map(|s| packed.place(s)));
+            offset += window.advance;
+        }
+
+        tracing::info!(segments = segments.len(), "Transcription complete");
+        Ok(segments)
     }
 }
 
@@ -126,54 +187,164 @@ fn run_encoder(
     })
 }
 
+/// Decodes one window, retrying at the next temperature while the result
+/// looks like a hallucination or repetition loop. Without timestamp rules
+/// the prompt asks for plain text.
 fn decode_autoregressive(
     decoder_session: &Mutex<Session>,
+    decoder_with_past_session: Option<&Mutex<Session>>,
     tokenizer: &WhisperTokenizer,
     hidden_states: &ndarray::Array3<f32>,
+    timestamp_rules: Option<TimestampRules>,
+    options: &DecodingOptions,
 ) -> Result<Vec<i64>, OrigaError> {
-    let mut tokens = build_prompt_tokens(tokenizer)?;
+    let prompt = build_prompt_tokens(tokenizer, timestamp_rules.is_some())?;
     let eos_id = tokenizer
         .token_to_id("<|endoftext|>")
         .ok_or_else(|| OrigaError::SttError {
             reason: "Missing EOS token".into(),
         })?;
 
-    for _ in 0..MAX_DECODE_TOKENS {
-        let input_array =
-            Array2::from_shape_vec((1, tokens.len()), tokens.clone()).map_err(|e| {
-                OrigaError::SttError {
-                    reason: format!("Input IDs: {:?}", e),
-                }
-            })?;
-        let input_tensor = Value::from_array(input_array).map_err(|e| OrigaError::SttError {
-            reason: format!("Input tensor: {:?}", e),
-        })?;
-        let hidden_tensor =
-            Value::from_array(hidden_states.clone()).map_err(|e| OrigaError::SttError {
-                reason: format!("Hidden tensor: {:?}", e),
-            })?;
-
-        let new_token = {
-            let mut guard = decoder_session.lock().map_err(|e| OrigaError::SttError {
-                reason: format!("Decoder lock: {:?}", e),
-            })?;
-            let outputs = guard
-                .run(ort::inputs![
-                    "input_ids" => input_tensor,
-                    "encoder_hidden_states" => hidden_tensor
-                ])
-                .map_err(|e| OrigaError::SttError {
-                    reason: format!("Decoder run: {:?}", e),
-                })?;
-
-            argmax_last_position(&outputs[0])?
-        };
+    let mut tokens = Vec::new();
+    for &temperature in &options.temperatures {
+        let mut decoder = Decoder::new(prompt.clone(), eos_id, options, temperature);
+        if let Some(rules) = timestamp_rules {
+            decoder = decoder.with_timestamp_rules(rules);
+        }
+        match decoder_with_past_session {
+            Some(with_past) => {
+                decode_cached(decoder_session, with_past, &mut decoder, hidden_states)?
+            },
+            None => decode_uncached(decoder_session, &mut decoder, hidden_states)?,
+        }
 
-        if new_token == eos_id {
+        let sequence = decoder.finish();
+        let retry =
+            options.needs_fallback(&tokenizer.decode(&sequence.tokens), sequence.avg_logprob);
+        tracing::debug!(
+            temperature,
+            avg_logprob = sequence.avg_logprob,
+            retry,
+            "Decoded window"
+        );
+        tokens = sequence.tokens;
+        if !retry {
             break;
         }
-        tokens.push(new_token);
     }
 
     Ok(tokens)
 }
+
+/// Runs the prompt once through the full decoder to seed the KV cache, then
+/// feeds only the newest token of every beam to `decoder_with_past`.
+fn decode_cached(
+    decoder_session: &Mutex<Session>,
+    decoder_with_past_session: &Mutex<Session>,
+    decoder: &mut Decoder,
+    hidden_states: &ndarray::Array3<f32>,
+) -> Result<(), OrigaError> {
+    let prompt = decoder.sequences().concat();
+    let inputs = vec![
+        (
+            "input_ids".to_string(),
+            tensor_value(vec![1, prompt.len()], prompt)?.into(),
+        ),
+        (
+            "encoder_hidden_states".to_string(),
+            hidden_states_value(hidden_states, 1)?.into(),
+        ),
+    ];
+    let (logits, presents) = run_decoder(decoder_session, inputs, logits_and_presents)?;
+    let mut cache = KvCache::from_presents(presents)?;
+    decoder.step(&logits);
+
+    let mut cross_attention: Option<(usize, Vec<(String, DynValue)>)> = None;
+    while !decoder.is_done() {
+        cache.reorder(decoder.parents());
+        let last_tokens = decoder.last_tokens();
+        let rows = last_tokens.len();
+        if cross_attention
+            .as_ref()
+            .map(|(cached_rows, _)| *cached_rows)
+            != Some(rows)
+        {
+            cross_attention = Some((rows, cache_values(cache.cross_attention(rows))?));
+        }
+
+        let mut inputs: Vec<(String, SessionInputValue)> = vec![(
+            "input_ids".to_string(),
+            tensor_value(vec![rows, 1], last_tokens)?.into(),
+        )];
+        for (name, value) in cache_values(cache.take_self_attention())? {
+            inputs.push((name, value.into()));
+        }
+        if let Some((_, values)) = &cross_attention {
+            for (name, value) in values {
+                inputs.push((name.clone(), value.into()));
+            }
+        }
+
+        let (logits, presents) =
+            run_decoder(decoder_with_past_session, inputs, logits_and_presents)?;
+        cache.update(presents);
+        decoder.step(&logits);
+    }
+    Ok(())
+}
+
+/// Runs the whole sequence every step, for exports without a
+/// `decoder_with_past` model.
+fn decode_uncached(
+    decoder_session: &Mutex<Session>,
+    decoder: &mut Decoder,
+    hidden_states: &ndarray::Array3<f32>,
+) -> Result<(), OrigaError> {
+    let mut hidden_batch: Option<(usize, DynValue)> = None;
+    while !decoder.is_done() {
+        let sequences = decoder.sequences();
+        let rows = sequences.len();
+        if hidden_batch.as_ref().map(|(cached_rows, _)| *cached_rows) != Some(rows) {
+            hidden_batch = Some((rows, hidden_states_value(hidden_states, rows)?));
+        }
+
+        let mut inputs: Vec<(String, SessionInputValue)> = vec![(
+            "input_ids".to_string(),
+            tensor_value(vec![rows, sequences[0].len()], sequences.concat())?.into(),
+        )];
+        if let Some((_, hidden)) = &hidden_batch {
+            inputs.push(("encoder_hidden_states".to_string(), hidden.into()));
+        }
+
+        let logits = run_decoder(decoder_session, inputs, |outputs| {
+            last_position_logits(&outputs[0])
+        })?;
+        decoder.step(&logits);
+    }
+    Ok(())
+}
+
+/// Runs one decoder step and reads what the caller needs from its outputs.
+fn run_decoder<T>(
+    session: &Mutex<Session>,
+    inputs: Vec<(String, SessionInputValue)>,
+    read: impl FnOnce(&SessionOutputs) -> Result<T, OrigaError>,
+) -> Result<T, OrigaError> {
+    let mut guard = session.lock().map_err(|e| OrigaError::SttError {
+        reason: format!("Decoder lock: {:?}", e),
+    })?;
+    let outputs = guard.run(inputs).map_err(|e| OrigaError::SttError {
+        reason: format!("Decoder run: {:?}", e),
+    })?;
+
+    read(&outputs)
+}
+
+fn logits_and_presents(
+    outputs: &SessionOutputs,
+) -> Result<(Vec<Vec<f32>>, Vec<CachedTensor>), OrigaError> {
+    Ok((
+        last_position_logits(&outputs[0])?,
+        present_tensors(outputs)?,
+    ))
+}
@@ -1,24 +1,37 @@
+use super::audio::detect_speech;
 use super::common::{
-    MAX_DECODE_TOKENS, argmax_last_position, build_prompt_tokens, strip_trailing_repeats,
+    build_prompt_tokens, cache_values, hidden_states_value, last_position_logits, present_tensors,
+    tensor_value, timestamp_begin, timestamp_rules,
+};
+use super::decoding::{Decoder, DecodingOptions, TimestampRules};
+use super::kv_cache::{CachedTensor, KvCache};
+use super::transcript::{
+    PackedSpeech, TranscriptSegment, split_timed_tokens, untimed_window, window_at,
 };
 use crate::domain::OrigaError;
 use crate::ort_init;
 use crate::stt::tokenizer::WhisperTokenizer;
 use futures::lock::Mutex;
 use ort::ep::WebGPU;
-use ort::session::RunOptions;
+use ort::session::{RunOptions, Session, SessionInputValue, SessionOutputs};
+use ort::value::DynValue;
 use ort_web::ValueExt;
 
 pub struct WhisperTranscriber {
-    encoder_session: Mutex<ort::session::Session>,
-    decoder_session: Mutex<ort::session::Session>,
+    encoder_session: Mutex<Session>,
+    decoder_session: Mutex<Session>,
+    /// `decoder_with_past_model.onnx`: one token per beam against a KV
+    /// cache. Older exports lack it and decode the whole sequence each step.
+    decoder_with_past_session: Option<Mutex<Session>>,
     tokenizer: WhisperTokenizer,
+    options: DecodingOptions,
 }
 
 impl WhisperTranscriber {
     pub async fn new(
         encoder_bytes: &[u8],
         decoder_bytes: &[u8],
+        decoder_with_past_bytes: Option<&[u8]>,
         tokenizer_bytes: &[u8],
     ) -> Result<Self, OrigaError> {
         let init = ort_init::ensure().await?;
@@ -30,6 +43,15 @@ impl WhisperTranscriber {
 
         let encoder_session = build_session(encoder_bytes, "encoder", init.webgpu_active).await?;
         let decoder_session = build_session(decoder_bytes, "decoder", init.webgpu_active).await?;
+        let decoder_with_past_session = match decoder_with_past_bytes {
+            Some(bytes) => {
+                Some(build_session(bytes, "decoder_with_past", init.webgpu_active).await?)
+            },
+            None => {
+                tracing::warn!("No decoder_with_past model, decoding without a KV cache");
+                None
+            },
+        };
 
         tracing::info!(
             inputs = ?encoder_session.inputs(),
@@ -48,23 +70,61 @@ impl WhisperTranscriber {
         Ok(Self {
             encoder_session: Mutex::new(encoder_session),
             decoder_session: Mutex::new(decoder_session),
+            decoder_with_past_session: decoder_with_past_session.map(Mutex::new),
             tokenizer,
+            options: DecodingOptions::default(),
         })
     }
 
-    pub async fn transcribe_from_samples(&self, samples: &[f32]) -> Result<String, OrigaError> {
+    pub fn with_decoding_options(mut self, options: DecodingOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Transcribes the detected speech regions of the recording, packed end to
+    /// end into overlapping 30-second windows; silence and music are skipped.
+    pub async fn transcribe_from_samples(
+        &self,
+        samples: &[f32],
+    ) -> Result<Vec<TranscriptSegment>, OrigaError> {
         tracing::info!(samples_len = samples.len(), "Transcribing audio (WASM)");
 
-        let mel = super::mel_spectrogram::compute_mel_spectrogram(samples)
-            .map_err(|reason| OrigaError::SttError { reason })?;
+        let timestamp_begin = timestamp_begin(&self.tokenizer);
+        let timestamp_rules = timestamp_rules(&self.tokenizer);
+
+        let regions = detect_speech(samples);
+        tracing::debug!(regions = regions.len(), "Speech regions");
+
+        let packed = PackedSpeech::new(samples, &regions);
+        let speech = packed.samples();
 
-        let hidden_states = self.run_encoder(&mel).await?;
-        let tokens = self.decode_autoregressive(&hidden_states).await?;
-        let text = self.tokenizer.decode(&tokens);
-        let text = strip_trailing_repeats(&text);
+        let mut segments = Vec::new();
+        let mut offset = 0;
+        while offset < speech.len() {
+            let mel = super::mel_spectrogram::compute_mel_spectrogram(&window_at(speech, offset))
+                .map_err(|reason| OrigaError::SttError { reason })?;
+            let hidden_states = self.run_encoder(&mel).await?;
+            let tokens = self
+                .decode_autoregressive(&hidden_states, timestamp_rules)
+                .await?;
 
-        tracing::info!(text = %text, "Transcription complete (WASM)");
-        Ok(text)
+            let window = match timestamp_begin {
+                Some(begin) => split_timed_tokens(&tokens, begin, offset, speech.len(), |t| {
+                    self.tokenizer.decode(t)
+                }),
+                None => untimed_window(&self.tokenizer.decode(&tokens), offset, speech.len()),
+            };
+            tracing::debug!(
+                offset,
+                segments = window.segments.len(),
+                "Transcribed window (WASM)"
+            );
+            segments.extend(window.segments.into_iter().map(|s| packed.place(s)));
+            offset += window.advance;
+        }
+
+        tracing::info!(segments = segments.len(), "Transcription complete (WASM)");
+        Ok(segments)
     }
 
     async fn run_encoder(
@@ -120,11 +180,15 @@ impl WhisperTranscriber {
         })
     }
 
+    /// Decodes one window, retrying at the next temperature while the
+    /// result looks like a hallucination or repetition loop. Without
+    /// timestamp rules the prompt asks for plain text.
     async fn decode_autoregressive(
         &self,
         hidden_states: &ndarray::Array3<f32>,
+        timestamp_rules: Option<TimestampRules>,
     ) -> Result<Vec<i64>, OrigaError> {
-        let mut tokens = build_prompt_tokens(&self.tokenizer)?;
+        let prompt = build_prompt_tokens(&self.tokenizer, timestamp_rules.is_some())?;
         let eos_id =
             self.tokenizer
                 .token_to_id("<|endoftext|>")
@@ -132,70 +196,178 @@ impl WhisperTranscriber {
                     reason: "Missing EOS token".into(),
                 })?;
 
-        for _ in 0..MAX_DECODE_TOKENS {
-            let input_shape = vec![1usize, tokens.len()];
-            let input_data: Vec<i64> = tokens.clone();
-            let input_tensor = ort::value::Tensor::from_array((input_shape, input_data))
-                .map_err(|e| OrigaError::SttError {
-                    reason: format!("Input tensor: {:?}", e),
-                })?
-                .into_dyn();
+        let mut tokens = Vec::new();
+        for &temperature in &self.options.temperatures {
+            let mut decoder = Decoder::new(prompt.clone(), eos_id, &self.options, temperature);
+            if let Some(rules) = timestamp_rules {
+                decoder = decoder.with_timestamp_rules(rules);
+            }
+            match &self.decoder_with_past_session {
+                Some(with_past) => {
+                    self.decode_cached(with_past, &mut decoder, hidden_states)
+                        .await?
+                },
+                None => self.decode_uncached(&mut decoder, hidden_states).await?,
+            }
 
-            let hidden_shape: Vec<usize> = hidden_states.shape().to_vec();
-            let hidden_data: Vec<f32> = hidden_states.clone().into_raw_vec_and_offset().0;
-            let hidden_tensor = ort::value::Tensor::from_array((hidden_shape, hidden_data))
-                .map_err(|e| OrigaError::SttError {
-                    reason: format!("Hidden tensor: {:?}", e),
-                })?
-                .into_dyn();
-
-            let new_token = {
-                let mut session = self.decoder_session.lock().await;
-                let run_options = RunOptions::new().map_err(|e| OrigaError::SttError {
-                    reason: format!("Run options: {:?}", e),
-                })?;
+            let sequence = decoder.finish();
+            let retry = self.options.needs_fallback(
+                &self.tokenizer.decode(&sequence.tokens),
+                sequence.avg_logprob,
+            );
+            tracing::debug!(
+                temperature,
+                avg_logprob = sequence.avg_logprob,
+                retry,
+                "Decoded window (WASM)"
+            );
+            tokens = sequence.tokens;
+            if !retry {
+                break;
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    /// Runs the prompt once through the full decoder to seed the KV cache,
+    /// then feeds only the newest token of every beam to
+    /// `decoder_with_past`.
+    async fn decode_cached(
+        &self,
+        decoder_with_past_session: &Mutex<Session>,
+        decoder: &mut Decoder,
+        hidden_states: &ndarray::Array3<f32>,
+    ) -> Result<(), OrigaError> {
+        let prompt = decoder.sequences().concat();
+        let inputs = vec![
+            (
+                "input_ids".to_string(),
+                tensor_value(vec![1, prompt.len()], prompt)?.into(),
+            ),
+            (
+                "encoder_hidden_states".to_string(),
+                hidden_states_value(hidden_states, 1)?.into(),
+            ),
+        ];
+        let (logits, presents) =
+            run_decoder(&self.decoder_session, inputs, logits_and_presents).await?;
+        let mut cache = KvCache::from_presents(presents)?;
+        decoder.step(&logits);
 
-                let mut outputs = session
-                    .run_async(
-                        ort::inputs![
-                            "input_ids" => input_tensor,
-                            "encoder_hidden_states" => hidden_tensor
-                        ],
-                        &run_options,
-                    )
-                    .await
-                    .map_err(|e| OrigaError::SttError {
-                        reason: format!("Decoder run: {:?}", e),
-                    })?;
-
-                for (_name, mut output) in outputs.iter_mut() {
-                    output
-                        .sync(ort_web::SyncDirection::Rust)
-                        .await
-                        .map_err(|e| OrigaError::SttError {
-                            reason: format!("Decoder sync: {:?}", e),
-                        })?;
+        let mut cross_attention: Option<(usize, Vec<(String, DynValue)>)> = None;
+        while !decoder.is_done() {
+            cache.reorder(decoder.parents());
+            let last_tokens = decoder.last_tokens();
+            let rows = last_tokens.len();
+            if cross_attention
+                .as_ref()
+                .map(|(cached_rows, _)| *cached_rows)
+                != Some(rows)
+            {
+                cross_attention = Some((rows, cache_values(cache.cross_attention(rows))?));
+            }
+
+            let mut inputs: Vec<(String, SessionInputValue)> = vec![(
+                "input_ids".to_string(),
+                tensor_value(vec![rows, 1], last_tokens)?.into(),
+            )];
+            for (name, value) in cache_values(cache.take_self_attention())? {
+                inputs.push((name, value.into()));
+            }
+            if let Some((_, values)) = &cross_attention {
+                for (name, value) in values {
+                    inputs.push((name.clone(), value.into()));
                 }
+            }
 
-                argmax_last_position(&outputs[0])?
-            };
+            let (logits, presents) =
+                run_decoder(decoder_with_past_session, inputs, logits_and_presents).await?;
+            cache.update(presents);
+            decoder.step(&logits);
+        }
+        Ok(())
+    }
 
-            if new_token == eos_id {
-                break;
+    /// Runs the whole sequence every step, for exports without a
+    /// `decoder_with_past` model.
+    async fn decode_uncached(
+        &self,
+        decoder: &mut Decoder,
+        hidden_states: &ndarray::Array3<f32>,
+    ) -> Result<(), OrigaError> {
+        let mut hidden_batch: Option<(usize, DynValue)> = None;
+        while !decoder.is_done() {
+            let sequences = decoder.sequences();
+            let rows = sequences.len();
+            if hidden_batch.as_ref().map(|(cached_rows, _)| *cached_rows) != Some(rows) {
+                hidden_batch = Some((rows, hidden_states_value(hidden_states, rows)?));
             }
-            tokens.push(new_token);
+
+            let mut inputs: Vec<(String, SessionInputValue)> = vec![(
+                "input_ids".to_string(),
+                tensor_value(vec![rows, sequences[0].len()], sequences.concat())?.into(),
+            )];
+            if let Some((_, hidden)) = &hidden_batch {
+                inputs.push(("encoder_hidden_states".to_string(), hidden.into()));
+            }
+
+            let logits = run_decoder(&self.decoder_session, inputs, |outputs| {
+                last_position_logits(&outputs[0])
+            })
+            .await?;
+            decoder.step(&logits);
         }
+        Ok(())
+    }
+}
 
-        Ok(tokens)
+/// Runs one decoder step and reads what the caller needs from its outputs.
+async fn run_decoder<T>(
+    session: &Mutex<Session>,
+    inputs: Vec<(String, SessionInputValue<'_>)>,
+    read: impl FnOnce(&SessionOutputs) -> Result<T, OrigaError>,
+) -> Result<T, OrigaError> {
+    let mut session = session.lock().await;
+    let run_options = RunOptions::new().map_err(|e| OrigaError::SttError {
+        reason: format!("Run options: {:?}", e),
+    })?;
+
+    let mut outputs =
+        session
+            .run_async(inputs, &run_options)
+            .await
+            .map_err(|e| OrigaError::SttError {
+                reason: format!("Decoder run: {:?}", e),
+            })?;
+
+    for (_name, mut output) in outputs.iter_mut() {
+        output
+            .sync(ort_web::SyncDirection::Rust)
+            .await
+            .map_err(|e| OrigaError::SttError {
+                reason: format!("Decoder sync: {:?}", e),
+            })?;
     }
+
+    read(&outputs)
+}
+
+fn logits_and_presents(
+    outputs: &SessionOutputs,
+) -> Result<(Vec<Vec<f32>>, Vec<CachedTensor>), OrigaError> {
+    Ok((
+        last_position_logits(&outputs[0])?,
+        present_tensors(outputs)?,
+    ))
 }
 
 async fn build_session(
     model_bytes: &[u8],
     label: &str,
     webgpu_active: bool,
-) -> Result<ort::session::Session, OrigaError> {
-    let builder = ort::session::Session::builder().map_err(|e| OrigaError::SttError {
+) -> Result<Session, OrigaError> {
+    let builder = Session::builder().map_err(|e| OrigaError::SttError {
         reason: format!("{label} builder: {e:?}"),
     })?;
 
@@ -0,0 +1,197 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use crate::dictionary::user_dictionary::get_frequency_rank;
+use crate::domain::{Book, BookChapter, ExampleContext, OrigaError, User, tokenize_text};
+use crate::traits::UserRepository;
+use crate::use_cases::WordToCreate;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+/// A sentence with exactly one unknown word — learnable in context.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NPlusOneSentence {
+    pub sentence: String,
+    pub unknown_word: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterAnalysis {
+    pub index: usize,
+    pub title: String,
+    pub comprehension_percentage: f32,
+    pub unknown_word_count: usize,
+    /// Unknown words of the chapter, most frequent first.
+    pub unknown_words: Vec<String>,
+    pub n_plus_one_sentences: Vec<NPlusOneSentence>,
+}
+
+impl ChapterAnalysis {
+    /// Unknown words in the shape `CreateCardsFromAnalysisUseCase` expects,
+    /// so a chapter can be mined directly. Words with an N+1 sentence keep
+    /// it as example context, labelled with `source` (the book title).
+    pub fn words_to_create(&self, source: &str) -> Vec<WordToCreate> {
+        self.unknown_words
+            .iter()
+            .map(|base_form| WordToCreate {
+                base_form: base_form.clone(),
+                context: self
+                    .n_plus_one_sentences
+                    .iter()
+                    .find(|s| s.unknown_word == *base_form)
+                    .map(|s| {
+                        ExampleContext::new(s.sentence.clone()).with_source(source.to_string())
+                    }),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookAnalysisResult {
+    pub title: String,
+    /// Share of the book's distinct vocabulary words already known, in
+    /// percent; a word recurring across chapters counts once.
+    pub comprehension_percentage: f32,
+    pub unknown_word_count: usize,
+    pub chapters: Vec<ChapterAnalysis>,
+}
+
+pub struct AnalyzeBookUseCase<'a, R: UserRepository> {
+    repository: &'a R,
+}
+
+impl<'a, R: UserRepository> AnalyzeBookUseCase<'a, R> {
+    pub fn new(repository: &'a R) -> Self {
+        Self { repository }
+    }
+
+    /// Scores every chapter against the user's known cards and picks up to
+    /// `sentence_limit` N+1 sentences per chapter, preferring sentences whose
+    /// unknown word recurs most often in that chapter.
+    pub async fn execute(
+        &self,
+        book: &Book,
+        sentence_limit: usize,
+    ) -> Result<BookAnalysisResult, OrigaError> {
+        let user = self
+            .repository
+            .get_current_user()
+            .await?
+            .ok_or(OrigaError::CurrentUserNotExist)?;
+
+        debug!(
+            title = book.title(),
+            chapter_count = book.chapters().len(),
+            "Analyzing book"
+        );
+
+        let mut book_words = BookWords::default();
+        let chapters = book
+            .chapters()
+            .iter()
+            .enumerate()
+            .map(|(index, chapter)| {
+                analyze_chapter(&user, index, chapter, sentence_limit, &mut book_words)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        info!(
+            title = book.title(),
+            chapter_count = chapters.len(),
+            "Book analyzed"
+        );
+
+        Ok(BookAnalysisResult {
+            title: book.title().to_string(),
+            comprehension_percentage: book_words.comprehension_percentage(),
+            unknown_word_count: book_words.unknown.len(),
+            chapters,
+        })
+    }
+}
+
+/// Distinct words across all chapters, for the whole-book score.
+#[derive(Default)]
+struct BookWords {
+    known: HashSet<String>,
+    unknown: HashSet<String>,
+}
+
+impl BookWords {
+    /// Same rule as `ScoreContentResult::comprehension_percentage`.
+    fn comprehension_percentage(&self) -> f32 {
+        let total = self.known.len() + self.unknown.len();
+        if total == 0 {
+            return 100.0;
+        }
+        self.known.len() as f32 / total as f32 * 100.0
+    }
+}
+
+fn analyze_chapter(
+    user: &User,
+    index: usize,
+    chapter: &BookChapter,
+    sentence_limit: usize,
+    book_words: &mut BookWords,
+) -> Result<ChapterAnalysis, OrigaError> {
+    let score = user.score_content(chapter.text())?;
+    book_words.known.extend(score.known_words().iter().cloned());
+    book_words
+        .unknown
+        .extend(score.unknown_words().iter().cloned());
+    let unknown: HashSet<&str> = score.unknown_words().iter().map(String::as_str).collect();
+
+    let mut frequency: HashMap<String, usize> = HashMap::new();
+    let mut candidates: Vec<NPlusOneSentence> = Vec::new();
+
+    for sentence in chapter.sentences() {
+        let sentence_unknown: HashSet<String> = tokenize_text(&sentence)?
+            .iter()
+            .filter(|token| token.part_of_speech().is_vocabulary_word())
+            .map(|token| token.orthographic_base_form().to_string())
+            .filter(|word| unknown.contains(word.as_str()))
+            .inspect(|word| *frequency.entry(word.clone()).or_default() += 1)
+            .collect();
+
+        if sentence_unknown.len() == 1 {
+            let unknown_word = sentence_unknown.into_iter().next().unwrap_or_default();
+            if !candidates.iter().any(|c| c.unknown_word == unknown_word) {
+                candidates.push(NPlusOneSentence {
+                    sentence,
+                    unknown_word,
+                });
+            }
+        }
+    }
+
+    // Words equally frequent in the chapter are ordered by their rank in an
+    // imported frequency dictionary; the stable sort keeps reading order
+    // among the rest.
+    candidates.sort_by_key(|c| {
+        (
+            Reverse(frequency.get(&c.unknown_word).copied().unwrap_or(0)),
+            get_frequency_rank(&c.unknown_word).unwrap_or(u32::MAX),
+        )
+    });
+    candidates.truncate(sentence_limit);
+
+    let mut unknown_words: Vec<String> = score.unknown_words().to_vec();
+    unknown_words.sort_by_cached_key(|word| {
+        (
+            Reverse(frequency.get(word).copied().unwrap_or(0)),
+            get_frequency_rank(word).unwrap_or(u32::MAX),
+            word.clone(),
+        )
+    });
+
+    Ok(ChapterAnalysis {
+        index,
+        title: chapter.title().to_string(),
+        comprehension_percentage: score.comprehension_percentage(),
+        unknown_word_count: unknown_words.len(),
+        unknown_words,
+        n_plus_one_sentences: candidates,
+    })
+}
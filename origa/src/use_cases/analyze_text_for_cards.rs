@@ -1,5 +1,6 @@
 use crate::{
-    domain::{OrigaError, PartOfSpeech, tokenize_text},
+    dictionary::pitch_audio::get_pitch_accent,
+    domain::{OrigaError, PartOfSpeech, PitchAccent, split_sentences, tokenize_text},
     traits::UserRepository,
 };
 use serde::{Deserialize, Serialize};
@@ -12,6 +13,11 @@ pub struct AnalyzedWord {
     pub part_of_speech: PartOfSpeech,
     pub is_known: bool,
     pub meaning: Option<String>,
+    /// First sentence of the text the word appears in.
+    #[serde(default)]
+    pub sentence: Option<String>,
+    #[serde(default)]
+    pub pitch_accent: Option<PitchAccent>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,34 +46,42 @@ impl<'a, R: UserRepository> AnalyzeTextForCardsUseCase<'a, R> {
             .await?
             .ok_or(OrigaError::CurrentUserNotExist)?;
 
-        let tokens = tokenize_text(text.as_str())?;
-
-        debug!(token_count = tokens.len(), "Tokenized text");
-
         let mut words: Vec<AnalyzedWord> = Vec::new();
         let mut seen_words = std::collections::HashSet::new();
 
-        for token in tokens {
-            if !token.part_of_speech().is_vocabulary_word() {
-                continue;
-            }
-
-            let word_text = token.orthographic_base_form().to_string();
-            if seen_words.contains(&word_text) {
-                continue;
+        // Tokenizing sentence by sentence lets each word keep the sentence
+        // it was first seen in as its example context. A line holding a
+        // single token is a word list entry, not an example.
+        for sentence in split_sentences(&text) {
+            let tokens = tokenize_text(&sentence)?;
+            let example = (tokens.len() > 1).then_some(sentence);
+
+            for token in tokens {
+                if !token.part_of_speech().is_vocabulary_word() {
+                    continue;
+                }
+
+                let word_text = token.orthographic_base_form().to_string();
+                if seen_words.contains(&word_text) {
+                    continue;
+                }
+
+                seen_words.insert(word_text.clone());
+
+                let knowledge = user.is_word_known(&word_text);
+                let reading = token.phonological_base_form().to_string();
+                let pitch_accent = get_pitch_accent(&word_text, &reading);
+
+                words.push(AnalyzedWord {
+                    base_form: word_text.clone(),
+                    reading,
+                    part_of_speech: token.part_of_speech().clone(),
+                    is_known: knowledge.is_known,
+                    meaning: knowledge.meaning,
+                    sentence: example.clone(),
+                    pitch_accent,
+                });
             }
-
-            seen_words.insert(word_text.clone());
-
-            let knowledge = user.is_word_known(&word_text);
-
-            words.push(AnalyzedWord {
-                base_form: word_text.clone(),
-                reading: token.phonological_base_form().to_string(),
-                part_of_speech: token.part_of_speech().clone(),
-                is_known: knowledge.is_known,
-                meaning: knowledge.meaning,
-            });
         }
 
         let total_found = words.len();
@@ -1,10 +1,13 @@
-use crate::domain::{Card, OrigaError, StudyCard, VocabularyCard};
+use crate::domain::{Card, ExampleContext, OrigaError, StudyCard, VocabularyCard};
 use crate::traits::UserRepository;
 use tracing::{debug, info, warn};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct WordToCreate {
     pub base_form: String,
+    /// Sentence the word was found in; stored on the created card and
+    /// linked to its phrase dataset entry when one matches.
+    pub context: Option<ExampleContext>,
 }
 
 pub struct CreateCardsFromAnalysisResult {
@@ -95,6 +98,11 @@ impl<'a, R: UserRepository> CreateCardsFromAnalysisUseCase<'a, R> {
                     word: word.base_form.clone(),
                 })?;
 
+        let vocab_card = match word.context.clone() {
+            Some(context) => vocab_card.with_context(context.with_matching_phrase()),
+            None => vocab_card,
+        };
+
         let card = Card::Vocabulary(vocab_card);
         user.create_card(card)
     }
@@ -0,0 +1,594 @@
+use crate::domain::{
+    Card, CardAnswer, CardState, KnowledgeSet, NativeLanguage, OrigaError, Rating, StudyCard,
+};
+use crate::traits::UserRepository;
+use chrono::{DateTime, NaiveTime, Utc};
+use rusqlite::{Connection, params};
+use serde_json::{Value, json};
+use sha1::{Digest, Sha1};
+use std::collections::HashSet;
+use std::io::{Cursor, Write};
+use tracing::{debug, info, warn};
+use ulid::Ulid;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+const FIELD_SEP: char = '\x1f';
+const DECK_ID: i64 = 1_718_000_000_000;
+const DECK_NAME: &str = "Origa";
+const FAVORITE_TAG: &str = "favorite";
+const ORIGA_TAG: &str = "origa";
+
+/// Anki collection schema 11, the layout every Anki version can import.
+const SCHEMA: &str = "
+CREATE TABLE col (
+    id integer primary key, crt integer not null, mod integer not null,
+    scm integer not null, ver integer not null, dty integer not null,
+    usn integer not null, ls integer not null, conf text not null,
+    models text not null, decks text not null, dconf text not null,
+    tags text not null
+);
+CREATE TABLE notes (
+    id integer primary key, guid text not null, mid integer not null,
+    mod integer not null, usn integer not null, tags text not null,
+    flds text not null, sfld integer not null, csum integer not null,
+    flags integer not null, data text not null
+);
+CREATE TABLE cards (
+    id integer primary key, nid integer not null, did integer not null,
+    ord integer not null, mod integer not null, usn integer not null,
+    type integer not null, queue integer not null, due integer not null,
+    ivl integer not null, factor integer not null, reps integer not null,
+    lapses integer not null, left integer not null, odue integer not null,
+    odid integer not null, flags integer not null, data text not null
+);
+CREATE TABLE revlog (
+    id integer primary key, cid integer not null, usn integer not null,
+    ease integer not null, ivl integer not null, lastIvl integer not null,
+    factor integer not null, time integer not null, type integer not null
+);
+CREATE TABLE graves (usn integer not null, oid integer not null, type integer not null);
+CREATE INDEX ix_notes_usn on notes (usn);
+CREATE INDEX ix_cards_usn on cards (usn);
+CREATE INDEX ix_revlog_usn on revlog (usn);
+CREATE INDEX ix_cards_nid on cards (nid);
+CREATE INDEX ix_cards_sched on cards (did, queue, due);
+CREATE INDEX ix_revlog_cid on revlog (cid);
+CREATE INDEX ix_notes_csum on notes (csum);
+";
+
+/// One Anki note type per Origa card kind. The first field is the sort
+/// field and the card front.
+struct NoteType {
+    id: i64,
+    name: &'static str,
+    fields: &'static [&'static str],
+}
+
+const VOCABULARY_NOTE: NoteType = NoteType {
+    id: 1_718_000_000_001,
+    name: "Origa Vocabulary",
+    fields: &["Word", "Meaning"],
+};
+const KANJI_NOTE: NoteType = NoteType {
+    id: 1_718_000_000_002,
+    name: "Origa Kanji",
+    fields: &["Kanji", "Meaning", "Onyomi", "Kunyomi"],
+};
+const GRAMMAR_NOTE: NoteType = NoteType {
+    id: 1_718_000_000_003,
+    name: "Origa Grammar",
+    fields: &["Title", "Description"],
+};
+const PHRASE_NOTE: NoteType = NoteType {
+    id: 1_718_000_000_004,
+    name: "Origa Phrase",
+    fields: &["Phrase", "Translation"],
+};
+const NOTE_TYPES: [&NoteType; 4] = [&VOCABULARY_NOTE, &KANJI_NOTE, &GRAMMAR_NOTE, &PHRASE_NOTE];
+
+pub struct ExportAnkiPackResult {
+    /// The `.apkg` file contents.
+    pub apkg: Vec<u8>,
+    pub exported_count: usize,
+    /// Cards whose content could not be resolved (e.g. a phrase missing
+    /// from the loaded phrase index).
+    pub skipped_card_ids: Vec<Ulid>,
+}
+
+/// Writes the user's knowledge set as an Anki package: one note per card,
+/// favorites tagged `favorite`, and the scheduling state of each card in
+/// the `cards` table (FSRS stability/difficulty in `cards.data`) with its
+/// reviews in `revlog`.
+pub struct ExportAnkiPackUseCase<'a, R: UserRepository> {
+    repository: &'a R,
+}
+
+impl<'a, R: UserRepository> ExportAnkiPackUseCase<'a, R> {
+    pub fn new(repository: &'a R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self) -> Result<ExportAnkiPackResult, OrigaError> {
+        let user = self
+            .repository
+            .get_current_user()
+            .await?
+            .ok_or(OrigaError::CurrentUserNotExist)?;
+
+        let result = build_anki_pack(user.knowledge_set(), user.native_language(), Utc::now())?;
+
+        info!(
+            exported = result.exported_count,
+            skipped = result.skipped_card_ids.len(),
+            "Anki export completed"
+        );
+        Ok(result)
+    }
+}
+
+pub fn build_anki_pack(
+    knowledge_set: &KnowledgeSet,
+    lang: &NativeLanguage,
+    now: DateTime<Utc>,
+) -> Result<ExportAnkiPackResult, OrigaError> {
+    let conn = Connection::open_in_memory().map_err(export_error)?;
+    conn.execute_batch(SCHEMA).map_err(export_error)?;
+
+    // Sort by id (creation time) so note order and new-card positions
+    // follow the order the cards were added in.
+    let mut study_cards: Vec<&StudyCard> = knowledge_set.study_cards().values().collect();
+    study_cards.sort_by_key(|card| *card.card_id());
+
+    let collection_start = collection_start(&study_cards, now);
+    let mut revlog_ids = HashSet::new();
+    let mut exported = 0;
+    let mut skipped = Vec::new();
+
+    for study_card in study_cards {
+        let Some(note) = AnkiNote::from_card(study_card.card(), lang) else {
+            debug!(card_id = %study_card.card_id(), "Card content unavailable, skipping");
+            skipped.push(*study_card.card_id());
+            continue;
+        };
+        // Note and card ids are millisecond timestamps in Anki; offsetting
+        // from the export time keeps them unique and ordered.
+        let id = now.timestamp_millis() + exported as i64;
+        let schedule = AnkiSchedule::from_study_card(study_card, collection_start, exported);
+
+        insert_note(&conn, id, study_card, &note, now)?;
+        insert_card(&conn, id, &schedule, now)?;
+        insert_revlog(&conn, id, study_card, &schedule, &mut revlog_ids)?;
+        exported += 1;
+    }
+
+    insert_collection(&conn, collection_start, now)?;
+
+    let db = conn.serialize("main").map_err(export_error)?;
+    let apkg = write_package(&db)?;
+
+    Ok(ExportAnkiPackResult {
+        apkg,
+        exported_count: exported,
+        skipped_card_ids: skipped,
+    })
+}
+
+struct AnkiNote {
+    note_type: &'static NoteType,
+    fields: Vec<String>,
+}
+
+impl AnkiNote {
+    fn from_card(card: &Card, lang: &NativeLanguage) -> Option<Self> {
+        let question = card.question(lang).ok()?.text().to_string();
+        let answer = card
+            .answer(lang)
+            .map(|answer| answer_text(&answer))
+            .unwrap_or_default();
+
+        let (note_type, fields) = match card {
+            Card::Vocabulary(_) => (&VOCABULARY_NOTE, vec![question, answer]),
+            Card::Kanji(kanji) => (
+                &KANJI_NOTE,
+                vec![
+                    question,
+                    answer,
+                    kanji.on_readings().join("、"),
+                    kanji.kun_readings().join("、"),
+                ],
+            ),
+            Card::Grammar(_) | Card::Particle(_) => (&GRAMMAR_NOTE, vec![question, answer]),
+            Card::Phrase(_) => (&PHRASE_NOTE, vec![question, answer]),
+        };
+        Some(Self { note_type, fields })
+    }
+}
+
+fn answer_text(answer: &CardAnswer) -> String {
+    let mut text = answer.translations().join("; ");
+    if let Some(description) = answer.description() {
+        text.push_str(" — ");
+        text.push_str(description);
+    }
+    text
+}
+
+/// Scheduling columns of an Anki `cards` row, derived from the card's
+/// `MemoryState`.
+struct AnkiSchedule {
+    card_type: i64,
+    queue: i64,
+    due: i64,
+    interval_days: i64,
+    factor: i64,
+    reps: i64,
+    lapses: i64,
+    data: String,
+}
+
+impl AnkiSchedule {
+    fn from_study_card(
+        study_card: &StudyCard,
+        collection_start: DateTime<Utc>,
+        new_position: usize,
+    ) -> Self {
+        let memory = study_card.memory();
+        let reps = i64::from(memory.reps());
+        let lapses = i64::from(memory.lapses());
+
+        let Some(state) = memory.memory_state() else {
+            return Self {
+                card_type: 0,
+                queue: 0,
+                due: new_position as i64 + 1,
+                interval_days: 0,
+                factor: 0,
+                reps,
+                lapses,
+                data: "{}".to_string(),
+            };
+        };
+
+        let next_review = *state.next_review_date();
+        let interval_days = memory
+            .last_review_date()
+            .map(|last| (next_review - last).num_days())
+            .unwrap_or_else(|| state.stability().value().round() as i64)
+            .max(1);
+        // Anki reads FSRS memory from cards.data; the ease factor only
+        // matters if the user later switches the deck back to SM-2.
+        let data = json!({
+            "s": state.stability().value(),
+            "d": state.difficulty().value(),
+        })
+        .to_string();
+        let factor = ease_factor(state.difficulty().value());
+
+        let (card_type, queue, due, interval_days) = match state.card_state() {
+            CardState::Learning | CardState::New => (1, 1, next_review.timestamp(), 0),
+            CardState::Relearning => (3, 1, next_review.timestamp(), interval_days),
+            CardState::Review => (
+                2,
+                2,
+                (next_review - collection_start).num_days(),
+                interval_days,
+            ),
+        };
+
+        Self {
+            card_type,
+            queue,
+            due,
+            interval_days,
+            factor,
+            reps,
+            lapses,
+            data,
+        }
+    }
+}
+
+/// Maps FSRS difficulty (1 = easiest, 10 = hardest) onto Anki's SM-2 ease
+/// range, 300% down to 130%, in permille.
+fn ease_factor(difficulty: f64) -> i64 {
+    let hardness = ((difficulty - 1.0) / 9.0).clamp(0.0, 1.0);
+    (3000.0 - hardness * 1700.0).round() as i64
+}
+
+/// Anki counts review due dates in days since the collection was created,
+/// so the collection starts on the day of the earliest review.
+fn collection_start(study_cards: &[&StudyCard], now: DateTime<Utc>) -> DateTime<Utc> {
+    let earliest = study_cards
+        .iter()
+        .filter_map(|card| card.memory().last_review_date())
+        .chain(std::iter::once(now))
+        .min()
+        .unwrap_or(now);
+    earliest.date_naive().and_time(NaiveTime::MIN).and_utc()
+}
+
+fn insert_note(
+    conn: &Connection,
+    id: i64,
+    study_card: &StudyCard,
+    note: &AnkiNote,
+    now: DateTime<Utc>,
+) -> Result<(), OrigaError> {
+    let mut tags = vec![ORIGA_TAG];
+    if study_card.is_favorite() {
+        tags.push(FAVORITE_TAG);
+    }
+    let fields: Vec<String> = note.fields.iter().map(|field| escape_html(field)).collect();
+
+    conn.execute(
+        "INSERT INTO notes (id, guid, mid, mod, usn, tags, flds, sfld, csum, flags, data)
+         VALUES (?1, ?2, ?3, ?4, -1, ?5, ?6, ?7, ?8, 0, '')",
+        params![
+            id,
+            study_card.card_id().to_string(),
+            note.note_type.id,
+            now.timestamp(),
+            format!(" {} ", tags.join(" ")),
+            fields.join(&FIELD_SEP.to_string()),
+            fields[0],
+            sort_field_checksum(&note.fields[0]),
+        ],
+    )
+    .map_err(export_error)?;
+    Ok(())
+}
+
+/// Anki's duplicate check: the first 8 hex digits of the SHA-1 of the
+/// HTML-stripped sort field. Fields are escaped on export, so the raw text
+/// is already what Anki strips them back to.
+fn sort_field_checksum(sort_field: &str) -> i64 {
+    let digest = Sha1::digest(sort_field.as_bytes());
+    i64::from(u32::from_be_bytes([
+        digest[0], digest[1], digest[2], digest[3],
+    ]))
+}
+
+fn insert_card(
+    conn: &Connection,
+    id: i64,
+    schedule: &AnkiSchedule,
+    now: DateTime<Utc>,
+) -> Result<(), OrigaError> {
+    let left = if schedule.queue == 1 { 1001 } else { 0 };
+    conn.execute(
+        "INSERT INTO cards (id, nid, did, ord, mod, usn, type, queue, due, ivl, factor,
+                            reps, lapses, left, odue, odid, flags, data)
+         VALUES (?1, ?1, ?2, 0, ?3, -1, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 0, 0, 0, ?12)",
+        params![
+            id,
+            DECK_ID,
+            now.timestamp(),
+            schedule.card_type,
+            schedule.queue,
+            schedule.due,
+            schedule.interval_days,
+            schedule.factor,
+            schedule.reps,
+            schedule.lapses,
+            left,
+            schedule.data,
+        ],
+    )
+    .map_err(export_error)?;
+    Ok(())
+}
+
+/// Writes the opt-in review log when the user kept one; otherwise only the
+/// last review is known, and it becomes a single revlog entry.
+fn insert_revlog(
+    conn: &Connection,
+    card_id: i64,
+    study_card: &StudyCard,
+    schedule: &AnkiSchedule,
+    used_ids: &mut HashSet<i64>,
+) -> Result<(), OrigaError> {
+    let memory = study_card.memory();
+    let mut reviews: Vec<(DateTime<Utc>, Rating)> = memory
+        .review_log()
+        .entries()
+        .iter()
+        .map(|entry| (entry.reviewed_at(), entry.rating()))
+        .collect();
+    if reviews.is_empty() {
+        if let (Some(at), Some(rating)) = (memory.last_review_date(), memory.last_rating()) {
+            reviews.push((at, rating));
+        }
+    }
+
+    let last = reviews.len().saturating_sub(1);
+    for (index, (reviewed_at, rating)) in reviews.into_iter().enumerate() {
+        // revlog ids are review timestamps in milliseconds and must be
+        // unique across the collection.
+        let mut id = reviewed_at.timestamp_millis();
+        while !used_ids.insert(id) {
+            id += 1;
+        }
+        let interval = if index == last {
+            schedule.interval_days
+        } else {
+            0
+        };
+        let review_type = if index == 0 { 0 } else { 1 };
+        conn.execute(
+            "INSERT INTO revlog (id, cid, usn, ease, ivl, lastIvl, factor, time, type)
+             VALUES (?1, ?2, -1, ?3, ?4, 0, ?5, 0, ?6)",
+            params![
+                id,
+                card_id,
+                anki_ease(rating),
+                interval,
+                schedule.factor,
+                review_type,
+            ],
+        )
+        .map_err(export_error)?;
+    }
+    Ok(())
+}
+
+fn anki_ease(rating: Rating) -> i64 {
+    match rating {
+        Rating::Again => 1,
+        Rating::Hard => 2,
+        Rating::Good => 3,
+        Rating::Easy => 4,
+    }
+}
+
+fn insert_collection(
+    conn: &Connection,
+    collection_start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), OrigaError> {
+    let modified = now.timestamp_millis();
+    let models: serde_json::Map<String, Value> = NOTE_TYPES
+        .iter()
+        .map(|note_type| (note_type.id.to_string(), note_type_json(note_type, now)))
+        .collect();
+
+    conn.execute(
+        "INSERT INTO col (id, crt, mod, scm, ver, dty, usn, ls, conf, models, decks, dconf, tags)
+         VALUES (1, ?1, ?2, ?2, 11, 0, 0, 0, ?3, ?4, ?5, ?6, '{}')",
+        params![
+            collection_start.timestamp(),
+            modified,
+            collection_conf_json().to_string(),
+            Value::Object(models).to_string(),
+            decks_json(now).to_string(),
+            deck_conf_json().to_string(),
+        ],
+    )
+    .map_err(export_error)?;
+    Ok(())
+}
+
+fn note_type_json(note_type: &NoteType, now: DateTime<Utc>) -> Value {
+    let fields: Vec<Value> = note_type
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(ord, name)| {
+            json!({
+                "name": name, "ord": ord, "sticky": false, "rtl": false,
+                "font": "Arial", "size": 20, "media": [],
+            })
+        })
+        .collect();
+    let back: Vec<String> = note_type.fields[1..]
+        .iter()
+        .map(|name| format!("{{{{{name}}}}}"))
+        .collect();
+
+    json!({
+        "id": note_type.id,
+        "name": note_type.name,
+        "type": 0,
+        "mod": now.timestamp(),
+        "usn": -1,
+        "sortf": 0,
+        "did": DECK_ID,
+        "flds": fields,
+        "tmpls": [{
+            "name": "Card 1",
+            "ord": 0,
+            "qfmt": format!("{{{{{}}}}}", note_type.fields[0]),
+            "afmt": format!("{{{{FrontSide}}}}<hr id=answer>{}", back.join("<br>")),
+            "did": null,
+            "bqfmt": "",
+            "bafmt": "",
+        }],
+        "css": ".card { font-family: arial; font-size: 20px; text-align: center; }",
+        "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\begin{document}\n",
+        "latexPost": "\\end{document}",
+        "req": [[0, "all", [0]]],
+        "tags": [],
+        "vers": [],
+    })
+}
+
+fn collection_conf_json() -> Value {
+    json!({
+        "activeDecks": [DECK_ID],
+        "curDeck": DECK_ID,
+        "curModel": VOCABULARY_NOTE.id,
+        "newSpread": 0,
+        "collapseTime": 1200,
+        "timeLim": 0,
+        "estTimes": true,
+        "dueCounts": true,
+        "sortType": "noteFld",
+        "sortBackwards": false,
+        "addToCur": true,
+        "nextPos": 1,
+    })
+}
+
+fn decks_json(now: DateTime<Utc>) -> Value {
+    let deck = |id: i64, name: &str| {
+        json!({
+            "id": id, "name": name, "mod": now.timestamp(), "usn": -1,
+            "desc": "", "dyn": 0, "conf": 1, "collapsed": false,
+            "browserCollapsed": false, "extendNew": 0, "extendRev": 0,
+            "newToday": [0, 0], "revToday": [0, 0], "lrnToday": [0, 0],
+            "timeToday": [0, 0],
+        })
+    };
+    json!({
+        "1": deck(1, "Default"),
+        DECK_ID.to_string(): deck(DECK_ID, DECK_NAME),
+    })
+}
+
+fn deck_conf_json() -> Value {
+    json!({
+        "1": {
+            "id": 1, "name": "Default", "mod": 0, "usn": 0, "maxTaken": 60,
+            "autoplay": true, "timer": 0, "replayq": true, "dyn": false,
+            "new": {
+                "bury": false, "delays": [1.0, 10.0], "initialFactor": 2500,
+                "ints": [1, 4, 0], "order": 1, "perDay": 20,
+            },
+            "rev": {
+                "bury": false, "ease4": 1.3, "ivlFct": 1.0, "maxIvl": 36500,
+                "perDay": 200, "hardFactor": 1.2,
+            },
+            "lapse": {
+                "delays": [10.0], "leechAction": 1, "leechFails": 8,
+                "minInt": 1, "mult": 0.0,
+            },
+        }
+    })
+}
+
+fn write_package(db: &[u8]) -> Result<Vec<u8>, OrigaError> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+
+    zip.start_file("collection.anki2", options)
+        .map_err(export_error)?;
+    zip.write_all(db).map_err(export_error)?;
+    zip.start_file("media", options).map_err(export_error)?;
+    zip.write_all(b"{}").map_err(export_error)?;
+
+    let cursor = zip.finish().map_err(export_error)?;
+    Ok(cursor.into_inner())
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn export_error(e: impl std::fmt::Display) -> OrigaError {
+    warn!("Anki export failed: {}", e);
+    OrigaError::AnkiExportFailed {
+        reason: e.to_string(),
+    }
+}
@@ -1,8 +1,69 @@
-use crate::domain::OrigaError;
-use crate::ocr::JapaneseOCRModel;
+use crate::domain::{ExampleContext, ExampleMedium, OrigaError, ReadingHint};
+use crate::ocr::{BoundingBox, JapaneseOCRModel, OcrLine, OcrResult};
 use std::rc::Rc;
 use tracing::info;
 
+/// Text read from an image, together with the image it came from, so every
+/// word mined from it keeps its sentence and source on the card. Text read
+/// by this crate's model also keeps its lines, whose printed ruby gives
+/// readings for the furigana shown with the mined words.
+#[derive(Debug, Clone)]
+pub struct ImageText {
+    text: String,
+    image_name: Option<String>,
+    result: Option<OcrResult>,
+}
+
+impl ImageText {
+    /// Wraps text recognized outside the use case (e.g. by the platform's
+    /// own OCR) so it carries the same context.
+    pub fn new(text: String, image_name: Option<String>) -> Self {
+        Self {
+            text,
+            image_name,
+            result: None,
+        }
+    }
+
+    fn from_result(result: OcrResult, image_name: Option<&str>) -> Self {
+        Self {
+            text: result.text(),
+            image_name: image_name.map(str::to_string),
+            result: Some(result),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn image_name(&self) -> Option<&str> {
+        self.image_name.as_deref()
+    }
+
+    /// Example context for a word found in `sentence` of this text.
+    pub fn example_context(&self, sentence: String) -> ExampleContext {
+        let context = ExampleContext::new(sentence).with_medium(ExampleMedium::Image);
+        match &self.image_name {
+            Some(name) => context.with_source(name.clone()),
+            None => context,
+        }
+    }
+
+    /// Printed readings for `word`, for `furiganize_segments_with_hints`:
+    /// the ruby of the line the word was read from first, or the whole
+    /// page's ruby when no line holds it verbatim (e.g. an inflected verb).
+    pub fn reading_hints_for(&self, word: &str) -> Vec<ReadingHint> {
+        let Some(result) = &self.result else {
+            return Vec::new();
+        };
+        match result.line_containing(word) {
+            Some(index) => result.reading_hints_for_line(index),
+            None => result.reading_hints(),
+        }
+    }
+}
+
 pub struct ExtractTextFromImageUseCase;
 
 impl Default for ExtractTextFromImageUseCase {
@@ -21,14 +82,40 @@ impl ExtractTextFromImageUseCase {
         &self,
         model: Rc<JapaneseOCRModel>,
         image_bytes: &[u8],
-    ) -> Result<String, OrigaError> {
+        image_name: Option<&str>,
+    ) -> Result<ImageText, OrigaError> {
         info!(
             bytes_len = image_bytes.len(),
             "Executing ExtractTextFromImageUseCase"
         );
 
         let img = decode_image(image_bytes)?;
-        model.run(&img)
+        let result = model.run_structured(&img)?;
+        Ok(ImageText::from_result(result, image_name))
+    }
+
+    /// Like `execute`, but keeps every line's box and confidences so the
+    /// caller can show where a word came from and drop bad regions.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn execute_structured(
+        &self,
+        model: Rc<JapaneseOCRModel>,
+        image_bytes: &[u8],
+    ) -> Result<OcrResult, OrigaError> {
+        let img = decode_image(image_bytes)?;
+        model.run_structured(&img)
+    }
+
+    /// Reads one region again, typically after the user corrected its box.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn rerun_region(
+        &self,
+        model: Rc<JapaneseOCRModel>,
+        image_bytes: &[u8],
+        bbox: BoundingBox,
+    ) -> Result<OcrLine, OrigaError> {
+        let img = decode_image(image_bytes)?;
+        Ok(model.recognize_region(&img, bbox))
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -36,14 +123,40 @@ impl ExtractTextFromImageUseCase {
         &self,
         model: Rc<JapaneseOCRModel>,
         image_bytes: &[u8],
-    ) -> Result<String, OrigaError> {
+        image_name: Option<&str>,
+    ) -> Result<ImageText, OrigaError> {
         info!(
             bytes_len = image_bytes.len(),
             "Executing ExtractTextFromImageUseCase"
         );
 
         let img = decode_image(image_bytes)?;
-        model.run(&img).await
+        let result = model.run_structured(&img).await?;
+        Ok(ImageText::from_result(result, image_name))
+    }
+
+    /// Like `execute`, but keeps every line's box and confidences so the
+    /// caller can show where a word came from and drop bad regions.
+    #[cfg(target_arch = "wasm32")]
+    pub async fn execute_structured(
+        &self,
+        model: Rc<JapaneseOCRModel>,
+        image_bytes: &[u8],
+    ) -> Result<OcrResult, OrigaError> {
+        let img = decode_image(image_bytes)?;
+        model.run_structured(&img).await
+    }
+
+    /// Reads one region again, typically after the user corrected its box.
+    #[cfg(target_arch = "wasm32")]
+    pub async fn rerun_region(
+        &self,
+        model: Rc<JapaneseOCRModel>,
+        image_bytes: &[u8],
+        bbox: BoundingBox,
+    ) -> Result<OcrLine, OrigaError> {
+        let img = decode_image(image_bytes)?;
+        Ok(model.recognize_region(&img, bbox).await)
     }
 }
 
@@ -56,6 +169,7 @@ fn decode_image(image_bytes: &[u8]) -> Result<image::DynamicImage, OrigaError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ocr::{Recognition, RubyText};
 
     #[test]
     fn new_creates_instance() {
@@ -67,6 +181,72 @@ mod tests {
         let _use_case = ExtractTextFromImageUseCase::new();
     }
 
+    #[test]
+    fn image_text_context_names_the_image() {
+        let text = ImageText::new("猫が好き".to_string(), Some("page.png".to_string()));
+
+        let context = text.example_context("猫が好き".to_string());
+
+        assert_eq!(context.sentence(), "猫が好き");
+        assert_eq!(context.source(), Some("page.png"));
+        assert_eq!(context.medium(), Some(ExampleMedium::Image));
+    }
+
+    #[test]
+    fn reading_hints_prefer_the_words_own_line() {
+        let bbox = BoundingBox {
+            x0: 0,
+            y0: 0,
+            x1: 10,
+            y1: 60,
+            confidence: 0.9,
+            class_index: 0,
+            pred_char_cnt: 3.0,
+        };
+        let line = |text: &str, reading: &str| OcrLine {
+            ruby: vec![RubyText {
+                reading: reading.to_string(),
+                base: "上手".to_string(),
+                bbox: bbox.clone(),
+            }],
+            ..OcrLine::new(
+                bbox.clone(),
+                Recognition {
+                    text: text.to_string(),
+                    confidence: 0.8,
+                },
+            )
+        };
+        let result = OcrResult {
+            width: 100,
+            height: 100,
+            lines: vec![line("上手に話す", "じょうず"), line("上手を取る", "うわて")],
+        };
+        let text = ImageText::from_result(result, None);
+
+        let first = |word: &str| text.reading_hints_for(word)[0].reading.clone();
+
+        assert_eq!(text.text(), "上手に話す上手を取る");
+        assert_eq!(first("取る"), "うわて");
+        assert_eq!(first("話す"), "じょうず");
+        assert_eq!(first("話した"), "じょうず");
+        assert!(
+            ImageText::new("上手".to_string(), None)
+                .reading_hints_for("上手")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn pasted_image_context_has_only_the_medium() {
+        let text = ImageText::new("猫".to_string(), None);
+
+        let context = text.example_context("猫".to_string());
+
+        assert!(context.source().is_none());
+        assert_eq!(context.medium(), Some(ExampleMedium::Image));
+    }
+
     mod decode_image {
         use super::*;
 
@@ -0,0 +1,30 @@
+use crate::domain::{ExamReadiness, JlptContent, OrigaError};
+use crate::traits::UserRepository;
+use tracing::debug;
+
+#[derive(Clone)]
+pub struct GetExamReadinessUseCase<'a, R: UserRepository> {
+    repository: &'a R,
+}
+
+impl<'a, R: UserRepository> GetExamReadinessUseCase<'a, R> {
+    pub fn new(repository: &'a R) -> Self {
+        Self { repository }
+    }
+
+    /// Readiness for the user's exam target; `None` when no exam is set.
+    pub async fn execute(
+        &self,
+        jlpt_content: &JlptContent,
+    ) -> Result<Option<ExamReadiness>, OrigaError> {
+        let user = self
+            .repository
+            .get_current_user()
+            .await?
+            .ok_or(OrigaError::CurrentUserNotExist)?;
+
+        debug!(user_id = %user.id(), "Assessing exam readiness");
+
+        Ok(user.exam_readiness(jlpt_content))
+    }
+}
@@ -1,7 +1,13 @@
-use crate::domain::{Card, OrigaError, VocabularyCard};
+use crate::domain::{
+    Card, CardState, Difficulty, ExampleContext, JapaneseChar, KanjiCard, MemoryHistory,
+    MemoryState, OrigaError, RateMode, Rating, ReviewLogEntry, Stability, User, VocabularyCard,
+    WordImportOutcome, WordImportPreview,
+};
 use crate::traits::UserRepository;
+use chrono::{DateTime, Duration, Utc};
 use rusqlite::Connection;
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::io::{Cursor, Read};
 use tracing::{debug, info, warn};
 use zip::ZipArchive;
@@ -12,11 +18,72 @@ const ANKI_DB_FILES: &[&str] = &[
     "collection.anki2",
 ];
 const FIELD_SEP: char = '\x1f';
+/// Learning-queue `due` values are unix timestamps; review `due` values are
+/// day numbers relative to the collection creation date.
+const DUE_TIMESTAMP_THRESHOLD: i64 = 1_000_000_000;
+const DEFAULT_DIFFICULTY: f64 = 5.0;
+const ANKI_SOURCE: &str = "Anki";
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AnkiCard {
     pub word: String,
     pub translation: Option<String>,
+    pub reading: Option<String>,
+    pub example_sentence: Option<String>,
+    pub kanji: Option<String>,
+    /// Anki scheduling of the note's first card; `None` for cards Anki
+    /// still considers new.
+    pub schedule: Option<AnkiSchedule>,
+}
+
+/// Which note fields feed which part of the imported cards. Field names
+/// are matched case-insensitively.
+///
+/// `word` (with `reading` as a fallback for kana-only words) becomes
+/// Vocabulary cards, `kanji` becomes Kanji cards, and `example_sentence`
+/// travels with the card as context.
+#[derive(Debug, Clone, Default)]
+pub struct AnkiFieldMapping {
+    pub word: String,
+    pub translation: Option<String>,
+    pub reading: Option<String>,
+    pub example_sentence: Option<String>,
+    pub kanji: Option<String>,
+}
+
+impl AnkiFieldMapping {
+    pub fn new(word: impl Into<String>) -> Self {
+        Self {
+            word: word.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Anki review state of a card, converted to Origa's memory model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnkiSchedule {
+    pub memory_state: MemoryState,
+    pub reps: u32,
+    pub lapses: u32,
+    pub last_review: Option<(DateTime<Utc>, Rating)>,
+    /// Every graded review from Anki's `revlog`, oldest first.
+    pub reviews: Vec<ReviewLogEntry>,
+}
+
+impl AnkiSchedule {
+    /// `log_reviews` mirrors the user's review-log opt-in: the imported
+    /// reviews are kept only when Origa would have logged them itself.
+    fn memory_history(&self, log_reviews: bool) -> MemoryHistory {
+        let reviews: &[ReviewLogEntry] = if log_reviews { &self.reviews } else { &[] };
+        MemoryHistory::imported(
+            self.memory_state.clone(),
+            self.reps,
+            self.lapses,
+            self.last_review,
+            reviews,
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -32,9 +99,21 @@ pub struct AnkiDeckInfo {
 
 pub struct ImportAnkiPackResult {
     pub total_created_count: usize,
+    /// Created cards that kept their Anki review history.
+    pub scheduled_count: usize,
     pub skipped_words: Vec<String>,
 }
 
+/// Dry-run outcome of importing one [`AnkiCard`].
+#[derive(Debug, Clone)]
+pub struct AnkiImportPreview {
+    pub word: WordImportPreview,
+    /// Kanji cards the import would add.
+    pub new_kanji: Vec<String>,
+    pub example_sentence: Option<String>,
+    pub schedule: Option<AnkiSchedule>,
+}
+
 pub fn extract_anki_db_bytes(data: &[u8]) -> Result<Vec<u8>, OrigaError> {
     let cursor = Cursor::new(data);
     let mut archive = ZipArchive::new(cursor).map_err(|e| OrigaError::AnkiInvalidFile {
@@ -58,11 +137,7 @@ pub fn extract_anki_db_bytes(data: &[u8]) -> Result<Vec<u8>, OrigaError> {
 
 pub fn read_anki_database(db_bytes: &[u8]) -> Result<AnkiDeckInfo, OrigaError> {
     let conn = open_db(db_bytes)?;
-    let models_json = query_models(&conn)?;
-    let models: Value =
-        serde_json::from_str(&models_json).map_err(|e| OrigaError::AnkiInvalidFile {
-            reason: format!("Failed to parse models JSON: {}", e),
-        })?;
+    let models = parse_models(&query_models(&conn)?)?;
     let detected_fields = detect_fields(&models)?;
     Ok(AnkiDeckInfo { detected_fields })
 }
@@ -73,32 +148,58 @@ pub fn parse_cards(
     word_tag: &str,
     translation_tag: Option<&str>,
 ) -> Result<Vec<AnkiCard>, OrigaError> {
-    let (word_idx, trans_idx) = find_field_indices(models, word_tag, translation_tag)?;
-    let mut cards = Vec::new();
-    for flds in notes_fields {
-        let (word, translation) = parse_fields(flds, word_idx, trans_idx);
-        if !word.is_empty() {
-            cards.push(AnkiCard { word, translation });
-        }
-    }
-    Ok(cards)
+    let mapping = AnkiFieldMapping {
+        translation: translation_tag.map(str::to_string),
+        ..AnkiFieldMapping::new(word_tag)
+    };
+    parse_cards_with_mapping(models, notes_fields, &mapping)
+}
+
+pub fn parse_cards_with_mapping(
+    models: &Value,
+    notes_fields: &[String],
+    mapping: &AnkiFieldMapping,
+) -> Result<Vec<AnkiCard>, OrigaError> {
+    let indices = find_field_indices(models, mapping)?;
+    Ok(notes_fields
+        .iter()
+        .filter_map(|flds| parse_fields(flds, &indices))
+        .collect())
 }
 
 pub fn extract_cards(
     data: &[u8],
     word_tag: &str,
     translation_tag: Option<&str>,
+) -> Result<(Vec<AnkiCard>, Vec<AnkiFieldInfo>), OrigaError> {
+    let mapping = AnkiFieldMapping {
+        translation: translation_tag.map(str::to_string),
+        ..AnkiFieldMapping::new(word_tag)
+    };
+    extract_cards_with_mapping(data, &mapping)
+}
+
+/// Reads notes through `mapping` and attaches each note's scheduling from
+/// the `cards` and `revlog` tables.
+pub fn extract_cards_with_mapping(
+    data: &[u8],
+    mapping: &AnkiFieldMapping,
 ) -> Result<(Vec<AnkiCard>, Vec<AnkiFieldInfo>), OrigaError> {
     let db_bytes = extract_anki_db_bytes(data)?;
     let conn = open_db(&db_bytes)?;
-    let models_json = query_models(&conn)?;
-    let notes_fields = query_notes(&conn)?;
-    let models: Value =
-        serde_json::from_str(&models_json).map_err(|e| OrigaError::AnkiInvalidFile {
-            reason: format!("Failed to parse models JSON: {}", e),
-        })?;
+    let models = parse_models(&query_models(&conn)?)?;
     let detected_fields = detect_fields(&models)?;
-    let cards = parse_cards(&models, &notes_fields, word_tag, translation_tag)?;
+    let indices = find_field_indices(&models, mapping)?;
+    let mut schedules = query_schedules(&conn)?;
+
+    let cards = query_notes(&conn)?
+        .into_iter()
+        .filter_map(|(note_id, flds)| {
+            let mut card = parse_fields(&flds, &indices)?;
+            card.schedule = schedules.remove(&note_id);
+            Some(card)
+        })
+        .collect();
     Ok((cards, detected_fields))
 }
 
@@ -111,6 +212,41 @@ impl<'a, R: UserRepository> ImportAnkiPackUseCase<'a, R> {
         Self { repository }
     }
 
+    /// Classifies `cards` the way [`Self::execute`] will import them,
+    /// without saving anything.
+    pub async fn preview(&self, cards: &[AnkiCard]) -> Result<Vec<AnkiImportPreview>, OrigaError> {
+        let user = self
+            .repository
+            .get_current_user()
+            .await?
+            .ok_or(OrigaError::CurrentUserNotExist)?;
+
+        let mut classifier = user.word_import_classifier();
+        let mut seen_kanji = existing_kanji(&user);
+
+        Ok(cards
+            .iter()
+            .map(|anki_card| {
+                let mut word = classifier.classify(&anki_card.word);
+                if word.outcome == WordImportOutcome::NoDictionaryEntry
+                    && let Some(reading) = &anki_card.reading
+                {
+                    word = classifier.classify(reading);
+                }
+                let new_kanji = kanji_chars(anki_card)
+                    .filter(|kanji| KanjiCard::new(kanji.clone()).is_ok())
+                    .filter(|kanji| seen_kanji.insert(kanji.clone()))
+                    .collect();
+                AnkiImportPreview {
+                    word,
+                    new_kanji,
+                    example_sentence: anki_card.example_sentence.clone(),
+                    schedule: anki_card.schedule.clone(),
+                }
+            })
+            .collect())
+    }
+
     pub async fn execute(&self, cards: Vec<AnkiCard>) -> Result<ImportAnkiPackResult, OrigaError> {
         let mut user = self
             .repository
@@ -118,17 +254,44 @@ impl<'a, R: UserRepository> ImportAnkiPackUseCase<'a, R> {
             .await?
             .ok_or(OrigaError::CurrentUserNotExist)?;
 
+        let log_reviews = user.srs_settings().review_log_enabled();
         let mut total_created = 0;
+        let mut scheduled = 0;
         let mut skipped = Vec::new();
 
         for anki_card in cards {
-            let question = anki_card.word.clone();
-            let result = VocabularyCard::from_text(&question, user.native_language());
+            let lang = *user.native_language();
+            let mut result = VocabularyCard::from_text(&anki_card.word, &lang);
+            if result.cards.is_empty()
+                && let Some(reading) = &anki_card.reading
+            {
+                result = VocabularyCard::from_text(reading, &lang);
+            }
 
             for vocab_card in result.cards {
+                let vocab_card = match &anki_card.example_sentence {
+                    Some(sentence) => vocab_card.with_context(
+                        ExampleContext::new(sentence.clone()).with_source(ANKI_SOURCE.to_string()),
+                    ),
+                    None => vocab_card,
+                };
                 let card = Card::Vocabulary(vocab_card);
-                match user.create_card(card) {
-                    Ok(_) => total_created += 1,
+                // Every word of the note was reviewed together in Anki, so
+                // each one inherits the note's schedule.
+                let created = match &anki_card.schedule {
+                    Some(schedule) => {
+                        let memory = schedule.memory_history(log_reviews);
+                        user.create_card_with_memory(card, memory)
+                    },
+                    None => user.create_card(card),
+                };
+                match created {
+                    Ok(_) => {
+                        total_created += 1;
+                        if anki_card.schedule.is_some() {
+                            scheduled += 1;
+                        }
+                    },
                     Err(OrigaError::DuplicateCard { question: q }) => {
                         debug!(word = %q, "Duplicate card, skipping");
                         skipped.push(q);
@@ -140,23 +303,67 @@ impl<'a, R: UserRepository> ImportAnkiPackUseCase<'a, R> {
                 warn!(word = %s, "Translation not found, skipping");
                 skipped.push(s.clone());
             }
+
+            total_created += create_kanji_cards(&mut user, &anki_card);
         }
 
         self.repository.save_sync(&user).await?;
 
         info!(
             created = total_created,
+            scheduled,
             skipped = skipped.len(),
             "Anki import completed"
         );
 
         Ok(ImportAnkiPackResult {
             total_created_count: total_created,
+            scheduled_count: scheduled,
             skipped_words: skipped,
         })
     }
 }
 
+/// Kanji cards start new: the Anki history belongs to the note's word, not
+/// to the individual characters.
+fn create_kanji_cards(user: &mut User, anki_card: &AnkiCard) -> usize {
+    let mut created = 0;
+    for kanji in kanji_chars(anki_card) {
+        let card = match KanjiCard::new(kanji.clone()) {
+            Ok(kanji_card) => Card::Kanji(kanji_card),
+            Err(e) => {
+                warn!(kanji = %kanji, error = %e, "Kanji not found, skipping");
+                continue;
+            },
+        };
+        match user.create_card(card) {
+            Ok(_) => created += 1,
+            Err(e) => debug!(kanji = %kanji, error = %e, "Kanji card not created"),
+        }
+    }
+    created
+}
+
+fn kanji_chars(anki_card: &AnkiCard) -> impl Iterator<Item = String> + '_ {
+    anki_card
+        .kanji
+        .iter()
+        .flat_map(|field| field.chars())
+        .filter(|c| c.is_kanji())
+        .map(String::from)
+}
+
+fn existing_kanji(user: &User) -> HashSet<String> {
+    user.knowledge_set()
+        .study_cards()
+        .values()
+        .filter_map(|study_card| match study_card.card() {
+            Card::Kanji(kanji_card) => Some(kanji_card.kanji().text().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
 fn open_db(db_bytes: &[u8]) -> Result<Connection, OrigaError> {
     let mut conn = Connection::open_in_memory().map_err(|e| OrigaError::AnkiInvalidFile {
         reason: format!("Failed to create in-memory database: {}", e),
@@ -181,25 +388,234 @@ fn query_models(conn: &Connection) -> Result<String, OrigaError> {
         })
 }
 
-fn query_notes(conn: &Connection) -> Result<Vec<String>, OrigaError> {
+fn parse_models(models_json: &str) -> Result<Value, OrigaError> {
+    serde_json::from_str(models_json).map_err(|e| OrigaError::AnkiInvalidFile {
+        reason: format!("Failed to parse models JSON: {}", e),
+    })
+}
+
+fn query_notes(conn: &Connection) -> Result<Vec<(i64, String)>, OrigaError> {
     let mut stmt =
-        conn.prepare("SELECT flds FROM notes")
+        conn.prepare("SELECT id, flds FROM notes")
             .map_err(|e| OrigaError::AnkiInvalidFile {
                 reason: format!("Failed to query notes: {}", e),
             })?;
     let rows = stmt
-        .query_map([], |row| row.get::<_, String>(0))
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
         .map_err(|e| OrigaError::AnkiInvalidFile {
             reason: format!("Failed to read notes: {}", e),
         })?;
-    let mut fields = Vec::new();
+    let mut notes = Vec::new();
     for row in rows {
         match row {
-            Ok(flds) => fields.push(flds),
+            Ok(note) => notes.push(note),
             Err(e) => warn!("Failed to read note field: {}", e),
         }
     }
-    Ok(fields)
+    Ok(notes)
+}
+
+/// Scheduling columns of an Anki `cards` row.
+struct AnkiCardRow {
+    card_id: i64,
+    card_type: i64,
+    due: i64,
+    interval_days: i64,
+    factor: i64,
+    reps: i64,
+    lapses: i64,
+    data: String,
+}
+
+/// A graded `revlog` row: review id (epoch milliseconds), ease and type.
+struct AnkiReviewRow {
+    review_id: i64,
+    ease: i64,
+    review_type: i64,
+}
+
+/// Schedules keyed by note id. A note with several cards (e.g. a reversed
+/// template) is represented by its first card.
+fn query_schedules(conn: &Connection) -> Result<HashMap<i64, AnkiSchedule>, OrigaError> {
+    let read_error = |e: rusqlite::Error| OrigaError::AnkiInvalidFile {
+        reason: format!("Failed to read scheduling: {}", e),
+    };
+    let collection_start: i64 = conn
+        .query_row("SELECT crt FROM col", [], |row| row.get(0))
+        .map_err(read_error)?;
+    let collection_start = DateTime::from_timestamp(collection_start, 0).unwrap_or_default();
+
+    let mut reviews = query_reviews(conn)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT c.nid, c.id, c.type, c.due, c.ivl, c.factor, c.reps, c.lapses, c.data
+             FROM cards c
+             ORDER BY c.nid, c.ord",
+        )
+        .map_err(read_error)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                AnkiCardRow {
+                    card_id: row.get(1)?,
+                    card_type: row.get(2)?,
+                    due: row.get(3)?,
+                    interval_days: row.get(4)?,
+                    factor: row.get(5)?,
+                    reps: row.get(6)?,
+                    lapses: row.get(7)?,
+                    data: row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+                },
+            ))
+        })
+        .map_err(read_error)?;
+
+    let mut schedules = HashMap::new();
+    let mut seen_notes = HashSet::new();
+    for row in rows {
+        let (note_id, card_row) = match row {
+            Ok(row) => row,
+            Err(e) => {
+                warn!("Failed to read card scheduling: {}", e);
+                continue;
+            },
+        };
+        if !seen_notes.insert(note_id) {
+            continue;
+        }
+        let card_reviews = reviews.remove(&card_row.card_id).unwrap_or_default();
+        if let Some(schedule) = card_row.to_schedule(collection_start, &card_reviews) {
+            schedules.insert(note_id, schedule);
+        }
+    }
+    Ok(schedules)
+}
+
+/// Graded reviews keyed by card id, oldest first. Ease 0 marks manual
+/// rescheduling, which is not a review.
+fn query_reviews(conn: &Connection) -> Result<HashMap<i64, Vec<AnkiReviewRow>>, OrigaError> {
+    let read_error = |e: rusqlite::Error| OrigaError::AnkiInvalidFile {
+        reason: format!("Failed to read review history: {}", e),
+    };
+    let mut stmt = conn
+        .prepare(
+            "SELECT cid, id, ease, type FROM revlog
+             WHERE ease BETWEEN 1 AND 4
+             ORDER BY cid, id",
+        )
+        .map_err(read_error)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                AnkiReviewRow {
+                    review_id: row.get(1)?,
+                    ease: row.get(2)?,
+                    review_type: row.get(3)?,
+                },
+            ))
+        })
+        .map_err(read_error)?;
+
+    let mut reviews: HashMap<i64, Vec<AnkiReviewRow>> = HashMap::new();
+    for row in rows {
+        match row {
+            Ok((card_id, review)) => reviews.entry(card_id).or_default().push(review),
+            Err(e) => warn!("Failed to read review: {}", e),
+        }
+    }
+    Ok(reviews)
+}
+
+impl AnkiReviewRow {
+    /// Learning and relearning steps (types 0 and 2) are same-day reviews,
+    /// which Origa rates in short-term mode.
+    fn to_entry(&self) -> Option<ReviewLogEntry> {
+        let mode = match self.review_type {
+            0 | 2 => RateMode::ShortTerm,
+            _ => RateMode::StandardLesson,
+        };
+        Some(ReviewLogEntry::new(
+            DateTime::from_timestamp_millis(self.review_id)?,
+            rating_from_ease(self.ease)?,
+            mode,
+        ))
+    }
+}
+
+impl AnkiCardRow {
+    fn to_schedule(
+        &self,
+        collection_start: DateTime<Utc>,
+        reviews: &[AnkiReviewRow],
+    ) -> Option<AnkiSchedule> {
+        let card_state = match self.card_type {
+            1 => CardState::Learning,
+            2 => CardState::Review,
+            3 => CardState::Relearning,
+            _ => return None,
+        };
+        let next_review = if self.due > DUE_TIMESTAMP_THRESHOLD {
+            DateTime::from_timestamp(self.due, 0)?
+        } else {
+            collection_start + Duration::days(self.due)
+        };
+
+        // Decks scheduled with FSRS keep their memory in `data`; SM-2 decks
+        // only have the interval, which FSRS stability approximates at the
+        // default 90% retention.
+        let fsrs: Value = serde_json::from_str(&self.data).unwrap_or(Value::Null);
+        let stability = fsrs["s"]
+            .as_f64()
+            .unwrap_or_else(|| self.interval_days.max(1) as f64);
+        let difficulty = fsrs["d"]
+            .as_f64()
+            .unwrap_or_else(|| difficulty_from_factor(self.factor));
+
+        let memory_state = MemoryState::with_card_state(
+            Stability::new(stability).ok()?,
+            Difficulty::new(difficulty).ok()?,
+            next_review,
+            card_state,
+        );
+        let reviews: Vec<ReviewLogEntry> =
+            reviews.iter().filter_map(AnkiReviewRow::to_entry).collect();
+        let last_review = reviews
+            .last()
+            .map(|review| (review.reviewed_at(), review.rating()));
+
+        Some(AnkiSchedule {
+            memory_state,
+            reps: u32::try_from(self.reps).unwrap_or_default(),
+            lapses: u32::try_from(self.lapses).unwrap_or_default(),
+            last_review,
+            reviews,
+        })
+    }
+}
+
+/// Inverse of the export mapping: Anki's SM-2 ease, 300% down to 130% in
+/// permille, onto FSRS difficulty 1..10.
+fn difficulty_from_factor(factor: i64) -> f64 {
+    if factor <= 0 {
+        return DEFAULT_DIFFICULTY;
+    }
+    let hardness = ((3000 - factor) as f64 / 1700.0).clamp(0.0, 1.0);
+    1.0 + hardness * 9.0
+}
+
+fn rating_from_ease(ease: i64) -> Option<Rating> {
+    match ease {
+        1 => Some(Rating::Again),
+        2 => Some(Rating::Hard),
+        3 => Some(Rating::Good),
+        4 => Some(Rating::Easy),
+        _ => None,
+    }
 }
 
 fn detect_fields(models: &Value) -> Result<Vec<AnkiFieldInfo>, OrigaError> {
@@ -224,56 +640,115 @@ fn detect_fields(models: &Value) -> Result<Vec<AnkiFieldInfo>, OrigaError> {
     Ok(fields)
 }
 
+struct FieldIndices {
+    word: usize,
+    translation: Option<usize>,
+    reading: Option<usize>,
+    example_sentence: Option<usize>,
+    kanji: Option<usize>,
+}
+
+/// Finds the first note type holding the word field and every other
+/// mapped field.
 fn find_field_indices(
     models: &Value,
-    word_tag: &str,
-    translation_tag: Option<&str>,
-) -> Result<(usize, Option<usize>), OrigaError> {
+    mapping: &AnkiFieldMapping,
+) -> Result<FieldIndices, OrigaError> {
+    let mut missing = mapping.word.clone();
     if let Some(map) = models.as_object() {
         for (_id, data) in map {
-            if let Some(flds) = data["flds"].as_array() {
-                let mut word_idx = None;
-                let mut trans_idx = None;
-
-                for (i, f) in flds.iter().enumerate() {
-                    if let Some(name) = f["name"].as_str() {
-                        let lower = name.to_lowercase();
-                        if lower == word_tag.to_lowercase() {
-                            word_idx = Some(i);
-                        }
-                        if let Some(tag) = translation_tag {
-                            if lower == tag.to_lowercase() {
-                                trans_idx = Some(i);
-                            }
-                        }
-                    }
-                }
+            let Some(flds) = data["flds"].as_array() else {
+                continue;
+            };
+            let names: Vec<String> = flds
+                .iter()
+                .map(|f| f["name"].as_str().unwrap_or_default().to_lowercase())
+                .collect();
+            let index_of = |tag: &str| {
+                let tag = tag.to_lowercase();
+                names.iter().position(|name| *name == tag)
+            };
 
-                if let Some(idx) = word_idx
-                    && (translation_tag.is_none() || trans_idx.is_some())
-                {
-                    return Ok((idx, trans_idx));
-                }
+            let Some(word) = index_of(&mapping.word) else {
+                continue;
+            };
+            let optional = [
+                &mapping.translation,
+                &mapping.reading,
+                &mapping.example_sentence,
+                &mapping.kanji,
+            ]
+            .map(|tag| tag.as_deref().map(|tag| (tag, index_of(tag))));
+            if let Some((tag, _)) = optional.iter().flatten().find(|(_, idx)| idx.is_none()) {
+                missing = tag.to_string();
+                continue;
             }
+            let [translation, reading, example_sentence, kanji] =
+                optional.map(|found| found.and_then(|(_, idx)| idx));
+            return Ok(FieldIndices {
+                word,
+                translation,
+                reading,
+                example_sentence,
+                kanji,
+            });
         }
     }
     Err(OrigaError::AnkiFieldNotFound {
-        field_name: word_tag.to_string(),
+        field_name: missing,
     })
 }
 
-fn parse_fields(flds: &str, word_idx: usize, trans_idx: Option<usize>) -> (String, Option<String>) {
+fn parse_fields(flds: &str, indices: &FieldIndices) -> Option<AnkiCard> {
     let fields: Vec<&str> = flds.split(FIELD_SEP).collect();
-    let word = clean_html(fields.get(word_idx).unwrap_or(&""));
-    let translation = trans_idx.and_then(|idx| {
-        let cleaned = clean_html(fields.get(idx).unwrap_or(&""));
+    let field = |idx: Option<usize>| {
+        let cleaned = strip_furigana(&clean_html(fields.get(idx?).unwrap_or(&"")));
         if cleaned.is_empty() {
             None
         } else {
             Some(cleaned)
         }
-    });
-    (word, translation)
+    };
+    let word = field(Some(indices.word))?;
+    Some(AnkiCard {
+        word,
+        translation: indices
+            .translation
+            .map(|idx| clean_html(fields.get(idx).unwrap_or(&"")))
+            .filter(|translation| !translation.is_empty()),
+        reading: field(indices.reading),
+        example_sentence: field(indices.example_sentence),
+        kanji: field(indices.kanji),
+        schedule: None,
+    })
+}
+
+/// Drops Anki furigana annotations: `日本語[にほんご]を 勉強[べんきょう]`
+/// becomes `日本語を勉強`. A space directly before an annotated group only
+/// delimits it and is dropped too; every other space is kept.
+fn strip_furigana(text: &str) -> String {
+    if !text.contains('[') {
+        return text.to_string();
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut in_reading = false;
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            '[' => in_reading = true,
+            ']' => in_reading = false,
+            _ if in_reading => {},
+            ' ' if starts_annotated_group(&chars[i + 1..]) => {},
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Whether `rest` opens with a non-empty word followed by `[reading]`.
+fn starts_annotated_group(rest: &[char]) -> bool {
+    let word_len = rest.iter().take_while(|&&c| c != ' ' && c != '[').count();
+    word_len > 0 && rest.get(word_len) == Some(&'[')
 }
 
 fn clean_html(raw: &str) -> String {
@@ -0,0 +1,174 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use crate::domain::{
+    ExampleContext, OrigaError, PartOfSpeech, SubtitleFormat, SubtitleLine, parse_subtitle_file,
+    parse_subtitles, tokenize_text,
+};
+use crate::traits::UserRepository;
+use crate::use_cases::{
+    CreateCardsFromAnalysisResult, CreateCardsFromAnalysisUseCase, WordToCreate,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinedWord {
+    pub base_form: String,
+    pub reading: String,
+    pub part_of_speech: PartOfSpeech,
+    pub occurrences: usize,
+    pub meaning: Option<String>,
+    /// First cue the word appears in; becomes the card's example context.
+    pub example: SubtitleLine,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtitleMiningResult {
+    /// Words without a card yet, most frequent first.
+    pub words: Vec<MinedWord>,
+    pub lines_count: usize,
+    pub known_count: usize,
+    pub comprehension_percentage: f32,
+}
+
+pub struct MineSubtitlesUseCase<'a, R: UserRepository> {
+    repository: &'a R,
+}
+
+impl<'a, R: UserRepository> MineSubtitlesUseCase<'a, R> {
+    pub fn new(repository: &'a R) -> Self {
+        Self { repository }
+    }
+
+    /// Parses an episode's subtitles and ranks the words the user has no card
+    /// for by how often they occur across all cues.
+    pub async fn execute(
+        &self,
+        content: &str,
+        format: SubtitleFormat,
+    ) -> Result<SubtitleMiningResult, OrigaError> {
+        let lines = parse_subtitles(content, format)?;
+        debug!(lines_count = lines.len(), format = ?format, "Parsed subtitles");
+
+        self.execute_lines(lines).await
+    }
+
+    /// Like [`Self::execute`] for an uploaded file, whatever its encoding.
+    pub async fn execute_file(
+        &self,
+        file_name: &str,
+        data: &[u8],
+    ) -> Result<SubtitleMiningResult, OrigaError> {
+        let lines = parse_subtitle_file(file_name, data)?;
+        debug!(lines_count = lines.len(), file_name, "Parsed subtitle file");
+
+        self.execute_lines(lines).await
+    }
+
+    /// Mines already timed lines, e.g. `TranscriptionResult::subtitle_lines`
+    /// of a transcribed recording.
+    pub async fn execute_lines(
+        &self,
+        lines: Vec<SubtitleLine>,
+    ) -> Result<SubtitleMiningResult, OrigaError> {
+        let user = self
+            .repository
+            .get_current_user()
+            .await?
+            .ok_or(OrigaError::CurrentUserNotExist)?;
+
+        let mut words: Vec<MinedWord> = Vec::new();
+        let mut index_by_word: HashMap<String, usize> = HashMap::new();
+        let mut known_words: HashSet<String> = HashSet::new();
+
+        for line in &lines {
+            for token in tokenize_text(line.text())? {
+                if !token.part_of_speech().is_vocabulary_word() {
+                    continue;
+                }
+                let base_form = token.orthographic_base_form().to_string();
+
+                if let Some(&index) = index_by_word.get(&base_form) {
+                    words[index].occurrences += 1;
+                    continue;
+                }
+
+                if known_words.contains(&base_form) {
+                    continue;
+                }
+                let knowledge = user.is_word_known(&base_form);
+                if knowledge.is_known {
+                    known_words.insert(base_form);
+                    continue;
+                }
+
+                index_by_word.insert(base_form.clone(), words.len());
+                words.push(MinedWord {
+                    meaning: knowledge.meaning,
+                    reading: token.phonological_base_form().to_string(),
+                    part_of_speech: token.part_of_speech().clone(),
+                    base_form,
+                    occurrences: 1,
+                    example: line.clone(),
+                });
+            }
+        }
+
+        // Stable sort keeps first-appearance order among equally frequent words.
+        words.sort_by_key(|w| Reverse(w.occurrences));
+
+        let episode_text = lines
+            .iter()
+            .map(SubtitleLine::text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let comprehension_percentage = user
+            .score_content(&episode_text)?
+            .comprehension_percentage();
+        let known_count = known_words.len();
+
+        info!(
+            lines_count = lines.len(),
+            unknown_count = words.len(),
+            known_count,
+            comprehension_percentage,
+            "Subtitles mined"
+        );
+
+        Ok(SubtitleMiningResult {
+            words,
+            lines_count: lines.len(),
+            known_count,
+            comprehension_percentage,
+        })
+    }
+
+    /// Creates vocabulary cards for the selected words, keeping the subtitle
+    /// line and its timestamp as each card's example context. `source` is
+    /// the episode label shown next to the sentence.
+    pub async fn create_cards(
+        &self,
+        words: Vec<MinedWord>,
+        source: Option<String>,
+    ) -> Result<CreateCardsFromAnalysisResult, OrigaError> {
+        let words = words
+            .into_iter()
+            .map(|word| {
+                let mut context = ExampleContext::new(word.example.text().to_string())
+                    .with_timestamp_ms(word.example.start_ms());
+                if let Some(source) = &source {
+                    context = context.with_source(source.clone());
+                }
+                WordToCreate {
+                    base_form: word.base_form,
+                    context: Some(context),
+                }
+            })
+            .collect();
+
+        CreateCardsFromAnalysisUseCase::new(self.repository)
+            .execute(words, None)
+            .await
+    }
+}
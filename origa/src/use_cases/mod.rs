@@ -1,3 +1,4 @@
+mod analyze_book;
 mod analyze_text_for_cards;
 mod complete_onboarding_scoring;
 mod create_cards_from_analysis;
@@ -6,19 +7,24 @@ mod create_kanji_card;
 mod create_phrase_card;
 mod create_vocabulary_card;
 mod delete_card;
+mod export_anki_pack;
 mod extract_text_from_image;
+mod get_exam_readiness;
 mod import_anki_pack;
 mod import_onboarding_sets;
 mod mark_card_as_known;
 mod migrate_kanji_companions;
 mod migrate_vocabulary_lemmas;
 mod migrate_vocabulary_part_of_speech;
+mod mine_subtitles;
+mod optimize_srs_parameters;
 mod rate_card;
 mod rate_card_with_side_effects;
 mod seed_ready_phrases;
 mod select_cards_to_lesson;
 mod toggle_favorite;
 mod transcribe_audio;
+mod update_srs_settings;
 mod update_user_profile;
 
 #[cfg(test)]
@@ -26,6 +32,7 @@ mod tests;
 #[cfg(test)]
 pub use tests::fixtures::{init_phrase_index_from_cdn, init_real_dictionaries};
 
+pub use analyze_book::{AnalyzeBookUseCase, BookAnalysisResult, ChapterAnalysis, NPlusOneSentence};
 pub use analyze_text_for_cards::{AnalyzeTextForCardsUseCase, AnalyzeTextResult, AnalyzedWord};
 pub use complete_onboarding_scoring::CompleteOnboardingScoringUseCase;
 pub use create_cards_from_analysis::{
@@ -36,10 +43,13 @@ pub use create_kanji_card::CreateKanjiCardUseCase;
 pub use create_phrase_card::CreatePhraseCardUseCase;
 pub use create_vocabulary_card::CreateVocabularyCardUseCase;
 pub use delete_card::DeleteCardUseCase;
-pub use extract_text_from_image::ExtractTextFromImageUseCase;
+pub use export_anki_pack::{ExportAnkiPackResult, ExportAnkiPackUseCase, build_anki_pack};
+pub use extract_text_from_image::{ExtractTextFromImageUseCase, ImageText};
+pub use get_exam_readiness::GetExamReadinessUseCase;
 pub use import_anki_pack::{
-    AnkiCard, AnkiDeckInfo, AnkiFieldInfo, ImportAnkiPackResult, ImportAnkiPackUseCase,
-    extract_anki_db_bytes, extract_cards, parse_cards, read_anki_database,
+    AnkiCard, AnkiDeckInfo, AnkiFieldInfo, AnkiFieldMapping, AnkiImportPreview, AnkiSchedule,
+    ImportAnkiPackResult, ImportAnkiPackUseCase, extract_anki_db_bytes, extract_cards,
+    extract_cards_with_mapping, parse_cards, parse_cards_with_mapping, read_anki_database,
 };
 pub use import_onboarding_sets::{ImportOnboardingResult, ImportOnboardingSetsUseCase};
 pub use mark_card_as_known::MarkCardAsKnownUseCase;
@@ -48,6 +58,8 @@ pub use migrate_vocabulary_lemmas::{LemmaMigrationResult, MigrateVocabularyLemma
 pub use migrate_vocabulary_part_of_speech::{
     MigrateVocabularyPartOfSpeechUseCase, PartOfSpeechMigrationResult,
 };
+pub use mine_subtitles::{MineSubtitlesUseCase, MinedWord, SubtitleMiningResult};
+pub use optimize_srs_parameters::OptimizeSrsParametersUseCase;
 pub use rate_card::RateCardUseCase;
 pub use rate_card_with_side_effects::RateCardWithSideEffectsUseCase;
 pub use seed_ready_phrases::SeedReadyPhrasesUseCase;
@@ -55,5 +67,6 @@ pub use seed_ready_phrases::collect_known_grammar_rules;
 pub use seed_ready_phrases::{classify_orphaned_phrases, delete_phrase_cards_by_phrase_ids};
 pub use select_cards_to_lesson::SelectCardsToLessonUseCase;
 pub use toggle_favorite::ToggleFavoriteUseCase;
-pub use transcribe_audio::TranscribeAudioUseCase;
+pub use transcribe_audio::{TranscribeAudioUseCase, TranscriptionResult};
+pub use update_srs_settings::{SrsSettingsChange, UpdateSrsSettingsUseCase};
 pub use update_user_profile::UpdateUserProfileUseCase;
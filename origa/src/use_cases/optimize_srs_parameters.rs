@@ -0,0 +1,62 @@
+use std::future::Future;
+
+use crate::domain::{OrigaError, SrsOptimizationReport};
+use crate::traits::UserRepository;
+use tracing::{debug, info};
+
+/// Optimizer iterations between two yields. Each iteration replays every
+/// review log 38 times (a central-difference gradient over 19 weights).
+const ITERATIONS_PER_CHUNK: usize = 2;
+
+/// Fits per-user FSRS weights from the opt-in review log. A full fit is 150
+/// iterations per scheduling family, which takes seconds on WASM for a heavy
+/// user's log, so it runs on demand in chunks rather than after every lesson.
+#[derive(Clone)]
+pub struct OptimizeSrsParametersUseCase<'a, R: UserRepository> {
+    repository: &'a R,
+}
+
+impl<'a, R: UserRepository> OptimizeSrsParametersUseCase<'a, R> {
+    pub fn new(repository: &'a R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<SrsOptimizationReport>, OrigaError> {
+        self.execute_with_yield(|| std::future::ready(())).await
+    }
+
+    /// Like [`Self::execute`], awaiting `yield_now` between chunks of the fit
+    /// so a single-threaded (WASM) caller keeps the UI responsive.
+    pub async fn execute_with_yield<Y, F>(
+        &self,
+        yield_now: Y,
+    ) -> Result<Vec<SrsOptimizationReport>, OrigaError>
+    where
+        Y: Fn() -> F,
+        F: Future<Output = ()>,
+    {
+        debug!("Optimizing SRS parameters");
+
+        let mut user = self
+            .repository
+            .get_current_user()
+            .await?
+            .ok_or(OrigaError::CurrentUserNotExist)?;
+
+        let mut optimization = user.start_srs_optimization();
+        while optimization.advance(ITERATIONS_PER_CHUNK) {
+            yield_now().await;
+        }
+        let reports = user.finish_srs_optimization(optimization);
+
+        if reports.iter().any(SrsOptimizationReport::applied) {
+            self.repository.save_sync(&user).await?;
+        }
+
+        info!(
+            applied = reports.iter().filter(|r| r.applied()).count(),
+            "SRS parameters optimized"
+        );
+        Ok(reports)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::domain::{Card, OrigaError, RateMode, Rating};
+use crate::domain::{Card, OrigaError, ParticleCard, RateMode, Rating};
 use crate::traits::UserRepository;
 use crate::use_cases::{CreateGrammarCardUseCase, RateCardUseCase};
 use tracing::warn;
@@ -19,19 +19,77 @@ impl<'a, R: UserRepository> RateCardWithSideEffectsUseCase<'a, R> {
         rate_mode: RateMode,
         rating: Rating,
         grammar_rule_id: Option<Ulid>,
+        particle: Option<String>,
     ) -> Result<(), OrigaError> {
-        RateCardUseCase::new(self.repository)
-            .execute(card_id, rate_mode, rating)
-            .await?;
+        // A particle missed in a phrase cloze is a mistake about the particle,
+        // not the phrase: only the particle card takes the lapse and the
+        // phrase card keeps its schedule.
+        let is_particle_miss = particle.is_some() && rating == Rating::Again;
+        if !is_particle_miss {
+            RateCardUseCase::new(self.repository)
+                .execute(card_id, rate_mode, rating)
+                .await?;
+        }
 
         if let Some(grammar_rule_id) = grammar_rule_id {
             self.handle_grammar_dual_rating(grammar_rule_id, rating)
                 .await;
         }
 
+        if let Some(particle) = particle {
+            self.handle_particle_rating(particle, rating).await;
+        }
+
         Ok(())
     }
 
+    /// Rates the particle card of a particle cloze. A missing card is only
+    /// created on a miss: a particle the learner always gets right needs no
+    /// memory of its own.
+    async fn handle_particle_rating(&self, particle: String, rating: Rating) {
+        let Some(mut user) = self.repository.get_current_user().await.ok().flatten() else {
+            return;
+        };
+
+        let existing_card_id = user
+            .knowledge_set()
+            .study_cards()
+            .iter()
+            .find(|(_, study_card)| {
+                let Card::Particle(particle_card) = study_card.card() else {
+                    return false;
+                };
+                particle_card.particle() == particle
+            })
+            .map(|(id, _)| *id);
+
+        let card_id = match existing_card_id {
+            Some(card_id) => card_id,
+            None if rating == Rating::Again => {
+                let created = match user.create_card(Card::Particle(ParticleCard::new(particle))) {
+                    Ok(created) => created,
+                    Err(e) => {
+                        warn!(error = ?e, "Failed to create particle card");
+                        return;
+                    },
+                };
+                if let Err(e) = self.repository.save(&user).await {
+                    warn!(error = ?e, "Failed to save new particle card");
+                    return;
+                }
+                *created.card_id()
+            },
+            None => return,
+        };
+
+        if let Err(e) = RateCardUseCase::new(self.repository)
+            .execute(card_id, RateMode::GrammarReview, rating)
+            .await
+        {
+            warn!(error = ?e, "Failed to rate particle card");
+        }
+    }
+
     async fn handle_grammar_dual_rating(&self, grammar_rule_id: Ulid, rating: Rating) {
         let Some(user) = self.repository.get_current_user().await.ok().flatten() else {
             return;
@@ -82,7 +140,9 @@ impl<'a, R: UserRepository> RateCardWithSideEffectsUseCase<'a, R> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Card, GrammarRuleCard, NativeLanguage, Question, User, VocabularyCard};
+    use crate::domain::{
+        Card, GrammarRuleCard, NativeLanguage, PhraseCard, Question, User, VocabularyCard,
+    };
     use crate::use_cases::tests::fixtures::InMemoryUserRepository;
 
     fn create_test_user_with_vocab() -> User {
@@ -109,7 +169,7 @@ mod tests {
         let use_case = RateCardWithSideEffectsUseCase::new(&repo);
 
         let result = use_case
-            .execute(card_id, RateMode::StandardLesson, Rating::Good, None)
+            .execute(card_id, RateMode::StandardLesson, Rating::Good, None, None)
             .await;
 
         assert!(result.is_ok());
@@ -139,6 +199,7 @@ mod tests {
                 RateMode::StandardLesson,
                 Rating::Good,
                 Some(grammar_rule_id),
+                None,
             )
             .await;
 
@@ -165,6 +226,7 @@ mod tests {
                 RateMode::StandardLesson,
                 Rating::Good,
                 Some(grammar_rule_id),
+                None,
             )
             .await;
 
@@ -187,9 +249,150 @@ mod tests {
         let use_case = RateCardWithSideEffectsUseCase::new(&repo);
 
         let result = use_case
-            .execute(Ulid::new(), RateMode::StandardLesson, Rating::Good, None)
+            .execute(
+                Ulid::new(),
+                RateMode::StandardLesson,
+                Rating::Good,
+                None,
+                None,
+            )
             .await;
 
         assert!(result.is_err());
     }
+
+    fn create_phrase_card() -> Card {
+        Card::Phrase(PhraseCard::new(Ulid::new()))
+    }
+
+    fn particle_card_id(user: &User, particle: &str) -> Option<Ulid> {
+        user.knowledge_set()
+            .study_cards()
+            .iter()
+            .find(|(_, study_card)| {
+                matches!(study_card.card(), Card::Particle(pc) if pc.particle() == particle)
+            })
+            .map(|(id, _)| *id)
+    }
+
+    #[tokio::test]
+    async fn particle_miss_creates_and_rates_particle_card() {
+        // Arrange
+        let mut user = create_test_user_with_vocab();
+        let study_card = user.create_card(create_phrase_card()).unwrap();
+        let card_id = *study_card.card_id();
+        let repo = InMemoryUserRepository::with_user(user);
+        let use_case = RateCardWithSideEffectsUseCase::new(&repo);
+
+        // Act
+        let result = use_case
+            .execute(
+                card_id,
+                RateMode::PhraseReview,
+                Rating::Again,
+                None,
+                Some("に".to_string()),
+            )
+            .await;
+
+        // Assert
+        assert!(result.is_ok());
+        let updated_user = repo.get_current_user().await.unwrap().unwrap();
+        let particle_id = particle_card_id(&updated_user, "に").expect("particle card created");
+        let particle_card = updated_user.knowledge_set().get_card(particle_id).unwrap();
+        assert!(!particle_card.is_new());
+    }
+
+    #[tokio::test]
+    async fn particle_miss_leaves_phrase_card_memory_unchanged() {
+        // Arrange
+        let mut user = create_test_user_with_vocab();
+        let study_card = user.create_card(create_phrase_card()).unwrap();
+        let card_id = *study_card.card_id();
+        user.rate_card(card_id, Rating::Good, RateMode::PhraseReview)
+            .unwrap();
+        let memory_before = user
+            .knowledge_set()
+            .get_card(card_id)
+            .unwrap()
+            .memory()
+            .clone();
+        let repo = InMemoryUserRepository::with_user(user);
+        let use_case = RateCardWithSideEffectsUseCase::new(&repo);
+
+        // Act
+        let result = use_case
+            .execute(
+                card_id,
+                RateMode::PhraseReview,
+                Rating::Again,
+                None,
+                Some("を".to_string()),
+            )
+            .await;
+
+        // Assert
+        assert!(result.is_ok());
+        let updated_user = repo.get_current_user().await.unwrap().unwrap();
+        let phrase_card = updated_user.knowledge_set().get_card(card_id).unwrap();
+        assert_eq!(phrase_card.memory(), &memory_before);
+        assert!(particle_card_id(&updated_user, "を").is_some());
+    }
+
+    #[tokio::test]
+    async fn particle_hit_does_not_create_particle_card() {
+        // Arrange
+        let mut user = create_test_user_with_vocab();
+        let study_card = user.create_card(create_phrase_card()).unwrap();
+        let card_id = *study_card.card_id();
+        let repo = InMemoryUserRepository::with_user(user);
+        let use_case = RateCardWithSideEffectsUseCase::new(&repo);
+
+        // Act
+        let result = use_case
+            .execute(
+                card_id,
+                RateMode::PhraseReview,
+                Rating::Good,
+                None,
+                Some("に".to_string()),
+            )
+            .await;
+
+        // Assert
+        assert!(result.is_ok());
+        let updated_user = repo.get_current_user().await.unwrap().unwrap();
+        assert!(particle_card_id(&updated_user, "に").is_none());
+    }
+
+    #[tokio::test]
+    async fn particle_hit_rates_existing_particle_card() {
+        // Arrange
+        let mut user = create_test_user_with_vocab();
+        let study_card = user.create_card(create_phrase_card()).unwrap();
+        let card_id = *study_card.card_id();
+        let particle_study = user
+            .create_card(Card::Particle(ParticleCard::new("は".to_string())))
+            .unwrap();
+        let particle_id = *particle_study.card_id();
+        let repo = InMemoryUserRepository::with_user(user);
+        let use_case = RateCardWithSideEffectsUseCase::new(&repo);
+
+        // Act
+        let result = use_case
+            .execute(
+                card_id,
+                RateMode::PhraseReview,
+                Rating::Good,
+                None,
+                Some("は".to_string()),
+            )
+            .await;
+
+        // Assert
+        assert!(result.is_ok());
+        let updated_user = repo.get_current_user().await.unwrap().unwrap();
+        let particle_card = updated_user.knowledge_set().get_card(particle_id).unwrap();
+        assert!(!particle_card.is_new());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::domain::{DailyBudget, JlptContent, LessonData, OrigaError};
+use crate::domain::{JlptContent, LessonData, OrigaError};
 use crate::traits::UserRepository;
 use tracing::{debug, info};
 
@@ -21,7 +21,7 @@ impl<'a, R: UserRepository> SelectCardsToLessonUseCase<'a, R> {
 
         debug!(user_id = %user.id(), "Selecting cards to lesson");
 
-        let budget = DailyBudget::from_load(*user.daily_load());
+        let budget = user.lesson_budget(jlpt_content);
         let user_level = user.current_japanese_level();
         let native_language = *user.native_language();
         let lesson_data =
@@ -0,0 +1,81 @@
+use crate::domain::{Book, NativeLanguage, OrigaError, User};
+use crate::use_cases::tests::fixtures::{InMemoryUserRepository, init_real_dictionaries};
+use crate::use_cases::{AnalyzeBookUseCase, CreateCardsFromAnalysisUseCase};
+
+const BOOK_TEXT: &str = "第一章
+猫が好きです。猫が来た。
+
+第二章
+犬が好きです。";
+
+fn create_repo() -> InMemoryUserRepository {
+    InMemoryUserRepository::with_user(User::new(
+        "test@example.com".to_string(),
+        NativeLanguage::Russian,
+        None,
+    ))
+}
+
+#[tokio::test]
+async fn book_analysis_scores_each_chapter() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = create_repo();
+    let book = Book::from_plain_text("物語", BOOK_TEXT);
+
+    // Act
+    let result = AnalyzeBookUseCase::new(&repo)
+        .execute(&book, 5)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(result.title, "物語");
+    assert_eq!(result.comprehension_percentage, 0.0);
+    // 好き appears in both chapters but counts once for the book.
+    assert!(
+        result.unknown_word_count
+            < result.chapters[0].unknown_word_count + result.chapters[1].unknown_word_count
+    );
+    assert_eq!(result.chapters.len(), 2);
+    let first = &result.chapters[0];
+    assert_eq!(first.title, "第一章");
+    assert_eq!(first.comprehension_percentage, 0.0);
+    assert_eq!(first.unknown_word_count, first.unknown_words.len());
+    assert_eq!(first.unknown_words[0], "猫");
+}
+
+#[tokio::test]
+async fn chapter_unknown_words_can_be_mined_into_cards() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = create_repo();
+    let book = Book::from_plain_text("物語", "猫が来た。");
+    let analysis = AnalyzeBookUseCase::new(&repo)
+        .execute(&book, 5)
+        .await
+        .unwrap();
+    let chapter = &analysis.chapters[0];
+
+    // Act
+    let result = CreateCardsFromAnalysisUseCase::new(&repo)
+        .execute(chapter.words_to_create(&analysis.title), None)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(result.created_cards.len(), chapter.unknown_word_count);
+}
+
+#[tokio::test]
+async fn book_analysis_requires_current_user() {
+    // Arrange
+    let repo = InMemoryUserRepository::new();
+    let book = Book::from_plain_text("物語", BOOK_TEXT);
+
+    // Act
+    let result = AnalyzeBookUseCase::new(&repo).execute(&book, 5).await;
+
+    // Assert
+    assert!(matches!(result, Err(OrigaError::CurrentUserNotExist)));
+}
@@ -237,3 +237,24 @@ async fn analyze_text_whitespace_only() {
 
     assert!(analysis.words.is_empty());
 }
+
+#[tokio::test]
+async fn analyze_text_keeps_sentence_for_each_word() {
+    init_real_dictionaries();
+
+    let user = User::new(
+        "test@example.com".to_string(),
+        NativeLanguage::Russian,
+        None,
+    );
+    let repo = InMemoryUserRepository::with_user(user);
+    let use_case = AnalyzeTextForCardsUseCase::new(&repo);
+
+    let analysis = use_case
+        .execute("猫が好きです。犬も走る。".to_string())
+        .await
+        .unwrap();
+
+    let dog = analysis.words.iter().find(|w| w.base_form == "犬").unwrap();
+    assert_eq!(dog.sentence.as_deref(), Some("犬も走る。"));
+}
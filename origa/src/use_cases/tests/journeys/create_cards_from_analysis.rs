@@ -1,4 +1,4 @@
-use crate::domain::{CardType, NativeLanguage, OrigaError, User};
+use crate::domain::{Card, CardType, ExampleContext, NativeLanguage, OrigaError, User};
 use crate::traits::UserRepository;
 use crate::use_cases::tests::fixtures::{InMemoryUserRepository, init_real_dictionaries};
 use crate::use_cases::{CreateCardsFromAnalysisUseCase, WordToCreate};
@@ -19,6 +19,7 @@ async fn create_cards_from_analysis_creates_vocabulary_cards() {
 
     let words = vec![WordToCreate {
         base_form: "あい変わらず".to_string(),
+        ..Default::default()
     }];
 
     let result = use_case.execute(words, None).await.unwrap();
@@ -40,9 +41,11 @@ async fn create_cards_from_analysis_creates_multiple_cards() {
     let words = vec![
         WordToCreate {
             base_form: "あい変わらず".to_string(),
+            ..Default::default()
         },
         WordToCreate {
             base_form: "あい昧".to_string(),
+            ..Default::default()
         },
     ];
 
@@ -59,6 +62,7 @@ async fn create_cards_from_analysis_returns_error_when_user_not_found() {
 
     let words = vec![WordToCreate {
         base_form: "あい変わらず".to_string(),
+        ..Default::default()
     }];
 
     let result = use_case.execute(words, None).await;
@@ -74,6 +78,7 @@ async fn create_cards_from_analysis_skips_duplicates() {
 
     let words = vec![WordToCreate {
         base_form: "あい変わらず".to_string(),
+        ..Default::default()
     }];
 
     let first = use_case.execute(words.clone(), None).await.unwrap();
@@ -95,9 +100,11 @@ async fn create_cards_from_analysis_handles_failed_words() {
     let words = vec![
         WordToCreate {
             base_form: "あい変わらず".to_string(),
+            ..Default::default()
         },
         WordToCreate {
             base_form: "not_japanese".to_string(),
+            ..Default::default()
         },
     ];
 
@@ -116,6 +123,7 @@ async fn create_cards_from_analysis_marks_set_as_imported() {
 
     let words = vec![WordToCreate {
         base_form: "あい変わらず".to_string(),
+        ..Default::default()
     }];
     let set_id = "test-set-123".to_string();
 
@@ -137,6 +145,7 @@ async fn create_cards_from_analysis_marks_multiple_sets_as_imported() {
 
     let words = vec![WordToCreate {
         base_form: "あい変わらず".to_string(),
+        ..Default::default()
     }];
     let set_ids = vec![
         "set-1".to_string(),
@@ -183,6 +192,7 @@ async fn create_cards_from_analysis_persists_cards_in_repository() {
 
     let words = vec![WordToCreate {
         base_form: "あい変わらず".to_string(),
+        ..Default::default()
     }];
 
     use_case.execute(words, None).await.unwrap();
@@ -200,9 +210,11 @@ async fn create_cards_from_analysis_mixed_results() {
     let words = vec![
         WordToCreate {
             base_form: "あい変わらず".to_string(),
+            ..Default::default()
         },
         WordToCreate {
             base_form: "invalid".to_string(),
+            ..Default::default()
         },
     ];
 
@@ -215,3 +227,29 @@ async fn create_cards_from_analysis_mixed_results() {
     assert!(second.created_cards.is_empty());
     assert!(second.skipped_words.contains(&"あい変わらず".to_string()));
 }
+
+#[tokio::test]
+async fn create_cards_from_analysis_stores_example_context() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = create_repo();
+    let use_case = CreateCardsFromAnalysisUseCase::new(&repo);
+    let words = vec![WordToCreate {
+        base_form: "猫".to_string(),
+        context: Some(
+            ExampleContext::new("猫が好きです。".to_string()).with_source("photo.png".to_string()),
+        ),
+    }];
+
+    // Act
+    let result = use_case.execute(words, None).await.unwrap();
+
+    // Assert
+    let Card::Vocabulary(vocab) = result.created_cards[0].card() else {
+        panic!("expected vocabulary card");
+    };
+    let context = vocab.context().unwrap();
+    assert_eq!(context.sentence(), "猫が好きです。");
+    assert_eq!(context.source(), Some("photo.png"));
+    assert_eq!(vocab.example_cloze().as_deref(), Some("＿＿が好きです。"));
+}
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+
+use chrono::{Duration, Utc};
+
+use crate::domain::{DailyLoad, JapaneseLevel, JlptContent, RateMode, Rating};
+use crate::traits::UserRepository;
+use crate::use_cases::tests::fixtures::{InMemoryUserRepository, create_user_with_vocab_cards};
+use crate::use_cases::{
+    GetExamReadinessUseCase, RateCardUseCase, SelectCardsToLessonUseCase, SrsSettingsChange,
+    UpdateSrsSettingsUseCase,
+};
+
+const WORDS: usize = 40;
+
+fn n5_content() -> JlptContent {
+    let mut content = JlptContent::new();
+    content.words_by_level.insert(
+        JapaneseLevel::N5,
+        (0..WORDS).map(|i| format!("word_{}", i)).collect(),
+    );
+    content
+}
+
+fn minimal_load_repo() -> InMemoryUserRepository {
+    let mut user = create_user_with_vocab_cards(WORDS);
+    user.set_daily_load(DailyLoad::Minimal);
+    InMemoryUserRepository::with_user(user)
+}
+
+async fn set_exam_in(repo: &InMemoryUserRepository, days: i64) {
+    UpdateSrsSettingsUseCase::new(repo)
+        .execute(SrsSettingsChange::ExamTarget {
+            level: JapaneseLevel::N5,
+            exam_date: Utc::now().date_naive() + Duration::days(days),
+        })
+        .await
+        .unwrap();
+}
+
+async fn distinct_lesson_cards(repo: &InMemoryUserRepository, content: &JlptContent) -> usize {
+    let lesson = SelectCardsToLessonUseCase::new(repo)
+        .execute(content)
+        .await
+        .unwrap();
+    lesson.card_ids().into_iter().collect::<HashSet<_>>().len()
+}
+
+#[tokio::test]
+async fn exam_target_raises_new_card_intake_to_required_pace() {
+    // Arrange
+    let content = n5_content();
+    let repo = minimal_load_repo();
+    let baseline = distinct_lesson_cards(&repo, &content).await;
+
+    // Act
+    set_exam_in(&repo, 4).await;
+    let boosted = distinct_lesson_cards(&repo, &content).await;
+
+    // Assert
+    assert_eq!(baseline, DailyLoad::Minimal.new_cards_per_day());
+    assert_eq!(boosted, WORDS.div_ceil(4));
+}
+
+#[tokio::test]
+async fn exam_target_pulls_reviews_before_the_exam() {
+    // Arrange
+    let repo = minimal_load_repo();
+    set_exam_in(&repo, 3).await;
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let card_id = *user.knowledge_set().study_cards().keys().next().unwrap();
+    let exam_date = user.srs_settings().exam_target().unwrap().exam_date();
+
+    // Act
+    let rate = RateCardUseCase::new(&repo);
+    for _ in 0..4 {
+        rate.execute(card_id, RateMode::StandardLesson, Rating::Easy)
+            .await
+            .unwrap();
+    }
+
+    // Assert
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let card = user.knowledge_set().get_card(card_id).unwrap();
+    let next_review = card.memory().next_review_date().unwrap();
+    assert!(next_review.date_naive() < exam_date);
+}
+
+#[tokio::test]
+async fn readiness_report_tracks_the_exam_target() {
+    // Arrange
+    let content = n5_content();
+    let repo = minimal_load_repo();
+    let readiness = GetExamReadinessUseCase::new(&repo);
+    assert!(readiness.execute(&content).await.unwrap().is_none());
+
+    // Act
+    set_exam_in(&repo, 10).await;
+    let report = readiness.execute(&content).await.unwrap().unwrap();
+
+    // Assert
+    assert_eq!(report.target().level(), JapaneseLevel::N5);
+    assert_eq!(report.new_cards_remaining(), WORDS);
+    assert_eq!(report.missing_items(), 0);
+    assert_eq!(report.required_pace(), 4);
+    assert!(!report.is_reachable(), "no pace history yet");
+}
+
+#[tokio::test]
+async fn exam_date_in_the_past_is_rejected() {
+    let repo = minimal_load_repo();
+
+    let result = UpdateSrsSettingsUseCase::new(&repo)
+        .execute(SrsSettingsChange::ExamTarget {
+            level: JapaneseLevel::N3,
+            exam_date: Utc::now().date_naive() - Duration::days(1),
+        })
+        .await;
+
+    assert!(result.is_err());
+}
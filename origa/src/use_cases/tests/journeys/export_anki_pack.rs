@@ -0,0 +1,166 @@
+use std::io::Cursor;
+
+use rusqlite::Connection;
+use sha1::{Digest, Sha1};
+
+use crate::domain::{OrigaError, RateMode, Rating, User};
+use crate::traits::UserRepository;
+use crate::use_cases::tests::fixtures::{InMemoryUserRepository, create_user_with_vocab_cards};
+use crate::use_cases::{
+    ExportAnkiPackUseCase, RateCardUseCase, ToggleFavoriteUseCase, extract_anki_db_bytes,
+    extract_cards,
+};
+
+fn open_exported_db(apkg: &[u8]) -> Connection {
+    let db_bytes = extract_anki_db_bytes(apkg).unwrap();
+    let mut conn = Connection::open_in_memory().unwrap();
+    let size = db_bytes.len();
+    conn.deserialize_read_exact("main", Cursor::new(db_bytes), size, true)
+        .unwrap();
+    conn
+}
+
+fn count(conn: &Connection, sql: &str) -> i64 {
+    conn.query_row(sql, [], |row| row.get(0)).unwrap()
+}
+
+async fn first_card_id(repo: &InMemoryUserRepository) -> ulid::Ulid {
+    let user: User = repo.get_current_user().await.unwrap().unwrap();
+    let mut ids: Vec<_> = user.knowledge_set().study_cards().keys().copied().collect();
+    ids.sort();
+    ids[0]
+}
+
+#[tokio::test]
+async fn export_writes_a_note_and_card_per_study_card() {
+    // Arrange
+    let repo = InMemoryUserRepository::with_user(create_user_with_vocab_cards(3));
+
+    // Act
+    let result = ExportAnkiPackUseCase::new(&repo).execute().await.unwrap();
+
+    // Assert
+    assert_eq!(result.exported_count, 3);
+    assert!(result.skipped_card_ids.is_empty());
+    let conn = open_exported_db(&result.apkg);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM notes"), 3);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM cards"), 3);
+    // Unreviewed cards are exported as new cards with no review history.
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM cards WHERE type = 0"), 3);
+    assert_eq!(count(&conn, "SELECT COUNT(*) FROM revlog"), 0);
+}
+
+#[tokio::test]
+async fn export_writes_sort_field_checksums() {
+    // Arrange
+    let repo = InMemoryUserRepository::with_user(create_user_with_vocab_cards(2));
+
+    // Act
+    let result = ExportAnkiPackUseCase::new(&repo).execute().await.unwrap();
+
+    // Assert — csum is the first 8 hex digits of SHA-1 of the sort field.
+    let conn = open_exported_db(&result.apkg);
+    let mut stmt = conn.prepare("SELECT sfld, csum FROM notes").unwrap();
+    let notes: Vec<(String, i64)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(notes.len(), 2);
+    for (sort_field, csum) in notes {
+        let digest = Sha1::digest(sort_field.as_bytes());
+        let hex: String = digest[..4].iter().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(csum, i64::from_str_radix(&hex, 16).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn export_tags_favorites() {
+    // Arrange
+    let repo = InMemoryUserRepository::with_user(create_user_with_vocab_cards(2));
+    let card_id = first_card_id(&repo).await;
+    ToggleFavoriteUseCase::new(&repo)
+        .execute(card_id)
+        .await
+        .unwrap();
+
+    // Act
+    let result = ExportAnkiPackUseCase::new(&repo).execute().await.unwrap();
+
+    // Assert
+    let conn = open_exported_db(&result.apkg);
+    let favorite_guid: String = conn
+        .query_row(
+            "SELECT guid FROM notes WHERE tags LIKE '% favorite %'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(favorite_guid, card_id.to_string());
+    assert_eq!(
+        count(
+            &conn,
+            "SELECT COUNT(*) FROM notes WHERE tags LIKE '% origa %'"
+        ),
+        2
+    );
+}
+
+#[tokio::test]
+async fn export_carries_reviewed_card_scheduling_into_cards_and_revlog() {
+    // Arrange
+    let repo = InMemoryUserRepository::with_user(create_user_with_vocab_cards(1));
+    let card_id = first_card_id(&repo).await;
+    RateCardUseCase::new(&repo)
+        .execute(card_id, RateMode::StandardLesson, Rating::Good)
+        .await
+        .unwrap();
+
+    // Act
+    let result = ExportAnkiPackUseCase::new(&repo).execute().await.unwrap();
+
+    // Assert — FSRS memory goes to cards.data, the review to revlog.
+    let conn = open_exported_db(&result.apkg);
+    let (card_type, reps, data): (i64, i64, String) = conn
+        .query_row("SELECT type, reps, data FROM cards", [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .unwrap();
+    assert_ne!(card_type, 0);
+    assert_eq!(reps, 1);
+    let memory: serde_json::Value = serde_json::from_str(&data).unwrap();
+    assert!(memory["s"].as_f64().unwrap() > 0.0);
+    assert!(memory["d"].as_f64().unwrap() > 0.0);
+    let ease: i64 = conn
+        .query_row("SELECT ease FROM revlog", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(ease, 3);
+}
+
+#[tokio::test]
+async fn exported_pack_reads_back_through_anki_import() {
+    // Arrange
+    let repo = InMemoryUserRepository::with_user(create_user_with_vocab_cards(2));
+
+    // Act
+    let result = ExportAnkiPackUseCase::new(&repo).execute().await.unwrap();
+    let (cards, fields) = extract_cards(&result.apkg, "Word", Some("Meaning")).unwrap();
+
+    // Assert
+    let mut words: Vec<_> = cards.into_iter().map(|card| card.word).collect();
+    words.sort();
+    assert_eq!(words, vec!["word_0", "word_1"]);
+    assert!(fields.iter().any(|field| field.name == "Kanji"));
+}
+
+#[tokio::test]
+async fn export_without_user_fails() {
+    // Arrange
+    let repo = InMemoryUserRepository::new();
+
+    // Act
+    let result = ExportAnkiPackUseCase::new(&repo).execute().await;
+
+    // Assert
+    assert!(matches!(result, Err(OrigaError::CurrentUserNotExist)));
+}
@@ -1,9 +1,17 @@
-use crate::domain::{NativeLanguage, OrigaError, User};
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+    CardState, Difficulty, MemoryState, NativeLanguage, OrigaError, RateMode, Rating,
+    ReviewLogEntry, Stability, User, WordImportOutcome,
+};
 use crate::traits::UserRepository;
-use crate::use_cases::tests::fixtures::{InMemoryUserRepository, init_real_dictionaries};
+use crate::use_cases::tests::fixtures::{
+    InMemoryUserRepository, create_user_with_vocab_cards, init_real_dictionaries,
+};
 use crate::use_cases::{
-    AnkiCard, ImportAnkiPackUseCase, extract_anki_db_bytes, extract_cards, parse_cards,
-    read_anki_database,
+    AnkiCard, AnkiFieldMapping, AnkiSchedule, ExportAnkiPackUseCase, ImportAnkiPackUseCase,
+    RateCardUseCase, extract_anki_db_bytes, extract_cards, extract_cards_with_mapping, parse_cards,
+    parse_cards_with_mapping, read_anki_database,
 };
 use serde_json::Value;
 
@@ -187,6 +195,279 @@ fn parse_cards_handles_empty_notes() {
     assert!(result.unwrap().is_empty());
 }
 
+#[test]
+fn parse_cards_with_mapping_reads_reading_example_and_kanji() {
+    let models = r#"{ "123": { "flds": [
+        {"name": "Expression"}, {"name": "Reading"}, {"name": "Meaning"},
+        {"name": "Sentence"}, {"name": "Kanji"}
+    ] } }"#;
+    let notes = vec![
+        [
+            "日本語",
+            "にほんご",
+            "Japanese",
+            "日本語[にほんご]を 勉強[べんきょう]する",
+            "日本",
+        ]
+        .join("\x1f"),
+    ];
+    let mapping = AnkiFieldMapping {
+        translation: Some("meaning".to_string()),
+        reading: Some("reading".to_string()),
+        example_sentence: Some("sentence".to_string()),
+        kanji: Some("kanji".to_string()),
+        ..AnkiFieldMapping::new("expression")
+    };
+
+    let models: Value = serde_json::from_str(models).unwrap();
+    let cards = parse_cards_with_mapping(&models, &notes, &mapping).unwrap();
+
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0].reading.as_deref(), Some("にほんご"));
+    // Furigana annotations are dropped from Japanese fields.
+    assert_eq!(
+        cards[0].example_sentence.as_deref(),
+        Some("日本語を勉強する")
+    );
+    assert_eq!(cards[0].kanji.as_deref(), Some("日本"));
+    assert!(cards[0].schedule.is_none());
+}
+
+#[test]
+fn parse_cards_with_mapping_keeps_spaces_outside_furigana_groups() {
+    let models = r#"{ "123": { "flds": [
+        {"name": "Expression"}, {"name": "Sentence"}
+    ] } }"#;
+    let notes =
+        vec!["日本語\x1f彼は 日本語[にほんご]を 話[はな]す。 I speak Japanese.".to_string()];
+    let mapping = AnkiFieldMapping {
+        example_sentence: Some("sentence".to_string()),
+        ..AnkiFieldMapping::new("expression")
+    };
+
+    let models: Value = serde_json::from_str(models).unwrap();
+    let cards = parse_cards_with_mapping(&models, &notes, &mapping).unwrap();
+
+    assert_eq!(
+        cards[0].example_sentence.as_deref(),
+        Some("彼は日本語を話す。 I speak Japanese.")
+    );
+}
+
+#[test]
+fn parse_cards_with_mapping_reports_missing_mapped_field() {
+    let models = r#"{ "123": { "flds": [
+        {"name": "Expression"}, {"name": "Meaning"}
+    ] } }"#;
+    let mapping = AnkiFieldMapping {
+        reading: Some("Reading".to_string()),
+        ..AnkiFieldMapping::new("expression")
+    };
+
+    let models: Value = serde_json::from_str(models).unwrap();
+    let result = parse_cards_with_mapping(&models, &[], &mapping);
+
+    assert!(matches!(
+        result,
+        Err(OrigaError::AnkiFieldNotFound { field_name }) if field_name == "Reading"
+    ));
+}
+
+// ── scheduling ────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn exported_review_history_survives_reimport() {
+    // Arrange — one reviewed card and one new card, exported to Anki.
+    let repo = InMemoryUserRepository::with_user(create_user_with_vocab_cards(2));
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let mut ids: Vec<_> = user.knowledge_set().study_cards().keys().copied().collect();
+    ids.sort();
+    RateCardUseCase::new(&repo)
+        .execute(ids[0], RateMode::StandardLesson, Rating::Good)
+        .await
+        .unwrap();
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let reviewed = user.knowledge_set().get_card(ids[0]).unwrap().memory();
+    let apkg = ExportAnkiPackUseCase::new(&repo)
+        .execute()
+        .await
+        .unwrap()
+        .apkg;
+
+    // Act
+    let (cards, _) = extract_cards_with_mapping(&apkg, &AnkiFieldMapping::new("Word")).unwrap();
+
+    // Assert — FSRS memory, counters and the last rating come back.
+    let scheduled: Vec<_> = cards
+        .iter()
+        .filter_map(|card| card.schedule.as_ref())
+        .collect();
+    assert_eq!(cards.len(), 2);
+    assert_eq!(scheduled.len(), 1);
+    let schedule = scheduled[0];
+    let state = reviewed.memory_state().unwrap();
+    assert_eq!(schedule.memory_state.stability(), state.stability());
+    assert_eq!(schedule.memory_state.difficulty(), state.difficulty());
+    assert_eq!(schedule.memory_state.card_state(), state.card_state());
+    assert_eq!(
+        schedule.memory_state.next_review_date().timestamp(),
+        state.next_review_date().timestamp()
+    );
+    assert_eq!(schedule.reps, 1);
+    assert_eq!(schedule.lapses, 0);
+    assert_eq!(
+        schedule.last_review.map(|(_, rating)| rating),
+        Some(Rating::Good)
+    );
+    assert_eq!(schedule.reviews.len(), 1);
+    assert_eq!(schedule.reviews[0].rating(), Rating::Good);
+}
+
+fn anki_schedule_with_reviews(next_review: chrono::DateTime<Utc>) -> AnkiSchedule {
+    let reviews = vec![
+        ReviewLogEntry::new(
+            Utc::now() - Duration::days(40),
+            Rating::Again,
+            RateMode::ShortTerm,
+        ),
+        ReviewLogEntry::new(
+            Utc::now() - Duration::days(10),
+            Rating::Good,
+            RateMode::StandardLesson,
+        ),
+        ReviewLogEntry::new(
+            Utc::now() - Duration::days(1),
+            Rating::Good,
+            RateMode::StandardLesson,
+        ),
+    ];
+    AnkiSchedule {
+        memory_state: MemoryState::with_card_state(
+            Stability::new(30.0).unwrap(),
+            Difficulty::new(4.0).unwrap(),
+            next_review,
+            CardState::Review,
+        ),
+        reps: 12,
+        lapses: 2,
+        last_review: reviews
+            .last()
+            .map(|review| (review.reviewed_at(), review.rating())),
+        reviews,
+    }
+}
+
+#[tokio::test]
+async fn execute_keeps_anki_schedule_on_created_cards() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = InMemoryUserRepository::with_user(User::new(
+        "test@example.com".to_string(),
+        NativeLanguage::Russian,
+        None,
+    ));
+    let next_review = Utc::now() + Duration::days(30);
+    let cards = vec![AnkiCard {
+        word: "日本語".to_string(),
+        schedule: Some(anki_schedule_with_reviews(next_review)),
+        ..Default::default()
+    }];
+
+    // Act
+    let result = ImportAnkiPackUseCase::new(&repo)
+        .execute(cards)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(result.scheduled_count, 1);
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let memory = user
+        .knowledge_set()
+        .study_cards()
+        .values()
+        .next()
+        .unwrap()
+        .memory();
+    assert_eq!(memory.reps(), 12);
+    assert_eq!(memory.lapses(), 2);
+    assert_eq!(memory.next_review_date(), Some(&next_review));
+    assert!(!memory.is_new());
+    // Review logging is opt-in, so the Anki history is not kept.
+    assert!(memory.review_log().is_empty());
+}
+
+#[tokio::test]
+async fn execute_imports_anki_reviews_into_enabled_review_log() {
+    // Arrange
+    init_real_dictionaries();
+    let mut user = User::new(
+        "test@example.com".to_string(),
+        NativeLanguage::Russian,
+        None,
+    );
+    user.set_review_log_enabled(true);
+    let repo = InMemoryUserRepository::with_user(user);
+    let schedule = anki_schedule_with_reviews(Utc::now() + Duration::days(30));
+    let expected = schedule.reviews.clone();
+    let cards = vec![AnkiCard {
+        word: "日本語".to_string(),
+        schedule: Some(schedule),
+        ..Default::default()
+    }];
+
+    // Act
+    ImportAnkiPackUseCase::new(&repo)
+        .execute(cards)
+        .await
+        .unwrap();
+
+    // Assert
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let memory = user
+        .knowledge_set()
+        .study_cards()
+        .values()
+        .next()
+        .unwrap()
+        .memory();
+    assert_eq!(memory.review_log().entries(), expected.as_slice());
+}
+
+#[tokio::test]
+async fn preview_predicts_import_without_saving() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = InMemoryUserRepository::with_user(User::new(
+        "test@example.com".to_string(),
+        NativeLanguage::Russian,
+        None,
+    ));
+    let cards = vec![
+        AnkiCard {
+            word: "日本語".to_string(),
+            kanji: Some("日本".to_string()),
+            ..Default::default()
+        },
+        AnkiCard {
+            word: "日本語".to_string(),
+            kanji: Some("日".to_string()),
+            ..Default::default()
+        },
+    ];
+    let use_case = ImportAnkiPackUseCase::new(&repo);
+
+    // Act
+    let preview = use_case.preview(&cards).await.unwrap();
+
+    // Assert
+    assert_eq!(preview[0].word.outcome, WordImportOutcome::New);
+    assert_eq!(preview[0].new_kanji, vec!["日", "本"]);
+    assert!(preview[1].new_kanji.is_empty());
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    assert!(user.knowledge_set().study_cards().is_empty());
+}
+
 // ── extract_cards with real .apkg files ───────────────────────────────
 
 #[test]
@@ -340,10 +621,12 @@ async fn execute_imports_cards_into_user_collection() {
         AnkiCard {
             word: "日本語".to_string(),
             translation: Some("Japanese".to_string()),
+            ..Default::default()
         },
         AnkiCard {
             word: "勉強".to_string(),
             translation: Some("study".to_string()),
+            ..Default::default()
         },
     ];
 
@@ -377,10 +660,12 @@ async fn execute_skips_duplicate_cards() {
         AnkiCard {
             word: "日本語".to_string(),
             translation: Some("Japanese".to_string()),
+            ..Default::default()
         },
         AnkiCard {
             word: "日本語".to_string(),
             translation: Some("Japanese".to_string()),
+            ..Default::default()
         },
     ];
 
@@ -401,7 +686,7 @@ async fn execute_returns_error_when_no_current_user() {
 
     let cards = vec![AnkiCard {
         word: "test".to_string(),
-        translation: None,
+        ..Default::default()
     }];
 
     let result = use_case.execute(cards).await;
@@ -50,6 +50,7 @@ async fn preview_word_imports_default_selection_matches_import_result() {
         .iter()
         .map(|w| WordToCreate {
             base_form: w.to_string(),
+            ..Default::default()
         })
         .collect();
     let result = use_case.execute(words, None).await.unwrap();
@@ -135,6 +136,7 @@ async fn preview_word_imports_counts_a_word_duplicated_across_sets_once() {
         .execute(
             vec![WordToCreate {
                 base_form: "ねこ".to_string(),
+                ..Default::default()
             }],
             None,
         )
@@ -0,0 +1,152 @@
+use crate::domain::{Card, NativeLanguage, OrigaError, SubtitleFormat, User};
+use crate::stt::TranscriptSegment;
+use crate::traits::UserRepository;
+use crate::use_cases::tests::fixtures::{InMemoryUserRepository, init_real_dictionaries};
+use crate::use_cases::{
+    CreateCardsFromAnalysisUseCase, MineSubtitlesUseCase, TranscriptionResult, WordToCreate,
+};
+
+const EPISODE_SRT: &str = "1
+00:00:01,000 --> 00:00:03,000
+猫が好きです。
+
+2
+00:00:04,000 --> 00:00:06,000
+<i>猫はかわいい。</i>
+
+3
+00:01:10,500 --> 00:01:12,000
+犬も好きです。
+";
+
+fn create_repo() -> InMemoryUserRepository {
+    InMemoryUserRepository::with_user(User::new(
+        "test@example.com".to_string(),
+        NativeLanguage::Russian,
+        None,
+    ))
+}
+
+#[tokio::test]
+async fn mining_ranks_unknown_words_by_episode_frequency() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = create_repo();
+    let use_case = MineSubtitlesUseCase::new(&repo);
+
+    // Act
+    let result = use_case
+        .execute(EPISODE_SRT, SubtitleFormat::Srt)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(result.lines_count, 3);
+    let cat = &result.words[0];
+    assert_eq!(cat.base_form, "猫");
+    assert_eq!(cat.occurrences, 2);
+    assert_eq!(cat.example.text(), "猫が好きです。");
+    assert_eq!(cat.example.start_ms(), 1_000);
+    assert_eq!(result.comprehension_percentage, 0.0);
+}
+
+#[tokio::test]
+async fn mining_excludes_words_that_already_have_cards() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = create_repo();
+    CreateCardsFromAnalysisUseCase::new(&repo)
+        .execute(
+            vec![WordToCreate {
+                base_form: "猫".to_string(),
+                ..Default::default()
+            }],
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let result = MineSubtitlesUseCase::new(&repo)
+        .execute(EPISODE_SRT, SubtitleFormat::Srt)
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(result.words.iter().all(|w| w.base_form != "猫"));
+    assert_eq!(result.known_count, 1);
+}
+
+#[tokio::test]
+async fn created_cards_keep_subtitle_line_as_example() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = create_repo();
+    let use_case = MineSubtitlesUseCase::new(&repo);
+    let mined = use_case
+        .execute(EPISODE_SRT, SubtitleFormat::Srt)
+        .await
+        .unwrap();
+    let dog = mined
+        .words
+        .into_iter()
+        .filter(|w| w.base_form == "犬")
+        .collect::<Vec<_>>();
+
+    // Act
+    let result = use_case
+        .create_cards(dog, Some("Episode 1".to_string()))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(result.created_cards.len(), 1);
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let card = user.knowledge_set().study_cards().values().next().unwrap();
+    let Card::Vocabulary(vocab) = card.card() else {
+        panic!("expected vocabulary card");
+    };
+    let context = vocab.context().unwrap();
+    assert_eq!(context.sentence(), "犬も好きです。");
+    assert_eq!(context.source(), Some("Episode 1"));
+    assert_eq!(context.timestamp_ms(), Some(70_500));
+}
+
+#[tokio::test]
+async fn mining_transcript_links_words_to_time_offsets() {
+    // Arrange
+    init_real_dictionaries();
+    let repo = create_repo();
+    let transcription = TranscriptionResult {
+        segments: vec![
+            TranscriptSegment::new(0, 2_000, "猫が好きです。".to_string()),
+            TranscriptSegment::new(1_830_000, 1_833_000, "犬も好きです。".to_string()),
+        ],
+    };
+
+    // Act
+    let result = MineSubtitlesUseCase::new(&repo)
+        .execute_lines(transcription.subtitle_lines())
+        .await
+        .unwrap();
+
+    // Assert
+    let dog = result.words.iter().find(|w| w.base_form == "犬").unwrap();
+    assert_eq!(dog.example.start_ms(), 1_830_000);
+    assert_eq!(dog.example.timestamp(), "0:30:30");
+}
+
+#[tokio::test]
+async fn mining_rejects_malformed_subtitles() {
+    // Arrange
+    let repo = create_repo();
+    let use_case = MineSubtitlesUseCase::new(&repo);
+
+    // Act
+    let result = use_case
+        .execute("1\nnot a time --> 00:00:02,000\n猫\n", SubtitleFormat::Srt)
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(OrigaError::SubtitleParseError { .. })));
+}
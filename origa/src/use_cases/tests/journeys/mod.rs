@@ -1,8 +1,11 @@
+mod analyze_book;
 mod analyze_text;
 mod card_lifecycle;
 mod complete_onboarding_scoring;
 mod create_cards_from_analysis;
 mod create_vocabulary_card;
+mod exam_mode;
+mod export_anki_pack;
 mod grammar;
 mod import_anki_pack;
 mod import_onboarding_target_level;
@@ -11,6 +14,8 @@ mod jlpt_progress_journey;
 mod learning_lesson;
 mod learning_short_term;
 mod mark_card_as_known;
+mod mine_subtitles;
 mod onboarding;
 mod phrase;
+mod srs_personalization;
 mod yesno_journey;
@@ -0,0 +1,174 @@
+use crate::domain::{NativeLanguage, OrigaError, RateMode, Rating, User};
+use crate::traits::UserRepository;
+use crate::use_cases::tests::fixtures::{InMemoryUserRepository, create_test_vocab_card};
+use crate::use_cases::{
+    OptimizeSrsParametersUseCase, RateCardUseCase, SrsSettingsChange, UpdateSrsSettingsUseCase,
+};
+
+fn user_with_card() -> (User, ulid::Ulid) {
+    let mut user = User::new(
+        "test@example.com".to_string(),
+        NativeLanguage::Russian,
+        None,
+    );
+    let study_card = user.create_card(create_test_vocab_card("猫")).unwrap();
+    let card_id = *study_card.card_id();
+    (user, card_id)
+}
+
+#[tokio::test]
+async fn reviews_are_not_logged_by_default() {
+    // Arrange
+    let (user, card_id) = user_with_card();
+    let repo = InMemoryUserRepository::with_user(user);
+
+    // Act
+    RateCardUseCase::new(&repo)
+        .execute(card_id, RateMode::StandardLesson, Rating::Good)
+        .await
+        .unwrap();
+
+    // Assert
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let card = user.knowledge_set().get_card(card_id).unwrap();
+    assert!(card.memory().review_log().is_empty());
+}
+
+#[tokio::test]
+async fn enabled_review_log_records_each_rating_with_effective_mode() {
+    // Arrange
+    let (user, card_id) = user_with_card();
+    let repo = InMemoryUserRepository::with_user(user);
+    let settings = UpdateSrsSettingsUseCase::new(&repo)
+        .execute(SrsSettingsChange::ReviewLog { enabled: true })
+        .await
+        .unwrap();
+    assert!(settings.review_log_enabled());
+
+    // Act
+    let rate = RateCardUseCase::new(&repo);
+    rate.execute(card_id, RateMode::ShortTerm, Rating::Again)
+        .await
+        .unwrap();
+    rate.execute(card_id, RateMode::StandardLesson, Rating::Good)
+        .await
+        .unwrap();
+
+    // Assert
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let log = user
+        .knowledge_set()
+        .get_card(card_id)
+        .unwrap()
+        .memory()
+        .review_log()
+        .clone();
+    assert_eq!(log.len(), 2);
+    let modes: Vec<RateMode> = log.entries().iter().map(|e| e.mode()).collect();
+    assert!(modes.contains(&RateMode::ShortTerm));
+    assert!(modes.contains(&RateMode::StandardLesson));
+}
+
+#[tokio::test]
+async fn optimize_without_enough_reviews_keeps_stock_weights() {
+    // Arrange
+    let (user, card_id) = user_with_card();
+    let repo = InMemoryUserRepository::with_user(user);
+    UpdateSrsSettingsUseCase::new(&repo)
+        .execute(SrsSettingsChange::ReviewLog { enabled: true })
+        .await
+        .unwrap();
+    RateCardUseCase::new(&repo)
+        .execute(card_id, RateMode::StandardLesson, Rating::Good)
+        .await
+        .unwrap();
+
+    // Act
+    let reports = OptimizeSrsParametersUseCase::new(&repo)
+        .execute()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(reports.len(), 4);
+    assert!(
+        reports
+            .iter()
+            .all(|r| !r.applied() && r.fitted_loss().is_none())
+    );
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    assert!(
+        user.srs_settings()
+            .optimized_weights(RateMode::StandardLesson)
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn optimize_without_current_user_returns_error() {
+    // Arrange
+    let repo = InMemoryUserRepository::new();
+
+    // Act
+    let result = OptimizeSrsParametersUseCase::new(&repo).execute().await;
+
+    // Assert
+    assert!(matches!(result, Err(OrigaError::CurrentUserNotExist)));
+}
+
+#[tokio::test]
+async fn custom_vocabulary_schedule_caps_review_interval() {
+    // Arrange
+    let (user, card_id) = user_with_card();
+    let repo = InMemoryUserRepository::with_user(user);
+    UpdateSrsSettingsUseCase::new(&repo)
+        .execute(SrsSettingsChange::ModeSchedule {
+            mode: RateMode::StandardLesson,
+            request_retention: 0.95,
+            maximum_interval: 1,
+        })
+        .await
+        .unwrap();
+
+    // Act
+    RateCardUseCase::new(&repo)
+        .execute(card_id, RateMode::StandardLesson, Rating::Easy)
+        .await
+        .unwrap();
+
+    // Assert
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    let next_review = *user
+        .knowledge_set()
+        .get_card(card_id)
+        .unwrap()
+        .memory()
+        .next_review_date()
+        .unwrap();
+    assert!(next_review <= chrono::Utc::now() + chrono::Duration::days(1));
+}
+
+#[tokio::test]
+async fn out_of_range_retention_is_rejected_and_not_saved() {
+    // Arrange
+    let (user, _) = user_with_card();
+    let repo = InMemoryUserRepository::with_user(user);
+
+    // Act
+    let result = UpdateSrsSettingsUseCase::new(&repo)
+        .execute(SrsSettingsChange::ModeSchedule {
+            mode: RateMode::PhraseReview,
+            request_retention: 0.5,
+            maximum_interval: 365,
+        })
+        .await;
+
+    // Assert
+    assert!(matches!(result, Err(OrigaError::InvalidSrsSettings { .. })));
+    let user = repo.get_current_user().await.unwrap().unwrap();
+    assert!(
+        !user
+            .srs_settings()
+            .is_mode_schedule_customized(RateMode::PhraseReview)
+    );
+}
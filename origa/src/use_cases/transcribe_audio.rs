@@ -1,9 +1,36 @@
-use crate::domain::OrigaError;
-use crate::stt::WhisperTranscriber;
+use crate::domain::{OrigaError, SubtitleLine};
+use crate::stt::{TranscriptSegment, WhisperTranscriber};
+use serde::{Deserialize, Serialize};
 #[cfg(target_arch = "wasm32")]
 use std::rc::Rc;
 use tracing::info;
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptionResult {
+    /// Timed segments in recording order.
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl TranscriptionResult {
+    /// Plain transcript, one segment per line.
+    pub fn text(&self) -> String {
+        self.segments
+            .iter()
+            .map(TranscriptSegment::text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Segments as subtitle cues, so `MineSubtitlesUseCase::execute_lines`
+    /// can mine a recording and every card keeps its time offset.
+    pub fn subtitle_lines(&self) -> Vec<SubtitleLine> {
+        self.segments
+            .iter()
+            .map(|s| SubtitleLine::new(s.start_ms(), s.end_ms(), s.text().to_string()))
+            .collect()
+    }
+}
+
 pub struct TranscribeAudioUseCase;
 
 impl Default for TranscribeAudioUseCase {
@@ -21,26 +48,50 @@ impl TranscribeAudioUseCase {
     pub fn execute_with_path(
         &self,
         model: &WhisperTranscriber,
-        wav_path: &std::path::Path,
-    ) -> Result<String, OrigaError> {
-        info!(path = ?wav_path, "Executing TranscribeAudioUseCase");
-        model.transcribe(wav_path)
+        audio_path: &std::path::Path,
+    ) -> Result<TranscriptionResult, OrigaError> {
+        info!(path = ?audio_path, "Executing TranscribeAudioUseCase");
+        let segments = model.transcribe(audio_path)?;
+        Ok(TranscriptionResult { segments })
     }
 
+    /// Transcribes 16 kHz mono samples, as `crate::stt::load_audio_bytes`
+    /// returns them; the caller decodes so it can fall back to the browser
+    /// for codecs the Rust decoders lack.
     #[cfg(target_arch = "wasm32")]
     pub async fn execute(
         &self,
         model: Rc<WhisperTranscriber>,
-        audio_bytes: &[u8],
-    ) -> Result<String, OrigaError> {
+        samples: &[f32],
+    ) -> Result<TranscriptionResult, OrigaError> {
         info!(
-            bytes_len = audio_bytes.len(),
+            samples_len = samples.len(),
             "Executing TranscribeAudioUseCase (WASM)"
         );
 
-        let samples = crate::stt::load_audio_bytes(audio_bytes)
-            .map_err(|reason| OrigaError::SttError { reason })?;
+        let segments = model.transcribe_from_samples(samples).await?;
+        Ok(TranscriptionResult { segments })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcript_lines_keep_segment_offsets() {
+        let result = TranscriptionResult {
+            segments: vec![
+                TranscriptSegment::new(0, 2_000, "猫が好き".to_string()),
+                TranscriptSegment::new(95_000, 97_500, "犬も好き".to_string()),
+            ],
+        };
+
+        let lines = result.subtitle_lines();
 
-        model.transcribe_from_samples(&samples).await
+        assert_eq!(result.text(), "猫が好き\n犬も好き");
+        assert_eq!(lines[1].start_ms(), 95_000);
+        assert_eq!(lines[1].end_ms(), 97_500);
+        assert_eq!(lines[1].text(), "犬も好き");
     }
 }
@@ -0,0 +1,73 @@
+use chrono::NaiveDate;
+
+use crate::domain::{ExamTarget, JapaneseLevel, ModeSchedule, OrigaError, RateMode, SrsSettings};
+use crate::traits::UserRepository;
+use tracing::{debug, info};
+
+/// A single user-facing change to [`SrsSettings`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SrsSettingsChange {
+    /// Turn the per-card review log the optimizer learns from on or off.
+    ReviewLog { enabled: bool },
+    /// Forget fitted weights and schedule with stock FSRS again.
+    ResetOptimizedWeights,
+    /// Desired retention and interval ceiling of one long-term review mode.
+    ModeSchedule {
+        mode: RateMode,
+        request_retention: f64,
+        maximum_interval: i32,
+    },
+    /// Restore the built-in retention and interval ceiling of every mode.
+    ResetModeSchedules,
+    /// Prepare for a JLPT sitting of `level` on `exam_date`.
+    ExamTarget {
+        level: JapaneseLevel,
+        exam_date: NaiveDate,
+    },
+    /// Leave exam mode.
+    ClearExamTarget,
+}
+
+#[derive(Clone)]
+pub struct UpdateSrsSettingsUseCase<'a, R: UserRepository> {
+    repository: &'a R,
+}
+
+impl<'a, R: UserRepository> UpdateSrsSettingsUseCase<'a, R> {
+    pub fn new(repository: &'a R) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, change: SrsSettingsChange) -> Result<SrsSettings, OrigaError> {
+        debug!(?change, "Updating SRS settings");
+
+        let mut user = self
+            .repository
+            .get_current_user()
+            .await?
+            .ok_or(OrigaError::CurrentUserNotExist)?;
+
+        match change {
+            SrsSettingsChange::ReviewLog { enabled } => user.set_review_log_enabled(enabled),
+            SrsSettingsChange::ResetOptimizedWeights => user.reset_optimized_weights(),
+            SrsSettingsChange::ModeSchedule {
+                mode,
+                request_retention,
+                maximum_interval,
+            } => user.set_mode_schedule(
+                mode,
+                ModeSchedule::new(request_retention, maximum_interval)?,
+            )?,
+            SrsSettingsChange::ResetModeSchedules => user.reset_mode_schedules(),
+            SrsSettingsChange::ExamTarget { level, exam_date } => {
+                user.set_exam_target(Some(ExamTarget::new(level, exam_date)?))
+            },
+            SrsSettingsChange::ClearExamTarget => user.set_exam_target(None),
+        }
+
+        self.repository.save_sync(&user).await?;
+
+        info!("SRS settings updated");
+        Ok(user.srs_settings().clone())
+    }
+}
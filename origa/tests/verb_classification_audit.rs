@@ -0,0 +1,224 @@
+//! Verb classification audit over the whole vocabulary dictionary.
+//!
+//! Every vocabulary word the tokenizer reads as a single verb is conjugated
+//! to its te- and masu-form, and the result is tokenized again: a verb put in
+//! the wrong group (切る as ichidan gives 切て) no longer analyses back to its
+//! own lemma. Acceptance criterion: mismatches < 2%.
+//!
+//! That round trip checks the tokenizer against itself, so a checked-in
+//! reference sample (group and te-form taken from a grammar reference, not
+//! from the tokenizer) is checked as well and must match exactly.
+//!
+//! Like the other corpus audits this needs the gitignored `cdn/` artifacts;
+//! without them the test skips with a stderr note.
+//!
+//! Run: `cargo test -p origa --test verb_classification_audit -- --nocapture`
+
+use std::collections::BTreeSet;
+
+use origa::dictionary::grammar::FormatAction;
+use origa::domain::{PartOfSpeech, apply_format_actions, tokenize_text};
+
+#[path = "translation_smoke/bootstrap.rs"]
+mod bootstrap;
+
+fn load_vocabulary_words() -> BTreeSet<String> {
+    let mut words = BTreeSet::new();
+    for n in 1..=11u8 {
+        let path = bootstrap::cdn_path(&["dictionary", &format!("chunk_{n:02}.json")]);
+        let Ok(body) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let body = body.strip_prefix('\u{FEFF}').unwrap_or(&body);
+        let Ok(serde_json::Value::Object(entries)) = serde_json::from_str(body) else {
+            continue;
+        };
+        words.extend(entries.keys().cloned());
+    }
+    words
+}
+
+/// Lemma of `word` when the tokenizer reads it as one dictionary-form verb.
+fn verb_lemma(word: &str) -> Option<String> {
+    let tokens = tokenize_text(word).ok()?;
+    let [token] = tokens.as_slice() else {
+        return None;
+    };
+    (*token.part_of_speech() == PartOfSpeech::Verb
+        && token.conjugation_type().is_some()
+        && token.orthographic_surface_form() == word)
+        .then(|| token.orthographic_base_form().to_string())
+}
+
+fn first_lemma(text: &str) -> Option<String> {
+    tokenize_text(text)
+        .ok()?
+        .first()
+        .map(|token| token.orthographic_base_form().to_string())
+}
+
+fn conjugate(word: &str, action: FormatAction) -> String {
+    apply_format_actions(word, &[action], &PartOfSpeech::Verb).unwrap_or_default()
+}
+
+#[test]
+fn audit_vocabulary_verbs_conjugate_back_to_their_lemma() {
+    if !bootstrap::ensure_all_dictionaries() {
+        eprintln!("[skip] verb_classification_audit: cdn artifacts absent");
+        return;
+    }
+    let words = load_vocabulary_words();
+
+    let mut verbs = 0;
+    let mut mismatched = 0;
+    let mut examples: Vec<String> = Vec::new();
+
+    for word in &words {
+        let Some(lemma) = verb_lemma(word) else {
+            continue;
+        };
+        verbs += 1;
+
+        let forms = [
+            conjugate(word, FormatAction::VerbToTeForm {}),
+            conjugate(word, FormatAction::VerbToMasu {}),
+        ];
+        let broken: Vec<&String> = forms
+            .iter()
+            .filter(|form| first_lemma(form).as_deref() != Some(lemma.as_str()))
+            .collect();
+        if !broken.is_empty() {
+            mismatched += 1;
+            if examples.len() < 30 {
+                examples.push(format!("{word} → {broken:?}"));
+            }
+        }
+    }
+
+    assert!(verbs > 0, "vocabulary dictionary has no verbs");
+    let rate = mismatched as f64 / verbs as f64;
+    eprintln!(
+        "=== VERB CLASSIFICATION AUDIT === verbs={verbs} mismatched={mismatched} ({:.2}%)",
+        100.0 * rate
+    );
+    eprintln!("mismatch examples: {examples:?}");
+    assert!(
+        rate < 0.02,
+        "{:.2}% of vocabulary verbs do not conjugate back to their lemma",
+        100.0 * rate
+    );
+}
+
+#[test]
+fn audit_hidden_vowel_and_irregular_verbs() {
+    if !bootstrap::ensure_all_dictionaries() {
+        eprintln!("[skip] verb_classification_audit: cdn artifacts absent");
+        return;
+    }
+
+    let cases = [
+        ("切る", FormatAction::VerbToTeForm {}, "切って"),
+        ("着る", FormatAction::VerbToTeForm {}, "着て"),
+        ("帰る", FormatAction::VerbToMasu {}, "帰ります"),
+        ("行く", FormatAction::VerbToTa {}, "行った"),
+        ("ある", FormatAction::VerbToNai {}, "ない"),
+        ("くださる", FormatAction::VerbToMasu {}, "くださいます"),
+        (
+            "いらっしゃる",
+            FormatAction::VerbToMasu {},
+            "いらっしゃいます",
+        ),
+        ("勉強する", FormatAction::VerbToNai {}, "勉強しない"),
+    ];
+    for (word, action, expected) in cases {
+        assert_eq!(conjugate(word, action), expected, "conjugating {word}");
+    }
+}
+
+/// Reference te-forms; the hidden-vowel godan verbs are the ones a guess from
+/// the kana before る gets wrong.
+const REFERENCE_TE_FORMS: &[(&str, &str)] = &[
+    // Godan verbs ending in -iru / -eru.
+    ("切る", "切って"),
+    ("帰る", "帰って"),
+    ("入る", "入って"),
+    ("走る", "走って"),
+    ("知る", "知って"),
+    ("要る", "要って"),
+    ("減る", "減って"),
+    ("限る", "限って"),
+    ("喋る", "喋って"),
+    ("蹴る", "蹴って"),
+    ("滑る", "滑って"),
+    ("焦る", "焦って"),
+    ("握る", "握って"),
+    ("練る", "練って"),
+    ("照る", "照って"),
+    ("散る", "散って"),
+    ("茂る", "茂って"),
+    ("遮る", "遮って"),
+    ("参る", "参って"),
+    ("混じる", "混じって"),
+    ("陥る", "陥って"),
+    ("湿る", "湿って"),
+    // Ichidan verbs.
+    ("見る", "見て"),
+    ("着る", "着て"),
+    ("寝る", "寝て"),
+    ("出る", "出て"),
+    ("似る", "似て"),
+    ("煮る", "煮て"),
+    ("食べる", "食べて"),
+    ("起きる", "起きて"),
+    ("借りる", "借りて"),
+    ("信じる", "信じて"),
+    ("感じる", "感じて"),
+    ("教える", "教えて"),
+    ("落ちる", "落ちて"),
+    ("伸びる", "伸びて"),
+    ("閉める", "閉めて"),
+    ("考える", "考えて"),
+    ("生きる", "生きて"),
+    ("浴びる", "浴びて"),
+    ("足りる", "足りて"),
+    // Other godan endings.
+    ("書く", "書いて"),
+    ("泳ぐ", "泳いで"),
+    ("話す", "話して"),
+    ("待つ", "待って"),
+    ("死ぬ", "死んで"),
+    ("遊ぶ", "遊んで"),
+    ("読む", "読んで"),
+    ("買う", "買って"),
+    ("行く", "行って"),
+    ("作る", "作って"),
+    ("分かる", "分かって"),
+    ("ある", "あって"),
+    // Irregular verbs.
+    ("する", "して"),
+    ("来る", "来て"),
+    ("勉強する", "勉強して"),
+    ("運転する", "運転して"),
+];
+
+#[test]
+fn audit_reference_sample_te_forms() {
+    if !bootstrap::ensure_all_dictionaries() {
+        eprintln!("[skip] verb_classification_audit: cdn artifacts absent");
+        return;
+    }
+
+    let mismatched: Vec<String> = REFERENCE_TE_FORMS
+        .iter()
+        .filter_map(|(word, expected)| {
+            let actual = conjugate(word, FormatAction::VerbToTeForm {});
+            (actual != *expected).then(|| format!("{word} → {actual} (expected {expected})"))
+        })
+        .collect();
+    assert!(
+        mismatched.is_empty(),
+        "{} of {} reference verbs conjugate wrongly: {mismatched:?}",
+        mismatched.len(),
+        REFERENCE_TE_FORMS.len()
+    );
+}
@@ -74,7 +74,7 @@ fn get_card_cdn_resources(card: &StudyCard) -> Vec<String> {
         Card::Vocabulary(v) => vocabulary_resources(v.word().text()),
         Card::Phrase(p) => phrase_resources(p.phrase_id()),
         Card::Kanji(k) => kanji_svg_resources(k.kanji().text(), Some(&k.jlpt().to_string())),
-        Card::Grammar(_) => vec![],
+        Card::Grammar(_) | Card::Particle(_) => vec![],
     }
 }
 
@@ -1,6 +1,7 @@
 use origa::dictionary::grammar::{GrammarData, init_grammar, is_grammar_loaded};
 use origa::dictionary::kanji::{KanjiData, init_kanji, is_kanji_loaded};
 use origa::dictionary::radical::{RadicalData, init_radicals, is_radicals_loaded};
+use origa::dictionary::user_dictionary::init_user_dictionary_from_rkyv;
 use origa::dictionary::vocabulary::{
     VocabularyChunkData, init_vocabulary, init_vocabulary_from_rkyv, is_vocabulary_loaded,
     serialize_vocabulary_to_rkyv,
@@ -9,7 +10,9 @@ use origa::domain::OrigaError;
 use origa::traits::CdnProvider;
 
 use crate::repository::cdn_provider;
-use crate::repository::{get_cached_vocabulary_rkyv, save_vocabulary_to_cache_rkyv};
+use crate::repository::{
+    get_cached_user_dictionary_rkyv, get_cached_vocabulary_rkyv, save_vocabulary_to_cache_rkyv,
+};
 use crate::utils::{now_ms, yield_to_browser};
 
 pub async fn load_vocabulary() -> Result<(), OrigaError> {
@@ -118,6 +121,18 @@ pub async fn load_vocabulary() -> Result<(), OrigaError> {
     Ok(())
 }
 
+/// Restores the dictionaries the user imported on this device. They only
+/// live in the Cache API, so there is no network fallback.
+pub async fn load_user_dictionary() -> Result<(), OrigaError> {
+    let Some(bytes) = get_cached_user_dictionary_rkyv().await? else {
+        tracing::debug!("📖 No imported user dictionaries");
+        return Ok(());
+    };
+    init_user_dictionary_from_rkyv(&bytes)?;
+    tracing::info!("📖 User dictionaries restored ({} bytes)", bytes.len());
+    Ok(())
+}
+
 pub async fn load_kanji() -> Result<(), OrigaError> {
     if is_kanji_loaded() {
         tracing::debug!("📖 Kanji already loaded");
@@ -1,10 +1,12 @@
 use origa::domain::OrigaError;
 use origa::stt::WhisperTranscriber;
-use tracing::info;
+use tracing::{info, warn};
+use web_sys::Cache;
 
 use crate::loaders::model_cache::ModelCache;
 
 const WHISPER_FILE_COUNT: usize = 3;
+const DECODER_WITH_PAST_FILE: &str = "onnx/decoder_with_past_model.onnx";
 
 fn to_stt_error(reason: String) -> OrigaError {
     OrigaError::SttError { reason }
@@ -13,6 +15,9 @@ fn to_stt_error(reason: String) -> OrigaError {
 pub struct WhisperModelFiles {
     pub encoder: Vec<u8>,
     pub decoder: Vec<u8>,
+    /// Absent from older model exports; transcription then decodes without a
+    /// KV cache.
+    pub decoder_with_past: Option<Vec<u8>>,
     pub tokenizer: Vec<u8>,
 }
 
@@ -41,25 +46,68 @@ impl WhisperModelLoader {
             "tokenizer.json",
         ]
         .iter()
-        .map(|&f| (f, format!("{}/{}", self.base_url.trim_end_matches('/'), f)))
+        .map(|&f| (f, self.file_url(f)))
         .collect();
 
-        if model_cache.ensure_files_cached(&cache, &files).await? {
+        let loaded = if model_cache.ensure_files_cached(&cache, &files).await? {
             info!("Whisper models found in cache, loading...");
-            let loaded = model_cache.load_files_from_cache(&cache, &files).await?;
-            self.build_model_files(loaded)
+            model_cache.load_files_from_cache(&cache, &files).await?
         } else {
             info!("Whisper models not in cache, downloading...");
-            let loaded = model_cache.download_and_cache_model(&cache, &files).await?;
-            self.build_model_files(loaded)
-        }
+            model_cache.download_and_cache_model(&cache, &files).await?
+        };
+        let decoder_with_past = self.load_decoder_with_past(&model_cache, &cache).await;
+        self.build_model_files(loaded, decoder_with_past)
     }
 
     pub async fn init_model(files: WhisperModelFiles) -> Result<WhisperTranscriber, OrigaError> {
-        WhisperTranscriber::new(&files.encoder, &files.decoder, &files.tokenizer).await
+        WhisperTranscriber::new(
+            &files.encoder,
+            &files.decoder,
+            files.decoder_with_past.as_deref(),
+            &files.tokenizer,
+        )
+        .await
+    }
+
+    fn file_url(&self, file: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), file)
+    }
+
+    /// Only speeds decoding up, so a CDN without the file is not an error.
+    async fn load_decoder_with_past(
+        &self,
+        model_cache: &ModelCache,
+        cache: &Cache,
+    ) -> Option<Vec<u8>> {
+        let url = self.file_url(DECODER_WITH_PAST_FILE);
+        let cached = model_cache
+            .is_file_cached(cache, DECODER_WITH_PAST_FILE, &url)
+            .await
+            .unwrap_or(false);
+        let loaded = if cached {
+            model_cache
+                .load_file_from_cache(cache, DECODER_WITH_PAST_FILE, &url)
+                .await
+        } else {
+            model_cache
+                .download_and_cache_file(cache, DECODER_WITH_PAST_FILE, &url)
+                .await
+        };
+        match loaded {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                warn!("Whisper decoder with past unavailable: {:?}", e);
+                None
+            },
+        }
     }
 
-    fn build_model_files(&self, mut loaded: Vec<Vec<u8>>) -> Result<WhisperModelFiles, OrigaError> {
+    fn build_model_files(
+        &self,
+        mut loaded: Vec<Vec<u8>>,
+        decoder_with_past: Option<Vec<u8>>,
+    ) -> Result<WhisperModelFiles, OrigaError> {
         if loaded.len() != WHISPER_FILE_COUNT {
             return Err(OrigaError::SttError {
                 reason: format!(
@@ -72,6 +120,7 @@ impl WhisperModelLoader {
         Ok(WhisperModelFiles {
             encoder: loaded.remove(0),
             decoder: loaded.remove(0),
+            decoder_with_past,
             tokenizer: loaded.remove(0),
         })
     }
@@ -68,6 +68,7 @@ pub fn compute_studied_today(
                     Card::Kanji(k) => k.kanji().text().to_string(),
                     Card::Grammar(g) => g.rule_id().to_string(),
                     Card::Phrase(p) => p.phrase_id().to_string(),
+                    Card::Particle(p) => p.particle().to_string(),
                 });
 
             let meaning = format_answer_text(card, lang);
@@ -75,7 +76,7 @@ pub fn compute_studied_today(
             let card_type = match CardType::from(card) {
                 CardType::Kanji => "kanji",
                 CardType::Vocabulary => "vocabulary",
-                CardType::Grammar => "grammar",
+                CardType::Grammar | CardType::Particle => "grammar",
                 CardType::Phrase => "vocabulary",
             };
 
@@ -12,6 +12,7 @@ pub enum CardType {
     Kanji,
     Grammar,
     Phrase,
+    Particle,
 }
 
 impl CardType {
@@ -41,6 +42,12 @@ impl CardType {
                 .phrase()
                 .inner()
                 .to_string(),
+            CardType::Particle => i18n
+                .get_keys_untracked()
+                .lesson()
+                .particle()
+                .inner()
+                .to_string(),
         }
     }
 
@@ -48,7 +55,7 @@ impl CardType {
         match self {
             CardType::Vocabulary => TagVariant::Default,
             CardType::Kanji => TagVariant::Olive,
-            CardType::Grammar => TagVariant::Terracotta,
+            CardType::Grammar | CardType::Particle => TagVariant::Terracotta,
             CardType::Phrase => TagVariant::Sage,
         }
     }
@@ -63,6 +70,7 @@ impl CardType {
             CardType::Kanji => 1,
             CardType::Vocabulary => 2,
             CardType::Phrase => 3,
+            CardType::Particle => 4,
         }
     }
 }
@@ -74,6 +82,7 @@ impl From<&DomainCard> for CardType {
             DomainCard::Kanji(_) => CardType::Kanji,
             DomainCard::Grammar(_) => CardType::Grammar,
             DomainCard::Phrase(_) => CardType::Phrase,
+            DomainCard::Particle(_) => CardType::Particle,
         }
     }
 }
@@ -89,6 +98,7 @@ mod tests {
         assert_eq!(CardType::Kanji.tag_variant(), TagVariant::Olive);
         assert_eq!(CardType::Grammar.tag_variant(), TagVariant::Terracotta);
         assert_eq!(CardType::Phrase.tag_variant(), TagVariant::Sage);
+        assert_eq!(CardType::Particle.tag_variant(), TagVariant::Terracotta);
     }
 
     #[test]
@@ -97,6 +107,7 @@ mod tests {
         assert_eq!(CardType::Kanji.sort_order(), 1);
         assert_eq!(CardType::Vocabulary.sort_order(), 2);
         assert_eq!(CardType::Phrase.sort_order(), 3);
+        assert_eq!(CardType::Particle.sort_order(), 4);
     }
 
     #[test]
@@ -0,0 +1,150 @@
+use crate::i18n::*;
+use crate::ui_components::{
+    Button, ButtonVariant, Card, Heading, HeadingLevel, Input, Text, TextSize, TypographyVariant,
+};
+use leptos::prelude::*;
+use origa::domain::{ConjugationCheck, ConjugationDrillCard};
+
+use super::card_type::CardType;
+use super::lesson_card_header::LessonCardTagsQuiz;
+use super::next_card_button::NextCardButton;
+use super::quiz_card::QuizVariant;
+use super::quiz_result::QuizResult;
+use super::quiz_result_display::QuizResultDisplay;
+
+/// Typed conjugation drill: the learner writes the word through the rule's
+/// chain; a miss lists every intermediate form and marks the step that broke.
+#[component]
+pub fn ConjugationDrillCardView(
+    drill: ConjugationDrillCard,
+    show_result: Signal<bool>,
+    drill_check: Signal<Option<ConjugationCheck>>,
+    on_submit: Callback<String>,
+    on_dont_know: Callback<()>,
+    dont_know_selected: Signal<bool>,
+    #[prop(default = Signal::derive(|| false))] waiting_for_next: Signal<bool>,
+    #[prop(default = Callback::new(|_: ()| {}))] on_next_card: Callback<()>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let answer = RwSignal::new(String::new());
+    let title = drill.grammar_info().title().to_string();
+    let word = drill.word_text().to_string();
+    let chain = drill.step_labels().join(" + ");
+    // Dont-know has no check, so the expected form is computed up front.
+    let expected = StoredValue::new(
+        drill
+            .check_answer("")
+            .map(|check| check.expected)
+            .unwrap_or_default(),
+    );
+
+    let submit = move || on_submit.run(answer.get_untracked());
+
+    let quiz_result = move || {
+        if dont_know_selected.get() {
+            return QuizResult::DontKnow;
+        }
+        match drill_check.get() {
+            Some(check) if check.is_correct => QuizResult::Correct,
+            _ if show_result.get() => QuizResult::Incorrect,
+            _ => QuizResult::None,
+        }
+    };
+
+    view! {
+        <div class="flex flex-col">
+            <LessonCardTagsQuiz card_type=CardType::Grammar quiz_variant=QuizVariant::Conjugation />
+            <Card class=Signal::derive(|| super::LESSON_CARD_CLASS.to_string()) shadow=true test_id="lesson-card-root">
+
+            <div class="flex-1 flex flex-col justify-center">
+                <div class="text-center mb-4">
+                    <Text size=TextSize::Default variant=TypographyVariant::Muted>
+                        {title}
+                    </Text>
+                    <Heading level=HeadingLevel::H2 class="mt-2">
+                        {word}
+                    </Heading>
+                    <Text size=TextSize::Default variant=TypographyVariant::Muted class="mt-2">
+                        {t!(i18n, lesson.type_conjugation)} {" "} {chain}
+                    </Text>
+                </div>
+
+                <Input
+                    value=answer
+                    disabled=show_result
+                    class="text-center font-serif text-lg"
+                    test_id="conjugation-drill-input"
+                    on_keydown=Callback::new(move |ev: leptos::ev::KeyboardEvent| {
+                        if ev.key() == "Enter" && !ev.is_composing() {
+                            ev.prevent_default();
+                            submit();
+                        }
+                    })
+                />
+
+                <Show when=move || !show_result.get()>
+                    <div class="mt-3 grid grid-cols-2 gap-2">
+                        <Button
+                            variant=Signal::derive(|| ButtonVariant::Filled)
+                            on_click=Callback::new(move |_| submit())
+                            test_id="conjugation-drill-check-btn"
+                        >
+                            {t!(i18n, lesson.check)}
+                        </Button>
+                        <Button
+                            on_click=Callback::new(move |_| on_dont_know.run(()))
+                            test_id="quiz-dont-know-btn"
+                        >
+                            {t!(i18n, lesson.dont_know)}
+                        </Button>
+                    </div>
+                </Show>
+
+                <Show when=move || show_result.get()>
+                    <QuizResultDisplay quiz_result=quiz_result() />
+
+                    <div class="mt-3 p-3 bg-[var(--bg-secondary)] text-center font-serif text-lg" data-testid="conjugation-drill-expected">
+                        {move || expected.get_value()}
+                    </div>
+
+                    {move || {
+                        drill_check.get().filter(|check| !check.is_correct).map(|check| {
+                            let wrong_step = check.wrong_step;
+                            view! {
+                                <ol class="mt-3 text-sm" data-testid="conjugation-drill-steps">
+                                    {check
+                                        .steps
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(index, step)| {
+                                            let class = if Some(index) == wrong_step {
+                                                "text-[var(--error)] font-bold"
+                                            } else {
+                                                "text-[var(--fg-muted)]"
+                                            };
+                                            view! {
+                                                <li class=class>
+                                                    {format!("{}. {} → {}", index + 1, step.label, step.form)}
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ol>
+                                <Show when=move || wrong_step.is_some()>
+                                    <Text size=TextSize::Small variant=TypographyVariant::Muted class="mt-1">
+                                        {t!(i18n, lesson.conjugation_wrong_step)}
+                                    </Text>
+                                </Show>
+                            }
+                        })
+                    }}
+                </Show>
+
+                <Show when=move || waiting_for_next.get() && show_result.get()>
+                    <NextCardButton on_next_card=on_next_card />
+                </Show>
+            </div>
+        </Card>
+        </div>
+    }
+}
@@ -272,6 +272,8 @@ pub fn LessonContent() -> impl IntoView {
                             selected_quiz_options: HashSet::new(),
                             multi_quiz_submitted: false,
                             multi_result: None,
+                            drill_check: None,
+                            production_result: None,
                         });
                     }
                 },
@@ -0,0 +1,42 @@
+use crate::i18n::{t_string, use_i18n};
+use crate::ui_components::{Text, TextSize, TypographyVariant};
+use leptos::prelude::*;
+use origa::domain::ExampleMedium;
+
+/// The sentence a vocabulary card was mined from: a cloze while the card
+/// is being recalled, the full sentence with its source once answered.
+/// A sentence without a named source shows the kind of input it was read from.
+#[component]
+pub fn ExampleSentence(
+    cloze: String,
+    sentence: String,
+    source: Option<String>,
+    medium: Option<ExampleMedium>,
+    show_answer: Signal<bool>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let cloze = StoredValue::new(cloze);
+    let sentence = StoredValue::new(sentence);
+    let source = StoredValue::new(source);
+    let label = move || {
+        source.get_value().or_else(|| {
+            medium.map(|medium| match medium {
+                ExampleMedium::Image => t_string!(i18n, lesson.example_from_image).to_string(),
+                ExampleMedium::Audio => t_string!(i18n, lesson.example_from_audio).to_string(),
+            })
+        })
+    };
+
+    view! {
+        <div class="mt-4 text-center space-y-1" data-testid="lesson-example-sentence">
+            <Text size=TextSize::Large>
+                {move || if show_answer.get() { sentence.get_value() } else { cloze.get_value() }}
+            </Text>
+            <Show when=move || show_answer.get() && label().is_some()>
+                <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                    {move || label().unwrap_or_default()}
+                </Text>
+            </Show>
+        </div>
+    }
+}
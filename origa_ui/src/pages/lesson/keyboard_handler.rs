@@ -67,6 +67,8 @@ pub fn create_keyboard_handler(
                     LessonCardView::Quiz(_)
                         | LessonCardView::KanjiReadingQuiz(_)
                         | LessonCardView::GrammarQuiz(_)
+                        | LessonCardView::PitchAccentQuiz(_)
+                        | LessonCardView::ParticleCloze(_)
                 )
             })
             .unwrap_or(false);
@@ -76,6 +78,19 @@ pub fn create_keyboard_handler(
         let is_phrase_listen = current_card
             .map(|c| matches!(c.view(), LessonCardView::PhraseListen { .. }))
             .unwrap_or(false);
+        // Typed cards are answered in their own text field; keys outside it
+        // must not reveal the answer or rate the card.
+        let is_typed_answer = current_card
+            .map(|c| {
+                matches!(
+                    c.view(),
+                    LessonCardView::ConjugationDrill(_) | LessonCardView::Production(_)
+                )
+            })
+            .unwrap_or(false);
+        if is_typed_answer {
+            return;
+        }
         if !state.showing_answer {
             if is_quiz || is_phrase_listen {
                 if is_multi_quiz {
@@ -15,6 +15,7 @@ use tracing;
 
 use super::answer_display::extract_card_answer;
 use super::card_type::CardType;
+use super::example_sentence::ExampleSentence;
 use super::kanji_card_details::RadicalDisplay;
 use super::lesson_card_answer::LessonCardAnswer;
 use super::lesson_card_header::{CardHeaderAudio, LessonCardTags};
@@ -36,6 +37,9 @@ pub fn LessonCard(
     /// `cdn_provider::resolve_audio_url` for the gzip-on-CDN root cause.
     #[prop(into)]
     audio_path: Option<String>,
+    /// Shows the card's example sentence (Normal and Reversed views only).
+    #[prop(optional)]
+    with_example: bool,
 ) -> impl IntoView {
     let card_type = CardType::from(&card);
     let is_phrase = card_type == CardType::Phrase;
@@ -96,6 +100,21 @@ pub fn LessonCard(
     let answer_translations_stored = StoredValue::new(answer_data.translations);
     let answer_description_stored = StoredValue::new(answer_data.description);
 
+    let example = match &card {
+        DomainCard::Vocabulary(vocab) if with_example => vocab
+            .context()
+            .zip(vocab.example_cloze())
+            .map(|(context, cloze)| {
+                (
+                    cloze,
+                    context.sentence().to_string(),
+                    context.source().map(str::to_string),
+                    context.medium(),
+                )
+            }),
+        _ => None,
+    };
+
     let radicals: Option<Vec<RadicalDisplay>> = match &card {
         DomainCard::Kanji(kanji) => match kanji.radicals_info() {
             Ok(r) => Some(
@@ -347,6 +366,12 @@ pub fn LessonCard(
                             native_language=native_language
                         />
                     </Show>
+
+                    {example.map(|(cloze, sentence, source, medium)| {
+                        view! {
+                            <ExampleSentence cloze sentence source medium show_answer />
+                        }
+                    })}
                 </div>
             </Card>
         </div>
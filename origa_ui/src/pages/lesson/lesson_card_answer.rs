@@ -3,9 +3,10 @@ use super::kanji_card_details::{KanjiCardDetails, RadicalDisplay};
 use crate::i18n::*;
 use crate::ui_components::{
     Button, ButtonVariant, FuriganaText, Heading, HeadingLevel, MarkdownText, MarkdownVariant,
-    Text, TextSize, TranslatorText, TypographyVariant, WordTranslations,
+    PitchContour, Text, TextSize, TranslatorText, TypographyVariant, WordTranslations,
 };
 use leptos::{ev::MouseEvent, prelude::*};
+use origa::dictionary::pitch_audio::pitch_accent_for_word;
 use origa::domain::{GrammarInfo, NativeLanguage};
 use std::collections::HashSet;
 
@@ -31,6 +32,11 @@ pub fn LessonCardAnswer(
     native_language: NativeLanguage,
 ) -> impl IntoView {
     let i18n = use_i18n();
+    let pitch_accent = StoredValue::new(if is_kanji || is_phrase || is_reversed {
+        None
+    } else {
+        pitch_accent_for_word(&question_text)
+    });
     let question = StoredValue::new(question_text);
     let answer = StoredValue::new(answer_text);
     let answer_translations_stored = StoredValue::new(answer_translations);
@@ -85,6 +91,16 @@ pub fn LessonCardAnswer(
                 </Heading>
             </Show>
 
+            {move || {
+                pitch_accent.get_value().map(|accent| {
+                    view! {
+                        <div class="mb-2">
+                            <PitchContour accent />
+                        </div>
+                    }
+                })
+            }}
+
             <div
                 node_ref=content_ref
                 class="border-t border-[var(--border-light)] pt-4 mt-4"
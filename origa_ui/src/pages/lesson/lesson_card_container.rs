@@ -1,13 +1,18 @@
+use super::conjugation_drill_card::ConjugationDrillCardView;
 use super::keyboard_handler::{KeyboardActions, create_keyboard_handler, is_typing_target};
 use super::lesson_card_renderer::render_lesson_card;
 use super::lesson_state::LessonContext;
 use super::on_dont_know::create_on_dont_know;
+use super::on_drill_submit::create_on_drill_submit;
+use super::on_production_submit::create_on_production_submit;
 use super::on_quiz_select::create_on_quiz_select;
 use super::on_quiz_submit::create_on_quiz_submit;
 use super::on_quiz_toggle::create_on_quiz_toggle;
 use super::on_rate::create_on_rate_callback;
 use super::on_yesno_select::create_on_yesno_select;
+use super::particle_cloze_card::ParticleClozeCardView;
 use super::phrase_card::PhraseCardView;
+use super::production_card::ProductionCardView;
 use super::quiz_card::QuizCardView;
 use super::quiz_card::QuizVariant;
 use super::writing_card::WritingCard;
@@ -41,9 +46,13 @@ pub fn LessonCardContainer() -> impl IntoView {
 
     let on_quiz_toggle = create_on_quiz_toggle(lesson_state);
     let on_quiz_submit = create_on_quiz_submit(lesson_state);
+    let on_drill_submit = create_on_drill_submit(lesson_state);
 
     let on_quiz_dont_know = create_on_dont_know(lesson_state);
     let on_yesno_dont_know = create_on_dont_know(lesson_state);
+    let on_drill_dont_know = create_on_dont_know(lesson_state);
+    let on_production_submit = create_on_production_submit(lesson_state);
+    let on_production_dont_know = create_on_dont_know(lesson_state);
 
     let on_next_card = Callback::new(move |_: ()| {
         // Pure-manual advance (ADR-033) contract: every on_* handler that
@@ -129,6 +138,34 @@ pub fn LessonCardContainer() -> impl IntoView {
             .unwrap_or(false)
     });
 
+    let is_pitch_accent_quiz_mode = Memo::new(move |_| {
+        current_lesson_card
+            .get()
+            .map(|c| matches!(c.view(), LessonCardView::PitchAccentQuiz(_)))
+            .unwrap_or(false)
+    });
+
+    let is_conjugation_drill_mode = Memo::new(move |_| {
+        current_lesson_card
+            .get()
+            .map(|c| matches!(c.view(), LessonCardView::ConjugationDrill(_)))
+            .unwrap_or(false)
+    });
+
+    let is_production_mode = Memo::new(move |_| {
+        current_lesson_card
+            .get()
+            .map(|c| matches!(c.view(), LessonCardView::Production(_)))
+            .unwrap_or(false)
+    });
+
+    let is_particle_cloze_mode = Memo::new(move |_| {
+        current_lesson_card
+            .get()
+            .map(|c| matches!(c.view(), LessonCardView::ParticleCloze(_)))
+            .unwrap_or(false)
+    });
+
     on_cleanup(move || {
         stop_current_audio();
     });
@@ -142,7 +179,7 @@ pub fn LessonCardContainer() -> impl IntoView {
 
     view! {
         <Show when=move || current_lesson_card.get().is_some()>
-            <Show when=move || !is_quiz_mode.get() && !is_writing_mode.get() && !is_yesno_mode.get() && !is_phrase_listen_mode.get() && !is_kanji_reading_quiz_mode.get() && !is_grammar_quiz_mode.get()>
+            <Show when=move || !is_quiz_mode.get() && !is_writing_mode.get() && !is_yesno_mode.get() && !is_phrase_listen_mode.get() && !is_kanji_reading_quiz_mode.get() && !is_grammar_quiz_mode.get() && !is_pitch_accent_quiz_mode.get() && !is_conjugation_drill_mode.get() && !is_production_mode.get() && !is_particle_cloze_mode.get()>
                 {move || {
                     current_lesson_card.get().map(|lesson_card| {
                         render_lesson_card(
@@ -349,6 +386,107 @@ pub fn LessonCardContainer() -> impl IntoView {
                     })
                 }}
             </Show>
+
+            <Show when=move || is_pitch_accent_quiz_mode.get()>
+                {move || {
+                    current_lesson_card.get().and_then(|lesson_card| {
+                        if let LessonCardView::PitchAccentQuiz(quiz) = lesson_card.into_view() {
+                            let state = lesson_state.get();
+                            let selected_option = state.selected_quiz_option;
+
+                            Some(view! {
+                                <QuizCardView
+                                    quiz_card=quiz
+                                    show_result=Signal::derive(move || lesson_state.get().showing_answer)
+                                    selected_option=selected_option
+                                    on_select_option=on_quiz_select
+                                    on_dont_know=on_quiz_dont_know
+                                    dont_know_selected=Signal::derive(move || lesson_state.get().dont_know_selected)
+                                    native_language=native_language.get()
+                                    known_kanji=Signal::from(known_kanji)
+                                    quiz_variant=QuizVariant::PitchAccent
+                                    waiting_for_next=Signal::derive(move || lesson_state.get().waiting_for_next)
+                                    on_next_card=on_next_card
+                                />
+                            })
+                        } else {
+                            None
+                        }
+                    })
+                }}
+            </Show>
+
+            <Show when=move || is_conjugation_drill_mode.get()>
+                {move || {
+                    current_lesson_card.get().and_then(|lesson_card| {
+                        if let LessonCardView::ConjugationDrill(drill) = lesson_card.into_view() {
+                            Some(view! {
+                                <ConjugationDrillCardView
+                                    drill=drill
+                                    show_result=Signal::derive(move || lesson_state.get().showing_answer)
+                                    drill_check=Signal::derive(move || lesson_state.get().drill_check)
+                                    on_submit=on_drill_submit
+                                    on_dont_know=on_drill_dont_know
+                                    dont_know_selected=Signal::derive(move || lesson_state.get().dont_know_selected)
+                                    waiting_for_next=Signal::derive(move || lesson_state.get().waiting_for_next)
+                                    on_next_card=on_next_card
+                                />
+                            })
+                        } else {
+                            None
+                        }
+                    })
+                }}
+            </Show>
+
+            <Show when=move || is_production_mode.get()>
+                {move || {
+                    current_lesson_card.get().and_then(|lesson_card| {
+                        if let LessonCardView::Production(production) = lesson_card.into_view() {
+                            Some(view! {
+                                <ProductionCardView
+                                    production=production
+                                    show_result=Signal::derive(move || lesson_state.get().showing_answer)
+                                    production_result=Signal::derive(move || lesson_state.get().production_result)
+                                    on_submit=on_production_submit
+                                    on_dont_know=on_production_dont_know
+                                    dont_know_selected=Signal::derive(move || lesson_state.get().dont_know_selected)
+                                    waiting_for_next=Signal::derive(move || lesson_state.get().waiting_for_next)
+                                    on_next_card=on_next_card
+                                />
+                            })
+                        } else {
+                            None
+                        }
+                    })
+                }}
+            </Show>
+
+            <Show when=move || is_particle_cloze_mode.get()>
+                {move || {
+                    current_lesson_card.get().and_then(|lesson_card| {
+                        if let LessonCardView::ParticleCloze(cloze) = lesson_card.into_view() {
+                            let selected_option = lesson_state.get().selected_quiz_option;
+
+                            Some(view! {
+                                <ParticleClozeCardView
+                                    cloze=cloze
+                                    show_result=Signal::derive(move || lesson_state.get().showing_answer)
+                                    selected_option=selected_option
+                                    on_select_option=on_quiz_select
+                                    on_dont_know=on_quiz_dont_know
+                                    dont_know_selected=Signal::derive(move || lesson_state.get().dont_know_selected)
+                                    known_kanji=Signal::from(known_kanji)
+                                    waiting_for_next=Signal::derive(move || lesson_state.get().waiting_for_next)
+                                    on_next_card=on_next_card
+                                />
+                            })
+                        } else {
+                            None
+                        }
+                    })
+                }}
+            </Show>
         </Show>
     }
 }
@@ -11,7 +11,7 @@ use super::quiz_card::QuizVariant;
 fn quiz_variant_matches_card_type(quiz_variant: QuizVariant, card_type: CardType) -> bool {
     matches!(
         (quiz_variant, card_type),
-        (QuizVariant::Grammar, CardType::Grammar)
+        (QuizVariant::Grammar, CardType::Grammar) | (QuizVariant::Particle, CardType::Particle)
     )
 }
 
@@ -146,6 +146,26 @@ pub fn LessonCardTagsQuiz(
                                 {t!(i18n, lesson.grammar)}
                             </Tag>
                         }.into_any(),
+                        QuizVariant::PitchAccent => view! {
+                            <Tag variant=Signal::derive(move || TagVariant::Filled)>
+                                {t!(i18n, lesson.pitch_accent)}
+                            </Tag>
+                        }.into_any(),
+                        QuizVariant::Conjugation => view! {
+                            <Tag variant=Signal::derive(move || TagVariant::Filled)>
+                                {t!(i18n, lesson.conjugation)}
+                            </Tag>
+                        }.into_any(),
+                        QuizVariant::Production => view! {
+                            <Tag variant=Signal::derive(move || TagVariant::Filled)>
+                                {t!(i18n, lesson.production)}
+                            </Tag>
+                        }.into_any(),
+                        QuizVariant::Particle => view! {
+                            <Tag variant=Signal::derive(move || TagVariant::Filled)>
+                                {t!(i18n, lesson.particle)}
+                            </Tag>
+                        }.into_any(),
                     }
                 }}
             </Show>
@@ -261,4 +281,16 @@ mod tests {
             CardType::Phrase
         ));
     }
+
+    #[test]
+    fn particle_variant_matches_particle_card_type() {
+        assert!(quiz_variant_matches_card_type(
+            QuizVariant::Particle,
+            CardType::Particle
+        ));
+        assert!(!quiz_variant_matches_card_type(
+            QuizVariant::Particle,
+            CardType::Phrase
+        ));
+    }
 }
@@ -10,6 +10,7 @@ struct LessonCardParams {
     card: Card,
     is_reversed: bool,
     grammar_info: Option<GrammarInfo>,
+    with_example: bool,
 }
 
 pub(in crate::pages::lesson) fn render_lesson_card(
@@ -39,24 +40,31 @@ pub(in crate::pages::lesson) fn render_lesson_card(
                 card,
                 is_reversed: false,
                 grammar_info,
+                with_example: true,
             }
         },
         LessonCardView::Reversed(card) => LessonCardParams {
             card,
             is_reversed: true,
             grammar_info: None,
+            with_example: true,
         },
         LessonCardView::GrammarMutated { card, grammar_info } => LessonCardParams {
             card,
             is_reversed: false,
             grammar_info: Some(grammar_info),
+            with_example: false,
         },
         LessonCardView::Quiz(_)
         | LessonCardView::Writing(_)
         | LessonCardView::YesNo(_)
         | LessonCardView::PhraseListen { .. }
         | LessonCardView::KanjiReadingQuiz(_)
-        | LessonCardView::GrammarQuiz(_) => {
+        | LessonCardView::GrammarQuiz(_)
+        | LessonCardView::PitchAccentQuiz(_)
+        | LessonCardView::ConjugationDrill(_)
+        | LessonCardView::Production(_)
+        | LessonCardView::ParticleCloze(_) => {
             return ().into_any();
         },
     };
@@ -79,6 +87,7 @@ pub(in crate::pages::lesson) fn render_lesson_card(
                 native_language=native_language.get()
                 known_kanji=Signal::from(known_kanji)
                 audio_path=phrase_audio_path
+                with_example=params.with_example
             />
 
             <Show when=move || show_answer.get()>
@@ -101,6 +110,7 @@ pub(in crate::pages::lesson) fn render_lesson_card(
                 native_language=native_language.get()
                 known_kanji=Signal::from(known_kanji)
                 audio_path=None
+                with_example=params.with_example
             />
 
             <Show when=move || show_answer.get()>
@@ -1,6 +1,8 @@
 use crate::repository::HybridUserRepository;
 use leptos::prelude::*;
-use origa::domain::{LessonCard, MultiQuizResult, NativeLanguage, Rating};
+use origa::domain::{
+    ConjugationCheck, LessonCard, MultiQuizResult, NativeLanguage, ProductionResult, Rating,
+};
 use std::collections::{HashMap, HashSet};
 use ulid::Ulid;
 
@@ -78,6 +80,8 @@ pub struct LessonState {
     pub selected_quiz_options: HashSet<usize>,
     pub multi_quiz_submitted: bool,
     pub multi_result: Option<MultiQuizResult>,
+    pub drill_check: Option<ConjugationCheck>,
+    pub production_result: Option<ProductionResult>,
 }
 
 #[derive(Clone)]
@@ -1,8 +1,10 @@
 mod answer_display;
 pub mod card_type;
 pub(crate) mod complete_screen;
+mod conjugation_drill_card;
 mod content;
 mod empty_state_view;
+mod example_sentence;
 mod grammar_details_expand;
 mod grammar_info_badge;
 mod header;
@@ -21,14 +23,18 @@ mod lesson_wasm_tests;
 mod na_adjective_helper;
 mod next_card_button;
 mod on_dont_know;
+mod on_drill_submit;
+mod on_production_submit;
 mod on_quiz_select;
 mod on_quiz_submit;
 mod on_quiz_toggle;
 mod on_rate;
 mod on_yesno_select;
+mod particle_cloze_card;
 pub(crate) mod phrase_card;
 pub(crate) mod phrase_rating_buttons;
 pub(crate) mod pos_label;
+mod production_card;
 mod quiz_card;
 mod quiz_options;
 mod quiz_options_multi;
@@ -11,6 +11,8 @@ pub fn create_on_dont_know(lesson_state: RwSignal<LessonState>) -> Callback<()>
             state.showing_answer = true;
             state.selected_quiz_options.clear();
             state.multi_result = None;
+            state.drill_check = None;
+            state.production_result = None;
             state.multi_quiz_submitted = true;
         });
 
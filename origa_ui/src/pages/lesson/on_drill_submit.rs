@@ -0,0 +1,120 @@
+use super::lesson_state::LessonState;
+use leptos::prelude::*;
+use origa::domain::{LessonCardView, Rating};
+use tracing::warn;
+
+pub fn create_on_drill_submit(lesson_state: RwSignal<LessonState>) -> Callback<String> {
+    Callback::new(move |answer: String| {
+        if answer.trim().is_empty() {
+            return;
+        }
+
+        let state = lesson_state.get();
+        let Some(lesson_card) = state
+            .card_ids
+            .get(state.current_index)
+            .and_then(|id| state.cards.get(id))
+        else {
+            return;
+        };
+
+        let LessonCardView::ConjugationDrill(drill) = lesson_card.view() else {
+            return;
+        };
+
+        // The chain was applied once at generation, so an error here means
+        // the card changed under us; grade it as a miss rather than hang.
+        let check = match drill.check_answer(&answer) {
+            Ok(check) => Some(check),
+            Err(e) => {
+                warn!(error = %e, "Failed to check conjugation answer");
+                None
+            },
+        };
+        let rating = if check.as_ref().is_some_and(|c| c.is_correct) {
+            Rating::Good
+        } else {
+            Rating::Again
+        };
+
+        // Pure-manual advance (ADR-033), same as the option quizzes.
+        lesson_state.update(|state| {
+            state.showing_answer = true;
+            state.drill_check = check;
+            state.waiting_for_next = true;
+            state.pending_rating = Some(rating);
+        });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use origa::dictionary::grammar::FormatAction;
+    use origa::domain::{
+        Card, ConjugationDrillCard, GrammarInfo, LessonCard, PartOfSpeech, PhraseCard,
+    };
+    use ulid::Ulid;
+
+    fn drill_state() -> RwSignal<LessonState> {
+        let drill = ConjugationDrillCard::new(
+            Card::Phrase(PhraseCard::new(Ulid::new())),
+            GrammarInfo::new(None, "ない形".to_string(), String::new()),
+            "書く".to_string(),
+            Some("かく".to_string()),
+            PartOfSpeech::Verb,
+            vec![FormatAction::VerbToNai {}],
+        );
+        let slot_id = Ulid::new();
+        let mut cards = std::collections::HashMap::new();
+        cards.insert(
+            slot_id,
+            LessonCard::new(slot_id, LessonCardView::ConjugationDrill(drill), false),
+        );
+        RwSignal::new(LessonState {
+            card_ids: vec![slot_id],
+            cards,
+            ..LessonState::default()
+        })
+    }
+
+    #[test]
+    fn kana_answer_is_graded_good_and_waits_for_next() {
+        let state = Owner::new().with(|| {
+            let lesson_state = drill_state();
+            create_on_drill_submit(lesson_state).run("かかない".to_string());
+            lesson_state.get()
+        });
+
+        assert!(state.showing_answer);
+        assert!(state.waiting_for_next);
+        assert_eq!(state.pending_rating, Some(Rating::Good));
+        assert!(state.drill_check.is_some_and(|c| c.is_correct));
+    }
+
+    #[test]
+    fn wrong_answer_is_graded_again_with_feedback() {
+        let state = Owner::new().with(|| {
+            let lesson_state = drill_state();
+            create_on_drill_submit(lesson_state).run("書きない".to_string());
+            lesson_state.get()
+        });
+
+        assert_eq!(state.pending_rating, Some(Rating::Again));
+        let check = state.drill_check.expect("check stored");
+        assert_eq!(check.expected, "書かない");
+        assert_eq!(check.wrong_step, Some(0));
+    }
+
+    #[test]
+    fn blank_answer_is_ignored() {
+        let state = Owner::new().with(|| {
+            let lesson_state = drill_state();
+            create_on_drill_submit(lesson_state).run("  ".to_string());
+            lesson_state.get()
+        });
+
+        assert!(!state.showing_answer);
+        assert_eq!(state.pending_rating, None);
+    }
+}
@@ -0,0 +1,98 @@
+use super::lesson_state::LessonState;
+use leptos::prelude::*;
+use origa::domain::LessonCardView;
+
+pub fn create_on_production_submit(lesson_state: RwSignal<LessonState>) -> Callback<String> {
+    Callback::new(move |answer: String| {
+        if answer.trim().is_empty() {
+            return;
+        }
+
+        let state = lesson_state.get();
+        let Some(lesson_card) = state
+            .card_ids
+            .get(state.current_index)
+            .and_then(|id| state.cards.get(id))
+        else {
+            return;
+        };
+
+        let LessonCardView::Production(production) = lesson_card.view() else {
+            return;
+        };
+        let result = production.check_answer(&answer);
+
+        // Pure-manual advance (ADR-033); a near miss keeps its Hard rating
+        // instead of the Good a correct option pick gets.
+        lesson_state.update(|state| {
+            state.showing_answer = true;
+            state.waiting_for_next = true;
+            state.pending_rating = Some(result.rating);
+            state.production_result = Some(result);
+        });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use origa::domain::{Card, LessonCard, PhraseCard, ProductionCard, Rating};
+    use ulid::Ulid;
+
+    fn production_state() -> RwSignal<LessonState> {
+        let production = ProductionCard::new(
+            Card::Phrase(PhraseCard::new(Ulid::new())),
+            "student".to_string(),
+            vec!["がくせい".to_string()],
+        );
+        let slot_id = Ulid::new();
+        let mut cards = std::collections::HashMap::new();
+        cards.insert(
+            slot_id,
+            LessonCard::new(slot_id, LessonCardView::Production(production), false),
+        );
+        RwSignal::new(LessonState {
+            card_ids: vec![slot_id],
+            cards,
+            ..LessonState::default()
+        })
+    }
+
+    #[test]
+    fn romaji_answer_is_graded_good_and_waits_for_next() {
+        let state = Owner::new().with(|| {
+            let lesson_state = production_state();
+            create_on_production_submit(lesson_state).run("gakusei".to_string());
+            lesson_state.get()
+        });
+
+        assert!(state.showing_answer);
+        assert!(state.waiting_for_next);
+        assert_eq!(state.pending_rating, Some(Rating::Good));
+    }
+
+    #[test]
+    fn typo_is_graded_hard_with_expected_reading() {
+        let state = Owner::new().with(|| {
+            let lesson_state = production_state();
+            create_on_production_submit(lesson_state).run("がくせ".to_string());
+            lesson_state.get()
+        });
+
+        assert_eq!(state.pending_rating, Some(Rating::Hard));
+        let result = state.production_result.expect("result stored");
+        assert_eq!(result.expected, "がくせい");
+    }
+
+    #[test]
+    fn blank_answer_is_ignored() {
+        let state = Owner::new().with(|| {
+            let lesson_state = production_state();
+            create_on_production_submit(lesson_state).run(" ".to_string());
+            lesson_state.get()
+        });
+
+        assert!(!state.showing_answer);
+        assert_eq!(state.pending_rating, None);
+    }
+}
@@ -28,16 +28,19 @@ pub fn create_on_quiz_select(lesson_state: RwSignal<LessonState>) -> Callback<us
 
         if let Some(lesson_card) = lesson_state.get().cards.get(&card_id) {
             let is_correct = match lesson_card.view() {
-                LessonCardView::Quiz(q) | LessonCardView::KanjiReadingQuiz(q) => {
-                    Some(q.check_answer(option_index))
-                },
+                LessonCardView::Quiz(q)
+                | LessonCardView::KanjiReadingQuiz(q)
+                | LessonCardView::PitchAccentQuiz(q) => Some(q.check_answer(option_index)),
                 LessonCardView::GrammarQuiz(gq) => Some(gq.quiz().check_answer(option_index)),
+                LessonCardView::ParticleCloze(cloze) => Some(cloze.check_answer(option_index)),
                 LessonCardView::PhraseListen { options, .. } => {
                     options.get(option_index).map(|o| o.is_correct())
                 },
                 _ => None,
             };
 
+            // A pitch-accent answer still sets a rating to satisfy the
+            // advance contract; `on_rate` leaves the card itself unrated.
             if let Some(is_correct) = is_correct {
                 let rating = if is_correct {
                     Rating::Good
@@ -63,7 +66,8 @@ pub fn create_on_quiz_select(lesson_state: RwSignal<LessonState>) -> Callback<us
 mod tests {
     use super::*;
     use origa::domain::{
-        Card, LessonCard, LessonCardView, PhraseCard, QuizCard, QuizMode, QuizOption,
+        Card, LessonCard, LessonCardView, ParticleClozeCard, PhraseCard, QuizCard, QuizMode,
+        QuizOption,
     };
     use ulid::Ulid;
 
@@ -124,6 +128,33 @@ mod tests {
         assert_eq!(state.pending_rating, Some(Rating::Good));
     }
 
+    #[test]
+    fn quiz_select_for_particle_cloze_rates_wrong_particle_again() {
+        let state = Owner::new().with(|| {
+            provide_context(StoredValue::<()>::new(()));
+            let cloze = ParticleClozeCard::new(
+                phrase_card(),
+                "学校".to_string(),
+                "行きます".to_string(),
+                "に".to_string(),
+                None,
+                vec![
+                    QuizOption::new_simple("に".to_string(), true),
+                    QuizOption::new_simple("で".to_string(), false),
+                ],
+            );
+            let card = LessonCard::new(Ulid::new(), LessonCardView::ParticleCloze(cloze), false);
+            let (lesson_state, _) = setup_state(card);
+            let on_quiz_select = create_on_quiz_select(lesson_state);
+
+            on_quiz_select.run(1);
+            lesson_state.get()
+        });
+
+        assert!(state.waiting_for_next);
+        assert_eq!(state.pending_rating, Some(Rating::Again));
+    }
+
     // Non-phrase branch was previously gated by a 1500ms spawn_local timer;
     // pure-manual advance (ADR-033) unified it with the phrase branch. Both
     // branches now set `waiting_for_next` synchronously; only the synchronous
@@ -3,7 +3,7 @@ use crate::core::haptics;
 use crate::hooks::phrase_checker;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use origa::domain::{Card, CardType, LessonCard, RateMode, Rating};
+use origa::domain::{Card, CardType, LessonCard, LessonCardView, RateMode, Rating};
 use origa::traits::UserRepository;
 use origa::use_cases::{CreatePhraseCardUseCase, RateCardWithSideEffectsUseCase};
 use tracing::warn;
@@ -17,7 +17,7 @@ fn determine_rate_mode(card: &LessonCard) -> RateMode {
         return RateMode::ShortTerm;
     }
     match CardType::from(card.card()) {
-        CardType::Grammar => RateMode::GrammarReview,
+        CardType::Grammar | CardType::Particle => RateMode::GrammarReview,
         CardType::Kanji => RateMode::KanjiReview,
         // Phrase cards are intercepted by the early return above, so the only
         // remaining type reaching this arm is Vocabulary.
@@ -27,14 +27,33 @@ fn determine_rate_mode(card: &LessonCard) -> RateMode {
 
 fn extract_grammar_rule_id(card: &LessonCard) -> Option<Ulid> {
     match card.view() {
-        origa::domain::LessonCardView::GrammarMutated { grammar_info, .. } => {
-            grammar_info.rule_id()
+        LessonCardView::GrammarMutated { grammar_info, .. } => grammar_info.rule_id(),
+        LessonCardView::GrammarQuiz(gq) => gq.grammar_info().rule_id(),
+        LessonCardView::ConjugationDrill(drill) => drill.grammar_info().rule_id(),
+        _ => None,
+    }
+}
+
+/// Particle blanked by a cloze over a phrase. A cloze over a particle card
+/// is rated as that card, so there is nothing extra to rate.
+fn extract_particle(card: &LessonCard) -> Option<String> {
+    match card.view() {
+        LessonCardView::ParticleCloze(cloze)
+            if CardType::from(cloze.card()) != CardType::Particle =>
+        {
+            Some(cloze.particle().to_string())
         },
-        origa::domain::LessonCardView::GrammarQuiz(gq) => gq.grammar_info().rule_id(),
         _ => None,
     }
 }
 
+/// A pitch-accent quiz tests a known word's accent, not its meaning, so the
+/// answer only advances the lesson; rating it would lapse the word's memory
+/// on one wrong accent guess.
+fn rates_card(card: &LessonCard) -> bool {
+    !matches!(card.view(), LessonCardView::PitchAccentQuiz(_))
+}
+
 async fn check_and_create_ready_phrases<R: UserRepository>(
     card_id: Ulid,
     card_type: CardType,
@@ -97,6 +116,8 @@ fn advance_lesson_state(
             state.selected_quiz_options.clear();
             state.multi_quiz_submitted = false;
             state.multi_result = None;
+            state.drill_check = None;
+            state.production_result = None;
         }
     });
 }
@@ -131,6 +152,7 @@ pub fn create_on_rate_callback(
             .map(determine_rate_mode)
             .unwrap_or(RateMode::StandardLesson);
         let grammar_rule_id = lesson_card.and_then(extract_grammar_rule_id);
+        let particle = lesson_card.and_then(extract_particle);
         let card_type = lesson_card
             .map(|lc| CardType::from(lc.card()))
             .unwrap_or(CardType::Vocabulary);
@@ -138,6 +160,11 @@ pub fn create_on_rate_callback(
         let Some(real_card_id) = real_card_id else {
             return;
         };
+        if !lesson_card.is_some_and(rates_card) {
+            advance_lesson_state(lesson_state, lesson_ctx.is_completed);
+            is_rating.set(None);
+            return;
+        }
 
         let repo = lesson_ctx.repository.clone();
         let lesson_state = lesson_state;
@@ -148,7 +175,7 @@ pub fn create_on_rate_callback(
             let use_case = RateCardWithSideEffectsUseCase::new(&repo);
 
             if let Err(e) = use_case
-                .execute(real_card_id, rate_mode, rating, grammar_rule_id)
+                .execute(real_card_id, rate_mode, rating, grammar_rule_id, particle)
                 .await
             {
                 warn!(error = ?e, "Failed to rate card");
@@ -169,7 +196,9 @@ pub fn create_on_rate_callback(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use origa::domain::{LessonCardView, PhraseCard};
+    use origa::domain::{
+        ParticleCard, ParticleClozeCard, PhraseCard, QuizCard, QuizMode, QuizOption,
+    };
 
     fn phrase_lesson_card(is_short_term: bool) -> LessonCard {
         let card = Card::Phrase(PhraseCard::new(Ulid::new()));
@@ -213,4 +242,52 @@ mod tests {
         let card = vocab_lesson_card(true);
         assert_eq!(determine_rate_mode(&card), RateMode::ShortTerm);
     }
+
+    fn particle_cloze_lesson_card(card: Card) -> LessonCard {
+        let cloze = ParticleClozeCard::new(
+            card,
+            "学校".to_string(),
+            "行きます".to_string(),
+            "に".to_string(),
+            None,
+            vec![
+                QuizOption::new_simple("に".to_string(), true),
+                QuizOption::new_simple("で".to_string(), false),
+            ],
+        );
+        LessonCard::new(Ulid::new(), LessonCardView::ParticleCloze(cloze), false)
+    }
+
+    #[test]
+    fn extract_particle_from_phrase_cloze() {
+        let card = particle_cloze_lesson_card(Card::Phrase(PhraseCard::new(Ulid::new())));
+        assert_eq!(extract_particle(&card), Some("に".to_string()));
+    }
+
+    #[test]
+    fn extract_particle_skips_particle_card_cloze() {
+        let card = particle_cloze_lesson_card(Card::Particle(ParticleCard::new("に".to_string())));
+        assert_eq!(extract_particle(&card), None);
+        assert_eq!(determine_rate_mode(&card), RateMode::GrammarReview);
+    }
+
+    #[test]
+    fn pitch_accent_quiz_leaves_the_vocabulary_card_unrated() {
+        let vocab = vocab_lesson_card(false);
+        let pitch = LessonCard::new(
+            Ulid::new(),
+            LessonCardView::PitchAccentQuiz(QuizCard::new(
+                vocab.card().clone(),
+                vec![
+                    QuizOption::new_simple("heiban".to_string(), true),
+                    QuizOption::new_simple("atamadaka".to_string(), false),
+                ],
+                QuizMode::Single,
+            )),
+            false,
+        );
+
+        assert!(rates_card(&vocab));
+        assert!(!rates_card(&pitch));
+    }
 }
@@ -0,0 +1,97 @@
+use crate::i18n::*;
+use crate::ui_components::{Card, Heading, HeadingLevel, Text, TextSize, TypographyVariant};
+use leptos::prelude::*;
+use origa::domain::ParticleClozeCard;
+use std::collections::HashSet;
+
+use super::card_type::CardType;
+use super::lesson_card_header::LessonCardTagsQuiz;
+use super::next_card_button::NextCardButton;
+use super::quiz_card::QuizVariant;
+use super::quiz_options::QuizOptions;
+use super::quiz_result::QuizResult;
+use super::quiz_result_display::QuizResultDisplay;
+
+/// Phrase with one particle blanked out; the learner picks it among the
+/// particles it is confused with, and the full phrase is shown afterwards.
+#[component]
+pub fn ParticleClozeCardView(
+    cloze: ParticleClozeCard,
+    show_result: Signal<bool>,
+    selected_option: Option<usize>,
+    on_select_option: Callback<usize>,
+    on_dont_know: Callback<()>,
+    dont_know_selected: Signal<bool>,
+    #[prop(into)] known_kanji: Signal<HashSet<char>>,
+    #[prop(default = Signal::derive(|| false))] waiting_for_next: Signal<bool>,
+    #[prop(default = Callback::new(|_: ()| {}))] on_next_card: Callback<()>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let card_type = CardType::from(cloze.card());
+    let prompt = format!("{}＿＿{}", cloze.before(), cloze.after());
+    let phrase_text = cloze.phrase_text();
+    let translation = cloze.translation().map(str::to_string);
+    let options = StoredValue::new(cloze.options().to_vec());
+
+    let quiz_result = move || {
+        if dont_know_selected.get() && show_result.get() {
+            return QuizResult::DontKnow;
+        }
+        match selected_option.and_then(|selected| options.get_value().get(selected).cloned()) {
+            Some(option) if option.is_correct() => QuizResult::Correct,
+            Some(_) => QuizResult::Incorrect,
+            None => QuizResult::None,
+        }
+    };
+
+    view! {
+        <div class="flex flex-col">
+            <LessonCardTagsQuiz card_type=card_type quiz_variant=QuizVariant::Particle />
+            <Card class=Signal::derive(|| super::LESSON_CARD_CLASS.to_string()) shadow=true test_id="lesson-card-root">
+
+            <div class="flex-1 flex flex-col justify-center">
+                <div class="text-center mb-4">
+                    <Heading level=HeadingLevel::H2>
+                        <span data-testid="particle-cloze-prompt">{prompt}</span>
+                    </Heading>
+                    {translation.map(|translation| view! {
+                        <Text size=TextSize::Default variant=TypographyVariant::Muted class="mt-2">
+                            {translation}
+                        </Text>
+                    })}
+                    <Show when=move || !show_result.get()>
+                        <Text size=TextSize::Default variant=TypographyVariant::Muted class="mt-4">
+                            {t!(i18n, lesson.choose_particle)}
+                        </Text>
+                    </Show>
+                </div>
+
+                <Show when=move || !show_result.get()>
+                    <QuizOptions
+                        options=options.get_value()
+                        selected_option=selected_option
+                        show_result=show_result
+                        quiz_result=quiz_result()
+                        on_select_option=on_select_option
+                        on_dont_know=on_dont_know
+                        dont_know_selected=dont_know_selected
+                        known_kanji=known_kanji
+                    />
+                </Show>
+
+                <Show when=move || show_result.get()>
+                    <QuizResultDisplay quiz_result=quiz_result() />
+
+                    <div class="mt-3 p-3 bg-[var(--bg-secondary)] text-center font-serif text-lg" data-testid="particle-cloze-answer">
+                        {phrase_text.clone()}
+                    </div>
+                </Show>
+
+                <Show when=move || waiting_for_next.get() && show_result.get()>
+                    <NextCardButton on_next_card=on_next_card />
+                </Show>
+            </div>
+        </Card>
+        </div>
+    }
+}
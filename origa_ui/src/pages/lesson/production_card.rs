@@ -0,0 +1,128 @@
+use crate::i18n::*;
+use crate::ui_components::{
+    Button, ButtonVariant, Card, Heading, HeadingLevel, Input, Text, TextSize, TypographyVariant,
+};
+use leptos::prelude::*;
+use origa::domain::{ProductionCard, ProductionResult, Rating, romaji_to_kana_partial};
+
+use super::card_type::CardType;
+use super::lesson_card_header::LessonCardTagsQuiz;
+use super::next_card_button::NextCardButton;
+use super::quiz_card::QuizVariant;
+use super::quiz_result::QuizResult;
+use super::quiz_result_display::QuizResultDisplay;
+
+/// Typed production review: the learner sees the meaning and types the
+/// reading. Romaji is turned into kana as it is typed, so no IME is needed.
+#[component]
+pub fn ProductionCardView(
+    production: ProductionCard,
+    show_result: Signal<bool>,
+    production_result: Signal<Option<ProductionResult>>,
+    on_submit: Callback<String>,
+    on_dont_know: Callback<()>,
+    dont_know_selected: Signal<bool>,
+    #[prop(default = Signal::derive(|| false))] waiting_for_next: Signal<bool>,
+    #[prop(default = Callback::new(|_: ()| {}))] on_next_card: Callback<()>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let answer = RwSignal::new(String::new());
+    let prompt = production.prompt().to_string();
+    let readings = StoredValue::new(production.readings().join("・"));
+
+    // Pending romaji such as a lone "k" stays as typed until its syllable
+    // is complete, so rewriting the value converges after one pass.
+    Effect::new(move |_| {
+        let typed = answer.get();
+        let kana = romaji_to_kana_partial(&typed);
+        if kana != typed {
+            answer.set(kana);
+        }
+    });
+
+    let submit = move || on_submit.run(answer.get_untracked());
+
+    let quiz_result = move || {
+        if dont_know_selected.get() {
+            return QuizResult::DontKnow;
+        }
+        match production_result.get() {
+            Some(result) if result.rating != Rating::Again => QuizResult::Correct,
+            _ if show_result.get() => QuizResult::Incorrect,
+            _ => QuizResult::None,
+        }
+    };
+    let is_typo = move || {
+        production_result
+            .get()
+            .is_some_and(|result| result.rating == Rating::Hard)
+    };
+
+    view! {
+        <div class="flex flex-col">
+            <LessonCardTagsQuiz card_type=CardType::Vocabulary quiz_variant=QuizVariant::Production />
+            <Card class=Signal::derive(|| super::LESSON_CARD_CLASS.to_string()) shadow=true test_id="lesson-card-root">
+
+            <div class="flex-1 flex flex-col justify-center">
+                <div class="text-center mb-4">
+                    <Heading level=HeadingLevel::H2>
+                        {prompt}
+                    </Heading>
+                    <Text size=TextSize::Default variant=TypographyVariant::Muted class="mt-2">
+                        {t!(i18n, lesson.type_reading)}
+                    </Text>
+                </div>
+
+                <Input
+                    value=answer
+                    disabled=show_result
+                    class="text-center font-serif text-lg"
+                    test_id="production-input"
+                    on_keydown=Callback::new(move |ev: leptos::ev::KeyboardEvent| {
+                        if ev.key() == "Enter" && !ev.is_composing() {
+                            ev.prevent_default();
+                            submit();
+                        }
+                    })
+                />
+
+                <Show when=move || !show_result.get()>
+                    <div class="mt-3 grid grid-cols-2 gap-2">
+                        <Button
+                            variant=Signal::derive(|| ButtonVariant::Filled)
+                            on_click=Callback::new(move |_| submit())
+                            test_id="production-check-btn"
+                        >
+                            {t!(i18n, lesson.check)}
+                        </Button>
+                        <Button
+                            on_click=Callback::new(move |_| on_dont_know.run(()))
+                            test_id="quiz-dont-know-btn"
+                        >
+                            {t!(i18n, lesson.dont_know)}
+                        </Button>
+                    </div>
+                </Show>
+
+                <Show when=move || show_result.get()>
+                    <QuizResultDisplay quiz_result=quiz_result() />
+
+                    <Show when=is_typo>
+                        <Text size=TextSize::Small variant=TypographyVariant::Muted class="mt-1 text-center">
+                            {t!(i18n, lesson.production_typo)}
+                        </Text>
+                    </Show>
+
+                    <div class="mt-3 p-3 bg-[var(--bg-secondary)] text-center font-serif text-lg" data-testid="production-expected">
+                        {move || readings.get_value()}
+                    </div>
+                </Show>
+
+                <Show when=move || waiting_for_next.get() && show_result.get()>
+                    <NextCardButton on_next_card=on_next_card />
+                </Show>
+            </div>
+        </Card>
+        </div>
+    }
+}
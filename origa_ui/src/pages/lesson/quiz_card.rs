@@ -1,10 +1,11 @@
 use crate::i18n::*;
 use crate::ui_components::{
     Card, DisplayText, FuriganaText, Heading, HeadingLevel, KanjiViewMode, KanjiWritingSection,
-    MarkdownText, MarkdownVariant, Text, TextSize, TypographyVariant, is_speech_supported,
-    speak_word, stop_current_audio,
+    MarkdownText, MarkdownVariant, PitchContour, Text, TextSize, TypographyVariant,
+    is_speech_supported, speak_word, stop_current_audio,
 };
 use leptos::prelude::*;
+use origa::dictionary::pitch_audio::pitch_accent_for_word;
 use origa::domain::{Card as DomainCard, MultiQuizResult, NativeLanguage, QuizCard, QuizMode};
 use std::collections::HashSet;
 use tracing::warn;
@@ -23,6 +24,10 @@ pub enum QuizVariant {
     Meaning,
     Reading,
     Grammar,
+    PitchAccent,
+    Conjugation,
+    Production,
+    Particle,
 }
 
 #[component]
@@ -75,6 +80,11 @@ pub fn QuizCardView(
     let options: StoredValue<Vec<origa::domain::QuizOption>> =
         StoredValue::new(quiz_card.options().to_vec());
     let multi_result_stored = StoredValue::new(multi_result);
+    let pitch_accent = StoredValue::new(if quiz_variant == QuizVariant::PitchAccent {
+        pitch_accent_for_word(&question_text)
+    } else {
+        None
+    });
 
     let quiz_result = move || {
         if dont_know_selected.get() && show_result.get() {
@@ -182,6 +192,10 @@ pub fn QuizCardView(
                                     QuizVariant::Meaning => t!(i18n, lesson.choose_answer).into_any(),
                                     QuizVariant::Reading => t!(i18n, lesson.choose_reading).into_any(),
                                     QuizVariant::Grammar => t!(i18n, lesson.choose_grammar).into_any(),
+                                    QuizVariant::PitchAccent => t!(i18n, lesson.choose_pitch_accent).into_any(),
+                                    QuizVariant::Conjugation => t!(i18n, lesson.type_conjugation).into_any(),
+                                    QuizVariant::Production => t!(i18n, lesson.type_reading).into_any(),
+                                    QuizVariant::Particle => t!(i18n, lesson.choose_particle).into_any(),
                                 }
                             }}
                         </Text>
@@ -257,6 +271,15 @@ pub fn QuizCardView(
                                 variant=MarkdownVariant::Default
                                 known_kanji=known_kanji.get()
                             />
+                            {move || {
+                                pitch_accent.get_value().map(|accent| {
+                                    view! {
+                                        <div class="mt-2">
+                                            <PitchContour accent />
+                                        </div>
+                                    }
+                                })
+                            }}
                         </div>
                     </Show>
 
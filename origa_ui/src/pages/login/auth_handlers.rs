@@ -4,7 +4,7 @@ use crate::repository::{
 };
 use crate::store::auth_store::AuthStore;
 use chrono::Utc;
-use origa::domain::{DailyLoad, JlptProgress, KnowledgeSet, User};
+use origa::domain::{DailyLoad, JlptProgress, KnowledgeSet, SrsSettings, User};
 use origa::traits::UserRepository;
 use std::collections::HashSet;
 
@@ -123,6 +123,7 @@ pub(super) fn create_new_user_from_session(
         HashSet::new(),
         DailyLoad::default(),
         0,
+        SrsSettings::default(),
     ))
 }
 
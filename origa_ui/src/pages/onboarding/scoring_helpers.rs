@@ -86,6 +86,17 @@ pub(super) fn extract_card_data(
             readings: None,
             card_type: CardType::Phrase,
         },
+        DomainCard::Particle(p) => ScoringCard {
+            card_id,
+            question: p.particle().to_string(),
+            answer: match p.answer(lang).ok() {
+                Some(CardAnswer::Vocabulary { translations, .. }) => translations.join(", "),
+                Some(CardAnswer::Text(s)) => s,
+                None => no_translation(),
+            },
+            readings: None,
+            card_type: CardType::Particle,
+        },
     }
 }
 
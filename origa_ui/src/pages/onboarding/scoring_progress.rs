@@ -47,7 +47,9 @@ pub(super) fn compute_section_bounds(card_types: &[CardType]) -> Vec<SectionBoun
 fn section_label(i18n: &I18nContext<Locale>, card_type: CardType) -> String {
     let locale = i18n.get_locale();
     match card_type {
-        CardType::Grammar => td_string!(locale, onboarding.scoring.section.grammar).to_string(),
+        CardType::Grammar | CardType::Particle => {
+            td_string!(locale, onboarding.scoring.section.grammar).to_string()
+        },
         CardType::Kanji => td_string!(locale, onboarding.scoring.section.kanji).to_string(),
         CardType::Vocabulary => {
             td_string!(locale, onboarding.scoring.section.vocabulary).to_string()
@@ -60,7 +62,7 @@ fn section_label(i18n: &I18nContext<Locale>, card_type: CardType) -> String {
 /// Grammar → Terracotta, Kanji → Olive, Vocabulary/Phrase → default (black).
 fn section_color_class(card_type: CardType) -> &'static str {
     match card_type {
-        CardType::Grammar => "scoring-section-grammar",
+        CardType::Grammar | CardType::Particle => "scoring-section-grammar",
         CardType::Kanji => "scoring-section-kanji",
         CardType::Vocabulary | CardType::Phrase => "",
     }
@@ -0,0 +1,107 @@
+use crate::i18n::*;
+use crate::store::AuthStore;
+use crate::ui_components::{
+    Alert, AlertType, Button, ButtonVariant, Text, TextSize, TypographyVariant,
+};
+use crate::utils::file::save_bytes_as_file;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use origa::use_cases::ExportAnkiPackUseCase;
+
+const EXPORT_FILE_NAME: &str = "origa.apkg";
+const EXPORT_MIME_TYPE: &str = "application/octet-stream";
+
+/// Exports every card with its scheduling as an Anki `.apkg` package.
+#[component]
+pub fn AnkiExportCard(#[prop(optional, into)] test_id: Signal<String>) -> impl IntoView {
+    let i18n = use_i18n();
+    let auth_store = use_context::<AuthStore>().expect("AuthStore not provided");
+
+    let is_exporting = RwSignal::new(false);
+    let error_message = RwSignal::<Option<String>>::new(None);
+    let success_message = RwSignal::<Option<String>>::new(None);
+    let disposed = StoredValue::new(());
+
+    let on_export = Callback::new(move |_| {
+        let repository = auth_store.repository().clone();
+        error_message.set(None);
+        success_message.set(None);
+        is_exporting.set(true);
+
+        spawn_local(async move {
+            let result = ExportAnkiPackUseCase::new(&repository)
+                .execute()
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| {
+                    save_bytes_as_file(&result.apkg, EXPORT_FILE_NAME, EXPORT_MIME_TYPE)
+                        .map(|_| result.exported_count)
+                });
+            if disposed.is_disposed() {
+                return;
+            }
+            match result {
+                Ok(exported) => success_message.set(Some(
+                    td_string!(i18n.get_locale(), profile.anki_export_done).replacen(
+                        "{}",
+                        &exported.to_string(),
+                        1,
+                    ),
+                )),
+                Err(e) => error_message.set(Some(format!(
+                    "{}: {}",
+                    td_string!(i18n.get_locale(), profile.anki_export_error),
+                    e
+                ))),
+            }
+            is_exporting.set(false);
+        });
+    });
+
+    let test_id_val = move || {
+        let val = test_id.get();
+        if val.is_empty() { None } else { Some(val) }
+    };
+
+    view! {
+        <div data-testid=test_id_val class="p-6 space-y-4">
+            <div class="space-y-2">
+                <Text size=TextSize::Large>
+                    {t!(i18n, profile.anki_export)}
+                </Text>
+                <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                    {t!(i18n, profile.anki_export_desc)}
+                </Text>
+            </div>
+
+            <Show when=move || error_message.get().is_some()>
+                <Alert
+                    alert_type=Signal::from(AlertType::Error)
+                    message=Signal::derive(move || error_message.get().unwrap_or_default())
+                    test_id="anki-export-error"
+                />
+            </Show>
+
+            <Show when=move || success_message.get().is_some()>
+                <Alert
+                    alert_type=Signal::from(AlertType::Success)
+                    message=Signal::derive(move || success_message.get().unwrap_or_default())
+                    test_id="anki-export-success"
+                />
+            </Show>
+
+            <Button
+                variant=ButtonVariant::Filled
+                on_click=on_export
+                disabled=Signal::derive(move || is_exporting.get())
+                test_id="anki-export-btn"
+            >
+                {move || if is_exporting.get() {
+                    t!(i18n, profile.anki_exporting).into_any()
+                } else {
+                    t!(i18n, profile.anki_export_button).into_any()
+                }}
+            </Button>
+        </div>
+    }
+}
@@ -1,4 +1,7 @@
-use super::{DangerZoneCard, PasswordCard, PersonalDataCard, SettingsCard, legal_card};
+use super::{
+    AnkiExportCard, DangerZoneCard, PasswordCard, PersonalDataCard, SettingsCard, SrsSettingsCard,
+    legal_card,
+};
 use crate::i18n::{native_language_to_locale, t, use_i18n};
 use crate::store::AuthStore;
 use crate::ui_components::{Card, OfflineBundleCard};
@@ -207,6 +210,12 @@ pub fn ProfileContent() -> impl IntoView {
                     <Card shadow=Signal::derive(|| true)>
                         <OfflineBundleCard test_id="profile-offline-bundle" />
                     </Card>
+                    <Card shadow=Signal::derive(|| true)>
+                        <SrsSettingsCard test_id="profile-srs-settings" />
+                    </Card>
+                    <Card shadow=Signal::derive(|| true)>
+                        <AnkiExportCard test_id="profile-anki-export" />
+                    </Card>
                 </div>
 
                 <div class="profile-col">
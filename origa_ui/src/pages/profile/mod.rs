@@ -1,16 +1,22 @@
+pub(crate) mod anki_export_card;
 pub(crate) mod content;
 pub(crate) mod danger_zone_card;
 pub(crate) mod legal_card;
 pub(crate) mod password_card;
 pub(crate) mod personal_data_card;
 pub(crate) mod settings_card;
+pub(crate) mod srs_exam_target;
+pub(crate) mod srs_mode_schedules;
+pub(crate) mod srs_settings_card;
 
+pub use anki_export_card::AnkiExportCard;
 pub use content::ProfileContent;
 pub use danger_zone_card::DangerZoneCard;
 pub use legal_card::legal_card;
 pub use password_card::PasswordCard;
 pub use personal_data_card::PersonalDataCard;
 pub use settings_card::SettingsCard;
+pub use srs_settings_card::SrsSettingsCard;
 
 use crate::repository::HybridUserRepository;
 use crate::ui_components::{CardLayout, CardLayoutSize, PageLayout, PageLayoutVariant};
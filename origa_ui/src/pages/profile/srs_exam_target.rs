@@ -0,0 +1,170 @@
+use crate::i18n::*;
+use crate::loaders::get_jlpt_content;
+use crate::store::AuthStore;
+use crate::ui_components::{
+    Button, ButtonVariant, Input, LevelSelector, Text, TextSize, TypographyVariant,
+};
+use chrono::NaiveDate;
+use leptos::prelude::*;
+use leptos_i18n::I18nContext;
+use origa::domain::{ExamReadiness, JapaneseLevel, User};
+use origa::use_cases::SrsSettingsChange;
+
+/// JLPT exam target, and whether the current pace covers its content in
+/// time.
+#[component]
+pub fn SrsExamTarget(
+    update_settings: Callback<SrsSettingsChange>,
+    #[prop(into)] disabled: Signal<bool>,
+    error_message: RwSignal<Option<String>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let auth_store = use_context::<AuthStore>().expect("AuthStore not provided");
+
+    let exam_target = Memo::new(move |_| {
+        auth_store.user.with(|u: &Option<User>| {
+            u.as_ref()
+                .and_then(|u| u.srs_settings().exam_target().copied())
+        })
+    });
+    let readiness = Memo::new(move |_| {
+        auth_store.user.with(|u: &Option<User>| {
+            u.as_ref()
+                .and_then(|u| u.exam_readiness(get_jlpt_content()))
+        })
+    });
+
+    let selected_level = RwSignal::new(JapaneseLevel::N5);
+    let exam_date = RwSignal::new(String::new());
+    Effect::new(move |_| {
+        if let Some(target) = exam_target.get() {
+            selected_level.set(target.level());
+            exam_date.set(target.exam_date().to_string());
+        }
+    });
+
+    let on_set = Callback::new(move |_| match parse_exam_date(&exam_date.get_untracked()) {
+        Some(exam_date) => update_settings.run(SrsSettingsChange::ExamTarget {
+            level: selected_level.get_untracked(),
+            exam_date,
+        }),
+        None => error_message.set(Some(
+            td_string!(i18n.get_locale(), profile.srs_exam_invalid_date).to_string(),
+        )),
+    });
+
+    view! {
+        <div class="space-y-3" data-testid="srs-exam-target">
+            <div class="space-y-1">
+                <Text size=TextSize::Default>
+                    {t!(i18n, profile.srs_exam_title)}
+                </Text>
+                <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                    {t!(i18n, profile.srs_exam_desc)}
+                </Text>
+            </div>
+
+            <LevelSelector
+                levels=JapaneseLevel::ALL.to_vec()
+                selected_level=selected_level
+                on_select=Callback::new(move |level| selected_level.set(level))
+                test_id_prefix="srs-exam-level"
+            />
+            <Input
+                value=exam_date
+                input_type="date"
+                disabled=disabled
+                test_id="srs-exam-date"
+            />
+
+            <Show when=move || readiness.get().is_some()>
+                <ul class="space-y-1 text-sm text-[var(--fg-muted)]" data-testid="srs-exam-readiness">
+                    {move || {
+                        readiness
+                            .get()
+                            .map(|readiness| {
+                                readiness_lines(i18n, &readiness)
+                                    .into_iter()
+                                    .map(|line| view! { <li>{line}</li> })
+                                    .collect_view()
+                            })
+                    }}
+                </ul>
+            </Show>
+
+            <div class="flex flex-wrap gap-3">
+                <Button
+                    variant=ButtonVariant::Filled
+                    on_click=on_set
+                    disabled=disabled
+                    test_id="srs-exam-set-btn"
+                >
+                    {t!(i18n, profile.srs_exam_set)}
+                </Button>
+                <Show when=move || exam_target.get().is_some()>
+                    <Button
+                        variant=ButtonVariant::Ghost
+                        on_click=Callback::new(move |_| update_settings.run(SrsSettingsChange::ClearExamTarget))
+                        disabled=disabled
+                        test_id="srs-exam-clear-btn"
+                    >
+                        {t!(i18n, profile.srs_exam_clear)}
+                    </Button>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+/// Value of an `<input type="date">`.
+fn parse_exam_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()
+}
+
+fn readiness_lines(i18n: I18nContext<Locale>, readiness: &ExamReadiness) -> Vec<String> {
+    let mut lines = vec![
+        t_string!(i18n, profile.srs_exam_progress)
+            .replacen("{}", readiness.target().level().code(), 1)
+            .replacen("{}", &readiness.learned_items().to_string(), 1)
+            .replacen("{}", &readiness.target_items().to_string(), 1)
+            .replacen("{}", &readiness.days_left().to_string(), 1),
+        match readiness.current_pace() {
+            Some(pace) => t_string!(i18n, profile.srs_exam_pace)
+                .replacen("{}", &readiness.required_pace().to_string(), 1)
+                .replacen("{}", &format!("{pace:.1}"), 1),
+            None => t_string!(i18n, profile.srs_exam_pace_unknown).replacen(
+                "{}",
+                &readiness.required_pace().to_string(),
+                1,
+            ),
+        },
+    ];
+    if readiness.missing_items() > 0 {
+        lines.push(t_string!(i18n, profile.srs_exam_missing).replacen(
+            "{}",
+            &readiness.missing_items().to_string(),
+            1,
+        ));
+    }
+    lines.push(if readiness.is_reachable() {
+        t_string!(i18n, profile.srs_exam_reachable).to_string()
+    } else {
+        t_string!(i18n, profile.srs_exam_unreachable).to_string()
+    });
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_exam_date_reads_the_date_input_format() {
+        assert_eq!(
+            parse_exam_date("2027-07-04"),
+            NaiveDate::from_ymd_opt(2027, 7, 4)
+        );
+        assert_eq!(parse_exam_date(""), None);
+        assert_eq!(parse_exam_date("04.07.2027"), None);
+    }
+}
@@ -0,0 +1,194 @@
+use super::srs_settings_card::family_label;
+use crate::i18n::*;
+use crate::store::AuthStore;
+use crate::ui_components::{Button, ButtonVariant, Input, Text, TextSize, TypographyVariant};
+use leptos::prelude::*;
+use origa::domain::{ModeSchedule, RateMode, SrsSettings, User};
+use origa::use_cases::SrsSettingsChange;
+
+/// Desired retention and interval ceiling of each long-term review mode.
+#[component]
+pub fn SrsModeSchedules(
+    update_settings: Callback<SrsSettingsChange>,
+    #[prop(into)] disabled: Signal<bool>,
+    error_message: RwSignal<Option<String>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let auth_store = use_context::<AuthStore>().expect("AuthStore not provided");
+
+    let any_customized = Memo::new(move |_| {
+        auth_store.user.with(|u: &Option<User>| {
+            u.as_ref().is_some_and(|u| {
+                SrsSettings::configurable_modes()
+                    .iter()
+                    .any(|&mode| u.srs_settings().is_mode_schedule_customized(mode))
+            })
+        })
+    });
+
+    view! {
+        <div class="space-y-3" data-testid="srs-mode-schedules">
+            <div class="space-y-1">
+                <Text size=TextSize::Default>
+                    {t!(i18n, profile.srs_schedules_title)}
+                </Text>
+                <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                    {t!(i18n, profile.srs_schedules_desc)}
+                </Text>
+            </div>
+
+            {SrsSettings::configurable_modes()
+                .iter()
+                .map(|&mode| {
+                    view! {
+                        <ModeScheduleRow
+                            mode=mode
+                            update_settings=update_settings
+                            disabled=disabled
+                            error_message=error_message
+                        />
+                    }
+                })
+                .collect_view()}
+
+            <Button
+                variant=ButtonVariant::Ghost
+                on_click=Callback::new(move |_| update_settings.run(SrsSettingsChange::ResetModeSchedules))
+                disabled=Signal::derive(move || disabled.get() || !any_customized.get())
+                test_id="srs-reset-schedules-btn"
+            >
+                {t!(i18n, profile.srs_schedules_reset)}
+            </Button>
+        </div>
+    }
+}
+
+#[component]
+fn ModeScheduleRow(
+    mode: RateMode,
+    update_settings: Callback<SrsSettingsChange>,
+    disabled: Signal<bool>,
+    error_message: RwSignal<Option<String>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let auth_store = use_context::<AuthStore>().expect("AuthStore not provided");
+
+    let schedule = Memo::new(move |_| {
+        auth_store.user.with(|u: &Option<User>| {
+            u.as_ref()
+                .map(|u| u.srs_settings().mode_schedule(mode))
+                .unwrap_or_else(|| SrsSettings::default().mode_schedule(mode))
+        })
+    });
+
+    let retention = RwSignal::new(String::new());
+    let maximum_interval = RwSignal::new(String::new());
+    Effect::new(move |_| {
+        let schedule = schedule.get();
+        retention.set(format_retention(&schedule));
+        maximum_interval.set(schedule.maximum_interval().to_string());
+    });
+
+    let on_save = Callback::new(move |_| {
+        match parse_mode_schedule(
+            &retention.get_untracked(),
+            &maximum_interval.get_untracked(),
+        ) {
+            Some((request_retention, maximum_interval)) => {
+                update_settings.run(SrsSettingsChange::ModeSchedule {
+                    mode,
+                    request_retention,
+                    maximum_interval,
+                })
+            },
+            None => error_message.set(Some(
+                td_string!(i18n.get_locale(), profile.srs_schedules_invalid).to_string(),
+            )),
+        }
+    });
+
+    let test_id = format!("srs-schedule-{}", mode_test_id(mode));
+
+    view! {
+        <div class="flex flex-wrap items-end gap-3" data-testid=test_id.clone()>
+            <Text size=TextSize::Small class="w-24">
+                {family_label(i18n, mode)}
+            </Text>
+            <label class="space-y-1">
+                <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                    {t!(i18n, profile.srs_schedules_retention)}
+                </Text>
+                <Input
+                    value=retention
+                    input_type="number"
+                    disabled=disabled
+                    test_id=format!("{test_id}-retention")
+                />
+            </label>
+            <label class="space-y-1">
+                <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                    {t!(i18n, profile.srs_schedules_max_interval)}
+                </Text>
+                <Input
+                    value=maximum_interval
+                    input_type="number"
+                    disabled=disabled
+                    test_id=format!("{test_id}-max-interval")
+                />
+            </label>
+            <Button
+                variant=ButtonVariant::Default
+                on_click=on_save
+                disabled=disabled
+                test_id=format!("{test_id}-save")
+            >
+                {t!(i18n, profile.srs_schedules_save)}
+            </Button>
+        </div>
+    }
+}
+
+fn mode_test_id(mode: RateMode) -> &'static str {
+    match mode {
+        RateMode::StandardLesson => "words",
+        RateMode::PhraseReview => "phrases",
+        RateMode::GrammarReview => "grammar",
+        RateMode::KanjiReview => "kanji",
+        RateMode::ShortTerm => "short-term",
+        RateMode::OnboardingScoring => "onboarding",
+    }
+}
+
+/// Retention as the whole percent the input shows.
+fn format_retention(schedule: &ModeSchedule) -> String {
+    format!("{}", (schedule.request_retention() * 100.0).round())
+}
+
+/// Retention in percent and interval ceiling in days, as typed. Range checks
+/// are left to `ModeSchedule::new`, which reports them through the use case.
+fn parse_mode_schedule(retention_percent: &str, maximum_interval: &str) -> Option<(f64, i32)> {
+    let retention = retention_percent.trim().parse::<f64>().ok()?;
+    let maximum_interval = maximum_interval.trim().parse::<i32>().ok()?;
+    Some((retention / 100.0, maximum_interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mode_schedule_reads_percent_and_days() {
+        assert_eq!(parse_mode_schedule("90", " 365 "), Some((0.9, 365)));
+        assert_eq!(parse_mode_schedule("", "365"), None);
+        assert_eq!(parse_mode_schedule("90", "1.5"), None);
+    }
+
+    #[test]
+    fn format_retention_round_trips_through_parse() {
+        let schedule = ModeSchedule::new(0.85, 180).unwrap();
+
+        let (retention, _) = parse_mode_schedule(&format_retention(&schedule), "180").unwrap();
+
+        assert_eq!(retention, 0.85);
+    }
+}
@@ -0,0 +1,230 @@
+use super::srs_exam_target::SrsExamTarget;
+use super::srs_mode_schedules::SrsModeSchedules;
+use crate::i18n::*;
+use crate::store::AuthStore;
+use crate::ui_components::{
+    Alert, AlertType, Button, ButtonVariant, Checkbox, Text, TextSize, TypographyVariant,
+};
+use crate::utils::yield_to_browser;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_i18n::I18nContext;
+use origa::domain::{RateMode, SrsOptimizationReport, User};
+use origa::use_cases::{OptimizeSrsParametersUseCase, SrsSettingsChange, UpdateSrsSettingsUseCase};
+
+/// Review-log opt-in, per-mode schedules, exam target and per-user FSRS
+/// parameter fitting.
+#[component]
+pub fn SrsSettingsCard(#[prop(optional, into)] test_id: Signal<String>) -> impl IntoView {
+    let i18n = use_i18n();
+    let auth_store = use_context::<AuthStore>().expect("AuthStore not provided");
+
+    let review_log_enabled = Memo::new(move |_| {
+        auth_store.user.with(|u: &Option<User>| {
+            u.as_ref()
+                .is_some_and(|u| u.srs_settings().review_log_enabled())
+        })
+    });
+    let is_busy = RwSignal::new(false);
+    let reports = RwSignal::new(Vec::<SrsOptimizationReport>::new());
+    let error_message = RwSignal::<Option<String>>::new(None);
+    let success_message = RwSignal::<Option<String>>::new(None);
+    let disposed = StoredValue::new(());
+
+    let update_settings = {
+        let auth_store = auth_store.clone();
+        Callback::new(move |change: SrsSettingsChange| {
+            let auth_store = auth_store.clone();
+            error_message.set(None);
+            success_message.set(None);
+            is_busy.set(true);
+
+            spawn_local(async move {
+                let result = UpdateSrsSettingsUseCase::new(auth_store.repository())
+                    .execute(change)
+                    .await;
+                if disposed.is_disposed() {
+                    return;
+                }
+                match result {
+                    Ok(_) => {
+                        let _ = auth_store.refresh_user().await;
+                        match change {
+                            SrsSettingsChange::ResetOptimizedWeights => {
+                                reports.set(Vec::new());
+                                success_message.set(Some(
+                                    td_string!(i18n.get_locale(), profile.srs_reset_done)
+                                        .to_string(),
+                                ));
+                            },
+                            SrsSettingsChange::ResetModeSchedules => {
+                                success_message.set(Some(
+                                    td_string!(i18n.get_locale(), profile.srs_schedules_reset_done)
+                                        .to_string(),
+                                ));
+                            },
+                            SrsSettingsChange::ReviewLog { .. }
+                            | SrsSettingsChange::ModeSchedule { .. }
+                            | SrsSettingsChange::ExamTarget { .. }
+                            | SrsSettingsChange::ClearExamTarget => {},
+                        }
+                    },
+                    Err(e) => error_message.set(Some(format!(
+                        "{}: {}",
+                        td_string!(i18n.get_locale(), profile.srs_error),
+                        e
+                    ))),
+                }
+                is_busy.set(false);
+            });
+        })
+    };
+
+    let on_optimize = {
+        let auth_store = auth_store.clone();
+        Callback::new(move |_| {
+            let auth_store = auth_store.clone();
+            error_message.set(None);
+            success_message.set(None);
+            is_busy.set(true);
+
+            spawn_local(async move {
+                let result = OptimizeSrsParametersUseCase::new(auth_store.repository())
+                    .execute_with_yield(yield_to_browser)
+                    .await;
+                if disposed.is_disposed() {
+                    return;
+                }
+                match result {
+                    Ok(result) => {
+                        let _ = auth_store.refresh_user().await;
+                        reports.set(result);
+                    },
+                    Err(e) => error_message.set(Some(format!(
+                        "{}: {}",
+                        td_string!(i18n.get_locale(), profile.srs_error),
+                        e
+                    ))),
+                }
+                is_busy.set(false);
+            });
+        })
+    };
+
+    let test_id_val = move || {
+        let val = test_id.get();
+        if val.is_empty() { None } else { Some(val) }
+    };
+
+    view! {
+        <div data-testid=test_id_val class="p-6 space-y-4">
+            <div class="space-y-2">
+                <Text size=TextSize::Large>
+                    {t!(i18n, profile.srs_title)}
+                </Text>
+                <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                    {t!(i18n, profile.srs_desc)}
+                </Text>
+            </div>
+
+            <Checkbox
+                checked=Signal::derive(move || review_log_enabled.get())
+                disabled=Signal::derive(move || is_busy.get())
+                label=Signal::derive(move || td_string!(i18n.get_locale(), profile.srs_review_log).to_string())
+                on_change=Callback::new(move |_| {
+                    update_settings.run(SrsSettingsChange::ReviewLog {
+                        enabled: !review_log_enabled.get_untracked(),
+                    })
+                })
+                test_id="srs-review-log-checkbox"
+            />
+
+            <SrsModeSchedules
+                update_settings=update_settings
+                disabled=Signal::derive(move || is_busy.get())
+                error_message=error_message
+            />
+
+            <SrsExamTarget
+                update_settings=update_settings
+                disabled=Signal::derive(move || is_busy.get())
+                error_message=error_message
+            />
+
+            <Show when=move || !reports.get().is_empty()>
+                <ul class="space-y-1 text-sm text-[var(--fg-muted)]" data-testid="srs-optimization-reports">
+                    <For
+                        each=move || reports.get()
+                        key=|report| report.family()
+                        children=move |report| view! { <li>{report_text(i18n, &report)}</li> }
+                    />
+                </ul>
+            </Show>
+
+            <Show when=move || error_message.get().is_some()>
+                <Alert
+                    alert_type=Signal::from(AlertType::Error)
+                    message=Signal::derive(move || error_message.get().unwrap_or_default())
+                    test_id="srs-settings-error"
+                />
+            </Show>
+
+            <Show when=move || success_message.get().is_some()>
+                <Alert
+                    alert_type=Signal::from(AlertType::Success)
+                    message=Signal::derive(move || success_message.get().unwrap_or_default())
+                    test_id="srs-settings-success"
+                />
+            </Show>
+
+            <div class="flex flex-wrap gap-3">
+                <Button
+                    variant=ButtonVariant::Filled
+                    on_click=on_optimize
+                    disabled=Signal::derive(move || is_busy.get() || !review_log_enabled.get())
+                    test_id="srs-optimize-btn"
+                >
+                    {move || if is_busy.get() {
+                        t!(i18n, profile.srs_optimizing).into_any()
+                    } else {
+                        t!(i18n, profile.srs_optimize).into_any()
+                    }}
+                </Button>
+                <Button
+                    variant=ButtonVariant::Ghost
+                    on_click=Callback::new(move |_| update_settings.run(SrsSettingsChange::ResetOptimizedWeights))
+                    disabled=Signal::derive(move || is_busy.get())
+                    test_id="srs-reset-weights-btn"
+                >
+                    {t!(i18n, profile.srs_reset_weights)}
+                </Button>
+            </div>
+        </div>
+    }
+}
+
+/// Name of the scheduling family `mode` belongs to.
+pub(crate) fn family_label(i18n: I18nContext<Locale>, mode: RateMode) -> &'static str {
+    match mode {
+        RateMode::PhraseReview => t_string!(i18n, profile.srs_family_phrases),
+        RateMode::GrammarReview => t_string!(i18n, profile.srs_family_grammar),
+        RateMode::KanjiReview => t_string!(i18n, profile.srs_family_kanji),
+        RateMode::StandardLesson | RateMode::ShortTerm | RateMode::OnboardingScoring => {
+            t_string!(i18n, profile.srs_family_words)
+        },
+    }
+}
+
+fn report_text(i18n: I18nContext<Locale>, report: &SrsOptimizationReport) -> String {
+    let family = family_label(i18n, report.family());
+    let template = if report.fitted_loss().is_none() {
+        t_string!(i18n, profile.srs_report_skipped)
+    } else if report.applied() {
+        t_string!(i18n, profile.srs_report_applied)
+    } else {
+        t_string!(i18n, profile.srs_report_kept)
+    };
+    template
+        .replacen("{}", family, 1)
+        .replacen("{}", &report.review_count().to_string(), 1)
+}
@@ -198,7 +198,10 @@ impl ImportPreviewModalState {
             .collect();
         let words_to_create: Vec<WordToCreate> = selected_words
             .into_iter()
-            .map(|base_form| WordToCreate { base_form })
+            .map(|base_form| WordToCreate {
+                base_form,
+                context: None,
+            })
             .collect();
         let repository = self.repository.clone();
         let is_importing = self.is_importing;
@@ -1,12 +1,15 @@
 use crate::i18n::{t, use_i18n};
 use crate::pages::words::add_words_preview_modal_handlers::create_preview_modal_handlers;
 use crate::pages::words::add_words_preview_modal_state::{
-    AnalysisStage, InputMode, PreviewModalState, analysis_stage,
+    AnalysisStage, InputMode, PreviewModalState, TextSource, analysis_stage,
 };
 use crate::pages::words::analyzed_word_item::AnalyzedWordItem;
 use crate::pages::words::anki_import_stage::AnkiImportStage;
 use crate::pages::words::audio_input_stage::AudioInputStage;
+use crate::pages::words::book_import_stage::BookImportStage;
 use crate::pages::words::image_input_stage::ImageInputStage;
+use crate::pages::words::subtitle_import_stage::SubtitleImportStage;
+use crate::pages::words::yomitan_import_stage::YomitanImportStage;
 use crate::repository::HybridUserRepository;
 use crate::ui_components::{
     Alert, AlertType, Button, ButtonVariant, Drawer, Input, TabItem, Tabs, Text, TextSize,
@@ -17,7 +20,7 @@ use leptos::prelude::*;
 use leptos::task::spawn_local;
 use origa::domain::User;
 use origa::traits::UserRepository;
-use origa::use_cases::AnalyzedWord;
+use origa::use_cases::{AnalyzedWord, ImageText};
 
 #[component]
 pub fn AddWordsPreviewModal(
@@ -60,6 +63,7 @@ pub fn AddWordsPreviewModal(
     let is_creating = state.is_creating;
     let input_mode = state.input_mode;
     let active_tab = state.active_tab;
+    let text_source = state.text_source;
     let handlers = create_preview_modal_handlers(state.clone(), is_open);
 
     Effect::new({
@@ -88,10 +92,22 @@ pub fn AddWordsPreviewModal(
             id: "anki".to_string(),
             label: i18n.get_keys().words().tab_anki().inner().to_string(),
         });
+        items.push(TabItem {
+            id: "book".to_string(),
+            label: i18n.get_keys().words().tab_book().inner().to_string(),
+        });
+        items.push(TabItem {
+            id: "subtitles".to_string(),
+            label: i18n.get_keys().words().tab_subtitles().inner().to_string(),
+        });
         items.push(TabItem {
             id: "audio".to_string(),
             label: i18n.get_keys().words().tab_audio().inner().to_string(),
         });
+        items.push(TabItem {
+            id: "dictionary".to_string(),
+            label: i18n.get_keys().words().tab_dictionary().inner().to_string(),
+        });
         items
     });
 
@@ -103,18 +119,31 @@ pub fn AddWordsPreviewModal(
                 InputMode::Image
             } else if tab == "anki" {
                 InputMode::Anki
+            } else if tab == "book" {
+                InputMode::Book
+            } else if tab == "subtitles" {
+                InputMode::Subtitles
             } else if tab == "audio" {
                 InputMode::Audio
+            } else if tab == "dictionary" {
+                InputMode::Dictionary
             } else {
                 InputMode::Text
             });
         }
     });
 
-    let on_text_extracted = {
+    let on_image_text_extracted = {
+        let state = state.clone();
+        Callback::new(move |text: ImageText| {
+            state.set_extracted_text(text.text().to_string(), TextSource::Image(text));
+        })
+    };
+
+    let on_audio_text_extracted = {
         let state = state.clone();
         Callback::new(move |text: String| {
-            state.set_extracted_text(text);
+            state.set_extracted_text(text, TextSource::Audio);
         })
     };
 
@@ -157,6 +186,10 @@ pub fn AddWordsPreviewModal(
                                 analyzed_words=words
                                 selected_words=selected_words
                                 known_kanji=known_kanji.get()
+                                image_text=match text_source.get() {
+                                    Some(TextSource::Image(text)) => Some(text),
+                                    Some(TextSource::Audio) | None => None,
+                                }
                                 is_creating=is_creating
                                 on_word_toggle=handlers.on_word_toggle
                                 on_cancel=handlers.on_cancel
@@ -200,10 +233,29 @@ pub fn AddWordsPreviewModal(
                                                 />
                                             }.into_any()
                                         },
+                                        InputMode::Book => view! {
+                                            <BookImportStage
+                                                is_open=is_open
+                                                refresh_trigger=refresh_trigger
+                                                test_id=Signal::derive(|| "words-drawer-book".to_string())
+                                            />
+                                        }.into_any(),
+                                        InputMode::Subtitles => view! {
+                                            <SubtitleImportStage
+                                                is_open=is_open
+                                                refresh_trigger=refresh_trigger
+                                                test_id=Signal::derive(|| "words-drawer-subtitles".to_string())
+                                            />
+                                        }.into_any(),
+                                        InputMode::Dictionary => view! {
+                                            <YomitanImportStage
+                                                test_id=Signal::derive(|| "words-drawer-dictionary".to_string())
+                                            />
+                                        }.into_any(),
                                         InputMode::Image => view! {
                                             <ImageInputStage
                                                 is_open=is_open
-                                                on_text_extracted=on_text_extracted
+                                                on_text_extracted=on_image_text_extracted
                                                 on_error=on_ocr_error
                                                 on_switch_to_text=on_switch_to_text
                                             />
@@ -211,7 +263,7 @@ pub fn AddWordsPreviewModal(
                                         InputMode::Audio => view! {
                                             <AudioInputStage
                                                 is_open=Signal::derive(move || is_open.get())
-                                                on_text_extracted=on_text_extracted
+                                                on_text_extracted=on_audio_text_extracted
                                                 on_error=on_ocr_error
                                                 on_switch_to_text=on_switch_to_text
                                             />
@@ -232,6 +284,8 @@ fn PreviewStage(
     analyzed_words: Vec<AnalyzedWord>,
     selected_words: RwSignal<std::collections::HashSet<String>>,
     known_kanji: std::collections::HashSet<char>,
+    /// OCR text the words came from; its printed ruby drives their furigana.
+    image_text: Option<ImageText>,
     is_creating: RwSignal<bool>,
     on_word_toggle: Callback<String>,
     on_cancel: Callback<MouseEvent>,
@@ -257,11 +311,16 @@ fn PreviewStage(
                 key=|word| word.base_form.clone()
                 children=move |word| {
                     let base_form = word.base_form.clone();
+                    let reading_hints = image_text
+                        .as_ref()
+                        .map(|text| text.reading_hints_for(&word.base_form))
+                        .unwrap_or_default();
                     view! {
                         <AnalyzedWordItem
                             analyzed_word=word
                             selected_words=selected_words
                             known_kanji=known_kanji.clone()
+                            reading_hints=reading_hints
                             on_toggle=Callback::new(move |_| on_word_toggle.run(base_form.clone()))
                         />
                     }
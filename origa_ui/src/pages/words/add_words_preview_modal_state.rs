@@ -1,9 +1,10 @@
 use crate::repository::HybridUserRepository;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
+use origa::domain::{ExampleContext, ExampleMedium};
 use origa::use_cases::{
     AnalyzeTextForCardsUseCase, AnalyzedWord, CreateCardsFromAnalysisResult,
-    CreateCardsFromAnalysisUseCase, WordToCreate,
+    CreateCardsFromAnalysisUseCase, ImageText, WordToCreate,
 };
 use std::collections::HashSet;
 use tracing::{error, info};
@@ -13,10 +14,30 @@ pub enum InputMode {
     #[default]
     Text,
     Anki,
+    Book,
+    Subtitles,
+    Dictionary,
     Image,
     Audio,
 }
 
+/// Where the text being analyzed was extracted from. Decides the example
+/// context stored on each created card.
+#[derive(Clone, Debug)]
+pub enum TextSource {
+    Image(ImageText),
+    Audio,
+}
+
+impl TextSource {
+    fn example_context(&self, sentence: String) -> ExampleContext {
+        match self {
+            TextSource::Image(text) => text.example_context(sentence),
+            TextSource::Audio => ExampleContext::new(sentence).with_medium(ExampleMedium::Audio),
+        }
+    }
+}
+
 /// Pure view-stage decision extracted from the component for unit testing.
 /// Determines which content the add-words modal renders, without needing
 /// reactive signals or DOM.
@@ -53,6 +74,8 @@ pub struct PreviewModalState {
     pub input_mode: RwSignal<InputMode>,
     pub active_tab: RwSignal<String>,
     pub input_text: RwSignal<String>,
+    /// Image or recording the text was extracted from, if any.
+    pub text_source: RwSignal<Option<TextSource>>,
     pub analyzed_words: RwSignal<Vec<AnalyzedWord>>,
     pub selected_words: RwSignal<HashSet<String>>,
     pub is_analyzing: RwSignal<bool>,
@@ -88,6 +111,7 @@ impl PreviewModalState {
             input_mode: RwSignal::new(InputMode::Text),
             active_tab: RwSignal::new("text".to_string()),
             input_text: RwSignal::new(String::new()),
+            text_source: RwSignal::new(None),
             analyzed_words: RwSignal::new(Vec::new()),
             selected_words,
             is_analyzing: RwSignal::new(false),
@@ -146,8 +170,9 @@ impl PreviewModalState {
         });
     }
 
-    pub fn set_extracted_text(&self, text: String) {
+    pub fn set_extracted_text(&self, text: String, source: TextSource) {
         self.input_text.set(text);
+        self.text_source.set(Some(source));
         self.analyze_text();
     }
 
@@ -155,6 +180,7 @@ impl PreviewModalState {
         self.input_mode.set(InputMode::Text);
         self.active_tab.set("text".to_string());
         self.input_text.set(String::new());
+        self.text_source.set(None);
         self.analyzed_words.set(Vec::new());
         self.selected_words.set(HashSet::new());
         self.has_analyzed.set(false);
@@ -175,9 +201,21 @@ impl PreviewModalState {
         &self,
     ) -> impl Future<Output = Result<CreateCardsFromAnalysisResult, String>> {
         let selected_words = self.selected_words.get_untracked();
+        let text_source = self.text_source.get_untracked();
+        let analyzed_words = self.analyzed_words.get_untracked();
         let words_to_create: Vec<WordToCreate> = selected_words
             .into_iter()
-            .map(|base_form| WordToCreate { base_form })
+            .map(|base_form| {
+                let context = analyzed_words
+                    .iter()
+                    .find(|w| w.base_form == base_form)
+                    .and_then(|w| w.sentence.clone())
+                    .map(|sentence| match &text_source {
+                        Some(source) => source.example_context(sentence),
+                        None => ExampleContext::new(sentence),
+                    });
+                WordToCreate { base_form, context }
+            })
             .collect();
         let repository = self.repository.clone();
         let is_creating = self.is_creating;
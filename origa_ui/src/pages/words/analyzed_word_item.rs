@@ -5,6 +5,7 @@ use crate::ui_components::{
     TooltipPlacementMode, TypographyVariant,
 };
 use leptos::prelude::*;
+use origa::domain::ReadingHint;
 use origa::use_cases::AnalyzedWord;
 use std::collections::HashSet;
 
@@ -14,6 +15,7 @@ pub fn AnalyzedWordItem(
     selected_words: RwSignal<HashSet<String>>,
     known_kanji: HashSet<char>,
     on_toggle: Callback<()>,
+    #[prop(optional)] reading_hints: Vec<ReadingHint>,
 ) -> impl IntoView {
     let i18n = use_i18n();
     let base_form = analyzed_word.base_form.clone();
@@ -87,6 +89,7 @@ pub fn AnalyzedWordItem(
                         <FuriganaText
                             text=analyzed_word.base_form.clone()
                             known_kanji=known_kanji_stored.get_value()
+                            reading_hints=reading_hints
                         />
                     </div>
 
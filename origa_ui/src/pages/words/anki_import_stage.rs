@@ -8,9 +8,10 @@ use crate::utils::file::read_file_as_bytes;
 use crate::utils::use_drag_and_drop;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
+use origa::domain::WordImportOutcome;
 use origa::use_cases::{
-    AnkiCard, AnkiFieldInfo, ImportAnkiPackUseCase, extract_anki_db_bytes, extract_cards,
-    read_anki_database,
+    AnkiCard, AnkiFieldInfo, AnkiFieldMapping, AnkiImportPreview, ImportAnkiPackUseCase,
+    extract_anki_db_bytes, extract_cards_with_mapping, read_anki_database,
 };
 use std::sync::Arc;
 use wasm_bindgen::JsCast;
@@ -44,7 +45,11 @@ pub fn AnkiImportStage(
     let file_bytes = StoredValue::new(Vec::<u8>::new());
     let selected_word_field = RwSignal::new(String::new());
     let selected_translation_field = RwSignal::new(String::new());
+    let selected_reading_field = RwSignal::new(String::new());
+    let selected_example_field = RwSignal::new(String::new());
+    let selected_kanji_field = RwSignal::new(String::new());
     let extracted_cards = RwSignal::new(Vec::<AnkiCard>::new());
+    let previews = RwSignal::new(Vec::<AnkiImportPreview>::new());
     let imported_count = RwSignal::new(0usize);
     let disposed = StoredValue::new(());
     let on_drop_file: Arc<dyn Fn(web_sys::File) + Send + Sync> =
@@ -151,7 +156,11 @@ pub fn AnkiImportStage(
             file_bytes.set_value(Vec::new());
             selected_word_field.set(String::new());
             selected_translation_field.set(String::new());
+            selected_reading_field.set(String::new());
+            selected_example_field.set(String::new());
+            selected_kanji_field.set(String::new());
             extracted_cards.set(Vec::new());
+            previews.set(Vec::new());
             imported_count.set(0);
         }
     });
@@ -185,61 +194,92 @@ pub fn AnkiImportStage(
             .collect::<Vec<_>>()
     });
 
-    let on_next = Callback::new(move |_: leptos::ev::MouseEvent| {
-        let word_field = selected_word_field.get();
-        if word_field.is_empty() {
-            return;
-        }
-        let trans_field = selected_translation_field.get();
-        let trans_opt = if trans_field.is_empty() {
-            None
-        } else {
-            Some(trans_field)
-        };
-        let bytes = file_bytes.get_value();
-
-        stage.set(Stage::Loading);
-        let i18n = i18n;
-
-        spawn_local(async move {
-            gloo_timers::future::sleep(std::time::Duration::from_millis(50)).await;
+    let optional_field = |field: RwSignal<String>| {
+        let name = field.get();
+        if name.is_empty() { None } else { Some(name) }
+    };
 
-            if disposed.is_disposed() {
+    let on_next = {
+        let repository = repository.clone();
+        Callback::new(move |_: leptos::ev::MouseEvent| {
+            let word_field = selected_word_field.get();
+            if word_field.is_empty() {
                 return;
             }
+            let mapping = AnkiFieldMapping {
+                translation: optional_field(selected_translation_field),
+                reading: optional_field(selected_reading_field),
+                example_sentence: optional_field(selected_example_field),
+                kanji: optional_field(selected_kanji_field),
+                ..AnkiFieldMapping::new(word_field)
+            };
+            let bytes = file_bytes.get_value();
+
+            stage.set(Stage::Loading);
+            let i18n = i18n;
+            let repo = repository.clone();
 
-            match extract_cards(&bytes, &word_field, trans_opt.as_deref()) {
-                Ok((cards, _)) => {
-                    if cards.is_empty() {
+            spawn_local(async move {
+                gloo_timers::future::sleep(std::time::Duration::from_millis(50)).await;
+
+                if disposed.is_disposed() {
+                    return;
+                }
+
+                match extract_cards_with_mapping(&bytes, &mapping) {
+                    Ok((cards, _)) => {
+                        if cards.is_empty() {
+                            stage.set(Stage::Error);
+                            error_message.set(
+                                i18n.get_keys()
+                                    .words()
+                                    .anki_import()
+                                    .cards_not_found()
+                                    .inner()
+                                    .to_string(),
+                            );
+                            return;
+                        }
+                        let preview = ImportAnkiPackUseCase::new(&repo).preview(&cards).await;
+                        if disposed.is_disposed() {
+                            return;
+                        }
+                        match preview {
+                            Ok(preview) => {
+                                previews.set(preview);
+                                extracted_cards.set(cards);
+                                stage.set(Stage::Preview);
+                            },
+                            Err(e) => {
+                                stage.set(Stage::Error);
+                                error_message.set(
+                                    i18n.get_keys()
+                                        .words()
+                                        .anki_import()
+                                        .extraction_error()
+                                        .inner()
+                                        .to_string()
+                                        .replacen("{}", &e.to_string(), 1),
+                                );
+                            },
+                        }
+                    },
+                    Err(e) => {
                         stage.set(Stage::Error);
                         error_message.set(
                             i18n.get_keys()
                                 .words()
                                 .anki_import()
-                                .cards_not_found()
+                                .extraction_error()
                                 .inner()
-                                .to_string(),
+                                .to_string()
+                                .replacen("{}", &e.to_string(), 1),
                         );
-                        return;
-                    }
-                    extracted_cards.set(cards);
-                    stage.set(Stage::Preview);
-                },
-                Err(e) => {
-                    stage.set(Stage::Error);
-                    error_message.set(
-                        i18n.get_keys()
-                            .words()
-                            .anki_import()
-                            .extraction_error()
-                            .inner()
-                            .to_string()
-                            .replacen("{}", &e.to_string(), 1),
-                    );
-                },
-            }
-        });
-    });
+                    },
+                }
+            });
+        })
+    };
 
     let on_back = Callback::new(move |_: leptos::ev::MouseEvent| {
         stage.set(Stage::FieldSelect);
@@ -398,6 +438,51 @@ pub fn AnkiImportStage(
                                 test_id=Signal::derive(|| "anki-import-field-translation".to_string())
                             />
                         </div>
+                        <div>
+                            <Text
+                                size=TextSize::Small
+                                variant=TypographyVariant::Muted
+                                class=Signal::derive(|| "mb-2".to_string())
+                            >
+                                {t!(i18n, words.anki_import.reading_field)}
+                            </Text>
+                            <Dropdown
+                                options=field_options
+                                selected=selected_reading_field
+                                placeholder=Signal::derive(move || i18n.get_keys().words().anki_import().select_field().inner().to_string())
+                                test_id=Signal::derive(|| "anki-import-field-reading".to_string())
+                            />
+                        </div>
+                        <div>
+                            <Text
+                                size=TextSize::Small
+                                variant=TypographyVariant::Muted
+                                class=Signal::derive(|| "mb-2".to_string())
+                            >
+                                {t!(i18n, words.anki_import.example_field)}
+                            </Text>
+                            <Dropdown
+                                options=field_options
+                                selected=selected_example_field
+                                placeholder=Signal::derive(move || i18n.get_keys().words().anki_import().select_field().inner().to_string())
+                                test_id=Signal::derive(|| "anki-import-field-example".to_string())
+                            />
+                        </div>
+                        <div>
+                            <Text
+                                size=TextSize::Small
+                                variant=TypographyVariant::Muted
+                                class=Signal::derive(|| "mb-2".to_string())
+                            >
+                                {t!(i18n, words.anki_import.kanji_field)}
+                            </Text>
+                            <Dropdown
+                                options=field_options
+                                selected=selected_kanji_field
+                                placeholder=Signal::derive(move || i18n.get_keys().words().anki_import().select_field().inner().to_string())
+                                test_id=Signal::derive(|| "anki-import-field-kanji".to_string())
+                            />
+                        </div>
                         <div class="flex justify-end">
                             <Button
                                 variant=ButtonVariant::Olive
@@ -418,22 +503,76 @@ pub fn AnkiImportStage(
                             <Text size=TextSize::Small variant=TypographyVariant::Muted>
                                 {i18n.get_keys().words().anki_import().found_cards().inner().to_string().replacen("{}", &extracted_cards.get().len().to_string(), 1)}
                             </Text>
+                            <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                                {move || {
+                                    let previews = previews.get();
+                                    let new_words = previews
+                                        .iter()
+                                        .filter(|p| p.word.outcome == WordImportOutcome::New)
+                                        .count();
+                                    let scheduled = previews
+                                        .iter()
+                                        .filter(|p| {
+                                            p.word.outcome == WordImportOutcome::New
+                                                && p.schedule.is_some()
+                                        })
+                                        .count();
+                                    let new_kanji: usize =
+                                        previews.iter().map(|p| p.new_kanji.len()).sum();
+                                    i18n.get_keys()
+                                        .words()
+                                        .anki_import()
+                                        .preview_summary()
+                                        .inner()
+                                        .to_string()
+                                        .replacen("{}", &new_words.to_string(), 1)
+                                        .replacen("{}", &scheduled.to_string(), 1)
+                                        .replacen("{}", &new_kanji.to_string(), 1)
+                                }}
+                            </Text>
                         </div>
                         <div
                             class="space-y-2 overflow-y-auto max-h-64"
                             data-testid="anki-import-card-list"
                         >
                             <For
-                                each=move || extracted_cards.get().clone()
-                                key=|card| card.word.clone()
-                                children=move |card| {
-                                    let translation =
-                                        card.translation.clone().unwrap_or_default();
+                                each=move || extracted_cards.get().into_iter().zip(previews.get())
+                                key=|(card, _)| card.word.clone()
+                                children=move |(card, preview)| {
+                                    let translation = card
+                                        .translation
+                                        .clone()
+                                        .or(preview.word.meaning.clone())
+                                        .unwrap_or_default();
+                                    let is_new = preview.word.outcome == WordImportOutcome::New;
+                                    let row_class = if is_new {
+                                        "flex justify-between items-center p-2 rounded bg-[var(--bg-secondary)]"
+                                    } else {
+                                        "flex justify-between items-center p-2 rounded bg-[var(--bg-secondary)] opacity-50"
+                                    };
+                                    let due = preview.schedule.as_ref().map(|schedule| {
+                                        i18n.get_keys()
+                                            .words()
+                                            .anki_import()
+                                            .next_review()
+                                            .inner()
+                                            .to_string()
+                                            .replacen(
+                                                "{}",
+                                                &schedule
+                                                    .memory_state
+                                                    .next_review_date()
+                                                    .format("%Y-%m-%d")
+                                                    .to_string(),
+                                                1,
+                                            )
+                                    });
                                     view! {
-                                        <div class="flex justify-between items-center p-2 rounded bg-[var(--bg-secondary)]">
+                                        <div class=row_class>
                                             <span class="font-medium">{card.word}</span>
                                             <span class="text-sm text-[var(--fg-muted)]">
                                                 {translation}
+                                                {due.map(|due| view! { <span class="ml-2">{due}</span> })}
                                             </span>
                                         </div>
                                     }
@@ -19,6 +19,8 @@ use crate::ui_components::{Alert, AlertType, Button, ButtonVariant};
 
 use super::audio_live_recorder::AudioLiveRecorder;
 #[cfg(target_arch = "wasm32")]
+use crate::utils::audio_decode::decode_with_browser;
+#[cfg(target_arch = "wasm32")]
 use crate::utils::file::read_file_as_bytes;
 #[cfg(target_arch = "wasm32")]
 use base64::{Engine, engine::general_purpose::STANDARD};
@@ -123,6 +125,19 @@ async fn transcribe_via_wasm(
         return Ok(text);
     }
 
+    // Opus (WebM, Ogg) has no Rust decoder here; the browser decodes it.
+    let samples = match origa::stt::load_audio_bytes(&bytes) {
+        Ok(samples) => samples,
+        Err(reason) => {
+            info!(reason, "Falling back to the browser audio decoder");
+            decode_with_browser(&bytes).await.map_err(|e| {
+                audio_state.set(AudioState::Error);
+                error_message.set(Some(e.clone()));
+                e
+            })?
+        },
+    };
+
     let model = get_or_load_whisper_model(i18n, status_text)
         .await
         .map_err(|e| {
@@ -148,13 +163,14 @@ async fn transcribe_via_wasm(
     let use_case = origa::use_cases::TranscribeAudioUseCase::new();
     let infer_start = web_sys::js_sys::Date::now();
     let result = use_case
-        .execute(model.clone(), &bytes)
+        .execute(model.clone(), &samples)
         .await
+        .map(|transcription| transcription.text())
         .map_err(|e| format!("Transcription failed: {:?}", e));
     let infer_ms = web_sys::js_sys::Date::now() - infer_start;
     info!(
         infer_ms,
-        bytes_len = bytes.len(),
+        samples_len = samples.len(),
         "Whisper inference timing"
     );
     result
@@ -218,12 +234,12 @@ pub(super) fn AudioInputStage(
 
     let handle_file = move |file: web_sys::File| {
         let name = file.name();
-        let is_wav = name.ends_with(".wav");
-        let valid_ext = is_wav
-            || name.ends_with(".mp3")
-            || name.ends_with(".webm")
-            || name.ends_with(".m4a")
-            || name.ends_with(".ogg");
+        let lower_name = name.to_lowercase();
+        let valid_ext = [
+            ".wav", ".mp3", ".m4a", ".aac", ".flac", ".ogg", ".opus", ".webm",
+        ]
+        .iter()
+        .any(|ext| lower_name.ends_with(ext));
 
         if !valid_ext {
             error_message.set(Some(
@@ -237,19 +253,6 @@ pub(super) fn AudioInputStage(
             return;
         }
 
-        #[cfg(target_arch = "wasm32")]
-        if !is_wav {
-            error_message.set(Some(
-                i18n.get_keys()
-                    .words()
-                    .audio()
-                    .wav_only()
-                    .inner()
-                    .to_string(),
-            ));
-            return;
-        }
-
         let max_size_mb = 50.0;
         if file.size() / (1024.0 * 1024.0) > max_size_mb {
             error_message.set(Some(
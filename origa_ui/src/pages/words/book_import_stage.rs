@@ -0,0 +1,290 @@
+use crate::i18n::{t, use_i18n};
+use crate::repository::HybridUserRepository;
+use crate::ui_components::{
+    Alert, AlertType, Button, ButtonVariant, Spinner, Text, TextSize, TypographyVariant,
+};
+use crate::utils::file::read_file_as_bytes;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use origa::domain::{Book, OrigaError};
+use origa::use_cases::{
+    AnalyzeBookUseCase, BookAnalysisResult, ChapterAnalysis, CreateCardsFromAnalysisUseCase,
+};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+
+/// N+1 sentences kept per chapter as example context for mined words.
+const SENTENCES_PER_CHAPTER: usize = 20;
+
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+enum Stage {
+    #[default]
+    Idle,
+    Analyzing,
+    Chapters,
+    Creating,
+    Done,
+    Error,
+}
+
+/// Scores an `.epub` or `.txt` book chapter by chapter and mines the unknown
+/// words of a chosen chapter into cards.
+#[component]
+pub fn BookImportStage(
+    is_open: RwSignal<bool>,
+    refresh_trigger: RwSignal<u32>,
+    #[prop(optional, into)] test_id: Signal<String>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let repository =
+        use_context::<HybridUserRepository>().expect("repository context not provided");
+
+    let stage = RwSignal::new(Stage::Idle);
+    let error_message = RwSignal::new(String::new());
+    let analysis = RwSignal::new(None::<BookAnalysisResult>);
+    let created_count = RwSignal::new(0usize);
+    let disposed = StoredValue::new(());
+    let test_id_val = move || {
+        let val = test_id.get();
+        if val.is_empty() { None } else { Some(val) }
+    };
+
+    Effect::new(move |_| {
+        if !is_open.get() {
+            stage.set(Stage::Idle);
+            error_message.set(String::new());
+            analysis.set(None);
+            created_count.set(0);
+        }
+    });
+
+    let on_file_change = {
+        let repository = repository.clone();
+        move |ev: leptos::ev::Event| {
+            let Some(file) = ev
+                .target()
+                .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|input| input.files())
+                .and_then(|files| files.get(0))
+            else {
+                return;
+            };
+            stage.set(Stage::Analyzing);
+            let repo = repository.clone();
+
+            spawn_local(async move {
+                let result = analyze_file(&repo, &file).await;
+                if disposed.is_disposed() {
+                    return;
+                }
+                match result {
+                    Ok(result) => {
+                        analysis.set(Some(result));
+                        stage.set(Stage::Chapters);
+                    },
+                    Err(e) => {
+                        error_message.set(
+                            i18n.get_keys()
+                                .words()
+                                .book_import()
+                                .analysis_error()
+                                .inner()
+                                .to_string()
+                                .replacen("{}", &e.to_string(), 1),
+                        );
+                        stage.set(Stage::Error);
+                    },
+                }
+            });
+        }
+    };
+
+    let on_mine_chapter = {
+        let repository = repository.clone();
+        Callback::new(move |chapter: ChapterAnalysis| {
+            let Some(title) = analysis.with_untracked(|a| a.as_ref().map(|a| a.title.clone()))
+            else {
+                return;
+            };
+            stage.set(Stage::Creating);
+            let repo = repository.clone();
+
+            spawn_local(async move {
+                let result = CreateCardsFromAnalysisUseCase::new(&repo)
+                    .execute(chapter.words_to_create(&title), None)
+                    .await;
+                if disposed.is_disposed() {
+                    return;
+                }
+                match result {
+                    Ok(result) => {
+                        created_count.set(result.created_cards.len());
+                        stage.set(Stage::Done);
+                        refresh_trigger.update(|v| *v += 1);
+                    },
+                    Err(e) => {
+                        error_message.set(
+                            i18n.get_keys()
+                                .words()
+                                .book_import()
+                                .create_error()
+                                .inner()
+                                .to_string()
+                                .replacen("{}", &e.to_string(), 1),
+                        );
+                        stage.set(Stage::Error);
+                    },
+                }
+            });
+        })
+    };
+
+    view! {
+        <div class="space-y-4" data-testid=test_id_val>
+            {move || match stage.get() {
+                Stage::Idle => view! {
+                    <label class="block border-2 border-dashed border-[var(--border-dark)] hover:border-[var(--accent-olive)]/50 p-8 text-center cursor-pointer">
+                        <input
+                            type="file"
+                            accept=".epub,.txt,application/epub+zip,text/plain"
+                            class="hidden"
+                            on:change=on_file_change.clone()
+                            data-testid="book-import-file-input"
+                        />
+                        <Text variant=TypographyVariant::Muted>
+                            {t!(i18n, words.book_import.drop_zone)}
+                        </Text>
+                        <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                            {t!(i18n, words.book_import.file_type)}
+                        </Text>
+                    </label>
+                }
+                .into_any(),
+
+                Stage::Analyzing | Stage::Creating => view! {
+                    <div class="flex flex-col items-center gap-3 py-8" data-testid="book-import-loading">
+                        <Spinner />
+                        <Text variant=TypographyVariant::Muted>{t!(i18n, common.processing)}</Text>
+                    </div>
+                }
+                .into_any(),
+
+                Stage::Chapters => {
+                    let Some(result) = analysis.get() else {
+                        return ().into_any();
+                    };
+                    let summary = i18n
+                        .get_keys()
+                        .words()
+                        .book_import()
+                        .book_summary()
+                        .inner()
+                        .to_string()
+                        .replacen("{}", &result.title, 1)
+                        .replacen("{}", &format!("{:.0}", result.comprehension_percentage), 1)
+                        .replacen("{}", &result.unknown_word_count.to_string(), 1);
+                    view! {
+                        <div class="space-y-4">
+                            <Text size=TextSize::Small test_id="book-import-summary">
+                                {summary}
+                            </Text>
+                            <div
+                                class="space-y-2 overflow-y-auto max-h-80"
+                                data-testid="book-import-chapter-list"
+                            >
+                                <For
+                                    each=move || result.chapters.clone()
+                                    key=|chapter| chapter.index
+                                    children=move |chapter| {
+                                        let details = i18n
+                                            .get_keys_untracked()
+                                            .words()
+                                            .book_import()
+                                            .chapter_summary()
+                                            .inner()
+                                            .to_string()
+                                            .replacen("{}", &format!("{:.0}", chapter.comprehension_percentage), 1)
+                                            .replacen("{}", &chapter.unknown_word_count.to_string(), 1);
+                                        let has_unknown = chapter.unknown_word_count > 0;
+                                        let title = chapter.title.clone();
+                                        view! {
+                                            <div class="flex justify-between items-center gap-2 p-2 rounded bg-[var(--bg-secondary)]">
+                                                <div class="min-w-0">
+                                                    <div class="font-medium truncate">{title}</div>
+                                                    <div class="text-sm text-[var(--fg-muted)]">{details}</div>
+                                                </div>
+                                                <Button
+                                                    variant=ButtonVariant::Olive
+                                                    disabled=Signal::derive(move || !has_unknown)
+                                                    on_click=Callback::new(move |_| on_mine_chapter.run(chapter.clone()))
+                                                    test_id="book-import-mine-btn"
+                                                >
+                                                    {t!(i18n, words.book_import.mine_chapter)}
+                                                </Button>
+                                            </div>
+                                        }
+                                    }
+                                />
+                            </div>
+                        </div>
+                    }
+                    .into_any()
+                },
+
+                Stage::Done => view! {
+                    <div class="space-y-4" data-testid="book-import-done">
+                        <Alert
+                            alert_type=AlertType::Success
+                            title=Signal::derive(move || i18n.get_keys().words().book_import().cards_created().inner().to_string())
+                            message=Signal::derive(move || {
+                                i18n.get_keys().words().anki_import().imported_cards().inner().to_string()
+                                    .replacen("{}", &created_count.get().to_string(), 1)
+                            })
+                        />
+                        <Button
+                            variant=ButtonVariant::Ghost
+                            on_click=Callback::new(move |_| stage.set(Stage::Chapters))
+                            test_id="book-import-back-btn"
+                        >
+                            {t!(i18n, common.back)}
+                        </Button>
+                    </div>
+                }
+                .into_any(),
+
+                Stage::Error => view! {
+                    <div class="space-y-4" data-testid="book-import-error">
+                        <Alert
+                            alert_type=AlertType::Error
+                            title=Signal::derive(move || i18n.get_keys().common().error().inner().to_string())
+                            message=Signal::derive(move || error_message.get())
+                        />
+                        <Button
+                            variant=ButtonVariant::Ghost
+                            on_click=Callback::new(move |_| stage.set(Stage::Idle))
+                            test_id="book-import-retry-btn"
+                        >
+                            {t!(i18n, words.anki_import.try_again)}
+                        </Button>
+                    </div>
+                }
+                .into_any(),
+            }}
+        </div>
+    }
+}
+
+async fn analyze_file(
+    repository: &HybridUserRepository,
+    file: &web_sys::File,
+) -> Result<BookAnalysisResult, OrigaError> {
+    let bytes = read_file_as_bytes(file)
+        .await
+        .map_err(|e| OrigaError::BookParseError {
+            reason: e.to_string(),
+        })?;
+    let book = Book::from_file(&file.name(), &bytes)?;
+    AnalyzeBookUseCase::new(repository)
+        .execute(&book, SENTENCES_PER_CHAPTER)
+        .await
+}
@@ -6,6 +6,7 @@ use crate::ui_components::{
 };
 use crate::utils::use_drag_and_drop;
 use leptos::prelude::*;
+use origa::use_cases::ImageText;
 use std::sync::Arc;
 use wasm_bindgen::JsCast;
 use web_sys::js_sys::Function;
@@ -36,7 +37,7 @@ fn stage_item_view(
 pub fn ImageInputStage(
     #[prop(optional, into)] class: Signal<String>,
     is_open: RwSignal<bool>,
-    on_text_extracted: Callback<String>,
+    on_text_extracted: Callback<ImageText>,
     on_error: Callback<String>,
     on_switch_to_text: Callback<()>,
 ) -> impl IntoView {
@@ -7,15 +7,18 @@ mod anki_import_stage;
 mod asr_provider;
 mod audio_input_stage;
 mod audio_live_recorder;
+mod book_import_stage;
 mod content;
 mod header;
 mod image_input_stage;
 mod ocr_device_ai;
 mod ocr_file_utils;
 mod ocr_processing;
+mod subtitle_import_stage;
 pub(crate) mod vocabulary_card_item;
 #[cfg(all(target_arch = "wasm32", test))]
 mod words_wasm_tests;
+mod yomitan_import_stage;
 
 pub use content::WordsContent;
 pub use header::WordsHeader;
@@ -1,7 +1,7 @@
 use crate::ui_components::{OcrLoadingStage, OcrLoadingState};
 use leptos::prelude::Set;
 use origa::ocr::JapaneseOCRModel;
-use origa::use_cases::ExtractTextFromImageUseCase;
+use origa::use_cases::{ExtractTextFromImageUseCase, ImageText};
 use std::cell::RefCell;
 use std::rc::Rc;
 use wasm_bindgen::JsCast;
@@ -107,6 +107,7 @@ pub(crate) fn calculate_speed_and_eta(
 
 pub(super) async fn init_ocr_model(
     model_files: origa::ocr::ModelFiles,
+    preprocessing: origa::ocr::PreprocessingConfig,
     loading_state: &OcrLoadingState,
 ) -> Result<JapaneseOCRModel, String> {
     #[cfg(target_arch = "wasm32")]
@@ -120,16 +121,19 @@ pub(super) async fn init_ocr_model(
                 });
                 format!("Failed to initialize OCR model: {:?}", e)
             })
+            .map(|model| model.with_preprocessing(preprocessing))
     }
     #[cfg(not(target_arch = "wasm32"))]
     {
-        JapaneseOCRModel::from_model_files(model_files).map_err(|e| {
-            loading_state.stage.set(OcrLoadingStage::Error {
-                stage: "init".to_string(),
-                message: format!("Failed to initialize OCR model: {:?}", e),
-            });
-            format!("Failed to initialize OCR model: {:?}", e)
-        })
+        JapaneseOCRModel::from_model_files(model_files)
+            .map_err(|e| {
+                loading_state.stage.set(OcrLoadingStage::Error {
+                    stage: "init".to_string(),
+                    message: format!("Failed to initialize OCR model: {:?}", e),
+                });
+                format!("Failed to initialize OCR model: {:?}", e)
+            })
+            .map(|model| model.with_preprocessing(preprocessing))
     }
 }
 
@@ -137,18 +141,19 @@ pub(super) async fn execute_ocr(
     use_case: &ExtractTextFromImageUseCase,
     model: Rc<JapaneseOCRModel>,
     bytes: &[u8],
-) -> Result<String, String> {
+    image_name: Option<&str>,
+) -> Result<ImageText, String> {
     #[cfg(target_arch = "wasm32")]
     {
         use_case
-            .execute(model.clone(), bytes)
+            .execute(model.clone(), bytes, image_name)
             .await
             .map_err(|e| format!("OCR failed: {:?}", e))
     }
     #[cfg(not(target_arch = "wasm32"))]
     {
         use_case
-            .execute(model.clone(), bytes)
+            .execute(model.clone(), bytes, image_name)
             .map_err(|e| format!("OCR failed: {:?}", e))
     }
 }
@@ -8,7 +8,7 @@ use crate::ui_components::{OcrLoadingStage, OcrLoadingState, ProgressInfo};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use origa::ocr::{JapaneseOCRModel, ModelConfig};
-use origa::use_cases::ExtractTextFromImageUseCase;
+use origa::use_cases::{ExtractTextFromImageUseCase, ImageText};
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use tracing::{debug, error, info};
@@ -42,13 +42,13 @@ pub(super) struct ProcessContext {
 
 pub(super) fn handle_ocr_result(
     i18n: &leptos_i18n::I18nContext<crate::i18n::Locale>,
-    result: Result<String, String>,
+    result: Result<ImageText, String>,
     ctx: &ProcessContext,
-    on_text_extracted: &Callback<String>,
+    on_text_extracted: &Callback<ImageText>,
 ) {
     match result {
         Ok(text) => {
-            if text.trim().is_empty() {
+            if text.text().trim().is_empty() {
                 let err = i18n
                     .get_keys()
                     .words()
@@ -63,7 +63,10 @@ pub(super) fn handle_ocr_result(
                     message: err.to_string(),
                 });
             } else {
-                info!(text_length = text.len(), "OCR completed successfully");
+                info!(
+                    text_length = text.text().len(),
+                    "OCR completed successfully"
+                );
                 ctx.ocr_state.set(OcrState::Ready);
                 ctx.ocr_loading_state.stage.set(OcrLoadingStage::Completed);
                 on_text_extracted.run(text);
@@ -84,8 +87,9 @@ pub(super) fn handle_ocr_result(
 async fn run_ocr_on_data_url(
     i18n: leptos_i18n::I18nContext<crate::i18n::Locale>,
     data_url: &str,
+    image_name: Option<&str>,
     ctx: &ProcessContext,
-    on_text_extracted: &Callback<String>,
+    on_text_extracted: &Callback<ImageText>,
 ) {
     ctx.ocr_state.set(OcrState::Processing);
     ctx.ocr_loading_state.start_time.set(Some(Date::now()));
@@ -96,7 +100,7 @@ async fn run_ocr_on_data_url(
     // never sees the loading stepper.
     crate::utils::yield_to_browser().await;
 
-    let result = process_image_with_ocr(data_url, &ctx.ocr_loading_state, &i18n).await;
+    let result = process_image_with_ocr(data_url, image_name, &ctx.ocr_loading_state, &i18n).await;
 
     if ctx.ocr_loading_state.cancel_requested.get_untracked() {
         return;
@@ -109,7 +113,7 @@ pub(super) fn process_file(
     i18n: leptos_i18n::I18nContext<crate::i18n::Locale>,
     file: File,
     ctx: ProcessContext,
-    on_text_extracted: Callback<String>,
+    on_text_extracted: Callback<ImageText>,
     on_error: Callback<String>,
 ) {
     let file_name = file.name();
@@ -153,7 +157,10 @@ pub(super) fn process_file(
                     return;
                 }
                 ctx.image_preview.set(Some(data_url.clone()));
-                run_ocr_on_data_url(i18n, &data_url, &ctx, &on_text_extracted).await;
+                // Some clipboard sources hand over an unnamed file; the card
+                // then only records that the sentence was read from an image.
+                let image_name = Some(file_name.as_str()).filter(|name| !name.is_empty());
+                run_ocr_on_data_url(i18n, &data_url, image_name, &ctx, &on_text_extracted).await;
             },
             Err(e) => {
                 error!(error = %e, "Failed to read file");
@@ -171,9 +178,10 @@ pub(super) fn process_file(
 
 async fn process_image_with_ocr(
     data_url: &str,
+    image_name: Option<&str>,
     loading_state: &OcrLoadingState,
     i18n: &leptos_i18n::I18nContext<crate::i18n::Locale>,
-) -> Result<String, String> {
+) -> Result<ImageText, String> {
     let base64_data = data_url
         .strip_prefix("data:image/")
         .and_then(|s| s.split_once(";base64,"))
@@ -185,7 +193,7 @@ async fn process_image_with_ocr(
     if let Some(text) =
         super::ocr_device_ai::recognize_via_device_ai(base64_data, loading_state).await
     {
-        return Ok(text);
+        return Ok(ImageText::new(text, image_name.map(str::to_string)));
     }
 
     let bytes = base64_decode(base64_data)?;
@@ -265,6 +273,7 @@ async fn process_image_with_ocr(
                         }
                     });
 
+                let preprocessing = config.preprocessing.clone();
                 let loader = ModelLoader::new(config).with_progress_callback(progress_callback);
 
                 loading_state.stage.set(OcrLoadingStage::DownloadingDeim {
@@ -293,7 +302,7 @@ async fn process_image_with_ocr(
                     model_name: "OCR models".to_string(),
                 });
 
-                let new_model = init_ocr_model(model_files, loading_state)
+                let new_model = init_ocr_model(model_files, preprocessing, loading_state)
                     .await
                     .map_err(|e| e.to_string())?;
 
@@ -339,7 +348,7 @@ async fn process_image_with_ocr(
     info!("Running OCR with layout analysis");
     let use_case = ExtractTextFromImageUseCase::new();
 
-    let result = execute_ocr(&use_case, model.clone(), &bytes).await;
+    let result = execute_ocr(&use_case, model.clone(), &bytes, image_name).await;
 
     if loading_state.cancel_requested.get_untracked() {
         return Err(i18n
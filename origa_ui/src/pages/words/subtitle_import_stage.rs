@@ -0,0 +1,352 @@
+use crate::i18n::{t, use_i18n};
+use crate::repository::HybridUserRepository;
+use crate::ui_components::{
+    Alert, AlertType, Button, ButtonVariant, Checkbox, Spinner, Text, TextSize, TypographyVariant,
+};
+use crate::utils::file::read_file_as_bytes;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use origa::domain::OrigaError;
+use origa::use_cases::{MineSubtitlesUseCase, MinedWord, SubtitleMiningResult};
+use std::collections::HashSet;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+enum Stage {
+    #[default]
+    Idle,
+    Analyzing,
+    Words,
+    Creating,
+    Done,
+    Error,
+}
+
+/// Ranks the unknown words of an episode's subtitles by frequency and turns
+/// the selected ones into cards with their subtitle line as the example.
+#[component]
+pub fn SubtitleImportStage(
+    is_open: RwSignal<bool>,
+    refresh_trigger: RwSignal<u32>,
+    #[prop(optional, into)] test_id: Signal<String>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let repository =
+        use_context::<HybridUserRepository>().expect("repository context not provided");
+
+    let stage = RwSignal::new(Stage::Idle);
+    let error_message = RwSignal::new(String::new());
+    let file_name = RwSignal::new(String::new());
+    let mining = RwSignal::new(None::<SubtitleMiningResult>);
+    let selected_words = RwSignal::new(HashSet::<String>::new());
+    let created_count = RwSignal::new(0usize);
+    let disposed = StoredValue::new(());
+    let test_id_val = move || {
+        let val = test_id.get();
+        if val.is_empty() { None } else { Some(val) }
+    };
+
+    Effect::new(move |_| {
+        if !is_open.get() {
+            stage.set(Stage::Idle);
+            error_message.set(String::new());
+            file_name.set(String::new());
+            mining.set(None);
+            selected_words.set(HashSet::new());
+            created_count.set(0);
+        }
+    });
+
+    let on_file_change = {
+        let repository = repository.clone();
+        move |ev: leptos::ev::Event| {
+            let Some(file) = ev
+                .target()
+                .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|input| input.files())
+                .and_then(|files| files.get(0))
+            else {
+                return;
+            };
+            stage.set(Stage::Analyzing);
+            file_name.set(file.name());
+            let repo = repository.clone();
+
+            spawn_local(async move {
+                let result = mine_file(&repo, &file).await;
+                if disposed.is_disposed() {
+                    return;
+                }
+                match result {
+                    Ok(result) => {
+                        // Words without a dictionary entry cannot become cards.
+                        selected_words.set(
+                            result
+                                .words
+                                .iter()
+                                .filter(|word| word.meaning.is_some())
+                                .map(|word| word.base_form.clone())
+                                .collect(),
+                        );
+                        mining.set(Some(result));
+                        stage.set(Stage::Words);
+                    },
+                    Err(e) => {
+                        error_message.set(
+                            i18n.get_keys()
+                                .words()
+                                .subtitle_import()
+                                .analysis_error()
+                                .inner()
+                                .to_string()
+                                .replacen("{}", &e.to_string(), 1),
+                        );
+                        stage.set(Stage::Error);
+                    },
+                }
+            });
+        }
+    };
+
+    let on_create = {
+        let repository = repository.clone();
+        Callback::new(move |_| {
+            let words: Vec<MinedWord> = mining.with_untracked(|mining| {
+                let selected = selected_words.get_untracked();
+                mining
+                    .iter()
+                    .flat_map(|mining| mining.words.iter())
+                    .filter(|word| selected.contains(&word.base_form))
+                    .cloned()
+                    .collect()
+            });
+            if words.is_empty() {
+                return;
+            }
+            stage.set(Stage::Creating);
+            let source = Some(file_name.get_untracked());
+            let repo = repository.clone();
+
+            spawn_local(async move {
+                let result = MineSubtitlesUseCase::new(&repo)
+                    .create_cards(words, source)
+                    .await;
+                if disposed.is_disposed() {
+                    return;
+                }
+                match result {
+                    Ok(result) => {
+                        created_count.set(result.created_cards.len());
+                        stage.set(Stage::Done);
+                        refresh_trigger.update(|v| *v += 1);
+                    },
+                    Err(e) => {
+                        error_message.set(
+                            i18n.get_keys()
+                                .words()
+                                .subtitle_import()
+                                .create_error()
+                                .inner()
+                                .to_string()
+                                .replacen("{}", &e.to_string(), 1),
+                        );
+                        stage.set(Stage::Error);
+                    },
+                }
+            });
+        })
+    };
+
+    view! {
+        <div class="space-y-4" data-testid=test_id_val>
+            {move || match stage.get() {
+                Stage::Idle => view! {
+                    <label class="block border-2 border-dashed border-[var(--border-dark)] hover:border-[var(--accent-olive)]/50 p-8 text-center cursor-pointer">
+                        <input
+                            type="file"
+                            accept=".srt,.ass,.ssa,.vtt"
+                            class="hidden"
+                            on:change=on_file_change.clone()
+                            data-testid="subtitle-import-file-input"
+                        />
+                        <Text variant=TypographyVariant::Muted>
+                            {t!(i18n, words.subtitle_import.drop_zone)}
+                        </Text>
+                        <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                            {t!(i18n, words.subtitle_import.file_type)}
+                        </Text>
+                    </label>
+                }
+                .into_any(),
+
+                Stage::Analyzing | Stage::Creating => view! {
+                    <div class="flex flex-col items-center gap-3 py-8" data-testid="subtitle-import-loading">
+                        <Spinner />
+                        <Text variant=TypographyVariant::Muted>{t!(i18n, common.processing)}</Text>
+                    </div>
+                }
+                .into_any(),
+
+                Stage::Words => {
+                    let Some(result) = mining.get() else {
+                        return ().into_any();
+                    };
+                    let summary = i18n
+                        .get_keys()
+                        .words()
+                        .subtitle_import()
+                        .summary()
+                        .inner()
+                        .to_string()
+                        .replacen("{}", &format!("{:.0}", result.comprehension_percentage), 1)
+                        .replacen("{}", &result.words.len().to_string(), 1);
+                    view! {
+                        <div class="space-y-4">
+                            <Text size=TextSize::Small test_id="subtitle-import-summary">
+                                {summary}
+                            </Text>
+                            <div
+                                class="overflow-y-auto max-h-80"
+                                data-testid="subtitle-import-word-list"
+                            >
+                                <For
+                                    each=move || result.words.clone()
+                                    key=|word| word.base_form.clone()
+                                    children=move |word| view! {
+                                        <MinedWordItem word=word selected_words=selected_words />
+                                    }
+                                />
+                            </div>
+                            <Button
+                                variant=ButtonVariant::Olive
+                                disabled=Signal::derive(move || selected_words.with(HashSet::is_empty))
+                                on_click=on_create
+                                test_id="subtitle-import-create-btn"
+                            >
+                                {t!(i18n, words.add_selected)}
+                            </Button>
+                        </div>
+                    }
+                    .into_any()
+                },
+
+                Stage::Done => view! {
+                    <div class="space-y-4" data-testid="subtitle-import-done">
+                        <Alert
+                            alert_type=AlertType::Success
+                            title=Signal::derive(move || i18n.get_keys().words().subtitle_import().cards_created().inner().to_string())
+                            message=Signal::derive(move || {
+                                i18n.get_keys().words().anki_import().imported_cards().inner().to_string()
+                                    .replacen("{}", &created_count.get().to_string(), 1)
+                            })
+                        />
+                        <Button
+                            variant=ButtonVariant::Ghost
+                            on_click=Callback::new(move |_| stage.set(Stage::Idle))
+                            test_id="subtitle-import-again-btn"
+                        >
+                            {t!(i18n, common.back)}
+                        </Button>
+                    </div>
+                }
+                .into_any(),
+
+                Stage::Error => view! {
+                    <div class="space-y-4" data-testid="subtitle-import-error">
+                        <Alert
+                            alert_type=AlertType::Error
+                            title=Signal::derive(move || i18n.get_keys().common().error().inner().to_string())
+                            message=Signal::derive(move || error_message.get())
+                        />
+                        <Button
+                            variant=ButtonVariant::Ghost
+                            on_click=Callback::new(move |_| stage.set(Stage::Idle))
+                            test_id="subtitle-import-retry-btn"
+                        >
+                            {t!(i18n, words.anki_import.try_again)}
+                        </Button>
+                    </div>
+                }
+                .into_any(),
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn MinedWordItem(word: MinedWord, selected_words: RwSignal<HashSet<String>>) -> impl IntoView {
+    let i18n = use_i18n();
+    let is_disabled = word.meaning.is_none();
+    let base_form = StoredValue::new(word.base_form.clone());
+    let is_selected = Memo::new(move |_| {
+        base_form
+            .with_value(|base_form| selected_words.with(|selected| selected.contains(base_form)))
+    });
+    let toggle = move || {
+        if is_disabled {
+            return;
+        }
+        let base_form = base_form.get_value();
+        selected_words.update(|selected| {
+            if !selected.remove(&base_form) {
+                selected.insert(base_form);
+            }
+        });
+    };
+    let details = i18n
+        .get_keys_untracked()
+        .words()
+        .subtitle_import()
+        .word_details()
+        .inner()
+        .to_string()
+        .replacen("{}", &word.occurrences.to_string(), 1)
+        .replacen("{}", &word.example.timestamp(), 1);
+
+    view! {
+        <div
+            class=move || {
+                let base = "flex items-start gap-4 py-3 px-4 border-b border-[var(--border-dark)]";
+                if is_disabled {
+                    format!("{} opacity-40 cursor-not-allowed", base)
+                } else {
+                    format!("{} hover:bg-[var(--bg-aged)] cursor-pointer", base)
+                }
+            }
+            data-testid="subtitle-import-word"
+            on:click=move |_| toggle()
+        >
+            <div class="pt-1">
+                <Checkbox
+                    checked=Signal::derive(move || is_selected.get())
+                    on_change=Callback::new(move |_| toggle())
+                />
+            </div>
+            <div class="min-w-0 space-y-1">
+                <div class="font-medium">
+                    {word.base_form.clone()}
+                    <span class="ml-2 text-sm text-[var(--fg-muted)]">{word.reading.clone()}</span>
+                </div>
+                <div class="text-sm">{word.meaning.clone().unwrap_or_default()}</div>
+                <div class="text-sm text-[var(--fg-muted)] truncate">
+                    {details} " · " {word.example.text().to_string()}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+async fn mine_file(
+    repository: &HybridUserRepository,
+    file: &web_sys::File,
+) -> Result<SubtitleMiningResult, OrigaError> {
+    let bytes = read_file_as_bytes(file)
+        .await
+        .map_err(|e| OrigaError::SubtitleParseError {
+            reason: e.to_string(),
+        })?;
+    MineSubtitlesUseCase::new(repository)
+        .execute_file(&file.name(), &bytes)
+        .await
+}
@@ -170,6 +170,8 @@ fn analyzed_word(known: bool, meaning: Option<String>) -> AnalyzedWord {
         part_of_speech: origa::domain::PartOfSpeech::Verb,
         is_known: known,
         meaning,
+        sentence: None,
+        pitch_accent: None,
     }
 }
 
@@ -0,0 +1,232 @@
+use crate::i18n::{t, use_i18n};
+use crate::repository::save_user_dictionary_to_cache_rkyv;
+use crate::store::AuthStore;
+use crate::ui_components::{
+    Alert, AlertType, Button, ButtonVariant, DeleteButton, NativeLanguageToggle, Spinner, Text,
+    TextSize, TypographyVariant,
+};
+use crate::utils::file::read_file_as_bytes;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use origa::dictionary::user_dictionary::{
+    UserDictionarySource, import_yomitan_dictionary, remove_user_dictionary,
+    serialize_user_dictionary_to_rkyv, user_dictionary_sources,
+};
+use origa::domain::{NativeLanguage, OrigaError, User};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+enum Stage {
+    #[default]
+    Idle,
+    Importing,
+    Done,
+    Error,
+}
+
+/// Imports a Yomitan dictionary zip into the local user-dictionary layer, so
+/// words missing from the built-in dictionary can still become cards.
+#[component]
+pub fn YomitanImportStage(#[prop(optional, into)] test_id: Signal<String>) -> impl IntoView {
+    let i18n = use_i18n();
+    let auth_store = use_context::<AuthStore>().expect("AuthStore not provided");
+
+    // Used only for zips whose index.json does not declare `targetLanguage`.
+    let glossary_language = RwSignal::new(auth_store.user.with_untracked(|u: &Option<User>| {
+        u.as_ref()
+            .map(|u| *u.native_language())
+            .unwrap_or(NativeLanguage::Russian)
+    }));
+    let stage = RwSignal::new(Stage::Idle);
+    let error_message = RwSignal::new(String::new());
+    let imported = RwSignal::new(None::<UserDictionarySource>);
+    let sources = RwSignal::new(user_dictionary_sources());
+    let disposed = StoredValue::new(());
+    let test_id_val = move || {
+        let val = test_id.get();
+        if val.is_empty() { None } else { Some(val) }
+    };
+
+    let on_file_change = move |ev: leptos::ev::Event| {
+        let Some(file) = ev
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+            .and_then(|input| input.files())
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+        stage.set(Stage::Importing);
+        let lang = glossary_language.get_untracked();
+
+        spawn_local(async move {
+            let result = import_file(&file, &lang).await;
+            if disposed.is_disposed() {
+                return;
+            }
+            match result {
+                Ok(source) => {
+                    imported.set(Some(source));
+                    sources.set(user_dictionary_sources());
+                    stage.set(Stage::Done);
+                },
+                Err(e) => {
+                    error_message.set(
+                        i18n.get_keys()
+                            .words()
+                            .dictionary_import()
+                            .import_error()
+                            .inner()
+                            .to_string()
+                            .replacen("{}", &e.to_string(), 1),
+                    );
+                    stage.set(Stage::Error);
+                },
+            }
+        });
+    };
+
+    view! {
+        <div class="space-y-4" data-testid=test_id_val>
+            {move || match stage.get() {
+                Stage::Idle => {
+                    view! {
+                    <label class="block border-2 border-dashed border-[var(--border-dark)] hover:border-[var(--accent-olive)]/50 p-8 text-center cursor-pointer">
+                        <input
+                            type="file"
+                            accept=".zip,application/zip"
+                            class="hidden"
+                            on:change=on_file_change
+                            data-testid="dictionary-import-file-input"
+                        />
+                        <Text variant=TypographyVariant::Muted>
+                            {t!(i18n, words.dictionary_import.drop_zone)}
+                        </Text>
+                        <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                            {t!(i18n, words.dictionary_import.file_type)}
+                        </Text>
+                    </label>
+                    <div class="flex items-center justify-between gap-2">
+                        <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                            {t!(i18n, words.dictionary_import.glossary_language)}
+                        </Text>
+                        <NativeLanguageToggle
+                            selected_language=glossary_language
+                            test_id="dictionary-import-language"
+                        />
+                    </div>
+                }
+                .into_any()
+                },
+
+                Stage::Importing => view! {
+                    <div class="flex flex-col items-center gap-3 py-8">
+                        <Spinner />
+                        <Text variant=TypographyVariant::Muted>{t!(i18n, common.importing)}</Text>
+                    </div>
+                }
+                .into_any(),
+
+                Stage::Done => view! {
+                    <div data-testid="dictionary-import-done">
+                        <Alert
+                            alert_type=AlertType::Success
+                            title=Signal::derive(move || i18n.get_keys().words().dictionary_import().import_complete().inner().to_string())
+                            message=Signal::derive(move || {
+                                let Some(source) = imported.get() else {
+                                    return String::new();
+                                };
+                                i18n.get_keys().words().dictionary_import().imported_summary().inner().to_string()
+                                    .replacen("{}", source.title(), 1)
+                                    .replacen("{}", &source.term_count().to_string(), 1)
+                                    .replacen("{}", &source.kanji_count().to_string(), 1)
+                            })
+                        />
+                    </div>
+                }
+                .into_any(),
+
+                Stage::Error => view! {
+                    <div class="space-y-4" data-testid="dictionary-import-error">
+                        <Alert
+                            alert_type=AlertType::Error
+                            title=Signal::derive(move || i18n.get_keys().common().error().inner().to_string())
+                            message=Signal::derive(move || error_message.get())
+                        />
+                        <Button
+                            variant=ButtonVariant::Ghost
+                            on_click=Callback::new(move |_| stage.set(Stage::Idle))
+                            test_id="dictionary-import-retry-btn"
+                        >
+                            {t!(i18n, words.anki_import.try_again)}
+                        </Button>
+                    </div>
+                }
+                .into_any(),
+            }}
+            <Show when=move || !sources.get().is_empty()>
+                <div class="space-y-1" data-testid="dictionary-import-sources">
+                    <Text size=TextSize::Small variant=TypographyVariant::Muted>
+                        {t!(i18n, words.dictionary_import.installed)}
+                    </Text>
+                    <For
+                        each=move || sources.get()
+                        key=|source| (source.title().to_string(), source.revision().to_string())
+                        children=move |source| {
+                            let title = source.title().to_string();
+                            let on_remove = Callback::new(move |_| {
+                                let source = source.clone();
+                                spawn_local(async move {
+                                    if remove_user_dictionary(&source) {
+                                        cache_user_dictionaries().await;
+                                    }
+                                    if !disposed.is_disposed() {
+                                        sources.set(user_dictionary_sources());
+                                    }
+                                });
+                            });
+                            view! {
+                                <div class="flex items-center justify-between text-sm">
+                                    <span>{title}</span>
+                                    <DeleteButton
+                                        on_click=on_remove
+                                        test_id="dictionary-import-remove-btn"
+                                    />
+                                </div>
+                            }
+                        }
+                    />
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+/// `fallback_lang` is the glossary language for zips that do not declare
+/// one. The cache write is best-effort, the layer stays usable for this
+/// session anyway.
+async fn import_file(
+    file: &web_sys::File,
+    fallback_lang: &NativeLanguage,
+) -> Result<UserDictionarySource, OrigaError> {
+    let bytes = read_file_as_bytes(file)
+        .await
+        .map_err(|e| OrigaError::YomitanInvalidFile {
+            reason: e.to_string(),
+        })?;
+    let source = import_yomitan_dictionary(&bytes, fallback_lang)?;
+    cache_user_dictionaries().await;
+    Ok(source)
+}
+
+async fn cache_user_dictionaries() {
+    match serialize_user_dictionary_to_rkyv() {
+        Ok(layer) => {
+            if let Err(e) = save_user_dictionary_to_cache_rkyv(&layer).await {
+                tracing::warn!("Failed to cache user dictionaries: {:?}", e);
+            }
+        },
+        Err(e) => tracing::warn!("Failed to serialize user dictionaries: {:?}", e),
+    }
+}
@@ -14,6 +14,12 @@ pub const VOCABULARY_CACHE_NAME: &str = "origa-vocabulary-rkyv-v1";
 /// Cache key for VocabularyDatabase rkyv blob.
 pub const VOCABULARY_CACHE_KEY: &str = "/__origa_vocabulary_cached__";
 
+/// Cache name for the imported user dictionaries (rkyv).
+pub const USER_DICTIONARY_CACHE_NAME: &str = "origa-user-dictionary-rkyv-v1";
+
+/// Cache key for the user dictionary rkyv blob.
+pub const USER_DICTIONARY_CACHE_KEY: &str = "/__origa_user_dictionary_cached__";
+
 /// The nine dictionary files cached under DICTIONARY_FILES_CACHE_NAME.
 pub const DICTIONARY_FILE_NAMES: &[&str] = &[
     "char_def.bin",
@@ -144,3 +150,15 @@ pub async fn save_vocabulary_to_cache_rkyv(bytes: &mut [u8]) -> Result<(), Origa
     let cache = open_cache(VOCABULARY_CACHE_NAME).await?;
     cache_write(&cache, VOCABULARY_CACHE_KEY, bytes).await
 }
+
+/// Get the cached user dictionary layer as raw rkyv bytes.
+pub async fn get_cached_user_dictionary_rkyv() -> Result<Option<Vec<u8>>, OrigaError> {
+    let cache = open_cache(USER_DICTIONARY_CACHE_NAME).await?;
+    cache_read(&cache, USER_DICTIONARY_CACHE_KEY).await
+}
+
+/// Save the user dictionary layer (rkyv bytes) to the Cache API.
+pub async fn save_user_dictionary_to_cache_rkyv(bytes: &[u8]) -> Result<(), OrigaError> {
+    let cache = open_cache(USER_DICTIONARY_CACHE_NAME).await?;
+    cache_write(&cache, USER_DICTIONARY_CACHE_KEY, bytes).await
+}
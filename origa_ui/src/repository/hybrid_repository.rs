@@ -27,16 +27,20 @@ impl HybridUserRepository {
         let local_result = self.local.get_current_user().await?;
 
         match (remote_result, local_result) {
-            (Some(remote_data), None) => {
+            (Some((remote_user, _)), None) => {
                 tracing::info!("Creating local user from remote");
-                self.save_local_and_sync_remote(&remote_data.0).await?;
+                let remote_events = self.remote.find_events().await?;
+                let mut user = remote_user.clone();
+                user.rebase(&remote_user, &remote_events);
+                self.save_local_and_sync_remote(&user).await?;
             },
             (None, Some(local_user)) => {
                 tracing::info!("Creating remote user from local");
-                self.remote.save(&local_user).await?;
+                self.sync_remote(&local_user).await?;
             },
-            (Some(remote_data), Some(mut local_user)) => {
-                local_user.merge(&remote_data.0);
+            (Some((remote_user, _)), Some(mut local_user)) => {
+                let remote_events = self.remote.find_events().await?;
+                local_user.rebase(&remote_user, &remote_events);
                 self.save_local_and_sync_remote(&local_user).await?;
             },
             (None, None) => {
@@ -57,7 +61,21 @@ impl HybridUserRepository {
                 .ok_or_else(|| OrigaError::RepositoryError {
                     reason: "User not found after local save".to_string(),
                 })?;
-        self.remote.save(&updated_user).await?;
+        self.sync_remote(&updated_user).await
+    }
+
+    // Uploads the user's unsynced knowledge events. When the remote save
+    // wrote a fresh checkpoint instead, the events it folded are dropped from
+    // the local journal so they are neither uploaded nor replayed again.
+    async fn sync_remote(&self, user: &User) -> Result<(), OrigaError> {
+        let Some(published) = self.remote.save_with_events(user).await? else {
+            return Ok(());
+        };
+
+        if let Some(mut current) = self.local.get_current_user().await? {
+            current.fold_knowledge_journal(&published);
+            self.local.save(&current).await?;
+        }
         Ok(())
     }
 
@@ -114,7 +132,7 @@ impl UserRepository for HybridUserRepository {
         self.local.save(user).await?;
         tracing::info!("save_sync: Local save completed for user {}", user.id());
 
-        if let Err(e) = self.remote.save(user).await {
+        if let Err(e) = self.sync_remote(user).await {
             tracing::error!(
                 "save_sync: Remote save failed for user {}: {:?}. Local save kept; surfacing error to caller.",
                 user.id(),
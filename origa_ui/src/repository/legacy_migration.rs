@@ -112,14 +112,15 @@ pub(super) fn reseat_user_id(legacy: &User, new_id: Ulid) -> User {
         legacy.imported_sets().clone(),
         *legacy.daily_load(),
         legacy.known_vocab_hash(),
+        legacy.srs_settings().clone(),
     )
 }
 
 /// Merge progress accumulated under the legacy nil key into the canonical row.
 ///
 /// Identity and profile fields (id, email, username, native_language,
-/// telegram_user_id, daily_load) stay canonical because the remote is the
-/// source of truth — `User::merge` is intentionally avoided here because it
+/// telegram_user_id, daily_load, srs_settings) stay canonical because the
+/// remote is the source of truth — `User::merge` is intentionally avoided here because it
 /// overwrites email/username, which would let a stale local copy clobber the
 /// canonical profile. Only the progress collections the user accumulated
 /// locally (knowledge_set, imported_sets) are unioned in so nothing is lost.
@@ -144,6 +145,7 @@ pub(super) fn merge_legacy_progress_into_canonical(canonical: &User, legacy: &Us
         merged_imported_sets,
         *canonical.daily_load(),
         canonical.known_vocab_hash(),
+        canonical.srs_settings().clone(),
     )
 }
 
@@ -3,7 +3,7 @@ use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use chrono::Utc;
 use origa::domain::{
-    Card, DailyLoad, JlptProgress, KnowledgeSet, NativeLanguage, PhraseCard, User,
+    Card, DailyLoad, JlptProgress, KnowledgeSet, NativeLanguage, PhraseCard, SrsSettings, User,
 };
 use std::collections::HashSet;
 
@@ -28,6 +28,7 @@ fn build_user_with_id(id: Ulid) -> User {
         HashSet::new(),
         DailyLoad::default(),
         0,
+        SrsSettings::default(),
     )
 }
 
@@ -48,6 +49,7 @@ fn build_canonical_user() -> User {
         HashSet::new(),
         DailyLoad::default(),
         0,
+        SrsSettings::default(),
     )
 }
 
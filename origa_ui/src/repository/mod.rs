@@ -19,7 +19,8 @@ pub mod trailbase_session;
 pub use cdn_provider::cdn as cdn_provider;
 
 pub use dictionary_cache::{
-    get_cached_dictionary_files, get_cached_vocabulary_rkyv, save_dictionary_files_to_cache,
+    get_cached_dictionary_files, get_cached_user_dictionary_rkyv, get_cached_vocabulary_rkyv,
+    save_dictionary_files_to_cache, save_user_dictionary_to_cache_rkyv,
     save_vocabulary_to_cache_rkyv,
 };
 pub use hybrid_repository::HybridUserRepository;
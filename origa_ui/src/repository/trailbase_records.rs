@@ -41,6 +41,86 @@ impl<C: AuthRequestClient> RecordApi<C> {
         Ok(list.records)
     }
 
+    /// Lists every matching record, following the list cursor page by page.
+    /// With `after_id`, only records whose primary key `id` is greater.
+    pub async fn list_all_filtered<T: DeserializeOwned>(
+        &self,
+        column: &str,
+        value: &str,
+        after_id: Option<i64>,
+    ) -> Result<Vec<T>, AuthError> {
+        const PAGE_SIZE: usize = 256;
+
+        #[derive(Deserialize)]
+        struct PageResponse<T> {
+            #[serde(default)]
+            cursor: Option<String>,
+            records: Vec<T>,
+        }
+
+        let mut records = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut path = format!(
+                "/api/records/v1/{}?filter[{}][$eq]={}&limit={}",
+                self.table_name,
+                urlencoding::encode(column),
+                urlencoding::encode(value),
+                PAGE_SIZE
+            );
+            if let Some(after_id) = after_id {
+                path.push_str(&format!("&filter[id][$gt]={}", after_id));
+            }
+            if let Some(cursor) = &cursor {
+                path.push_str(&format!("&cursor={}", urlencoding::encode(cursor)));
+            }
+            let response = self
+                .client
+                .request_with_auth(&path, Method::GET, None::<&()>)
+                .await?;
+
+            let page: PageResponse<T> = response
+                .json()
+                .await
+                .map_err(|e| AuthError::ApiError(format!("Failed to parse response: {}", e)))?;
+            let page_len = page.records.len();
+            records.extend(page.records);
+
+            match page.cursor {
+                Some(next) if page_len == PAGE_SIZE => cursor = Some(next),
+                _ => return Ok(records),
+            }
+        }
+    }
+
+    /// Inserts all `records` in one request, returning their ids in order.
+    pub async fn create_bulk<T: Serialize>(&self, records: &[T]) -> Result<Vec<String>, AuthError> {
+        let path = format!("/api/records/v1/{}", self.table_name);
+        let response = self
+            .client
+            .request_with_auth(&path, Method::POST, Some(&records))
+            .await?;
+
+        if !response.ok() {
+            let error_text = extract_error_text(response).await;
+            return Err(AuthError::ApiError(format!(
+                "Failed to create records: {}",
+                error_text
+            )));
+        }
+
+        #[derive(Deserialize)]
+        struct CreateResponse {
+            ids: Vec<String>,
+        }
+
+        let create_response: CreateResponse = response
+            .json()
+            .await
+            .map_err(|e| AuthError::ApiError(format!("Failed to parse response: {}", e)))?;
+        Ok(create_response.ids)
+    }
+
     pub async fn create<T: Serialize + std::fmt::Debug>(
         &self,
         record: &T,
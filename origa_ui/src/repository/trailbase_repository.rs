@@ -2,7 +2,10 @@ use super::session::{TrailBaseSession, get_session, set_session_async};
 use super::trailbase_client::{AuthError, TrailBaseClient};
 use super::trailbase_id::uuid_to_ulid;
 use chrono::{DateTime, Utc};
-use origa::domain::{DailyLoad, KnowledgeSet, NativeLanguage, OrigaError, User};
+use origa::domain::{
+    DailyLoad, EventId, KnowledgeEvent, KnowledgeJournal, KnowledgeSet, NativeLanguage, OrigaError,
+    SrsSettings, User,
+};
 use origa::traits::UserRepository;
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
@@ -14,11 +17,93 @@ use super::knowledge_set_codec;
 #[path = "trailbase_repository_tests.rs"]
 mod tests;
 
+/// Unpublished events after which a save writes a fresh checkpoint, so a
+/// new device does not replay an ever-growing stream.
+const CHECKPOINT_AFTER_EVENTS: usize = 500;
+
 #[derive(Clone)]
 pub struct TrailBaseUserRepository {
     client: TrailBaseClient,
     table_name: String,
+    events_table_name: String,
     user_cache: Arc<RwLock<HashMap<String, User>>>,
+    remote_snapshot: Arc<RwLock<Option<RemoteSnapshot>>>,
+}
+
+/// What this device last saw of the remote knowledge set: the checkpoint
+/// journal, the row's `updated_at` and the event rows uploaded after the
+/// checkpoint. Saves reuse it, so they fetch only rows newer than the last
+/// one seen instead of the whole row and event stream.
+#[derive(Clone, Debug)]
+struct RemoteSnapshot {
+    email: String,
+    record_id: i64,
+    checkpoint: KnowledgeJournal,
+    updated_at: DateTime<Utc>,
+    rows: Vec<KnowledgeEventRow>,
+}
+
+impl RemoteSnapshot {
+    fn new(
+        email: String,
+        record_id: i64,
+        checkpoint: KnowledgeJournal,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            email,
+            record_id,
+            checkpoint,
+            updated_at,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Whether `row` is still the row this snapshot was taken from. Another
+    /// device's checkpoint folds events that never reach the event table,
+    /// so a changed row is only visible here.
+    fn matches(&self, row: &UserRow) -> bool {
+        let checkpoint: KnowledgeSet = row
+            .knowledge_set
+            .as_deref()
+            .map(knowledge_set_codec::decode)
+            .unwrap_or_default();
+        self.updated_at == row.updated_at
+            && checkpoint.journal().folded() == self.checkpoint.folded()
+    }
+
+    fn last_row_id(&self) -> Option<i64> {
+        self.rows.iter().filter_map(|row| row.id).max()
+    }
+
+    /// Adds fetched or uploaded rows, skipping ids already held.
+    fn extend(&mut self, rows: Vec<KnowledgeEventRow>) {
+        let known: HashSet<i64> = self.rows.iter().filter_map(|row| row.id).collect();
+        self.rows.extend(
+            rows.into_iter()
+                .filter(|row| row.id.map_or(true, |id| !known.contains(&id))),
+        );
+    }
+
+    fn events(&self) -> Vec<KnowledgeEvent> {
+        self.rows
+            .iter()
+            .filter_map(KnowledgeEventRow::to_event)
+            .collect()
+    }
+
+    /// Switches to a newly published checkpoint, returning the rows it
+    /// folded so they can be deleted remotely.
+    fn publish(&mut self, checkpoint: KnowledgeJournal) -> Vec<KnowledgeEventRow> {
+        // A row that no longer parses can never be replayed either.
+        let (folded, kept) = std::mem::take(&mut self.rows).into_iter().partition(|row| {
+            row.to_event()
+                .map_or(true, |event| checkpoint.is_folded(event.id()))
+        });
+        self.rows = kept;
+        self.checkpoint = checkpoint;
+        folded
+    }
 }
 
 fn map_auth_error(e: AuthError) -> OrigaError {
@@ -38,7 +123,9 @@ impl TrailBaseUserRepository {
         Self {
             client: TrailBaseClient::new(),
             table_name: "domain_user".to_string(),
+            events_table_name: "knowledge_event".to_string(),
             user_cache: Arc::new(RwLock::new(HashMap::new())),
+            remote_snapshot: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -53,14 +140,7 @@ impl TrailBaseUserRepository {
             });
         }
 
-        let api = self.client.records(&self.table_name);
-
-        let records: Vec<UserRow> = api
-            .list_filtered("email", &session.email)
-            .await
-            .map_err(map_auth_error)?;
-
-        if let Some(row) = records.into_iter().next() {
+        if let Some(row) = self.fetch_row(&session.email).await? {
             let record_id = row.id.ok_or_else(|| OrigaError::RepositoryError {
                 reason: "Record ID missing from database row".to_string(),
             })?;
@@ -79,12 +159,299 @@ impl TrailBaseUserRepository {
             if let Ok(mut cache) = self.user_cache.write() {
                 cache.insert(session.email.clone(), user.clone());
             }
+            self.store_snapshot(RemoteSnapshot::new(
+                session.email.clone(),
+                record_id,
+                user.knowledge_set().journal().clone(),
+                row.updated_at,
+            ));
 
             return Ok(Some((user, record_id)));
         }
 
         Ok(None)
     }
+
+    async fn fetch_row(&self, email: &str) -> Result<Option<UserRow>, OrigaError> {
+        let records: Vec<UserRow> = self
+            .client
+            .records(&self.table_name)
+            .list_filtered("email", email)
+            .await
+            .map_err(map_auth_error)?;
+        Ok(records.into_iter().next())
+    }
+
+    /// Knowledge events uploaded by any device since the remote checkpoint
+    /// was written (and possibly some already folded into it).
+    pub async fn find_events(&self) -> Result<Vec<KnowledgeEvent>, OrigaError> {
+        let snapshot = match self.cached_snapshot()? {
+            Some(snapshot) => snapshot,
+            None => match self.find_current().await? {
+                Some(_) => self
+                    .cached_snapshot()?
+                    .ok_or_else(|| OrigaError::RepositoryError {
+                        reason: "Remote snapshot missing after fetching the user".to_string(),
+                    })?,
+                None => return Ok(Vec::new()),
+            },
+        };
+        Ok(self.refresh_events(snapshot).await?.events())
+    }
+
+    /// The snapshot for the signed-in user, if one was taken this session.
+    fn cached_snapshot(&self) -> Result<Option<RemoteSnapshot>, OrigaError> {
+        let session = get_session().ok_or_else(|| OrigaError::RepositoryError {
+            reason: "Not authenticated".to_string(),
+        })?;
+        Ok(self
+            .remote_snapshot
+            .read()
+            .ok()
+            .and_then(|snapshot| snapshot.clone())
+            .filter(|snapshot| snapshot.email == session.email))
+    }
+
+    fn store_snapshot(&self, snapshot: RemoteSnapshot) {
+        if let Ok(mut cached) = self.remote_snapshot.write() {
+            *cached = Some(snapshot);
+        }
+    }
+
+    fn clear_snapshot(&self) {
+        if let Ok(mut cached) = self.remote_snapshot.write() {
+            *cached = None;
+        }
+    }
+
+    /// Fetches only the event rows uploaded after the newest one in
+    /// `snapshot`, and stores the result.
+    async fn refresh_events(
+        &self,
+        mut snapshot: RemoteSnapshot,
+    ) -> Result<RemoteSnapshot, OrigaError> {
+        let session = get_session().ok_or_else(|| OrigaError::RepositoryError {
+            reason: "Not authenticated".to_string(),
+        })?;
+
+        let rows: Vec<KnowledgeEventRow> = self
+            .client
+            .records(&self.events_table_name)
+            .list_all_filtered(
+                "trailbase_id",
+                &session.trailbase_id,
+                snapshot.last_row_id(),
+            )
+            .await
+            .map_err(map_auth_error)?;
+        snapshot.extend(rows);
+        self.store_snapshot(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    /// Saves `user`, uploading only the knowledge events the remote has not
+    /// seen. When a fresh checkpoint of the knowledge set is written instead,
+    /// its journal is returned so the caller can fold the published events
+    /// (see [`User::fold_knowledge_journal`]).
+    pub async fn save_with_events(
+        &self,
+        user: &User,
+    ) -> Result<Option<KnowledgeJournal>, OrigaError> {
+        let session = get_session().ok_or_else(|| OrigaError::RepositoryError {
+            reason: "Not authenticated".to_string(),
+        })?;
+
+        if session.email.is_empty() {
+            return Err(OrigaError::RepositoryError {
+                reason: "Email not found in session. Please re-login.".to_string(),
+            });
+        }
+
+        let api = self.client.records(&self.table_name);
+
+        let snapshot = match self.cached_snapshot()? {
+            Some(snapshot) => Some(snapshot),
+            None => match self.find_current().await? {
+                Some(_) => self.cached_snapshot()?,
+                None => None,
+            },
+        };
+
+        let published = if let Some(snapshot) = snapshot {
+            let mut snapshot = self.refresh_events(snapshot).await?;
+
+            let mut plan = plan_knowledge_upload(
+                user.knowledge_set(),
+                &snapshot.checkpoint,
+                &snapshot.events(),
+            );
+            // The snapshot is read once per session; re-read the row before
+            // overwriting it so a checkpoint another device published since
+            // is not lost.
+            let mut stale = false;
+            if plan == KnowledgeUpload::Checkpoint {
+                let row = self.fetch_row(&session.email).await?;
+                if !row.is_some_and(|row| snapshot.matches(&row)) {
+                    tracing::info!("Remote checkpoint changed since it was read; uploading events");
+                    stale = true;
+                    plan = KnowledgeUpload::Events(unsynced_events(user, &snapshot));
+                }
+            }
+
+            let published = match plan {
+                KnowledgeUpload::Checkpoint => Some(user.knowledge_set().checkpoint()),
+                KnowledgeUpload::Events(events) => {
+                    let rows = self.upload_events(&events, &session.trailbase_id).await?;
+                    snapshot.extend(rows);
+                    None
+                },
+            };
+
+            let body = user_to_json(user, &session.trailbase_id, published.as_ref())?;
+            api.update(&snapshot.record_id.to_string(), &body)
+                .await
+                .map_err(map_auth_error)?;
+            snapshot.updated_at = *user.updated_at();
+
+            if let Some(checkpoint) = &published {
+                let folded = snapshot.publish(checkpoint.journal().clone());
+                self.delete_folded_event_rows(&folded).await;
+            }
+            if stale {
+                // Re-read on the next save; the local set only covers the
+                // newer checkpoint once it has been rebased onto it.
+                self.clear_snapshot();
+            } else {
+                self.store_snapshot(snapshot);
+            }
+            published
+        } else {
+            let checkpoint = user.knowledge_set().checkpoint();
+            let body = user_to_json(user, &session.trailbase_id, Some(&checkpoint))?;
+            let created_id = api.create(&body).await.map_err(map_auth_error)?;
+            let record_id: i64 = created_id
+                .parse()
+                .map_err(|_| OrigaError::RepositoryError {
+                    reason: "Invalid record ID returned from create".to_string(),
+                })?;
+
+            let updated_session = TrailBaseSession {
+                record_id: Some(record_id),
+                ..session.clone()
+            };
+            set_session_async(&updated_session)
+                .await
+                .map_err(|e| OrigaError::RepositoryError {
+                    reason: format!("Failed to update session: {}", e),
+                })?;
+            self.store_snapshot(RemoteSnapshot::new(
+                session.email.clone(),
+                record_id,
+                checkpoint.journal().clone(),
+                *user.updated_at(),
+            ));
+            Some(checkpoint)
+        };
+
+        if let Ok(mut cache) = self.user_cache.write() {
+            cache.insert(user.email().to_string(), user.clone());
+        }
+
+        Ok(published.map(|checkpoint| checkpoint.journal().clone()))
+    }
+
+    /// Uploads `events`, returning their rows with the ids the server
+    /// assigned.
+    async fn upload_events(
+        &self,
+        events: &[KnowledgeEvent],
+        trailbase_id: &str,
+    ) -> Result<Vec<KnowledgeEventRow>, OrigaError> {
+        if events.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut rows = events
+            .iter()
+            .map(|event| KnowledgeEventRow::from_event(event, trailbase_id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ids = self
+            .client
+            .records(&self.events_table_name)
+            .create_bulk(&rows)
+            .await
+            .map_err(map_auth_error)?;
+        for (row, id) in rows.iter_mut().zip(ids) {
+            row.id = id.parse().ok();
+        }
+        Ok(rows)
+    }
+
+    /// Best-effort cleanup: rows folded into the new checkpoint are ignored
+    /// on read anyway, so a failed delete is only logged.
+    async fn delete_folded_event_rows(&self, folded: &[KnowledgeEventRow]) {
+        let api = self.client.records(&self.events_table_name);
+        for id in folded.iter().filter_map(|row| row.id) {
+            if let Err(e) = api.delete(&id.to_string()).await {
+                tracing::warn!("Failed to delete folded knowledge event {}: {:?}", id, e);
+            }
+        }
+    }
+}
+
+/// How a save brings the remote knowledge set up to date.
+#[derive(Debug, PartialEq)]
+enum KnowledgeUpload {
+    /// Append these events after the remote checkpoint.
+    Events(Vec<KnowledgeEvent>),
+    /// Overwrite the remote checkpoint with the local set.
+    Checkpoint,
+}
+
+/// Local events the remote has neither folded nor stored as rows.
+fn unsynced_events(user: &User, snapshot: &RemoteSnapshot) -> Vec<KnowledgeEvent> {
+    let known: HashSet<EventId> = snapshot.events().iter().map(|event| *event.id()).collect();
+    user.knowledge_set()
+        .journal()
+        .unsynced(&snapshot.checkpoint, &known)
+        .cloned()
+        .collect()
+}
+
+/// Decides between appending events and writing a new checkpoint. A
+/// checkpoint is written when either side predates the journal, or when
+/// many events are pending — but only if the local set already contains
+/// the remote checkpoint and every remote event, so overwriting loses
+/// nothing.
+fn plan_knowledge_upload(
+    local: &KnowledgeSet,
+    checkpoint: &KnowledgeJournal,
+    remote_events: &[KnowledgeEvent],
+) -> KnowledgeUpload {
+    let journal = local.journal();
+    let known: HashSet<EventId> = remote_events.iter().map(|event| *event.id()).collect();
+    let local_ids: HashSet<EventId> = journal.events().iter().map(|event| *event.id()).collect();
+
+    let covers_remote = journal.contains_checkpoint(checkpoint)
+        && remote_events.iter().all(|event| {
+            checkpoint.is_folded(event.id())
+                || journal.is_folded(event.id())
+                || local_ids.contains(event.id())
+        });
+    let pending = remote_events
+        .iter()
+        .filter(|event| !checkpoint.is_folded(event.id()))
+        .count()
+        + journal.unsynced(checkpoint, &known).count();
+    let wants_checkpoint = !journal.is_replayable()
+        || !checkpoint.is_replayable()
+        || pending > CHECKPOINT_AFTER_EVENTS;
+
+    if wants_checkpoint && covers_remote {
+        KnowledgeUpload::Checkpoint
+    } else {
+        KnowledgeUpload::Events(journal.unsynced(checkpoint, &known).cloned().collect())
+    }
 }
 
 impl Default for TrailBaseUserRepository {
@@ -111,6 +478,8 @@ struct UserRow {
     daily_load: Option<i32>,
     #[serde(default)]
     known_vocab_hash: Option<i32>,
+    #[serde(default)]
+    srs_settings: Option<String>,
 }
 
 impl UserRow {
@@ -139,6 +508,12 @@ impl UserRow {
             .and_then(|s| serde_json::from_str(s).ok())
             .unwrap_or_default();
 
+        let srs_settings: SrsSettings = self
+            .srs_settings
+            .as_ref()
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default();
+
         User::from_row(
             ulid,
             self.email.clone(),
@@ -159,22 +534,71 @@ impl UserRow {
                 },
             },
             self.known_vocab_hash.unwrap_or(0) as u32,
+            srs_settings,
         )
     }
 }
 
-fn user_to_json(user: &User, trailbase_id: &str) -> Result<serde_json::Value, OrigaError> {
+/// One uploaded [`KnowledgeEvent`]. `replica`, `seq` and `at` duplicate the
+/// event id so the table can be inspected and indexed without parsing
+/// `event`.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+struct KnowledgeEventRow {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<i64>,
+    trailbase_id: String,
+    replica: String,
+    seq: i64,
+    at: DateTime<Utc>,
+    event: String,
+}
+
+impl KnowledgeEventRow {
+    fn from_event(event: &KnowledgeEvent, trailbase_id: &str) -> Result<Self, OrigaError> {
+        let json = serde_json::to_string(event).map_err(|e| OrigaError::RepositoryError {
+            reason: format!("knowledge event encode failed: {e}"),
+        })?;
+        Ok(Self {
+            id: None,
+            trailbase_id: trailbase_id.to_string(),
+            replica: event.id().replica().to_string(),
+            seq: event.id().seq() as i64,
+            at: event.id().at(),
+            event: json,
+        })
+    }
+
+    // Recovering like the knowledge_set decode: a row this client cannot
+    // parse (corrupt, or written by a newer client) is skipped.
+    fn to_event(&self) -> Option<KnowledgeEvent> {
+        serde_json::from_str(&self.event)
+            .map_err(|e| tracing::warn!("Skipping unreadable knowledge event row: {}", e))
+            .ok()
+    }
+}
+
+/// Row body. The knowledge set is only written as a whole when a
+/// `checkpoint` is given; otherwise it travels as events and the column is
+/// left untouched.
+fn user_to_json(
+    user: &User,
+    trailbase_id: &str,
+    checkpoint: Option<&KnowledgeSet>,
+) -> Result<serde_json::Value, OrigaError> {
     let jlpt_progress_json =
         serde_json::to_string(user.jlpt_progress()).map_err(|e| OrigaError::RepositoryError {
             reason: format!("jlpt_progress encode failed: {e}"),
         })?;
-    let knowledge_set_wire = knowledge_set_codec::encode(user.knowledge_set())?;
     let imported_sets_json =
         serde_json::to_string(user.imported_sets()).map_err(|e| OrigaError::RepositoryError {
             reason: format!("imported_sets encode failed: {e}"),
         })?;
+    let srs_settings_json =
+        serde_json::to_string(user.srs_settings()).map_err(|e| OrigaError::RepositoryError {
+            reason: format!("srs_settings encode failed: {e}"),
+        })?;
 
-    Ok(serde_json::json!({
+    let mut body = serde_json::json!({
         "trailbase_id": trailbase_id,
         "username": user.username(),
         "email": user.email(),
@@ -182,12 +606,16 @@ fn user_to_json(user: &User, trailbase_id: &str) -> Result<serde_json::Value, Or
         "current_japanese_level": i32::from(user.current_japanese_level()),
         "jlpt_progress": jlpt_progress_json,
         "telegram_user_id": user.telegram_user_id().copied().map(|id| id as i64),
-        "knowledge_set": knowledge_set_wire,
         "updated_at": user.updated_at().to_rfc3339(),
         "imported_sets": imported_sets_json,
         "daily_load": i32::from(*user.daily_load()),
         "known_vocab_hash": user.known_vocab_hash() as i32,
-    }))
+        "srs_settings": srs_settings_json,
+    });
+    if let Some(checkpoint) = checkpoint {
+        body["knowledge_set"] = knowledge_set_codec::encode(checkpoint)?.into();
+    }
+    Ok(body)
 }
 
 impl UserRepository for TrailBaseUserRepository {
@@ -198,47 +626,7 @@ impl UserRepository for TrailBaseUserRepository {
     }
 
     async fn save(&self, user: &User) -> Result<(), OrigaError> {
-        let session = get_session().ok_or_else(|| OrigaError::RepositoryError {
-            reason: "Not authenticated".to_string(),
-        })?;
-
-        if session.email.is_empty() {
-            return Err(OrigaError::RepositoryError {
-                reason: "Email not found in session. Please re-login.".to_string(),
-            });
-        }
-
-        let api = self.client.records(&self.table_name);
-        let body = user_to_json(user, &session.trailbase_id)?;
-
-        if let Some((_, record_id)) = self.find_current().await? {
-            api.update(&record_id.to_string(), &body)
-                .await
-                .map_err(map_auth_error)?;
-        } else {
-            let created_id = api.create(&body).await.map_err(map_auth_error)?;
-            let record_id: i64 = created_id
-                .parse()
-                .map_err(|_| OrigaError::RepositoryError {
-                    reason: "Invalid record ID returned from create".to_string(),
-                })?;
-
-            let updated_session = TrailBaseSession {
-                record_id: Some(record_id),
-                ..session.clone()
-            };
-            set_session_async(&updated_session)
-                .await
-                .map_err(|e| OrigaError::RepositoryError {
-                    reason: format!("Failed to update session: {}", e),
-                })?;
-        }
-
-        if let Ok(mut cache) = self.user_cache.write() {
-            cache.insert(user.email().to_string(), user.clone());
-        }
-
-        Ok(())
+        self.save_with_events(user).await.map(|_| ())
     }
 
     async fn delete(&self, _user_id: Ulid) -> Result<(), OrigaError> {
@@ -257,6 +645,7 @@ impl UserRepository for TrailBaseUserRepository {
         if let Ok(mut cache) = self.user_cache.write() {
             cache.clear();
         }
+        self.clear_snapshot();
 
         Ok(())
     }
@@ -1,5 +1,5 @@
 use super::*;
-use origa::domain::{Card, PhraseCard, RateMode, Rating};
+use origa::domain::{Card, JlptProgress, PhraseCard, RateMode, Rating};
 use ulid::Ulid;
 
 fn fixture_user() -> User {
@@ -28,14 +28,20 @@ fn user_to_json_then_userrow_roundtrip_preserves_knowledge_set() {
     // Act — encode to the wire body, then deserialize back as a UserRow
     // (the shape TrailBase returns), and rebuild the User exactly as the
     // production read path does.
-    let body = user_to_json(&user, "00000000-0000-0000-0000-000000000001").expect("user_to_json");
+    let body = user_to_json(
+        &user,
+        "00000000-0000-0000-0000-000000000001",
+        Some(&user.knowledge_set().checkpoint()),
+    )
+    .expect("user_to_json");
     let row: UserRow = serde_json::from_value(body).expect("UserRow deserialize from wire body");
     let restored = row.to_user();
 
     // Assert — the deflated wire format is lossless end-to-end: what the
-    // write path produces, the read path reconstructs.
+    // write path produces (a checkpoint of the set), the read path
+    // reconstructs.
     assert_eq!(
-        user.knowledge_set(),
+        &user.knowledge_set().checkpoint(),
         restored.knowledge_set(),
         "knowledge_set must survive the full encode -> wire -> decode roundtrip"
     );
@@ -49,8 +55,12 @@ fn userrow_to_user_self_heals_on_corrupt_knowledge_set() {
     // with an unparseable deflated payload. This models a corrupt remote
     // row (truncated write, bit flip in the BLOB, a partial column write).
     let user = fixture_user();
-    let mut body =
-        user_to_json(&user, "00000000-0000-0000-0000-000000000002").expect("user_to_json");
+    let mut body = user_to_json(
+        &user,
+        "00000000-0000-0000-0000-000000000002",
+        Some(&user.knowledge_set().checkpoint()),
+    )
+    .expect("user_to_json");
     body["knowledge_set"] = serde_json::Value::String("DEFLATE;!!!corrupt-base64!!!".to_string());
 
     // Act
@@ -93,7 +103,12 @@ fn user_to_json_then_userrow_roundtrip_preserves_onboarding_sentinels() {
     );
 
     // Act — encode to the wire body, deserialize back as a UserRow, rebuild.
-    let body = user_to_json(&user, "00000000-0000-0000-0000-000000000003").expect("user_to_json");
+    let body = user_to_json(
+        &user,
+        "00000000-0000-0000-0000-000000000003",
+        Some(&user.knowledge_set().checkpoint()),
+    )
+    .expect("user_to_json");
     let row: UserRow = serde_json::from_value(body).expect("UserRow deserialize from wire body");
     let restored = row.to_user();
 
@@ -117,8 +132,12 @@ fn user_to_json_then_userrow_roundtrip_preserves_onboarding_sentinels() {
         None,
     );
     finished.mark_onboarding_completed();
-    let body =
-        user_to_json(&finished, "00000000-0000-0000-0000-000000000004").expect("user_to_json");
+    let body = user_to_json(
+        &finished,
+        "00000000-0000-0000-0000-000000000004",
+        Some(&finished.knowledge_set().checkpoint()),
+    )
+    .expect("user_to_json");
     let row: UserRow = serde_json::from_value(body).expect("UserRow deserialize from wire body");
     let restored = row.to_user();
     assert!(
@@ -127,3 +146,256 @@ fn user_to_json_then_userrow_roundtrip_preserves_onboarding_sentinels() {
     );
     assert!(restored.is_onboarding_completed());
 }
+
+/// A checkpoint as another device would have published it, and the id of
+/// its only card.
+fn published_checkpoint() -> (KnowledgeSet, Ulid) {
+    let mut origin = KnowledgeSet::new();
+    let card_id = *origin
+        .create_card(Card::Phrase(PhraseCard::new(Ulid::new())))
+        .expect("create_card")
+        .card_id();
+    (origin.checkpoint(), card_id)
+}
+
+/// A device whose local knowledge set is `knowledge_set`.
+fn device_with(knowledge_set: KnowledgeSet) -> User {
+    User::from_row(
+        Ulid::new(),
+        "device-test@example.com".to_string(),
+        "device".to_string(),
+        JlptProgress::new(),
+        NativeLanguage::Russian,
+        None,
+        knowledge_set,
+        Utc::now(),
+        HashSet::new(),
+        DailyLoad::default(),
+        0,
+        SrsSettings::default(),
+    )
+}
+
+fn review(device: &mut User, card_id: Ulid) {
+    device
+        .rate_card(card_id, Rating::Good, RateMode::StandardLesson)
+        .expect("rate_card");
+}
+
+#[test]
+fn plan_knowledge_upload_appends_only_unsynced_events() {
+    // Arrange — the remote already holds the first of two local reviews.
+    let (checkpoint, card_id) = published_checkpoint();
+    let mut local = device_with(checkpoint.clone());
+    review(&mut local, card_id);
+    let remote_events = local.knowledge_set().journal().events().to_vec();
+    review(&mut local, card_id);
+
+    // Act
+    let plan = plan_knowledge_upload(local.knowledge_set(), checkpoint.journal(), &remote_events);
+
+    // Assert
+    assert_eq!(
+        plan,
+        KnowledgeUpload::Events(local.knowledge_set().journal().events()[1..].to_vec())
+    );
+}
+
+#[test]
+fn plan_knowledge_upload_replaces_a_legacy_remote_blob_with_a_checkpoint() {
+    // Arrange — the remote row was written before the journal existed.
+    let legacy_remote = KnowledgeSet::new();
+    let (checkpoint, _) = published_checkpoint();
+    let mut local = checkpoint.clone();
+    local.rebase(&legacy_remote, &[]);
+
+    // Act
+    let plan = plan_knowledge_upload(&local, legacy_remote.journal(), &[]);
+
+    // Assert
+    assert_eq!(plan, KnowledgeUpload::Checkpoint);
+}
+
+#[test]
+fn plan_knowledge_upload_never_overwrites_events_the_local_set_lacks() {
+    // Arrange — a legacy local set, and another device's review on the
+    // remote that this device has not merged yet.
+    let (checkpoint, card_id) = published_checkpoint();
+    let mut other_device = device_with(checkpoint.clone());
+    review(&mut other_device, card_id);
+    let remote_events = other_device.knowledge_set().journal().events().to_vec();
+    let local = fixture_user();
+
+    // Act
+    let plan = plan_knowledge_upload(local.knowledge_set(), checkpoint.journal(), &remote_events);
+
+    // Assert — the local events are appended; the checkpoint waits for a
+    // merge that includes the remote review.
+    assert_eq!(
+        plan,
+        KnowledgeUpload::Events(local.knowledge_set().journal().events().to_vec())
+    );
+}
+
+#[test]
+fn plan_knowledge_upload_checkpoints_a_long_event_stream() {
+    // Arrange
+    let (checkpoint, card_id) = published_checkpoint();
+    let mut local = device_with(checkpoint.clone());
+    for _ in 0..=CHECKPOINT_AFTER_EVENTS {
+        review(&mut local, card_id);
+    }
+
+    // Act
+    let plan = plan_knowledge_upload(local.knowledge_set(), checkpoint.journal(), &[]);
+
+    // Assert
+    assert_eq!(plan, KnowledgeUpload::Checkpoint);
+}
+
+#[test]
+fn plan_knowledge_upload_waits_for_a_rebase_onto_a_newer_checkpoint() {
+    // Arrange — another device published a checkpoint folding its own
+    // review; this device has many pending reviews but never merged it.
+    let (checkpoint, card_id) = published_checkpoint();
+    let mut other_device = device_with(checkpoint.clone());
+    review(&mut other_device, card_id);
+    let newer = other_device.knowledge_set().checkpoint();
+    let mut local = device_with(checkpoint);
+    for _ in 0..=CHECKPOINT_AFTER_EVENTS {
+        review(&mut local, card_id);
+    }
+
+    // Act
+    let plan = plan_knowledge_upload(local.knowledge_set(), newer.journal(), &[]);
+
+    // Assert
+    assert_eq!(
+        plan,
+        KnowledgeUpload::Events(local.knowledge_set().journal().events().to_vec())
+    );
+}
+
+/// The `domain_user` row as `user_to_json` writes it.
+fn stored_user_row(user: &User, checkpoint: &KnowledgeSet) -> UserRow {
+    let body = user_to_json(
+        user,
+        "00000000-0000-0000-0000-000000000005",
+        Some(checkpoint),
+    )
+    .expect("user_to_json");
+    serde_json::from_value(body).expect("UserRow deserialize from wire body")
+}
+
+#[test]
+fn remote_snapshot_goes_stale_when_another_device_publishes_a_checkpoint() {
+    // Arrange — both devices read the same row; the other device then
+    // publishes a checkpoint folding a review it never uploaded as a row.
+    let (checkpoint, card_id) = published_checkpoint();
+    let local = device_with(checkpoint.clone());
+    let read_row = stored_user_row(&local, &checkpoint);
+    let snapshot = RemoteSnapshot::new(
+        "device-test@example.com".to_string(),
+        1,
+        checkpoint.journal().clone(),
+        read_row.updated_at,
+    );
+    let mut other_device = device_with(checkpoint.clone());
+    review(&mut other_device, card_id);
+    let newer = other_device.knowledge_set().checkpoint();
+    let republished_row = stored_user_row(&other_device, &newer);
+
+    // Act
+    let unchanged = snapshot.matches(&read_row);
+    let after_publish = snapshot.matches(&republished_row);
+
+    // Assert
+    assert!(unchanged);
+    assert!(!after_publish);
+}
+
+#[test]
+fn knowledge_event_row_roundtrip_preserves_event() {
+    // Arrange
+    let (checkpoint, card_id) = published_checkpoint();
+    let mut local = device_with(checkpoint);
+    review(&mut local, card_id);
+    let event = &local.knowledge_set().journal().events()[0];
+
+    // Act
+    let row = KnowledgeEventRow::from_event(event, "00000000-0000-0000-0000-000000000005")
+        .expect("from_event");
+    let wire = serde_json::to_value(&row).expect("row serialize");
+    let row: KnowledgeEventRow = serde_json::from_value(wire).expect("row deserialize");
+
+    // Assert
+    assert_eq!(row.seq, 0);
+    assert_eq!(row.to_event().as_ref(), Some(event));
+}
+
+fn stored_row(event: &KnowledgeEvent, id: i64) -> KnowledgeEventRow {
+    KnowledgeEventRow {
+        id: Some(id),
+        ..KnowledgeEventRow::from_event(event, "00000000-0000-0000-0000-000000000005")
+            .expect("from_event")
+    }
+}
+
+#[test]
+fn remote_snapshot_resumes_after_the_newest_row_it_holds() {
+    // Arrange
+    let (checkpoint, card_id) = published_checkpoint();
+    let mut local = device_with(checkpoint.clone());
+    review(&mut local, card_id);
+    review(&mut local, card_id);
+    let events = local.knowledge_set().journal().events().to_vec();
+    let mut snapshot = RemoteSnapshot::new(
+        "device-test@example.com".to_string(),
+        1,
+        checkpoint.journal().clone(),
+        Utc::now(),
+    );
+
+    // Act — the second fetch repeats row 7 alongside the new row 9.
+    snapshot.extend(vec![stored_row(&events[0], 7)]);
+    snapshot.extend(vec![stored_row(&events[0], 7), stored_row(&events[1], 9)]);
+
+    // Assert
+    assert_eq!(snapshot.last_row_id(), Some(9));
+    assert_eq!(snapshot.events(), events);
+}
+
+#[test]
+fn remote_snapshot_publish_returns_only_folded_rows() {
+    // Arrange — the new checkpoint folds the first review but not a later
+    // one uploaded by another device.
+    let (checkpoint, card_id) = published_checkpoint();
+    let mut local = device_with(checkpoint.clone());
+    review(&mut local, card_id);
+    let published = local.knowledge_set().checkpoint();
+    let mut other_device = device_with(published.clone());
+    review(&mut other_device, card_id);
+    let folded_event = local.knowledge_set().journal().events()[0].clone();
+    let later_event = other_device.knowledge_set().journal().events()[0].clone();
+    let mut snapshot = RemoteSnapshot::new(
+        "device-test@example.com".to_string(),
+        1,
+        checkpoint.journal().clone(),
+        Utc::now(),
+    );
+    snapshot.extend(vec![
+        stored_row(&folded_event, 3),
+        stored_row(&later_event, 4),
+    ]);
+
+    // Act
+    let folded = snapshot.publish(published.journal().clone());
+
+    // Assert
+    assert_eq!(
+        folded.iter().map(|row| row.id).collect::<Vec<_>>(),
+        vec![Some(3)]
+    );
+    assert_eq!(snapshot.events(), vec![later_event]);
+    assert_eq!(snapshot.last_row_id(), Some(4));
+}
@@ -1,5 +1,5 @@
 use crate::loaders::{
-    data_loader::{load_grammar, load_kanji, load_radicals, load_vocabulary},
+    data_loader::{load_grammar, load_kanji, load_radicals, load_user_dictionary, load_vocabulary},
     dictionary::load_dictionary,
     furigana_dict_loader::load_furigana_dict,
     jlpt_content_loader::load_jlpt_content,
@@ -98,6 +98,9 @@ pub fn start_dictionary_loading(
         if let Err(e) = vocab_r {
             tracing::error!("Failed to load vocabulary: {e}");
         }
+        if let Err(e) = load_user_dictionary().await {
+            tracing::warn!("Failed to restore user dictionaries: {e}");
+        }
         auth_store.is_vocabulary_loaded.set(true);
 
         if let Err(e) = phrases_r {
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use leptos::prelude::*;
-use origa::domain::{FuriganaSegment, NativeLanguage, furiganize_segments};
+use origa::domain::{FuriganaSegment, NativeLanguage, ReadingHint, furiganize_segments_with_hints};
 
 use crate::ui_components::furigana_hover::{render_plain_segment, render_segment_with_hover};
 
@@ -13,9 +13,12 @@ pub fn FuriganaText(
     #[prop(optional, into)] test_id: Signal<String>,
     #[prop(optional, into)] native_language: Option<NativeLanguage>,
     #[prop(optional, default = false)] with_kanji_tooltip: bool,
+    /// Readings printed in the source (OCR ruby); they beat the dictionary.
+    #[prop(optional)]
+    reading_hints: Vec<ReadingHint>,
 ) -> impl IntoView {
     let segments = move || {
-        furiganize_segments(&text, &known_kanji)
+        furiganize_segments_with_hints(&text, &reading_hints, &known_kanji)
             .unwrap_or_else(|_| vec![FuriganaSegment::new(text.clone(), None, false)])
     };
 
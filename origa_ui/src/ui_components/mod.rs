@@ -37,6 +37,7 @@ mod nav_config;
 mod ocr_loading_stage;
 mod offline_bundle_card;
 mod page_header;
+mod pitch_contour;
 mod progress;
 mod reading_group;
 mod search;
@@ -124,6 +125,7 @@ pub use ocr_loading_stage::{
 };
 pub use offline_bundle_card::OfflineBundleCard;
 pub use page_header::PageHeader;
+pub use pitch_contour::PitchContour;
 pub use progress::ProgressBar;
 pub use reading_group::{ReadingGroup, ReadingItem};
 pub use search::Search;
@@ -0,0 +1,59 @@
+use leptos::prelude::*;
+use origa::domain::PitchAccent;
+
+/// Reading split into morae with a line over high morae and under low ones;
+/// a vertical tick marks the downstep. The trailing slot stands for an
+/// attached particle, which is what tells heiban and odaka apart.
+#[component]
+pub fn PitchContour(
+    accent: PitchAccent,
+    #[prop(optional, into)] class: Signal<String>,
+) -> impl IntoView {
+    let contour = accent.contour();
+    let accent_type = accent.accent_type();
+    let morae: Vec<String> = accent
+        .morae()
+        .iter()
+        .cloned()
+        .chain(std::iter::once("・".to_string()))
+        .collect();
+    // (mora, high, pitch drops right after it)
+    let cells: Vec<(String, bool, bool)> = morae
+        .into_iter()
+        .enumerate()
+        .map(|(index, mora)| {
+            let high = contour[index];
+            let drops = high && contour.get(index + 1) == Some(&false);
+            (mora, high, drops)
+        })
+        .collect();
+    let particle = cells.len() - 1;
+
+    view! {
+        <div
+            class=move || format!("inline-flex items-center gap-2 {}", class.get())
+            data-testid="pitch-contour"
+        >
+            <span class="inline-flex">
+                {cells
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (mora, high, drops))| {
+                        let line = if high { "border-t-2" } else { "border-b-2" };
+                        let tick = if drops { "border-r-2" } else { "" };
+                        let muted = if index == particle { "text-[var(--fg-muted)]" } else { "" };
+                        view! {
+                            <span class=format!(
+                                "px-0.5 leading-tight border-[var(--accent-terracotta)] {} {} {}",
+                                line, tick, muted,
+                            )>{mora}</span>
+                        }
+                    })
+                    .collect_view()}
+            </span>
+            <span class="text-xs text-[var(--fg-muted)]">
+                {format!("{} · {}", accent_type.japanese_name(), accent_type.romaji_name())}
+            </span>
+        </div>
+    }
+}
@@ -1,12 +1,21 @@
 use std::collections::HashSet;
 
+use crate::i18n::{Locale, t_string, use_i18n};
 use crate::pages::lesson::LessonContext;
+use crate::store::auth_store::AuthStore;
 use crate::ui_components::{MarkdownText, MarkdownVariant};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos::wasm_bindgen::JsCast;
+use leptos_i18n::I18nContext;
+use leptos_router::components::A;
 use leptos_use::use_event_listener;
-use origa::domain::{NativeLanguage, TokenTranslation, lookup_tokens_translations, tokenize_text};
+use origa::dictionary::grammar::{FormatAction, get_rule_by_id};
+use origa::domain::{
+    Card, Deconjugation, NativeLanguage, TokenTranslation, lookup_tokens_translations,
+    tokenize_text,
+};
+use ulid::Ulid;
 
 fn has_kanji(text: &str) -> bool {
     text.chars().any(|c| {
@@ -106,6 +115,7 @@ pub fn TranslatorText(
                         let translation_text = token.translation.clone();
                         let grammar_label = token.grammar_label.clone();
                         let grammar_description = token.grammar_description.clone();
+                        let conjugation = token.conjugation.clone();
                         let clickable = token.pos.is_vocabulary_word() || grammar_label.is_some();
                         let has_kanji = has_kanji(&surface);
                         let show_base = base_form != surface;
@@ -131,6 +141,7 @@ pub fn TranslatorText(
                             grammar_label.clone(),
                             grammar_description.clone(),
                             translation_text.clone(),
+                            conjugation,
                             show_base,
                         ));
 
@@ -165,7 +176,7 @@ pub fn TranslatorText(
                                     </span>
                                     {move || {
                                         if expanded.get() == Some(idx) {
-                                            let (s, r, bf, gl, gd, tt, sb) = popup_data.get_value();
+                                            let (s, r, bf, gl, gd, tt, cj, sb) = popup_data.get_value();
                                             view! {
                                                 <TokenPopup
                                                     surface=s
@@ -173,6 +184,8 @@ pub fn TranslatorText(
                                                     base_form=Signal::derive(move || if sb { Some(bf.clone()) } else { None })
                                                     grammar_label=Signal::derive(move || gl.clone())
                                                     grammar_description=Signal::derive(move || gd.clone())
+                                                    conjugation=Signal::derive(move || cj.clone())
+                                                    native_language=native_lang
                                                     translation_text=Signal::derive(move || tt.clone())
                                                 />
                                             }.into_any()
@@ -203,8 +216,12 @@ fn TokenPopup(
     #[prop(optional, into)] base_form: Signal<Option<String>>,
     #[prop(optional, into)] grammar_label: Signal<Option<String>>,
     #[prop(optional, into)] grammar_description: Signal<Option<String>>,
+    #[prop(optional, into)] conjugation: Signal<Option<Deconjugation>>,
     #[prop(optional, into)] translation_text: Signal<Option<String>>,
+    native_language: Signal<NativeLanguage>,
 ) -> impl IntoView {
+    let i18n = use_i18n();
+    let auth_store = use_context::<AuthStore>();
     let popup_ref: NodeRef<leptos::html::Div> = NodeRef::new();
     let shift_x: RwSignal<f64> = RwSignal::new(0.0);
 
@@ -251,6 +268,41 @@ fn TokenPopup(
                     <div class="token-popup-grammar-description">{desc}</div>
                 }.into_any()).unwrap_or_else(|| ().into_any())
             }}
+            {move || {
+                conjugation.get().map(|conjugation| {
+                    let lang = native_language.get();
+                    let steps = conjugation
+                        .steps
+                        .into_iter()
+                        .map(|step| {
+                            let name = conjugation_step_name(i18n, &step.action);
+                            let rule = step.rule_id.and_then(|rule_id| {
+                                let title = get_rule_by_id(&rule_id)?.content(&lang).title().to_string();
+                                let card_id = auth_store.as_ref().and_then(|store| grammar_card_for_rule(store, rule_id));
+                                Some(match card_id {
+                                    Some(card_id) => view! {
+                                        " · "
+                                        <A href=format!("/grammar/{}", card_id) attr:class="token-popup-rule-link">{title}</A>
+                                    }.into_any(),
+                                    None => view! { " · " {title} }.into_any(),
+                                })
+                            });
+                            view! {
+                                <li>{name} " → " {step.form} {rule}</li>
+                            }
+                        })
+                        .collect_view();
+                    let heading = t_string!(i18n, conjugation.derived_from)
+                        .to_string()
+                        .replacen("{}", &conjugation.lemma, 1);
+                    view! {
+                        <div class="token-popup-grammar-description" data-testid="token-popup-conjugation">
+                            <div>{heading}</div>
+                            <ol>{steps}</ol>
+                        </div>
+                    }.into_any()
+                }).unwrap_or_else(|| ().into_any())
+            }}
             {move || {
                 translation_text.get().map(|text| {
                     view! {
@@ -266,3 +318,53 @@ fn TokenPopup(
         </div>
     }
 }
+
+/// Grammar card the learner studies `rule_id` with, for linking a step to
+/// the rule's page.
+fn grammar_card_for_rule(auth_store: &AuthStore, rule_id: Ulid) -> Option<Ulid> {
+    auth_store.user.with_untracked(|user| {
+        user.as_ref()?
+            .knowledge_set()
+            .study_cards()
+            .iter()
+            .find(|(_, study_card)| {
+                matches!(study_card.card(), Card::Grammar(grammar) if *grammar.rule_id() == rule_id)
+            })
+            .map(|(card_id, _)| *card_id)
+    })
+}
+
+fn conjugation_step_name(i18n: I18nContext<Locale>, action: &FormatAction) -> String {
+    use FormatAction as A;
+    let name = match action {
+        A::VerbToCausativePassive {} => t_string!(i18n, conjugation.causative_passive),
+        A::VerbToCausative {} => t_string!(i18n, conjugation.causative),
+        A::VerbToPassive {} => t_string!(i18n, conjugation.passive),
+        A::VerbToPotential {} => t_string!(i18n, conjugation.potential),
+        A::VerbToSugiru {} | A::AdjectiveToSugiru {} => t_string!(i18n, conjugation.excessive),
+        A::VerbToChau {} => t_string!(i18n, conjugation.completion),
+        A::VerbToToku {} => t_string!(i18n, conjugation.preparatory),
+        A::VerbToTeru {} => t_string!(i18n, conjugation.progressive),
+        A::VerbToNai {} | A::AdjectiveToKunai {} => t_string!(i18n, conjugation.negative),
+        A::VerbToTai {} => t_string!(i18n, conjugation.desiderative),
+        A::VerbToYasui {} => t_string!(i18n, conjugation.easy_to),
+        A::VerbToNikui {} => t_string!(i18n, conjugation.hard_to),
+        A::VerbToTeForm {} | A::AdjectiveToKute {} => t_string!(i18n, conjugation.te_form),
+        A::VerbToTa {} | A::AdjectiveToKatta {} => t_string!(i18n, conjugation.past),
+        A::AdjectiveToKunakatta {} => t_string!(i18n, conjugation.negative_past),
+        A::VerbToTara {} => t_string!(i18n, conjugation.conditional_tara),
+        A::VerbToBa {} | A::AdjectiveToKereba {} => t_string!(i18n, conjugation.conditional_ba),
+        A::VerbToMasu {} => t_string!(i18n, conjugation.polite),
+        A::VerbToMasen {} => t_string!(i18n, conjugation.polite_negative),
+        A::VerbToMashita {} => t_string!(i18n, conjugation.polite_past),
+        A::VerbToMasenDeshita {} => t_string!(i18n, conjugation.polite_negative_past),
+        A::VerbToMashou {} => t_string!(i18n, conjugation.polite_volitional),
+        A::VerbToImperative {} => t_string!(i18n, conjugation.imperative),
+        A::VerbToVolitional {} => t_string!(i18n, conjugation.volitional),
+        A::VerbToZu {} => t_string!(i18n, conjugation.negative_zu),
+        A::VerbToSou {} | A::AdjectiveToSou {} => t_string!(i18n, conjugation.appearance_sou),
+        A::AdjectiveToKu {} => t_string!(i18n, conjugation.adverbial),
+        _ => t_string!(i18n, conjugation.form),
+    };
+    name.to_string()
+}
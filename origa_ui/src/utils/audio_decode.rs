@@ -0,0 +1,45 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AudioBuffer, OfflineAudioContext};
+
+/// Sample rate the speech recognizer expects.
+const SPEECH_SAMPLE_RATE: f32 = 16_000.0;
+
+/// Decodes audio with the browser's own codecs, resampled to 16 kHz mono.
+/// Covers what the Rust decoders in `origa::stt` lack, chiefly Opus in WebM
+/// or Ogg.
+pub async fn decode_with_browser(bytes: &[u8]) -> Result<Vec<f32>, String> {
+    let context = OfflineAudioContext::new_with_number_of_channels_and_length_and_sample_rate(
+        1,
+        1,
+        SPEECH_SAMPLE_RATE,
+    )
+    .map_err(|e| format!("Failed to create audio context: {e:?}"))?;
+    let data = js_sys::Uint8Array::from(bytes).buffer();
+    let promise = context
+        .decode_audio_data(&data)
+        .map_err(|e| format!("Failed to decode audio: {e:?}"))?;
+    let buffer: AudioBuffer = JsFuture::from(promise)
+        .await
+        .map_err(|e| format!("Failed to decode audio: {e:?}"))?
+        .dyn_into()
+        .map_err(|_| "Decoded audio is not an AudioBuffer".to_string())?;
+
+    let channels = buffer.number_of_channels();
+    let mut mono = buffer
+        .get_channel_data(0)
+        .map_err(|e| format!("Failed to read decoded audio: {e:?}"))?;
+    for channel in 1..channels {
+        let samples = buffer
+            .get_channel_data(channel)
+            .map_err(|e| format!("Failed to read decoded audio: {e:?}"))?;
+        for (sum, sample) in mono.iter_mut().zip(samples) {
+            *sum += sample;
+        }
+    }
+    if channels > 1 {
+        let scale = 1.0 / channels as f32;
+        mono.iter_mut().for_each(|sample| *sample *= scale);
+    }
+    Ok(mono)
+}
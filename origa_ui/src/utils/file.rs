@@ -1,3 +1,5 @@
+use wasm_bindgen::JsCast;
+
 pub async fn read_file_as_bytes(file: &web_sys::File) -> Result<Vec<u8>, String> {
     let array_buffer = wasm_bindgen_futures::JsFuture::from(file.array_buffer())
         .await
@@ -5,3 +7,27 @@ pub async fn read_file_as_bytes(file: &web_sys::File) -> Result<Vec<u8>, String>
     let uint8_array = js_sys::Uint8Array::new(&array_buffer);
     Ok(uint8_array.to_vec())
 }
+
+/// Hands `bytes` to the browser as a download named `file_name`.
+pub fn save_bytes_as_file(bytes: &[u8], file_name: &str, mime_type: &str) -> Result<(), String> {
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let uint8_array = js_sys::Uint8Array::new_with_length(bytes.len() as u32);
+    uint8_array.copy_from(bytes);
+    let parts = js_sys::Array::of1(&uint8_array);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(|e| format!("Failed to create Blob: {e:?}"))?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)
+        .map_err(|e| format!("Failed to create blob URL: {e:?}"))?;
+
+    let anchor = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("a").ok())
+        .and_then(|element| element.dyn_into::<web_sys::HtmlAnchorElement>().ok())
+        .ok_or_else(|| "Failed to create download link".to_string())?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url).map_err(|e| format!("Failed to revoke blob URL: {e:?}"))
+}
@@ -1,3 +1,4 @@
+pub mod audio_decode;
 mod drag_drop;
 mod fetch;
 pub mod file;
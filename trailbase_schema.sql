@@ -57,3 +57,54 @@ CREATE INDEX idx_user_email ON user(email);
 -- RLS rules for domain_user (same as user):
 -- _ROW_.trailbase_id = _USER_.id
 -- _REQ_.trailbase_id = _USER_.id
+--
+-- srs_settings (JSON, nullable): per-user SRS personalization — the opt-in
+-- review log switch and FSRS weights fitted from it. NULL reads as defaults.
+-- Added via the SQL Editor like domain_user itself; traildepot/ is not
+-- tracked, so a local or e2e TrailBase needs the same statement in its own
+-- traildepot/migrations/main/ (e.g. V103__add_srs_settings_column.sql):
+--   ALTER TABLE domain_user ADD COLUMN srs_settings TEXT
+--     CHECK(srs_settings IS NULL OR json_valid(srs_settings));
+
+-- ─────────────────────────────────────────────────────────────────────
+-- knowledge_event: append-only per-device knowledge journal
+-- (origa/src/domain/knowledge/journal.rs). A save uploads only the
+-- events the remote has not seen and leaves domain_user.knowledge_set
+-- untouched; that column becomes the *checkpoint* the events apply to.
+-- A device rewrites the checkpoint (and deletes the rows it folded) when
+-- either side predates the journal or more than 500 events are pending.
+--
+-- `event` is the serde JSON of a KnowledgeEvent; replica/seq/at repeat
+-- its id so rows can be inspected without parsing it. Clients page through
+-- rows with `id` greater than the newest one they have seen, so `id` must
+-- stay the autoincrement primary key.
+--
+-- Deploy by hand, like domain_user: run the statements below in the SQL
+-- Editor (/_/admin/editor), then add a `knowledge_event` Record API with
+-- the RLS rules listed after them. traildepot/ is not tracked, so a local
+-- or e2e TrailBase needs the table in its own
+-- traildepot/migrations/main/ (e.g. V102__create_knowledge_event_table.sql)
+-- and the API in its config.textproto:
+--   record_apis: [ ..., {
+--     name: "knowledge_event"
+--     table_name: "knowledge_event"
+--     acl_authenticated: [READ, CREATE, UPDATE, DELETE]
+--   } ]
+
+CREATE TABLE knowledge_event (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    trailbase_id BLOB NOT NULL,
+    replica TEXT NOT NULL,
+    seq INTEGER NOT NULL,
+    at TEXT NOT NULL,
+    event TEXT CHECK(json_valid(event)) NOT NULL,
+    UNIQUE (trailbase_id, replica, seq)
+) STRICT;
+
+CREATE INDEX idx_knowledge_event_trailbase_id ON knowledge_event(trailbase_id);
+
+-- RLS rules for knowledge_event (same as user):
+-- _ROW_.trailbase_id = _USER_.id
+-- _REQ_.trailbase_id = _USER_.id
+-- _ROW_.trailbase_id = _USER_.id AND _REQ_.trailbase_id = _USER_.id
+-- _ROW_.trailbase_id = _USER_.id
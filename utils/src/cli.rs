@@ -24,10 +24,15 @@ pub enum Commands {
 
     /// Japanese OCR using NDLOCR-Lite models
     Ndlocr {
-        /// Input image path
+        /// Input image, directory of page images, or PDF of scanned pages
         #[arg(short, long)]
         input: PathBuf,
 
+        /// Output directory for per-page text and JSON boxes (default: <input>_ocr
+        /// for directories and PDFs; a single image without it prints to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
         /// Detector model path
         #[arg(long, default_value = "../ndlocr-lite/src/model/deim-s-1024x1024.onnx")]
         detector: PathBuf,
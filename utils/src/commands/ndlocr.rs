@@ -1,7 +1,15 @@
+use flate2::read::ZlibDecoder;
+use image::{DynamicImage, GrayImage, ImageFormat, RgbImage};
+use lopdf::xobject::PdfImage;
+use lopdf::{Document, ObjectId};
 use origa::domain::OrigaError;
-use origa::ocr::{JapaneseOCRModel, ModelFiles};
+use origa::ocr::{JapaneseOCRModel, ModelFiles, OcrResult};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "bmp", "tif", "tiff"];
 
 /// Loads a model file and returns an error with context if it fails
 fn load_model_file(path: &PathBuf, model_name: &str) -> Result<Vec<u8>, OrigaError> {
@@ -10,33 +18,434 @@ fn load_model_file(path: &PathBuf, model_name: &str) -> Result<Vec<u8>, OrigaErr
     })
 }
 
-/// Runs Japanese OCR using NDLOCR-Lite models
+/// Runs Japanese OCR using NDLOCR-Lite models.
+///
+/// A single image is printed to stdout. A directory of images or a PDF of
+/// scanned pages (or any input with `output` set) is processed as a batch:
+/// every page gets `<page>.txt` and `<page>.json` with the boxes in the
+/// output directory. Pages whose JSON already exists are skipped, so running
+/// the same command again resumes after the last finished page.
+///
+/// A page that cannot be read (a broken image, a PDF image codec such as
+/// CCITT fax or JBIG2 that is not supported) does not stop the batch: the
+/// reason goes to `<page>.skipped` and the batch moves on. Skipped pages
+/// count as done on resume; delete the marker to retry one. A model error
+/// stops the batch without a marker, so running again retries that page.
+///
+/// Output files are named after the image stem, so a directory holding
+/// `p1.png` and `p1.jpg` is rejected instead of writing both to `p1.json`.
 pub fn run_ndlocr(
     input: PathBuf,
+    output: Option<PathBuf>,
     detector: PathBuf,
     rec30: PathBuf,
     rec50: PathBuf,
     rec100: PathBuf,
     vocab: PathBuf,
 ) -> Result<(), OrigaError> {
-    // Load image
-    let img = image::open(&input).map_err(|e| OrigaError::OcrError {
-        reason: format!("Failed to open image {:?}: {}", input, e),
-    })?;
+    let load_model = || -> Result<JapaneseOCRModel, OrigaError> {
+        let model_files = ModelFiles {
+            deim: load_model_file(&detector, "detector model")?,
+            parseq30: load_model_file(&rec30, "parseq30 model")?,
+            parseq50: load_model_file(&rec50, "parseq50 model")?,
+            parseq100: load_model_file(&rec100, "parseq100 model")?,
+            vocab: load_model_file(&vocab, "vocabulary")?,
+        };
+        JapaneseOCRModel::from_model_files(model_files)
+    };
 
-    // Load all model files
-    let model_files = ModelFiles {
-        deim: load_model_file(&detector, "detector model")?,
-        parseq30: load_model_file(&rec30, "parseq30 model")?,
-        parseq50: load_model_file(&rec50, "parseq50 model")?,
-        parseq100: load_model_file(&rec100, "parseq100 model")?,
-        vocab: load_model_file(&vocab, "vocabulary")?,
+    let pages = Pages::open(&input)?;
+    let output = match (output, &pages) {
+        (Some(output), _) => output,
+        (None, Pages::Images(images)) if !input.is_dir() && images.len() == 1 => {
+            let img = open_image(&input)?;
+            let text = load_model()?.run(&img)?;
+            println!("{}", text);
+            return Ok(());
+        },
+        (None, _) => default_output_dir(&input),
     };
 
-    // Run OCR
-    let model = JapaneseOCRModel::from_model_files(model_files)?;
-    let text = model.run(&img)?;
+    fs::create_dir_all(&output).map_err(|e| OrigaError::OcrError {
+        reason: format!("Failed to create {}: {}", output.display(), e),
+    })?;
+
+    let names = pages.names()?;
+    let pending: Vec<usize> = (0..names.len())
+        .filter(|&index| !is_done(&output, &names[index]))
+        .collect();
+    tracing::info!(
+        "{} pages, {} already done, writing to {}",
+        names.len(),
+        names.len() - pending.len(),
+        output.display()
+    );
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    // Models are loaded once for the whole batch.
+    let model = load_model()?;
+    let mut skipped = 0usize;
+    for index in pending {
+        let name = &names[index];
+        match pages.load(index) {
+            Ok(img) => {
+                let result = read_page(&model, img, name)?;
+                write_page(&output, name, &result)?;
+                tracing::info!("Page {} done: {} lines", name, result.lines.len());
+            },
+            Err(e) => {
+                tracing::warn!("Page {} skipped: {}", name, e);
+                write_skip(&output, name, &e)?;
+                skipped += 1;
+            },
+        }
+    }
+    if skipped > 0 {
+        tracing::warn!(
+            "{} pages skipped, see the .skipped files in {}",
+            skipped,
+            output.display()
+        );
+    }
 
-    println!("{}", text);
     Ok(())
 }
+
+fn read_page(
+    model: &JapaneseOCRModel,
+    img: Option<DynamicImage>,
+    name: &str,
+) -> Result<OcrResult, OrigaError> {
+    match img {
+        Some(img) => model.run_structured(&img),
+        None => {
+            tracing::warn!("Page {} has no image, writing it empty", name);
+            Ok(OcrResult::default())
+        },
+    }
+}
+
+/// Pages of a batch, in reading order.
+enum Pages {
+    Images(Vec<PathBuf>),
+    Pdf {
+        document: Box<Document>,
+        page_ids: Vec<ObjectId>,
+    },
+}
+
+impl Pages {
+    fn open(input: &Path) -> Result<Self, OrigaError> {
+        if input.is_dir() {
+            let mut images = Vec::new();
+            for entry in fs::read_dir(input).map_err(|e| OrigaError::OcrError {
+                reason: format!("Failed to read {}: {}", input.display(), e),
+            })? {
+                let path = entry
+                    .map_err(|e| OrigaError::OcrError {
+                        reason: format!("readdir: {}", e),
+                    })?
+                    .path();
+                if path.is_file() && has_extension(&path, IMAGE_EXTENSIONS) {
+                    images.push(path);
+                }
+            }
+            images.sort();
+            return Ok(Self::Images(images));
+        }
+
+        if has_extension(input, &["pdf"]) {
+            let document = Document::load(input).map_err(|e| OrigaError::OcrError {
+                reason: format!("Failed to open PDF {}: {}", input.display(), e),
+            })?;
+            let page_ids = document.get_pages().into_values().collect();
+            return Ok(Self::Pdf {
+                document: Box::new(document),
+                page_ids,
+            });
+        }
+
+        Ok(Self::Images(vec![input.to_path_buf()]))
+    }
+
+    /// Output file stem of every page.
+    fn names(&self) -> Result<Vec<String>, OrigaError> {
+        match self {
+            Self::Images(images) => image_names(images),
+            Self::Pdf { page_ids, .. } => Ok((1..=page_ids.len())
+                .map(|number| format!("page_{:04}", number))
+                .collect()),
+        }
+    }
+
+    /// The page image; `None` for a PDF page without an embedded image.
+    fn load(&self, index: usize) -> Result<Option<DynamicImage>, OrigaError> {
+        match self {
+            Self::Images(images) => open_image(&images[index]).map(Some),
+            Self::Pdf { document, page_ids } => {
+                let images = document
+                    .get_page_images(page_ids[index])
+                    .unwrap_or_default();
+                // A scanned page is one full-page image; stray logos are smaller.
+                images
+                    .iter()
+                    .max_by_key(|image| image.width * image.height)
+                    .map(decode_pdf_image)
+                    .transpose()
+            },
+        }
+    }
+}
+
+/// Image stems, which must be unique: two images sharing one would write
+/// the same output files.
+fn image_names(images: &[PathBuf]) -> Result<Vec<String>, OrigaError> {
+    let mut seen = HashMap::new();
+    let mut names = Vec::with_capacity(images.len());
+    for path in images {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if let Some(other) = seen.insert(name.clone(), path) {
+            return Err(OrigaError::OcrError {
+                reason: format!(
+                    "{} and {} would both be written as {}; rename one of them",
+                    other.display(),
+                    path.display(),
+                    name
+                ),
+            });
+        }
+        names.push(name);
+    }
+    Ok(names)
+}
+
+fn open_image(path: &Path) -> Result<DynamicImage, OrigaError> {
+    image::open(path).map_err(|e| OrigaError::OcrError {
+        reason: format!("Failed to open image {:?}: {}", path, e),
+    })
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
+}
+
+fn default_output_dir(input: &Path) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "pages".to_string());
+    input.with_file_name(format!("{}_ocr", stem))
+}
+
+/// The JSON is written last, so its presence marks a finished page.
+fn is_finished(output: &Path, name: &str) -> bool {
+    output.join(format!("{}.json", name)).is_file()
+}
+
+fn is_skipped(output: &Path, name: &str) -> bool {
+    output.join(format!("{}.skipped", name)).is_file()
+}
+
+fn is_done(output: &Path, name: &str) -> bool {
+    is_finished(output, name) || is_skipped(output, name)
+}
+
+fn write_skip(output: &Path, name: &str, error: &OrigaError) -> Result<(), OrigaError> {
+    let path = output.join(format!("{}.skipped", name));
+    fs::write(&path, format!("{}\n", error)).map_err(|e| OrigaError::OcrError {
+        reason: format!("Failed to write {}: {}", path.display(), e),
+    })
+}
+
+fn write_page(output: &Path, name: &str, result: &OcrResult) -> Result<(), OrigaError> {
+    let write_error = |path: &Path, e: std::io::Error| OrigaError::OcrError {
+        reason: format!("Failed to write {}: {}", path.display(), e),
+    };
+
+    let text_path = output.join(format!("{}.txt", name));
+    fs::write(&text_path, result.text()).map_err(|e| write_error(&text_path, e))?;
+
+    let json = serde_json::to_string_pretty(result).map_err(|e| OrigaError::OcrError {
+        reason: format!("Failed to serialize page {}: {}", name, e),
+    })?;
+    // Rename so an interrupted write never leaves a JSON that looks finished.
+    let partial_path = output.join(format!("{}.json.partial", name));
+    let json_path = output.join(format!("{}.json", name));
+    fs::write(&partial_path, json).map_err(|e| write_error(&partial_path, e))?;
+    fs::rename(&partial_path, &json_path).map_err(|e| write_error(&json_path, e))
+}
+
+/// Decodes the image XObjects scanners produce: JPEG (`DCTDecode`) and raw
+/// 8-bit gray/RGB or 1-bit pixels, optionally `FlateDecode`-compressed.
+/// The bilevel fax codecs are rejected up front; the batch skips such pages.
+fn decode_pdf_image(image: &PdfImage) -> Result<DynamicImage, OrigaError> {
+    let filters = image.filters.as_deref().unwrap_or_default();
+    check_filters_supported(filters)?;
+
+    let mut data = image.content.to_vec();
+    for filter in filters {
+        match filter.as_str() {
+            "FlateDecode" => {
+                let mut inflated = Vec::new();
+                ZlibDecoder::new(data.as_slice())
+                    .read_to_end(&mut inflated)
+                    .map_err(|e| OrigaError::OcrError {
+                        reason: format!("Failed to inflate PDF image: {}", e),
+                    })?;
+                data = inflated;
+            },
+            "DCTDecode" => {
+                return image::load_from_memory_with_format(&data, ImageFormat::Jpeg).map_err(
+                    |e| OrigaError::OcrError {
+                        reason: format!("Failed to decode PDF JPEG: {}", e),
+                    },
+                );
+            },
+            other => {
+                return Err(OrigaError::OcrError {
+                    reason: format!("Unsupported PDF image filter: {}", other),
+                });
+            },
+        }
+    }
+
+    raw_pixels(
+        image.width as u32,
+        image.height as u32,
+        image.bits_per_component.unwrap_or(8),
+        data,
+    )
+}
+
+/// `CCITTFaxDecode` and `JBIG2Decode` are typical for 1-bit book scans but
+/// have no decoder here.
+fn check_filters_supported(filters: &[String]) -> Result<(), OrigaError> {
+    match filters
+        .iter()
+        .find(|filter| matches!(filter.as_str(), "CCITTFaxDecode" | "JBIG2Decode"))
+    {
+        Some(filter) => Err(OrigaError::OcrError {
+            reason: format!("Unsupported PDF image codec {}", filter),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn raw_pixels(
+    width: u32,
+    height: u32,
+    bits_per_component: i64,
+    data: Vec<u8>,
+) -> Result<DynamicImage, OrigaError> {
+    let pixels = width as usize * height as usize;
+    let image = match bits_per_component {
+        1 => {
+            // Rows are padded to whole bytes; a set bit is white.
+            let row_bytes = (width as usize).div_ceil(8);
+            (data.len() >= row_bytes * height as usize).then(|| {
+                DynamicImage::ImageLuma8(GrayImage::from_fn(width, height, |x, y| {
+                    let byte = data[y as usize * row_bytes + x as usize / 8];
+                    let bit = (byte >> (7 - x % 8)) & 1;
+                    image::Luma([bit * 255])
+                }))
+            })
+        },
+        8 if data.len() == pixels => {
+            GrayImage::from_raw(width, height, data).map(DynamicImage::ImageLuma8)
+        },
+        8 if data.len() == pixels * 3 => {
+            RgbImage::from_raw(width, height, data).map(DynamicImage::ImageRgb8)
+        },
+        _ => None,
+    };
+
+    image.ok_or_else(|| OrigaError::OcrError {
+        reason: format!(
+            "Unsupported PDF image layout: {}x{}, {} bits per component",
+            width, height, bits_per_component
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GenericImageView;
+
+    #[test]
+    fn one_bit_rows_are_padded_to_bytes() {
+        // 10 pixels wide: each row takes two bytes.
+        let data = vec![0b1000_0000, 0b0100_0000, 0b0000_0000, 0b0000_0000];
+
+        let image = raw_pixels(10, 2, 1, data).unwrap();
+
+        assert_eq!(image.get_pixel(0, 0)[0], 255);
+        assert_eq!(image.get_pixel(1, 0)[0], 0);
+        assert_eq!(image.get_pixel(9, 0)[0], 255);
+        assert_eq!(image.get_pixel(0, 1)[0], 0);
+    }
+
+    #[test]
+    fn eight_bit_channels_are_taken_from_data_length() {
+        assert!(matches!(
+            raw_pixels(2, 2, 8, vec![0; 4]),
+            Ok(DynamicImage::ImageLuma8(_))
+        ));
+        assert!(matches!(
+            raw_pixels(2, 2, 8, vec![0; 12]),
+            Ok(DynamicImage::ImageRgb8(_))
+        ));
+        assert!(raw_pixels(2, 2, 8, vec![0; 5]).is_err());
+    }
+
+    #[test]
+    fn finished_pages_are_skipped_on_resume() {
+        let dir = std::env::temp_dir().join(format!("ndlocr_resume_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        write_page(&dir, "page_0001", &OcrResult::default()).unwrap();
+        fs::write(dir.join("page_0002.json.partial"), "{").unwrap();
+
+        assert!(is_finished(&dir, "page_0001"));
+        assert!(!is_finished(&dir, "page_0002"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn skipped_pages_are_not_retried_on_resume() {
+        let dir = std::env::temp_dir().join(format!("ndlocr_skip_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let error = check_filters_supported(&["CCITTFaxDecode".to_string()]).unwrap_err();
+
+        write_skip(&dir, "page_0003", &error).unwrap();
+
+        assert!(is_done(&dir, "page_0003"));
+        assert!(!is_finished(&dir, "page_0003"));
+        let reason = fs::read_to_string(dir.join("page_0003.skipped")).unwrap();
+        assert!(reason.contains("CCITTFaxDecode"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn images_sharing_a_stem_are_rejected() {
+        let names = image_names(&[PathBuf::from("p1.png"), PathBuf::from("p2.jpg")]).unwrap();
+        assert_eq!(names, ["p1", "p2"]);
+
+        let result = image_names(&[PathBuf::from("p1.jpg"), PathBuf::from("p1.png")]);
+
+        assert!(matches!(result, Err(OrigaError::OcrError { .. })));
+    }
+
+    #[test]
+    fn bilevel_fax_codecs_are_rejected() {
+        assert!(check_filters_supported(&["FlateDecode".to_string()]).is_ok());
+        assert!(check_filters_supported(&["JBIG2Decode".to_string()]).is_err());
+    }
+}
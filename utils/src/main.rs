@@ -24,12 +24,13 @@ async fn main() {
         Commands::Tokenize { text, file } => run_tokenize(text, file),
         Commands::Ndlocr {
             input,
+            output,
             detector,
             rec30,
             rec50,
             rec100,
             vocab,
-        } => run_ndlocr(input, detector, rec30, rec50, rec100, vocab),
+        } => run_ndlocr(input, output, detector, rec30, rec50, rec100, vocab),
         Commands::TokenizeWellKnown { path } => run_tokenize_well_known(path),
         Commands::FindMissing {
             output,